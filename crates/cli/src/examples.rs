@@ -0,0 +1,400 @@
+//! Glue between the command line and the example AIRs.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use stwo_prover::core::backend::cpu::CpuCircleEvaluation;
+use stwo_prover::core::backend::simd::m31::LOG_N_LANES;
+use stwo_prover::core::backend::Backend as BackendImpl;
+use stwo_prover::core::channel::{Blake2sChannel, Channel};
+use stwo_prover::core::fields::m31::BaseField;
+use stwo_prover::core::fields::{FieldExpOps, IntoSlice};
+use stwo_prover::core::pcs::PcsConfig;
+use stwo_prover::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use stwo_prover::core::poly::BitReversedOrder;
+use stwo_prover::core::prover::{
+    prove_with_config, verify_with_config, ProvingError, StarkProof, VerificationError,
+};
+use stwo_prover::core::vcs::blake2_hash::Blake2sHasher;
+use stwo_prover::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+use stwo_prover::core::vcs::hasher::Hasher;
+use stwo_prover::core::vcs::ops::MerkleOps;
+use stwo_prover::core::ColumnVec;
+use stwo_prover::examples::fibonacci::Fibonacci;
+use stwo_prover::examples::poseidon::{self, Poseidon2Invocation, PoseidonAir, PoseidonComponent};
+use stwo_prover::examples::wide_fibonacci::component::{
+    Input, WideFibAir, WideFibComponent, LOG_N_COLUMNS,
+};
+use stwo_prover::examples::wide_fibonacci::constraint_eval;
+use stwo_prover::examples::wide_fibonacci::simd::{self, SimdWideFibAir, SimdWideFibComponent};
+use stwo_prover::trace_generation::AirTraceGenerator;
+use thiserror::Error;
+
+/// The smallest Fibonacci trace that has room for both of its constraints.
+const FIBONACCI_MIN_LOG_SIZE: u32 = 2;
+
+/// The largest log size of a statement, which bounds the size of the trace a statement read from a
+/// file can make the prover or the verifier allocate.
+pub const MAX_LOG_SIZE: u32 = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Example {
+    Fibonacci,
+    WideFibonacci,
+    Poseidon,
+}
+
+impl Example {
+    pub fn supports(self, backend: Backend) -> bool {
+        match self {
+            Self::Fibonacci => backend == Backend::Cpu,
+            Self::WideFibonacci => true,
+            Self::Poseidon => backend == Backend::Simd,
+        }
+    }
+
+    pub fn default_backend(self) -> Backend {
+        if self.supports(Backend::Simd) {
+            Backend::Simd
+        } else {
+            Backend::Cpu
+        }
+    }
+
+    fn min_log_size(self, backend: Backend) -> u32 {
+        match (self, backend) {
+            (Self::Fibonacci, _) => FIBONACCI_MIN_LOG_SIZE,
+            // The lookup constraints of the CPU component do not hold on smaller traces.
+            (Self::WideFibonacci, Backend::Cpu) => 3,
+            // The SIMD quotient evaluation needs at least 4 packed rows per column.
+            (Self::WideFibonacci, Backend::Simd) => LOG_N_LANES + 2,
+            (Self::Poseidon, _) => LOG_N_LANES + 2 + poseidon::N_LOG_INSTANCES_PER_ROW as u32,
+        }
+    }
+}
+
+impl ValueEnum for Example {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Fibonacci, Self::WideFibonacci, Self::Poseidon]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            Self::Fibonacci => "fibonacci",
+            Self::WideFibonacci => "wide-fibonacci",
+            Self::Poseidon => "poseidon",
+        }))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    Cpu,
+    Simd,
+}
+
+impl ValueEnum for Backend {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Cpu, Self::Simd]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            Self::Cpu => "cpu",
+            Self::Simd => "simd",
+        }))
+    }
+}
+
+/// The public statement a proof is about.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Statement {
+    pub example: Example,
+    /// Different examples generate different AIRs on each backend, so the backend is part of the
+    /// statement.
+    pub backend: Backend,
+    pub log_size: u32,
+    /// The claimed last element of the sequence, for [Example::Fibonacci].
+    pub claim: Option<BaseField>,
+}
+
+impl Statement {
+    pub fn new(
+        example: Example,
+        backend: Backend,
+        log_size: u32,
+    ) -> Result<Self, InvalidStatement> {
+        let mut statement = Self {
+            example,
+            backend,
+            log_size,
+            claim: None,
+        };
+        // The claim is computed over a sequence of length `2^log_size`, so the size is checked
+        // first.
+        statement.validate_size()?;
+        statement.claim = (example == Example::Fibonacci).then(|| fibonacci_claim(log_size));
+        statement.validate()?;
+        Ok(statement)
+    }
+
+    fn validate(&self) -> Result<(), InvalidStatement> {
+        self.validate_size()?;
+        if (self.example == Example::Fibonacci) != self.claim.is_some() {
+            return Err(InvalidStatement::MissingClaim);
+        }
+        Ok(())
+    }
+
+    fn validate_size(&self) -> Result<(), InvalidStatement> {
+        if !self.example.supports(self.backend) {
+            return Err(InvalidStatement::UnsupportedBackend(
+                self.example,
+                self.backend,
+            ));
+        }
+        let min_log_size = self.example.min_log_size(self.backend);
+        if self.log_size < min_log_size {
+            return Err(InvalidStatement::LogSizeTooSmall {
+                log_size: self.log_size,
+                min_log_size,
+            });
+        }
+        if self.log_size > MAX_LOG_SIZE {
+            return Err(InvalidStatement::LogSizeTooLarge {
+                log_size: self.log_size,
+                max_log_size: MAX_LOG_SIZE,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let example = self.example.to_possible_value().unwrap();
+        let backend = self.backend.to_possible_value().unwrap();
+        write!(
+            f,
+            "{} (log size {}, {} backend",
+            example.get_name(),
+            self.log_size,
+            backend.get_name()
+        )?;
+        if let Some(claim) = self.claim {
+            write!(f, ", claim {claim}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum InvalidStatement {
+    #[error("the {0:?} example does not support the {1:?} backend")]
+    UnsupportedBackend(Example, Backend),
+    #[error("log size {log_size} is smaller than the minimum ({min_log_size})")]
+    LogSizeTooSmall { log_size: u32, min_log_size: u32 },
+    #[error("log size {log_size} is larger than the maximum ({max_log_size})")]
+    LogSizeTooLarge { log_size: u32, max_log_size: u32 },
+    #[error("a claim must be given for fibonacci statements, and only for them")]
+    MissingClaim,
+}
+
+pub struct ProvingTimings {
+    pub trace_generation: Duration,
+    pub proving: Duration,
+}
+
+pub fn prove(
+    statement: &Statement,
+    config: PcsConfig,
+) -> Result<(StarkProof, ProvingTimings), ProvingError> {
+    let channel = &mut statement_channel(statement);
+    let log_size = statement.log_size;
+    match (statement.example, statement.backend) {
+        (Example::Fibonacci, _) => {
+            let fib = Fibonacci::new(log_size, statement.claim.unwrap());
+            timed_prove(&fib.air, || vec![fib.get_trace()], channel, config)
+        }
+        (Example::WideFibonacci, Backend::Cpu) => {
+            let air = wide_fibonacci_cpu_air(log_size);
+            let gen_trace = || {
+                let inputs = vec![Input {
+                    a: BaseField::from(1),
+                    b: BaseField::from(1),
+                }];
+                let trace_domain = CanonicCoset::new(air.component.log_column_size());
+                constraint_eval::gen_trace(&air.component, inputs)
+                    .into_iter()
+                    .map(|column| CpuCircleEvaluation::new_canonical_ordered(trace_domain, column))
+                    .collect()
+            };
+            timed_prove(&air, gen_trace, channel, config)
+        }
+        (Example::WideFibonacci, Backend::Simd) => {
+            let air = wide_fibonacci_simd_air(log_size);
+            let gen_trace = || simd::gen_trace(air.component.log_column_size());
+            timed_prove(&air, gen_trace, channel, config)
+        }
+        (Example::Poseidon, _) => {
+            let air = poseidon_air(log_size);
+            let gen_trace = || {
+                let inputs = air
+                    .invocations
+                    .iter()
+                    .map(|invocation| invocation.input)
+                    .collect_vec();
+                poseidon::gen_trace(air.component.log_column_size(), &inputs)
+            };
+            timed_prove(&air, gen_trace, channel, config)
+        }
+    }
+}
+
+fn timed_prove<B: BackendImpl + MerkleOps<Blake2sMerkleHasher>>(
+    air: &impl AirTraceGenerator<B>,
+    gen_trace: impl FnOnce() -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    channel: &mut Blake2sChannel,
+    config: PcsConfig,
+) -> Result<(StarkProof, ProvingTimings), ProvingError> {
+    let start = Instant::now();
+    let trace = gen_trace();
+    let trace_generation = start.elapsed();
+
+    let start = Instant::now();
+    let proof = prove_with_config(air, channel, trace, config)?;
+    let timings = ProvingTimings {
+        trace_generation,
+        proving: start.elapsed(),
+    };
+    Ok((proof, timings))
+}
+
+pub fn verify(
+    statement: &Statement,
+    config: PcsConfig,
+    proof: StarkProof,
+) -> Result<(), VerificationError> {
+    statement
+        .validate()
+        .map_err(|err| VerificationError::InvalidStructure(err.to_string()))?;
+    let channel = &mut statement_channel(statement);
+    let log_size = statement.log_size;
+    match (statement.example, statement.backend) {
+        (Example::Fibonacci, _) => {
+            let fib = Fibonacci::new(log_size, statement.claim.unwrap());
+            verify_with_config(proof, &fib.air, channel, config)
+        }
+        (Example::WideFibonacci, Backend::Cpu) => {
+            verify_with_config(proof, &wide_fibonacci_cpu_air(log_size), channel, config)
+        }
+        (Example::WideFibonacci, Backend::Simd) => {
+            verify_with_config(proof, &wide_fibonacci_simd_air(log_size), channel, config)
+        }
+        (Example::Poseidon, _) => {
+            verify_with_config(proof, &poseidon_air(log_size), channel, config)
+        }
+    }
+}
+
+/// Returns the channel the examples' own tests use, seeded with the public claim.
+fn statement_channel(statement: &Statement) -> Blake2sChannel {
+    let public_input = statement.claim.into_iter().collect_vec();
+    Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&public_input)))
+}
+
+/// Returns the last element of the Fibonacci-squared sequence of length `2^log_size` proven by the
+/// [Fibonacci] example.
+fn fibonacci_claim(log_size: u32) -> BaseField {
+    let mut a = BaseField::from(1);
+    let mut b = BaseField::from(1);
+    for _ in 1..1u64 << log_size {
+        (a, b) = (b, a.square() + b.square());
+    }
+    a
+}
+
+/// The CPU component chains its rows with a lookup, so it proves a single long sequence rather
+/// than one sequence per row like its SIMD counterpart.
+fn wide_fibonacci_cpu_air(log_n_rows: u32) -> WideFibAir {
+    WideFibAir {
+        component: WideFibComponent {
+            log_fibonacci_size: LOG_N_COLUMNS as u32 + log_n_rows,
+            log_n_instances: 0,
+        },
+    }
+}
+
+fn wide_fibonacci_simd_air(log_n_rows: u32) -> SimdWideFibAir {
+    SimdWideFibAir {
+        component: SimdWideFibComponent {
+            log_fibonacci_size: LOG_N_COLUMNS as u32,
+            log_n_instances: log_n_rows,
+        },
+    }
+}
+
+/// Returns the AIR of the invocations of the permutation on the inputs `0..16`, `16..32`, etc.
+fn poseidon_air(log_n_instances: u32) -> PoseidonAir {
+    PoseidonAir {
+        component: PoseidonComponent {
+            log_n_rows: log_n_instances - poseidon::N_LOG_INSTANCES_PER_ROW as u32,
+        },
+        invocations: (0..1usize << log_n_instances)
+            .map(|i| Poseidon2Invocation::new(std::array::from_fn(|j| BaseField::from(16 * i + j))))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stwo_prover::core::fields::m31::BaseField;
+    use stwo_prover::core::pcs::PcsConfig;
+
+    use super::{fibonacci_claim, prove, verify, Backend, Example, Statement, MAX_LOG_SIZE};
+
+    #[test]
+    fn test_fibonacci_claim() {
+        // The claim used by the Fibonacci example's own tests.
+        assert_eq!(fibonacci_claim(5), BaseField::from(443693538));
+    }
+
+    #[test]
+    fn test_prove_and_verify_all_examples() {
+        for (example, backend, log_size) in [
+            (Example::Fibonacci, Backend::Cpu, 5),
+            (Example::WideFibonacci, Backend::Cpu, 3),
+            (Example::WideFibonacci, Backend::Simd, 6),
+            (Example::Poseidon, Backend::Simd, 9),
+        ] {
+            let statement = Statement::new(example, backend, log_size).unwrap();
+            let (proof, _) = prove(&statement, PcsConfig::default()).unwrap();
+            verify(&statement, PcsConfig::default(), proof).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_log_size_bounds() {
+        assert!(Statement::new(Example::Fibonacci, Backend::Cpu, MAX_LOG_SIZE + 1).is_err());
+        assert!(Statement::new(Example::Fibonacci, Backend::Cpu, u32::MAX).is_err());
+
+        let mut statement = Statement::new(Example::Fibonacci, Backend::Cpu, 5).unwrap();
+        let (proof, _) = prove(&statement, PcsConfig::default()).unwrap();
+        // As read from a crafted proof file.
+        statement.log_size = u32::MAX;
+
+        assert!(verify(&statement, PcsConfig::default(), proof).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_backend() {
+        assert!(Statement::new(Example::Poseidon, Backend::Cpu, 10).is_err());
+        assert!(Statement::new(Example::Fibonacci, Backend::Simd, 10).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! Command-line prover and verifier for the AIRs bundled in [stwo_prover::examples].
+//!
+//! ```text
+//! stwo prove --example poseidon --log-size 14 --output proof.json
+//! stwo verify --input proof.json
+//! ```
+//!
+//! Both commands take the same configuration arguments, and `verify` rejects proofs generated with
+//! another configuration.
+
+mod examples;
+mod proof_file;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use clap::{value_parser, Arg, ArgMatches, Command};
+use stwo_prover::core::fri::FriConfig;
+use stwo_prover::core::pcs::PcsConfig;
+use thiserror::Error;
+
+use crate::examples::{Backend, Example, Statement, MAX_LOG_SIZE};
+use crate::proof_file::ProofFile;
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+    let result = match matches.subcommand() {
+        Some(("prove", args)) => prove(args),
+        Some(("verify", args)) => verify(args),
+        _ => unreachable!("subcommand_required is set"),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cli() -> Command {
+    Command::new("stwo")
+        .about("Proves and verifies the example AIRs of stwo.")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("prove")
+                .about("Generates a proof for one of the example AIRs and writes it to a file.")
+                .arg(
+                    Arg::new("example")
+                        .long("example")
+                        .required(true)
+                        .value_parser(value_parser!(Example)),
+                )
+                .arg(
+                    Arg::new("log-size")
+                        .long("log-size")
+                        .required(true)
+                        .value_parser(value_parser!(u32).range(..=MAX_LOG_SIZE as i64))
+                        .help(
+                            "Log2 of the trace length (fibonacci), number of rows \
+                             (wide-fibonacci) or number of hash instances (poseidon).",
+                        ),
+                )
+                .arg(
+                    Arg::new("backend")
+                        .long("backend")
+                        .value_parser(value_parser!(Backend))
+                        .help("Defaults to the fastest backend supported by the example."),
+                )
+                .args(config_args())
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .short('o')
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Verifies a proof file written by `stwo prove`.")
+                .arg(
+                    Arg::new("input")
+                        .long("input")
+                        .short('i')
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .args(config_args()),
+        )
+}
+
+/// The arguments of the proof configuration. The verifier takes them from its own arguments rather
+/// than from the proof file, which could otherwise lower the security of the proof.
+fn config_args() -> [Arg; 4] {
+    let default_config = PcsConfig::default();
+    [
+        Arg::new("log-blowup-factor")
+            .long("log-blowup-factor")
+            .value_parser(value_parser!(u32).range(1..=1))
+            .default_value(default_config.fri_config.log_blowup_factor.to_string())
+            .help("FRI currently only supports a log blowup factor of 1."),
+        Arg::new("log-last-layer-degree-bound")
+            .long("log-last-layer-degree-bound")
+            .value_parser(value_parser!(u32).range(0..=10))
+            .default_value(
+                default_config
+                    .fri_config
+                    .log_last_layer_degree_bound
+                    .to_string(),
+            ),
+        Arg::new("n-queries")
+            .long("n-queries")
+            .value_parser(value_parser!(u64).range(1..))
+            .default_value(default_config.fri_config.n_queries.to_string()),
+        Arg::new("pow-bits")
+            .long("pow-bits")
+            .value_parser(value_parser!(u32).range(0..=64))
+            .default_value(default_config.pow_bits.to_string()),
+    ]
+}
+
+fn config(args: &ArgMatches) -> PcsConfig {
+    PcsConfig {
+        pow_bits: *args.get_one("pow-bits").unwrap(),
+        fri_config: FriConfig::new(
+            *args.get_one("log-last-layer-degree-bound").unwrap(),
+            *args.get_one("log-blowup-factor").unwrap(),
+            *args.get_one::<u64>("n-queries").unwrap() as usize,
+        ),
+    }
+}
+
+fn prove(args: &ArgMatches) -> Result<(), CliError> {
+    let example = *args.get_one::<Example>("example").unwrap();
+    let backend = args
+        .get_one::<Backend>("backend")
+        .copied()
+        .unwrap_or_else(|| example.default_backend());
+    let log_size = *args.get_one::<u32>("log-size").unwrap();
+    let config = config(args);
+    let output = args.get_one::<PathBuf>("output").unwrap();
+
+    let statement = Statement::new(example, backend, log_size)?;
+    println!("Proving {statement} with {}.", describe_config(&config));
+    let (proof, timings) = examples::prove(&statement, config)?;
+    println!(
+        "Trace generation: {}",
+        format_duration(timings.trace_generation)
+    );
+    println!("Proving: {}", format_duration(timings.proving));
+
+    let proof_file = ProofFile {
+        statement,
+        config,
+        proof,
+    };
+    let size = proof_file.write(output)?;
+    println!("Proof size: {size} bytes");
+    println!("Wrote proof to {}.", output.display());
+    Ok(())
+}
+
+fn verify(args: &ArgMatches) -> Result<(), CliError> {
+    let input = args.get_one::<PathBuf>("input").unwrap();
+    let config = config(args);
+    let (proof_file, size) = ProofFile::read(input)?;
+    let ProofFile {
+        statement,
+        config: proof_config,
+        proof,
+    } = proof_file;
+    if proof_config != config {
+        return Err(CliError::ConfigMismatch {
+            expected: describe_config(&config),
+            found: describe_config(&proof_config),
+        });
+    }
+    println!("Verifying {statement} with {}.", describe_config(&config));
+    println!("Proof size: {size} bytes");
+
+    let start = Instant::now();
+    examples::verify(&statement, config, proof)?;
+    println!("Verification: {}", format_duration(start.elapsed()));
+    println!("Proof is valid.");
+    Ok(())
+}
+
+fn describe_config(config: &PcsConfig) -> String {
+    format!(
+        "log blowup factor {}, log last layer degree bound {}, {} queries and {} PoW bits",
+        config.fri_config.log_blowup_factor,
+        config.fri_config.log_last_layer_degree_bound,
+        config.fri_config.n_queries,
+        config.pow_bits,
+    )
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.)
+}
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Statement(#[from] examples::InvalidStatement),
+    #[error("failed to access proof file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed proof file: {0}")]
+    Malformed(#[from] serde_json::Error),
+    #[error("the proof was generated with {found}, expected {expected}")]
+    ConfigMismatch { expected: String, found: String },
+    #[error("proving failed: {0}")]
+    Proving(#[from] stwo_prover::core::prover::ProvingError),
+    #[error("verification failed: {0}")]
+    Verification(#[from] stwo_prover::core::prover::VerificationError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cli;
+    use crate::examples::MAX_LOG_SIZE;
+
+    #[test]
+    fn test_cli_definition() {
+        cli().debug_assert();
+    }
+
+    #[test]
+    fn test_cli_rejects_unsound_or_oversized_arguments() {
+        let prove = |args: &[&str]| {
+            cli().try_get_matches_from(
+                [
+                    "stwo",
+                    "prove",
+                    "--example",
+                    "fibonacci",
+                    "--output",
+                    "proof.json",
+                ]
+                .iter()
+                .chain(args),
+            )
+        };
+        let max_log_size = MAX_LOG_SIZE.to_string();
+        let too_large_log_size = (MAX_LOG_SIZE + 1).to_string();
+
+        assert!(prove(&["--log-size", &max_log_size]).is_ok());
+        assert!(prove(&["--log-size", &too_large_log_size]).is_err());
+        assert!(prove(&["--log-size", "5", "--n-queries", "0"]).is_err());
+    }
+}
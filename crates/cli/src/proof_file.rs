@@ -0,0 +1,34 @@
+//! The on-disk format of proofs written by `stwo prove`.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use stwo_prover::core::pcs::PcsConfig;
+use stwo_prover::core::prover::StarkProof;
+
+use crate::examples::Statement;
+use crate::CliError;
+
+/// A proof together with everything needed to verify it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofFile {
+    pub statement: Statement,
+    pub config: PcsConfig,
+    pub proof: StarkProof,
+}
+
+impl ProofFile {
+    /// Writes the proof file to `path`, returning its size in bytes.
+    pub fn write(&self, path: &Path) -> Result<usize, CliError> {
+        let bytes = serde_json::to_vec(self)?;
+        fs::write(path, &bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Reads a proof file from `path`, returning it along with its size in bytes.
+    pub fn read(path: &Path) -> Result<(Self, usize), CliError> {
+        let bytes = fs::read(path)?;
+        Ok((serde_json::from_slice(&bytes)?, bytes.len()))
+    }
+}
@@ -0,0 +1,170 @@
+//! Scaling of the SIMD backend kernels with the number of threads.
+//! Run with `cargo bench --features parallel --bench parallel`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rayon::ThreadPoolBuilder;
+use stwo_prover::core::air::accumulation::AccumulationOps;
+use stwo_prover::core::backend::simd::SimdBackend;
+use stwo_prover::core::circle::SECURE_FIELD_CIRCLE_GEN;
+use stwo_prover::core::fields::m31::BaseField;
+use stwo_prover::core::fields::qm31::SecureField;
+use stwo_prover::core::fields::secure_column::SecureColumn;
+use stwo_prover::core::fri::FriOps;
+use stwo_prover::core::pcs::quotients::{ColumnSampleBatch, QuotientOps};
+use stwo_prover::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps, SecureEvaluation};
+use stwo_prover::core::poly::line::{LineDomain, LineEvaluation};
+use stwo_prover::core::poly::BitReversedOrder;
+
+const LOG_SIZE: u32 = 22;
+const LOG_N_QUOTIENT_COLS: u32 = 4;
+
+/// The numbers of threads to measure, up to the available parallelism.
+fn thread_counts() -> Vec<usize> {
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    (0..)
+        .map(|i| 1 << i)
+        .take_while(|&n| n < max_threads)
+        .chain([max_threads])
+        .collect()
+}
+
+/// Benchmarks `routine` on thread pools of every size in [thread_counts]. The input of the routine
+/// is generated by `setup`, outside of the measurement.
+fn bench_scaling<I: Send>(
+    c: &mut Criterion,
+    name: &str,
+    setup: impl Fn() -> I,
+    routine: impl Fn(I) + Sync,
+) {
+    let mut group = c.benchmark_group(format!("parallel {name} 2^{LOG_SIZE}"));
+    for n_threads in thread_counts() {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .build()
+            .unwrap();
+        group.bench_function(BenchmarkId::from_parameter(n_threads), |b| {
+            b.iter_batched(
+                &setup,
+                |input| pool.install(|| routine(input)),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn secure_column(log_size: u32) -> SecureColumn<SimdBackend> {
+    (0..1 << log_size)
+        .map(|i| SecureField::from_u32_unchecked(i, i + 1, i + 2, i + 3))
+        .collect()
+}
+
+fn fft_benches(c: &mut Criterion) {
+    let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+    let twiddles = SimdBackend::precompute_twiddles(domain.half_coset);
+    let values = (0..domain.size()).map(BaseField::from).collect();
+    let eval = CircleEvaluation::<SimdBackend, BaseField, BitReversedOrder>::new(domain, values);
+    let poly = eval.clone().interpolate_with_twiddles(&twiddles);
+
+    bench_scaling(
+        c,
+        "ifft",
+        || eval.clone(),
+        |eval| {
+            black_box(black_box(eval).interpolate_with_twiddles(&twiddles));
+        },
+    );
+    bench_scaling(
+        c,
+        "fft",
+        || (),
+        |()| {
+            black_box(black_box(&poly).evaluate_with_twiddles(domain, &twiddles));
+        },
+    );
+}
+
+fn quotients_benches(c: &mut Criterion) {
+    let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+    let values = (0..domain.size()).map(BaseField::from).collect();
+    let col = CircleEvaluation::<SimdBackend, BaseField, BitReversedOrder>::new(domain, values);
+    let cols = (0..1 << LOG_N_QUOTIENT_COLS)
+        .map(|_| col.clone())
+        .collect_vec();
+    let col_refs = cols.iter().collect_vec();
+    let random_coeff = SecureField::from_u32_unchecked(0, 1, 2, 3);
+    let value = SecureField::from_u32_unchecked(5, 6, 7, 8);
+    let samples = vec![ColumnSampleBatch {
+        point: SECURE_FIELD_CIRCLE_GEN,
+        columns_and_values: (0..1 << LOG_N_QUOTIENT_COLS).map(|i| (i, value)).collect(),
+    }];
+
+    bench_scaling(
+        c,
+        "quotients",
+        || (),
+        |()| {
+            black_box(SimdBackend::accumulate_quotients(
+                domain,
+                black_box(&col_refs),
+                random_coeff,
+                black_box(&samples),
+            ));
+        },
+    );
+}
+
+fn fri_benches(c: &mut Criterion) {
+    let alpha = SecureField::from_u32_unchecked(2213980, 2213981, 2213982, 2213983);
+    let circle_domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+    let twiddles = SimdBackend::precompute_twiddles(circle_domain.half_coset);
+    let line_domain = LineDomain::new(circle_domain.half_coset);
+    let line_eval = LineEvaluation::new(line_domain, secure_column(LOG_SIZE - 1));
+    let circle_eval = SecureEvaluation {
+        domain: circle_domain,
+        values: secure_column(LOG_SIZE),
+    };
+
+    bench_scaling(
+        c,
+        "fold_line",
+        || (),
+        |()| {
+            black_box(SimdBackend::fold_line(
+                black_box(&line_eval),
+                alpha,
+                &twiddles,
+            ));
+        },
+    );
+    bench_scaling(
+        c,
+        "fold_circle_into_line",
+        || LineEvaluation::new(line_domain, secure_column(LOG_SIZE - 1)),
+        |mut dst| {
+            SimdBackend::fold_circle_into_line(&mut dst, black_box(&circle_eval), alpha, &twiddles);
+            black_box(dst);
+        },
+    );
+}
+
+fn accumulation_benches(c: &mut Criterion) {
+    let other = secure_column(LOG_SIZE);
+
+    bench_scaling(
+        c,
+        "accumulate",
+        || secure_column(LOG_SIZE),
+        |mut column| {
+            SimdBackend::accumulate(&mut column, black_box(&other));
+            black_box(column);
+        },
+    );
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = fft_benches, quotients_benches, fri_benches, accumulation_benches);
+criterion_main!(benches);
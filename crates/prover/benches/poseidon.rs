@@ -6,21 +6,33 @@ use stwo_prover::core::fields::IntoSlice;
 use stwo_prover::core::prover::prove;
 use stwo_prover::core::vcs::blake2_hash::Blake2sHasher;
 use stwo_prover::core::vcs::hasher::Hasher;
-use stwo_prover::examples::poseidon::{gen_trace, PoseidonAir, PoseidonComponent};
+use stwo_prover::examples::poseidon::{
+    gen_trace, Poseidon2Invocation, PoseidonAir, PoseidonComponent,
+};
 
 pub fn simd_poseidon(c: &mut Criterion) {
     const LOG_N_ROWS: u32 = 15;
+    let inputs = (0..1 << (LOG_N_ROWS + 3))
+        .map(|i| std::array::from_fn(|j| BaseField::from(16 * i + j)))
+        .collect::<Vec<_>>();
+    let invocations = inputs
+        .iter()
+        .map(|&input| Poseidon2Invocation::new(input))
+        .collect::<Vec<_>>();
     let mut group = c.benchmark_group("poseidon2");
     group.throughput(Throughput::Elements(1u64 << (LOG_N_ROWS + 3)));
     group.bench_function(format!("poseidon2 2^{} instances", LOG_N_ROWS + 3), |b| {
         b.iter(|| {
             let component = PoseidonComponent {
-                log_n_instances: LOG_N_ROWS,
+                log_n_rows: LOG_N_ROWS,
             };
-            let trace = gen_trace(component.log_column_size());
+            let trace = gen_trace(component.log_column_size(), &inputs);
             let channel = &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
-            let air = PoseidonAir { component };
-            prove::<SimdBackend>(&air, channel, trace).unwrap()
+            let air = PoseidonAir {
+                component,
+                invocations: invocations.clone(),
+            };
+            prove::<SimdBackend, _>(&air, channel, trace).unwrap()
         });
     });
 }
@@ -0,0 +1,626 @@
+//! Lookup tables of bitwise operations on limbs of `LOG_LIMB` bits, for components operating on
+//! words that have no native representation in M31 (e.g. the 32-bit words of hash functions).
+//!
+//! A [BitwiseComponent] has a trace of `2^(2 * LOG_LIMB)` rows, one for each pair of limbs `(a,
+//! b)`, holding `a`, `b`, `c = a op b` and the number of lookups of the row. It yields each triple
+//! `(a, b, c)` to its [relation](BitwiseComponent::relation) with that multiplicity, through a
+//! [Logup]. The components computing bitwise operations split their words into limbs with
+//! [split_word], and use the triples of their limbs from the same relation.
+//!
+//! The tables are meant to be preprocessed: fixed columns committed once, whose root the verifier
+//! knows in advance. The prover only commits the trace, the interaction trace and the composition
+//! polynomial, so preprocessed tables are blocked on a preprocessed tree. Until then, `a`, `b` and
+//! `c` are committed in the trace together with the `2 * LOG_LIMB` bits of `a` and `b`, which let
+//! the constraints check every row bit by bit instead.
+
+use std::iter::zip;
+use std::marker::PhantomData;
+
+use itertools::{chain, Itertools};
+use num_traits::One;
+
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{Logup, LogupOps, LookupEntry, Multiplicity};
+use crate::core::air::{Component, ComponentProver, ComponentTrace};
+use crate::core::backend::{Backend, Column};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::coset_vanishing;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+const A: usize = 0;
+const B: usize = 1;
+const C: usize = 2;
+const MULTIPLICITY: usize = 3;
+/// The index of the first bit column. The bits of `a` come first, from the least significant.
+const BITS: usize = 4;
+
+/// A bitwise operation, applied to limbs bit by bit.
+pub trait BitwiseOp: Clone + Copy + Default + 'static {
+    const NAME: &'static str;
+
+    fn apply(a: u32, b: u32) -> u32;
+
+    /// Returns the result of the operation on two bits, as a polynomial in the bits.
+    fn apply_to_bits<F: ExtensionOf<BaseField>>(a: F, b: F) -> F;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Xor;
+
+impl BitwiseOp for Xor {
+    const NAME: &'static str = "xor";
+
+    fn apply(a: u32, b: u32) -> u32 {
+        a ^ b
+    }
+
+    fn apply_to_bits<F: ExtensionOf<BaseField>>(a: F, b: F) -> F {
+        a + b - a * b * BaseField::from(2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct And;
+
+impl BitwiseOp for And {
+    const NAME: &'static str = "and";
+
+    fn apply(a: u32, b: u32) -> u32 {
+        a & b
+    }
+
+    fn apply_to_bits<F: ExtensionOf<BaseField>>(a: F, b: F) -> F {
+        a * b
+    }
+}
+
+/// Splits a 32-bit word into limbs of `log_limb` bits, from the least significant. The last limb
+/// holds the remaining bits if `log_limb` does not divide 32.
+pub fn split_word(word: u32, log_limb: u32) -> Vec<BaseField> {
+    assert!(0 < log_limb && log_limb < 31);
+    (0..32)
+        .step_by(log_limb as usize)
+        .map(|shift| BaseField::from((word >> shift) & ((1 << log_limb) - 1)))
+        .collect()
+}
+
+/// A table of the bitwise operation `O` on limbs of `LOG_LIMB` bits. See the [module](self)
+/// documentation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BitwiseComponent<O: BitwiseOp, const LOG_LIMB: u32> {
+    _op: PhantomData<O>,
+}
+
+impl<O: BitwiseOp, const LOG_LIMB: u32> BitwiseComponent<O, LOG_LIMB> {
+    pub fn new() -> Self {
+        Self { _op: PhantomData }
+    }
+
+    pub fn log_size(&self) -> u32 {
+        2 * LOG_LIMB
+    }
+
+    pub fn n_columns(&self) -> usize {
+        BITS + 2 * LOG_LIMB as usize
+    }
+
+    /// Returns the relation the triples `(a, b, a op b)` are yielded to.
+    pub fn relation() -> String {
+        format!("{}_{LOG_LIMB}", O::NAME)
+    }
+
+    pub fn logup(&self) -> Logup {
+        Logup::new(
+            Self::relation(),
+            self.log_size(),
+            vec![LookupEntry::yields(
+                Self::relation(),
+                vec![A, B, C],
+                Multiplicity::Column(MULTIPLICITY),
+            )],
+        )
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.logup().interaction_element_ids()
+    }
+
+    fn n_table_constraints(&self) -> usize {
+        2 * LOG_LIMB as usize + 3
+    }
+
+    /// Returns the denominator of the table constraints at a point: the vanishing polynomial of the
+    /// trace domain.
+    fn table_constraint_denom<F: ExtensionOf<BaseField>>(&self, point: CirclePoint<F>) -> F {
+        coset_vanishing(CanonicCoset::new(self.log_size()).coset, point)
+    }
+
+    /// Evaluates the quotients of the table constraints, given the inverse of their denominator and
+    /// the values of the base trace columns of a row.
+    fn table_constraint_quotients<F: ExtensionOf<BaseField>>(
+        &self,
+        denom_inverse: F,
+        columns: impl Fn(usize) -> F,
+    ) -> Vec<F> {
+        let a_bits = (0..LOG_LIMB as usize)
+            .map(|i| columns(BITS + i))
+            .collect_vec();
+        let b_bits = (0..LOG_LIMB as usize)
+            .map(|i| columns(BITS + LOG_LIMB as usize + i))
+            .collect_vec();
+        let c_bits = zip(&a_bits, &b_bits)
+            .map(|(&a, &b)| O::apply_to_bits(a, b))
+            .collect_vec();
+        let from_bits = |bits: &[F]| {
+            bits.iter()
+                .rev()
+                .fold(F::zero(), |acc, &bit| acc * BaseField::from(2) + bit)
+        };
+        // The bits are boolean, and compose a, b and c.
+        chain![
+            chain(&a_bits, &b_bits).map(|&bit| bit * (bit - BaseField::one())),
+            [
+                columns(A) - from_bits(&a_bits),
+                columns(B) - from_bits(&b_bits),
+                columns(C) - from_bits(&c_bits),
+            ],
+        ]
+        .map(|numerator| numerator * denom_inverse)
+        .collect()
+    }
+}
+
+impl<O: BitwiseOp, const LOG_LIMB: u32> Component for BitwiseComponent<O, LOG_LIMB> {
+    fn n_constraints(&self) -> usize {
+        self.n_table_constraints() + self.logup().n_constraints()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.logup().max_constraint_log_degree_bound()
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        2
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        TreeVec::new(vec![
+            vec![self.log_size(); self.n_columns()],
+            self.logup().interaction_log_degree_bounds(),
+        ])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        TreeVec::new(vec![
+            vec![vec![point]; self.n_columns()],
+            self.logup().interaction_mask_points(point),
+        ])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let denom_inverse = self.table_constraint_denom(point).inverse();
+        let columns = |column: usize| mask[BASE_TRACE][column][0];
+        for quotient in self.table_constraint_quotients(denom_inverse, columns) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+}
+
+impl<B: LogupOps, O: BitwiseOp, const LOG_LIMB: u32> ComponentProver<B>
+    for BitwiseComponent<O, LOG_LIMB>
+{
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[BASE_TRACE][A].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let columns = &trace.evals[BASE_TRACE];
+
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, self.n_table_constraints())]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| [(self.table_constraint_denom(point), BaseField::one())],
+            |i, [denom_inverse]| {
+                self.table_constraint_quotients(denom_inverse, |column| {
+                    columns[column].values.at(i)
+                })
+            },
+        );
+
+        self.logup().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+
+    fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        self.logup().lookup_values(trace)
+    }
+}
+
+/// Counts the pairs of limbs looked up by other components, and writes the trace of a
+/// [BitwiseComponent].
+pub struct BitwiseTraceGenerator<B: Backend, O: BitwiseOp, const LOG_LIMB: u32> {
+    multiplicities: Vec<u32>,
+    _backend: PhantomData<B>,
+    _op: PhantomData<O>,
+}
+
+impl<B: Backend, O: BitwiseOp, const LOG_LIMB: u32> BitwiseTraceGenerator<B, O, LOG_LIMB> {
+    pub fn new() -> Self {
+        Self {
+            multiplicities: vec![0; 1 << (2 * LOG_LIMB)],
+            _backend: PhantomData,
+            _op: PhantomData,
+        }
+    }
+
+    /// Returns the number of lookups of each row, where the row of `(a, b)` is
+    /// `a * 2^LOG_LIMB + b`.
+    pub fn multiplicities(&self) -> &[u32] {
+        &self.multiplicities
+    }
+}
+
+impl<B: Backend, O: BitwiseOp, const LOG_LIMB: u32> Default
+    for BitwiseTraceGenerator<B, O, LOG_LIMB>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend + 'static, O: BitwiseOp, const LOG_LIMB: u32> ComponentGen
+    for BitwiseTraceGenerator<B, O, LOG_LIMB>
+{
+}
+
+impl<B: Backend + 'static, O: BitwiseOp, const LOG_LIMB: u32> ComponentTraceGenerator<B>
+    for BitwiseTraceGenerator<B, O, LOG_LIMB>
+{
+    type Component = BitwiseComponent<O, LOG_LIMB>;
+    type Inputs = Vec<[BaseField; 2]>;
+
+    /// Adds lookups of the operation on the pairs of limbs `inputs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a limb has more than `LOG_LIMB` bits.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        for [a, b] in inputs {
+            assert!(
+                a.0 < 1 << LOG_LIMB && b.0 < 1 << LOG_LIMB,
+                "({a}, {b}) are not limbs of {LOG_LIMB} bits"
+            );
+            self.multiplicities[((a.0 << LOG_LIMB) + b.0) as usize] += 1;
+        }
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let component = generator.component();
+        let rows = (0..1 << LOG_LIMB).cartesian_product(0..1 << LOG_LIMB);
+        let mut columns =
+            vec![Vec::with_capacity(1 << component.log_size()); component.n_columns()];
+        for ((a, b), &multiplicity) in rows.zip(&generator.multiplicities) {
+            let bits = |value: u32| (0..LOG_LIMB).map(move |i| (value >> i) & 1);
+            let row = [a, b, O::apply(a, b), multiplicity]
+                .into_iter()
+                .chain(bits(a))
+                .chain(bits(b));
+            for (column, value) in zip(&mut columns, row) {
+                column.push(BaseField::from(value));
+            }
+        }
+        let domain = CanonicCoset::new(component.log_size());
+        columns
+            .into_iter()
+            .map(|column| {
+                CircleEvaluation::new_canonical_ordered(domain, column.into_iter().collect())
+            })
+            .collect()
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        BitwiseComponent::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use itertools::{zip_eq, Itertools};
+    use num_traits::Zero;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{split_word, And, BitwiseComponent, BitwiseOp, BitwiseTraceGenerator, Xor, C};
+    use crate::core::air::logup::{
+        public_sum, relation_element_ids, verify_balanced_with_public, LogupOps, LookupDirection,
+        LookupError,
+    };
+    use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Backend, Column, CpuBackend};
+    use crate::core::channel::Blake2sChannel;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::FieldExpOps;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::CircleEvaluation;
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::prover::{prove, ProvingError};
+    use crate::core::test_utils::{draw_interaction_elements, prove_and_verify, test_channel};
+    use crate::core::utils::shifted_secure_combination;
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::ops::MerkleOps;
+    use crate::core::{ColumnVec, InteractionElements, LookupValues};
+    use crate::qm31;
+    use crate::trace_generation::registry::ComponentGenerationRegistry;
+    use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+    const LOG_LIMB: u32 = 4;
+    const BITWISE_ID: &str = "bitwise";
+
+    /// Looks up `O` on the limbs of the word pairs `pairs`, on behalf of the verifier.
+    #[derive(Clone)]
+    struct BitwiseAir<O: BitwiseOp> {
+        component: BitwiseComponent<O, LOG_LIMB>,
+        pairs: Vec<[u32; 2]>,
+    }
+
+    impl<O: BitwiseOp> BitwiseAir<O> {
+        fn new(pairs: Vec<[u32; 2]>) -> Self {
+            Self {
+                component: BitwiseComponent::new(),
+                pairs,
+            }
+        }
+    }
+
+    impl<O: BitwiseOp> Air for BitwiseAir<O> {
+        fn components(&self) -> Vec<&dyn Component> {
+            vec![&self.component]
+        }
+    }
+
+    impl<O: BitwiseOp> AirTraceVerifier for BitwiseAir<O> {
+        fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+            draw_interaction_elements(channel, self.component.interaction_element_ids())
+        }
+
+        fn verify_lookups(
+            &self,
+            interaction_elements: &InteractionElements,
+            lookup_values: &LookupValues,
+        ) -> Result<(), LookupError> {
+            let tuples = self.pairs.iter().flat_map(|&[a, b]| {
+                zip_eq(split_word(a, LOG_LIMB), split_word(b, LOG_LIMB))
+                    .map(|(a, b)| vec![a, b, BaseField::from(O::apply(a.0, b.0))])
+            });
+            let public_sum = public_sum(
+                &BitwiseComponent::<O, LOG_LIMB>::relation(),
+                LookupDirection::Use,
+                tuples,
+                interaction_elements,
+            );
+            verify_balanced_with_public(&[self.component.logup()], public_sum, lookup_values)
+        }
+    }
+
+    impl<B: LogupOps + 'static, O: BitwiseOp> AirTraceGenerator<B> for BitwiseAir<O> {
+        fn interact(
+            &self,
+            trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+            elements: &InteractionElements,
+        ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+            BitwiseTraceGenerator::<B, O, LOG_LIMB>::new()
+                .write_interaction_trace(&trace.iter().collect(), elements)
+        }
+
+        fn to_air_prover(&self) -> impl AirProver<B> {
+            self.clone()
+        }
+
+        fn composition_log_degree_bound(&self) -> u32 {
+            self.component.max_constraint_log_degree_bound()
+        }
+    }
+
+    impl<B: LogupOps, O: BitwiseOp> AirProver<B> for BitwiseAir<O> {
+        fn prover_components(&self) -> Vec<&dyn ComponentProver<B>> {
+            vec![&self.component]
+        }
+    }
+
+    /// Writes the trace of a table of `O`, with lookups of the limbs of the words in `pairs`
+    /// added by another component through the registry.
+    fn write_trace<B: Backend + 'static, O: BitwiseOp>(
+        pairs: &[[u32; 2]],
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let mut registry = ComponentGenerationRegistry::default();
+        registry.register(BITWISE_ID, BitwiseTraceGenerator::<B, O, LOG_LIMB>::new());
+        for &[a, b] in pairs {
+            let limbs = zip_eq(split_word(a, LOG_LIMB), split_word(b, LOG_LIMB))
+                .map(|(a, b)| [a, b])
+                .collect();
+            registry
+                .get_generator_mut::<BitwiseTraceGenerator<B, O, LOG_LIMB>>(BITWISE_ID)
+                .add_inputs(&limbs);
+        }
+        BitwiseTraceGenerator::<B, O, LOG_LIMB>::write_trace(BITWISE_ID, &mut registry)
+    }
+
+    fn random_pairs(n_pairs: usize) -> Vec<[u32; 2]> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..n_pairs).map(|_| rng.gen()).collect()
+    }
+
+    fn test_prove_and_verify<
+        B: LogupOps + MerkleOps<Blake2sMerkleHasher> + 'static,
+        O: BitwiseOp,
+    >() {
+        let air = BitwiseAir::<O>::new(random_pairs(100));
+        let trace = write_trace::<B, O>(&air.pairs);
+
+        prove_and_verify(&air, trace);
+    }
+
+    #[test]
+    fn test_xor_prove_cpu() {
+        test_prove_and_verify::<CpuBackend, Xor>();
+    }
+
+    #[test]
+    fn test_xor_prove_simd() {
+        test_prove_and_verify::<SimdBackend, Xor>();
+    }
+
+    #[test]
+    fn test_and_prove_cpu() {
+        test_prove_and_verify::<CpuBackend, And>();
+    }
+
+    #[test]
+    fn test_and_prove_simd() {
+        test_prove_and_verify::<SimdBackend, And>();
+    }
+
+    #[test]
+    fn test_claimed_sum() {
+        let pairs = random_pairs(10);
+        let component = BitwiseComponent::<Xor, LOG_LIMB>::new();
+        let relation = BitwiseComponent::<Xor, LOG_LIMB>::relation();
+        let [alpha, z] = [qm31!(5, 6, 7, 8), qm31!(1, 2, 3, 4)];
+        let elements = InteractionElements::new(BTreeMap::from_iter(zip_eq(
+            relation_element_ids(&relation),
+            [alpha, z],
+        )));
+        let trace = write_trace::<CpuBackend, Xor>(&pairs);
+        let interaction_trace = BitwiseTraceGenerator::<CpuBackend, Xor, LOG_LIMB>::new()
+            .write_interaction_trace(&trace.iter().collect(), &elements);
+        let [trace_polys, interaction_polys] = [trace, interaction_trace].map(|evals| {
+            evals
+                .into_iter()
+                .map(|eval| eval.interpolate())
+                .collect_vec()
+        });
+        let component_trace = ComponentTrace::new(
+            TreeVec::new(vec![
+                trace_polys.iter().collect(),
+                interaction_polys.iter().collect(),
+            ]),
+            TreeVec::new(vec![vec![], vec![]]),
+        );
+
+        let lookup_values = component.lookup_values(&component_trace);
+
+        let expected_sum: SecureField = pairs
+            .iter()
+            .flat_map(|&[a, b]| {
+                zip_eq(split_word(a, LOG_LIMB), split_word(b, LOG_LIMB)).map(|(a, b)| {
+                    let c = BaseField::from(a.0 ^ b.0);
+                    shifted_secure_combination(&[a, b, c], alpha, z).inverse()
+                })
+            })
+            .sum();
+        assert_eq!(component.logup().claimed_sum(&lookup_values), expected_sum);
+    }
+
+    #[test]
+    fn test_wrong_result_fails() {
+        let air = BitwiseAir::<Xor>::new(random_pairs(10));
+        let mut trace = write_trace::<CpuBackend, Xor>(&air.pairs);
+        let mut results = trace[C].values.to_cpu();
+        results[5] += BaseField::from(1);
+        trace[C] = CircleEvaluation::new(trace[C].domain, results);
+
+        let result = prove::<CpuBackend, _>(&air, &mut test_channel(), trace);
+
+        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
+    }
+
+    #[test]
+    #[should_panic(expected = "are not limbs of 4 bits")]
+    fn test_too_large_limb_panics() {
+        let mut generator = BitwiseTraceGenerator::<CpuBackend, Xor, LOG_LIMB>::new();
+
+        generator.add_inputs(&vec![[BaseField::zero(), BaseField::from(1 << LOG_LIMB)]]);
+    }
+
+    #[test]
+    fn test_multiplicities() {
+        let mut generator = BitwiseTraceGenerator::<CpuBackend, And, LOG_LIMB>::new();
+
+        generator.add_inputs(&vec![
+            [BaseField::zero(), BaseField::from(2)],
+            [BaseField::from(1), BaseField::zero()],
+            [BaseField::zero(), BaseField::from(2)],
+        ]);
+
+        assert_eq!(generator.multiplicities()[2], 2);
+        assert_eq!(generator.multiplicities()[1 << LOG_LIMB], 1);
+        assert_eq!(generator.multiplicities().iter().sum::<u32>(), 3);
+    }
+
+    #[test]
+    fn test_split_word() {
+        let word = 0x89abcdef;
+
+        assert_eq!(
+            split_word(word, 8),
+            [0xef, 0xcd, 0xab, 0x89].map(BaseField::from)
+        );
+        assert_eq!(
+            split_word(word, 12),
+            [0xdef, 0xabc, 0x89].map(BaseField::from)
+        );
+    }
+}
@@ -0,0 +1,707 @@
+//! A read-write memory, asserting that the values read by other components are the values last
+//! written to the same addresses.
+//!
+//! The memory is checked offline, with read and write sets: the state of each address is a tuple
+//! `(address, value, timestamp)` of a state relation. The verifier yields the initial state of
+//! each address, with the public initial memory at timestamp 0, see [initial_memory_sum]. A
+//! [MemoryComponent] has a row per access, using the previous state of the address and yielding
+//! its state after the access, which must have a later timestamp. A read leaves the value
+//! unchanged. Each address has a final row using its last state. As each state is used once, the
+//! accesses to an address form a chain, ordered by timestamp.
+//!
+//! The component yields each access `(address, value, timestamp, is_write)` to
+//! [MEMORY_RELATION], where the components issuing it use it. Their trace generators add their
+//! accesses to the [MemoryTraceGenerator] through the [ComponentGenerationRegistry]. The gaps
+//! between the timestamps of consecutive accesses to an address are looked up from a
+//! [RangeCheckComponent], so they must be at most `2^LOG_RANGE`, and the timestamps must stay below
+//! `P`.
+
+use std::collections::BTreeMap;
+use std::iter::zip;
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use num_traits::{One, Zero};
+
+use super::range_check::{RangeCheckComponent, RangeCheckTraceGenerator};
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{
+    public_sum, Logup, LogupOps, LookupDirection, LookupEntry, Multiplicity,
+};
+use crate::core::air::{Component, ComponentProver, ComponentTrace};
+use crate::core::backend::{Backend, Column};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::coset_vanishing;
+use crate::core::fields::m31::{BaseField, P};
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+/// The accesses to the memory, as tuples `(address, value, timestamp, is_write)`.
+pub const MEMORY_RELATION: &str = "memory";
+/// The states of the addresses, as tuples `(address, value, timestamp)`.
+const STATE_RELATION: &str = "memory_state";
+
+// The access, in the order of its tuple.
+const ADDRESS: usize = 0;
+const VALUE: usize = 1;
+const TIMESTAMP: usize = 2;
+const IS_WRITE: usize = 3;
+/// The state of the address before the access.
+const PREV_VALUE: usize = 4;
+const PREV_TIMESTAMP: usize = 5;
+/// The gap between the timestamps, minus one.
+const DELTA: usize = 6;
+const IS_READ: usize = 7;
+/// Whether the row ends the chain of its address, rather than accessing it.
+const IS_FINAL: usize = 8;
+const IS_ACCESS: usize = 9;
+/// Whether the row is an access or a final row, rather than padding.
+const ENABLED: usize = 10;
+const N_TRACE_COLUMNS: usize = 11;
+/// The number of constraints on the rows. The lookup constraints come after them.
+const N_ROW_CONSTRAINTS: usize = 8;
+
+/// An access to the memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub address: u32,
+    pub value: BaseField,
+    pub timestamp: u32,
+    pub is_write: bool,
+}
+
+impl MemoryAccess {
+    pub fn read(address: u32, value: BaseField, timestamp: u32) -> Self {
+        Self {
+            address,
+            value,
+            timestamp,
+            is_write: false,
+        }
+    }
+
+    pub fn write(address: u32, value: BaseField, timestamp: u32) -> Self {
+        Self {
+            address,
+            value,
+            timestamp,
+            is_write: true,
+        }
+    }
+
+    /// Returns the tuple of the access in [MEMORY_RELATION].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address or the timestamp is not below `P`, as it would be reduced.
+    pub fn tuple(&self) -> Vec<BaseField> {
+        self.check();
+        vec![
+            self.address.into(),
+            self.value,
+            self.timestamp.into(),
+            (self.is_write as u32).into(),
+        ]
+    }
+
+    fn check(&self) {
+        assert!(self.address < P, "address {} is not below P", self.address);
+        assert!(
+            self.timestamp < P,
+            "timestamp {} is not below P",
+            self.timestamp
+        );
+    }
+}
+
+/// Returns the sum of the fractions of the initial states of the addresses of `initial_memory`,
+/// yielded by the verifier.
+pub fn initial_memory_sum(
+    initial_memory: &[(u32, BaseField)],
+    interaction_elements: &InteractionElements,
+) -> SecureField {
+    public_sum(
+        STATE_RELATION,
+        LookupDirection::Yield,
+        initial_memory
+            .iter()
+            .map(|&(address, value)| vec![address.into(), value, BaseField::zero()]),
+        interaction_elements,
+    )
+}
+
+/// Checks the accesses to a memory. See the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct MemoryComponent<const LOG_RANGE: u32> {
+    pub log_n_rows: u32,
+}
+
+impl<const LOG_RANGE: u32> MemoryComponent<LOG_RANGE> {
+    pub fn new(log_n_rows: u32) -> Self {
+        // A chain has at most `2^log_n_rows` accesses, each at most `2^LOG_RANGE` after the last.
+        assert!(
+            log_n_rows + LOG_RANGE < 31,
+            "the timestamps may wrap around P"
+        );
+        Self { log_n_rows }
+    }
+
+    pub fn logup(&self) -> Logup {
+        Logup::new(
+            MEMORY_RELATION,
+            self.log_n_rows,
+            vec![
+                LookupEntry::uses(
+                    STATE_RELATION,
+                    vec![ADDRESS, PREV_VALUE, PREV_TIMESTAMP],
+                    Multiplicity::Column(ENABLED),
+                ),
+                LookupEntry::yields(
+                    STATE_RELATION,
+                    vec![ADDRESS, VALUE, TIMESTAMP],
+                    Multiplicity::Column(IS_ACCESS),
+                ),
+                LookupEntry::yields(
+                    MEMORY_RELATION,
+                    vec![ADDRESS, VALUE, TIMESTAMP, IS_WRITE],
+                    Multiplicity::Column(IS_ACCESS),
+                ),
+                LookupEntry::uses(
+                    RangeCheckComponent::<LOG_RANGE>::relation(),
+                    vec![DELTA],
+                    Multiplicity::Column(IS_ACCESS),
+                ),
+            ],
+        )
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.logup().interaction_element_ids()
+    }
+
+    /// Returns the denominator of the row constraints at a point: the vanishing polynomial of the
+    /// trace domain.
+    fn row_constraint_denom<F: ExtensionOf<BaseField>>(&self, point: CirclePoint<F>) -> F {
+        coset_vanishing(CanonicCoset::new(self.log_n_rows).coset, point)
+    }
+
+    /// Evaluates the quotients of the constraints of a row, given the inverse of their denominator
+    /// and the values of the columns of the row.
+    fn row_constraint_quotients<F: ExtensionOf<BaseField>>(
+        &self,
+        denom_inverse: F,
+        value: impl Fn(usize) -> F,
+    ) -> [F; N_ROW_CONSTRAINTS] {
+        let one = F::one();
+        let [is_read, is_write, is_final, is_access, enabled] =
+            [IS_READ, IS_WRITE, IS_FINAL, IS_ACCESS, ENABLED].map(&value);
+        [
+            is_read * (is_read - one),
+            is_write * (is_write - one),
+            is_final * (is_final - one),
+            enabled * (enabled - one),
+            enabled - is_read - is_write - is_final,
+            is_access - is_read - is_write,
+            // A read leaves the value unchanged.
+            is_read * (value(VALUE) - value(PREV_VALUE)),
+            is_access * (value(TIMESTAMP) - value(PREV_TIMESTAMP) - one - value(DELTA)),
+        ]
+        .map(|constraint| constraint * denom_inverse)
+    }
+}
+
+impl<const LOG_RANGE: u32> Component for MemoryComponent<LOG_RANGE> {
+    fn n_constraints(&self) -> usize {
+        N_ROW_CONSTRAINTS + self.logup().n_constraints()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        // The flag and chain constraints are quadratic, like the constraints of the state and
+        // access lookups.
+        self.logup().max_constraint_log_degree_bound()
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        2
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        TreeVec::new(vec![
+            vec![self.log_n_rows; N_TRACE_COLUMNS],
+            self.logup().interaction_log_degree_bounds(),
+        ])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        TreeVec::new(vec![
+            vec![vec![point]; N_TRACE_COLUMNS],
+            self.logup().interaction_mask_points(point),
+        ])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let denom_inverse = self.row_constraint_denom(point).inverse();
+        let value = |column: usize| mask[BASE_TRACE][column][0];
+        for quotient in self.row_constraint_quotients(denom_inverse, value) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+}
+
+impl<B: LogupOps, const LOG_RANGE: u32> ComponentProver<B> for MemoryComponent<LOG_RANGE> {
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[BASE_TRACE][ADDRESS].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let columns = &trace.evals[BASE_TRACE];
+
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, N_ROW_CONSTRAINTS)]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| [(self.row_constraint_denom(point), BaseField::one())],
+            |i, [denom_inverse]| {
+                self.row_constraint_quotients(denom_inverse, |column| columns[column].values.at(i))
+            },
+        );
+
+        self.logup().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+
+    fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        self.logup().lookup_values(trace)
+    }
+}
+
+/// Collects the accesses issued by other components, and writes the trace of a
+/// [MemoryComponent].
+///
+/// The trace generators of the other components add their accesses with
+/// [add_inputs](ComponentTraceGenerator::add_inputs), through the [ComponentGenerationRegistry],
+/// before the trace of the memory is written. The memory adds the gaps between the timestamps to
+/// the range check registered as `range_check_id`, whose trace must be written after it.
+pub struct MemoryTraceGenerator<B: Backend, const LOG_RANGE: u32> {
+    log_n_rows: u32,
+    initial_memory: Vec<(u32, BaseField)>,
+    accesses: Vec<MemoryAccess>,
+    range_check_id: String,
+    _backend: PhantomData<B>,
+}
+
+impl<B: Backend, const LOG_RANGE: u32> MemoryTraceGenerator<B, LOG_RANGE> {
+    pub fn new(
+        log_n_rows: u32,
+        initial_memory: Vec<(u32, BaseField)>,
+        range_check_id: impl Into<String>,
+    ) -> Self {
+        for &(address, _) in &initial_memory {
+            assert!(address < P, "address {address} is not below P");
+        }
+        Self {
+            log_n_rows,
+            initial_memory,
+            accesses: vec![],
+            range_check_id: range_check_id.into(),
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend + 'static, const LOG_RANGE: u32> ComponentGen
+    for MemoryTraceGenerator<B, LOG_RANGE>
+{
+}
+
+impl<B: Backend + 'static, const LOG_RANGE: u32> ComponentTraceGenerator<B>
+    for MemoryTraceGenerator<B, LOG_RANGE>
+{
+    type Component = MemoryComponent<LOG_RANGE>;
+    type Inputs = Vec<MemoryAccess>;
+
+    /// Adds accesses, in any order.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        self.accesses.extend(inputs);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if an address or a timestamp is not below `P`, if an access is to an address outside
+    /// the initial memory, if two accesses to an address have the same timestamp or timestamps too
+    /// far apart, or if a read does not return the last value written, as no valid trace exists
+    /// then.
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let log_n_rows = generator.log_n_rows;
+        let range_check_id = generator.range_check_id.clone();
+        let mut states = BTreeMap::from_iter(
+            generator
+                .initial_memory
+                .iter()
+                .map(|&(address, value)| (address, (value, 0))),
+        );
+        assert_eq!(
+            states.len(),
+            generator.initial_memory.len(),
+            "repeated address"
+        );
+        let accesses = generator
+            .accesses
+            .iter()
+            .sorted_by_key(|access| (access.address, access.timestamp))
+            .collect_vec();
+        assert!(
+            accesses.len() + states.len() <= 1 << log_n_rows,
+            "more than 2^{log_n_rows} rows"
+        );
+
+        let mut trace = vec![vec![BaseField::zero(); 1 << log_n_rows]; N_TRACE_COLUMNS];
+        let mut deltas = vec![];
+        let mut rows = vec![];
+        for access in accesses {
+            access.check();
+            let MemoryAccess {
+                address,
+                value,
+                timestamp,
+                is_write,
+            } = *access;
+            let Some(state) = states.get_mut(&address) else {
+                panic!("access to uninitialized address {address}");
+            };
+            let (prev_value, prev_timestamp) = *state;
+            assert!(
+                timestamp > prev_timestamp,
+                "accesses to address {address} at timestamp {timestamp}"
+            );
+            assert!(
+                is_write || value == prev_value,
+                "read of {value} at address {address}, holding {prev_value}"
+            );
+            let delta = BaseField::from(timestamp - prev_timestamp - 1);
+            deltas.push(delta);
+            *state = (value, timestamp);
+            rows.push([
+                address.into(),
+                value,
+                timestamp.into(),
+                (is_write as u32).into(),
+                prev_value,
+                prev_timestamp.into(),
+                delta,
+                (!is_write as u32).into(),
+                BaseField::zero(),
+                BaseField::one(),
+                BaseField::one(),
+            ]);
+        }
+        for (address, (value, timestamp)) in states {
+            let [address, timestamp] = [address, timestamp].map(BaseField::from);
+            rows.push([
+                address,
+                value,
+                timestamp,
+                BaseField::zero(),
+                value,
+                timestamp,
+                BaseField::zero(),
+                BaseField::zero(),
+                BaseField::one(),
+                BaseField::zero(),
+                BaseField::one(),
+            ]);
+        }
+        for (i, row) in rows.into_iter().enumerate() {
+            for (column, value) in zip(&mut trace, row) {
+                column[i] = value;
+            }
+        }
+        registry
+            .get_generator_mut::<RangeCheckTraceGenerator<B, LOG_RANGE>>(&range_check_id)
+            .add_inputs(&deltas);
+
+        let domain = CanonicCoset::new(log_n_rows).circle_domain();
+        trace
+            .into_iter()
+            .map(|column| CircleEvaluation::new(domain, column.into_iter().collect()))
+            .collect()
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        MemoryComponent::new(self.log_n_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        initial_memory_sum, MemoryAccess, MemoryComponent, MemoryTraceGenerator, MEMORY_RELATION,
+        PREV_VALUE, VALUE,
+    };
+    use crate::components::range_check::{RangeCheckComponent, RangeCheckTraceGenerator};
+    use crate::core::air::logup::{
+        public_sum, verify_balanced_with_public, LogupOps, LookupDirection, LookupError,
+    };
+    use crate::core::air::{Air, AirExt, AirProver, Component, ComponentProver};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Backend, Column, CpuBackend};
+    use crate::core::channel::Blake2sChannel;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::poly::circle::CircleEvaluation;
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::prover::{prove, verify, VerificationError, BASE_TRACE};
+    use crate::core::test_utils::{draw_interaction_elements, prove_and_verify, test_channel};
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::ops::MerkleOps;
+    use crate::core::{ColumnVec, InteractionElements, LookupValues};
+    use crate::trace_generation::registry::ComponentGenerationRegistry;
+    use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+    const LOG_RANGE: u32 = 4;
+    const LOG_N_ROWS: u32 = 5;
+    const MEMORY_ID: &str = "memory";
+    const RANGE_CHECK_ID: &str = "range_check";
+
+    /// An AIR proving public accesses to a public initial memory, where the verifier issues the
+    /// accesses.
+    #[derive(Clone)]
+    struct MemoryAir {
+        memory: MemoryComponent<LOG_RANGE>,
+        range_check: RangeCheckComponent<LOG_RANGE>,
+        initial_memory: Vec<(u32, BaseField)>,
+        accesses: Vec<MemoryAccess>,
+    }
+
+    impl Air for MemoryAir {
+        fn components(&self) -> Vec<&dyn Component> {
+            vec![&self.memory, &self.range_check]
+        }
+    }
+
+    impl AirTraceVerifier for MemoryAir {
+        fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+            let ids = self.memory.interaction_element_ids().into_iter();
+            draw_interaction_elements(
+                channel,
+                ids.chain(self.range_check.interaction_element_ids()),
+            )
+        }
+
+        fn verify_lookups(
+            &self,
+            interaction_elements: &InteractionElements,
+            lookup_values: &LookupValues,
+        ) -> Result<(), LookupError> {
+            let public_sum = initial_memory_sum(&self.initial_memory, interaction_elements)
+                + public_sum(
+                    MEMORY_RELATION,
+                    LookupDirection::Use,
+                    self.accesses.iter().map(MemoryAccess::tuple),
+                    interaction_elements,
+                );
+            verify_balanced_with_public(
+                &[self.memory.logup(), self.range_check.logup()],
+                public_sum,
+                lookup_values,
+            )
+        }
+    }
+
+    impl<B: LogupOps + 'static> AirTraceGenerator<B> for MemoryAir {
+        fn interact(
+            &self,
+            trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+            elements: &InteractionElements,
+        ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+            let n_memory_columns = self.memory.trace_log_degree_bounds()[BASE_TRACE].len();
+            let (memory_trace, range_check_trace) = trace.split_at(n_memory_columns);
+            let mut interaction_trace = self
+                .memory
+                .logup()
+                .write_interaction_trace(&memory_trace.iter().collect(), elements);
+            interaction_trace.extend(
+                self.range_check
+                    .logup()
+                    .write_interaction_trace(&range_check_trace.iter().collect(), elements),
+            );
+            interaction_trace
+        }
+
+        fn to_air_prover(&self) -> impl AirProver<B> {
+            self.clone()
+        }
+
+        fn composition_log_degree_bound(&self) -> u32 {
+            AirExt::composition_log_degree_bound(self)
+        }
+    }
+
+    impl<B: LogupOps> AirProver<B> for MemoryAir {
+        fn prover_components(&self) -> Vec<&dyn ComponentProver<B>> {
+            vec![&self.memory, &self.range_check]
+        }
+    }
+
+    fn test_air() -> MemoryAir {
+        let initial_memory = (0..4)
+            .map(|address| (address, BaseField::from(address)))
+            .collect();
+        let accesses = [
+            MemoryAccess::read(2, 2.into(), 1),
+            MemoryAccess::write(2, 7.into(), 2),
+            MemoryAccess::write(0, 5.into(), 3),
+            MemoryAccess::read(2, 7.into(), 4),
+            MemoryAccess::read(0, 5.into(), 10),
+            MemoryAccess::write(2, 8.into(), 12),
+            MemoryAccess::read(3, 3.into(), 13),
+            MemoryAccess::read(2, 8.into(), 20),
+        ];
+        MemoryAir {
+            memory: MemoryComponent::new(LOG_N_ROWS),
+            range_check: RangeCheckComponent,
+            initial_memory,
+            accesses: accesses.to_vec(),
+        }
+    }
+
+    /// Writes the trace of the memory of `air`, whose accesses are added by other components
+    /// through the registry.
+    fn write_trace<B: Backend + 'static>(
+        air: &MemoryAir,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let mut registry = ComponentGenerationRegistry::default();
+        registry.register(
+            MEMORY_ID,
+            MemoryTraceGenerator::<B, LOG_RANGE>::new(
+                LOG_N_ROWS,
+                air.initial_memory.clone(),
+                RANGE_CHECK_ID,
+            ),
+        );
+        registry.register(
+            RANGE_CHECK_ID,
+            RangeCheckTraceGenerator::<B, LOG_RANGE>::new(),
+        );
+        for chunk in air.accesses.chunks(3) {
+            registry
+                .get_generator_mut::<MemoryTraceGenerator<B, LOG_RANGE>>(MEMORY_ID)
+                .add_inputs(&chunk.to_vec());
+        }
+
+        let mut trace = MemoryTraceGenerator::<B, LOG_RANGE>::write_trace(MEMORY_ID, &mut registry);
+        trace.extend(RangeCheckTraceGenerator::<B, LOG_RANGE>::write_trace(
+            RANGE_CHECK_ID,
+            &mut registry,
+        ));
+        trace
+    }
+
+    fn test_prove_and_verify<B: LogupOps + MerkleOps<Blake2sMerkleHasher> + 'static>() {
+        let air = test_air();
+        let trace = write_trace::<B>(&air);
+
+        prove_and_verify(&air, trace);
+    }
+
+    #[test]
+    fn test_memory_prove_cpu() {
+        test_prove_and_verify::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_memory_prove_simd() {
+        test_prove_and_verify::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_tampered_read_fails_verification() {
+        let air = test_air();
+        let mut trace = write_trace::<CpuBackend>(&air);
+        // The read at timestamp 4 returns 9 instead of 7. Its row still reads the value it
+        // returns, so only the chain of the address breaks.
+        let row = 4;
+        for column in [VALUE, PREV_VALUE] {
+            let mut values = trace[column].values.to_cpu();
+            assert_eq!(values[row], BaseField::from(7));
+            values[row] = BaseField::from(9);
+            trace[column] = CircleEvaluation::new(trace[column].domain, values);
+        }
+        let mut tampered_air = air.clone();
+        tampered_air.accesses[3].value = BaseField::from(9);
+        let proof = prove::<CpuBackend, _>(&tampered_air, &mut test_channel(), trace).unwrap();
+
+        let result = verify(proof, &tampered_air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "read of 9 at address 2, holding 7")]
+    fn test_inconsistent_read_has_no_trace() {
+        let mut air = test_air();
+        air.accesses[3].value = BaseField::from(9);
+
+        write_trace::<CpuBackend>(&air);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp 2147483648 is not below P")]
+    fn test_timestamp_out_of_field_has_no_tuple() {
+        MemoryAccess::read(0, BaseField::from(0), 1 << 31).tuple();
+    }
+
+    #[test]
+    #[should_panic(expected = "the timestamps may wrap around P")]
+    fn test_memory_too_large_for_range() {
+        MemoryComponent::<16>::new(15);
+    }
+}
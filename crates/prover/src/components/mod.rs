@@ -0,0 +1,5 @@
+//! Reusable components, meant to be composed with the components of an application into an AIR.
+
+pub mod bitwise;
+pub mod memory;
+pub mod range_check;
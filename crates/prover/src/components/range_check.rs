@@ -0,0 +1,497 @@
+//! A range check, asserting that the values looked up by other components are in
+//! `[0, 2^LOG_RANGE)`.
+//!
+//! A [RangeCheckComponent] has a trace of `2^LOG_RANGE` rows: a table column holding the values
+//! `0..2^LOG_RANGE` in coset order, and a multiplicity column holding the number of lookups of
+//! each value. It yields each value of the table to its [relation](RangeCheckComponent::relation)
+//! with that multiplicity, through a [Logup]. The components looking up values use them from the
+//! same relation.
+//!
+//! The table column is committed with the trace, so it is constrained to start at 0 and to
+//! increase by 1 from each row to the next.
+
+use std::marker::PhantomData;
+
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{Logup, LogupOps, LookupEntry, Multiplicity};
+use crate::core::air::{Component, ComponentProver, ComponentTrace};
+use crate::core::backend::{Backend, Column};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::{coset_vanishing, point_excluder, point_vanishing_fraction};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::ExtensionOf;
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::utils::previous_bit_reversed_circle_domain_index;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+const TABLE: usize = 0;
+const MULTIPLICITY: usize = 1;
+const N_TRACE_COLUMNS: usize = 2;
+/// The number of constraints on the table column. The lookup constraints come after them.
+const N_TABLE_CONSTRAINTS: usize = 2;
+
+/// Checks that values are in `[0, 2^LOG_RANGE)`. See the [module](self) documentation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RangeCheckComponent<const LOG_RANGE: u32>;
+
+impl<const LOG_RANGE: u32> RangeCheckComponent<LOG_RANGE> {
+    /// Returns the relation the values in range are yielded to.
+    pub fn relation() -> String {
+        format!("range_check_{LOG_RANGE}")
+    }
+
+    pub fn logup(&self) -> Logup {
+        Logup::new(
+            Self::relation(),
+            LOG_RANGE,
+            vec![LookupEntry::yields(
+                Self::relation(),
+                vec![TABLE],
+                Multiplicity::Column(MULTIPLICITY),
+            )],
+        )
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.logup().interaction_element_ids()
+    }
+
+    /// Returns the denominators of the table constraints at a point, see
+    /// [domain_denom_inverses](crate::core::constraints::domain_denom_inverses).
+    fn table_constraint_denoms<F: ExtensionOf<BaseField>>(
+        &self,
+        point: CirclePoint<F>,
+    ) -> [(F, F); N_TABLE_CONSTRAINTS] {
+        let domain = CanonicCoset::new(LOG_RANGE).coset;
+        let first_point = domain.at(0);
+        [
+            point_vanishing_fraction(first_point, point),
+            // The step constraint holds on all the rows except the first.
+            (
+                coset_vanishing(domain, point),
+                point_excluder(first_point, point),
+            ),
+        ]
+    }
+
+    /// Evaluates the quotients of the table constraints, given the inverses of their denominators
+    /// and the table column at a point and at the previous point.
+    fn table_constraint_quotients<F: ExtensionOf<BaseField>>(
+        &self,
+        [first_inverse, step_inverse]: [F; N_TABLE_CONSTRAINTS],
+        table: F,
+        prev_table: F,
+    ) -> [F; N_TABLE_CONSTRAINTS] {
+        // The table starts at 0 and increases by 1 in each row.
+        [
+            table * first_inverse,
+            (table - prev_table - F::one()) * step_inverse,
+        ]
+    }
+}
+
+impl<const LOG_RANGE: u32> Component for RangeCheckComponent<LOG_RANGE> {
+    fn n_constraints(&self) -> usize {
+        N_TABLE_CONSTRAINTS + self.logup().n_constraints()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.logup().max_constraint_log_degree_bound()
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        2
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        TreeVec::new(vec![
+            vec![LOG_RANGE; N_TRACE_COLUMNS],
+            self.logup().interaction_log_degree_bounds(),
+        ])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        let prev_point = point - CanonicCoset::new(LOG_RANGE).step().into_ef();
+        TreeVec::new(vec![
+            vec![vec![point, prev_point], vec![point]],
+            self.logup().interaction_mask_points(point),
+        ])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let [table, prev_table] = mask[BASE_TRACE][TABLE][..] else {
+            unreachable!()
+        };
+        let denom_inverses = self
+            .table_constraint_denoms(point)
+            .map(|(numerator, denominator)| denominator / numerator);
+        for quotient in self.table_constraint_quotients(denom_inverses, table, prev_table) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+}
+
+impl<B: LogupOps, const LOG_RANGE: u32> ComponentProver<B> for RangeCheckComponent<LOG_RANGE> {
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[BASE_TRACE][TABLE].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let table = &trace.evals[BASE_TRACE][TABLE].values;
+
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, N_TABLE_CONSTRAINTS)]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| self.table_constraint_denoms(point),
+            |i, denom_inverses| {
+                let prev_i = previous_bit_reversed_circle_domain_index(i, LOG_RANGE, eval_log_size);
+                self.table_constraint_quotients(denom_inverses, table.at(i), table.at(prev_i))
+            },
+        );
+
+        self.logup().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+
+    fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        self.logup().lookup_values(trace)
+    }
+}
+
+/// Counts the values looked up by other components, and writes the trace of a
+/// [RangeCheckComponent].
+///
+/// The trace generators of the other components add the values they look up with
+/// [add_inputs](ComponentTraceGenerator::add_inputs), through the
+/// [ComponentGenerationRegistry], before the trace of the range check is written.
+pub struct RangeCheckTraceGenerator<B: Backend, const LOG_RANGE: u32> {
+    multiplicities: Vec<u32>,
+    _backend: PhantomData<B>,
+}
+
+impl<B: Backend, const LOG_RANGE: u32> RangeCheckTraceGenerator<B, LOG_RANGE> {
+    pub fn new() -> Self {
+        Self {
+            multiplicities: vec![0; 1 << LOG_RANGE],
+            _backend: PhantomData,
+        }
+    }
+
+    /// Returns the number of lookups of each value.
+    pub fn multiplicities(&self) -> &[u32] {
+        &self.multiplicities
+    }
+}
+
+impl<B: Backend, const LOG_RANGE: u32> Default for RangeCheckTraceGenerator<B, LOG_RANGE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend + 'static, const LOG_RANGE: u32> ComponentGen
+    for RangeCheckTraceGenerator<B, LOG_RANGE>
+{
+}
+
+impl<B: Backend + 'static, const LOG_RANGE: u32> ComponentTraceGenerator<B>
+    for RangeCheckTraceGenerator<B, LOG_RANGE>
+{
+    type Component = RangeCheckComponent<LOG_RANGE>;
+    type Inputs = Vec<BaseField>;
+
+    /// Adds lookups of `inputs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value is out of range, as no valid trace exists then.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        for value in inputs {
+            assert!(
+                value.0 < 1 << LOG_RANGE,
+                "{value} is out of the range [0, 2^{LOG_RANGE})"
+            );
+            self.multiplicities[value.0 as usize] += 1;
+        }
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let domain = CanonicCoset::new(LOG_RANGE);
+        let table = (0..1 << LOG_RANGE).map(BaseField::from_u32_unchecked);
+        let multiplicities = generator.multiplicities.iter().map(|&m| m.into());
+        vec![
+            CircleEvaluation::new_canonical_ordered(domain, table.collect()),
+            CircleEvaluation::new_canonical_ordered(domain, multiplicities.collect()),
+        ]
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        RangeCheckComponent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use itertools::{zip_eq, Itertools};
+    use num_traits::Zero;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{RangeCheckComponent, RangeCheckTraceGenerator};
+    use crate::core::air::logup::{
+        public_sum, relation_element_ids, verify_balanced_with_public, LogupOps, LookupDirection,
+        LookupError,
+    };
+    use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Backend, CpuBackend};
+    use crate::core::channel::Blake2sChannel;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::FieldExpOps;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::CircleEvaluation;
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::prover::{prove, verify, VerificationError};
+    use crate::core::test_utils::{draw_interaction_elements, prove_and_verify, test_channel};
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::ops::MerkleOps;
+    use crate::core::{ColumnVec, InteractionElements, LookupValues};
+    use crate::qm31;
+    use crate::trace_generation::registry::ComponentGenerationRegistry;
+    use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+    const LOG_RANGE: u32 = 8;
+    const RANGE_CHECK_ID: &str = "range_check";
+
+    /// Range checks `values`, looked up by a component standing for the verifier.
+    #[derive(Clone)]
+    struct RangeCheckAir {
+        component: RangeCheckComponent<LOG_RANGE>,
+        values: Vec<BaseField>,
+    }
+
+    impl Air for RangeCheckAir {
+        fn components(&self) -> Vec<&dyn Component> {
+            vec![&self.component]
+        }
+    }
+
+    impl AirTraceVerifier for RangeCheckAir {
+        fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+            draw_interaction_elements(channel, self.component.interaction_element_ids())
+        }
+
+        fn verify_lookups(
+            &self,
+            interaction_elements: &InteractionElements,
+            lookup_values: &LookupValues,
+        ) -> Result<(), LookupError> {
+            let public_sum = public_sum(
+                &RangeCheckComponent::<LOG_RANGE>::relation(),
+                LookupDirection::Use,
+                self.values.iter().map(|&value| vec![value]),
+                interaction_elements,
+            );
+            verify_balanced_with_public(&[self.component.logup()], public_sum, lookup_values)
+        }
+    }
+
+    impl<B: LogupOps + 'static> AirTraceGenerator<B> for RangeCheckAir {
+        fn interact(
+            &self,
+            trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+            elements: &InteractionElements,
+        ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+            RangeCheckTraceGenerator::<B, LOG_RANGE>::new()
+                .write_interaction_trace(&trace.iter().collect(), elements)
+        }
+
+        fn to_air_prover(&self) -> impl AirProver<B> {
+            self.clone()
+        }
+
+        fn composition_log_degree_bound(&self) -> u32 {
+            self.component.max_constraint_log_degree_bound()
+        }
+    }
+
+    impl<B: LogupOps> AirProver<B> for RangeCheckAir {
+        fn prover_components(&self) -> Vec<&dyn ComponentProver<B>> {
+            vec![&self.component]
+        }
+    }
+
+    /// Writes the trace of a range check of `values`, added by another component through the
+    /// registry.
+    fn write_trace<B: Backend + 'static>(
+        values: &[BaseField],
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let mut registry = ComponentGenerationRegistry::default();
+        registry.register(
+            RANGE_CHECK_ID,
+            RangeCheckTraceGenerator::<B, LOG_RANGE>::new(),
+        );
+        for chunk in values.chunks(10) {
+            registry
+                .get_generator_mut::<RangeCheckTraceGenerator<B, LOG_RANGE>>(RANGE_CHECK_ID)
+                .add_inputs(&chunk.to_vec());
+        }
+        RangeCheckTraceGenerator::<B, LOG_RANGE>::write_trace(RANGE_CHECK_ID, &mut registry)
+    }
+
+    fn random_values(n_values: usize) -> Vec<BaseField> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..n_values)
+            .map(|_| BaseField::from(rng.gen_range(0..1 << LOG_RANGE)))
+            .collect()
+    }
+
+    fn test_prove_and_verify<B: LogupOps + MerkleOps<Blake2sMerkleHasher> + 'static>() {
+        let air = RangeCheckAir {
+            component: RangeCheckComponent,
+            values: random_values(1000),
+        };
+        let trace = write_trace::<B>(&air.values);
+
+        prove_and_verify(&air, trace);
+    }
+
+    #[test]
+    fn test_range_check_prove_cpu() {
+        test_prove_and_verify::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_range_check_prove_simd() {
+        test_prove_and_verify::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_claimed_sum() {
+        let values = random_values(100);
+        let component = RangeCheckComponent::<LOG_RANGE>;
+        let relation = RangeCheckComponent::<LOG_RANGE>::relation();
+        let z = qm31!(1, 2, 3, 4);
+        let elements = InteractionElements::new(BTreeMap::from_iter(zip_eq(
+            relation_element_ids(&relation),
+            [qm31!(5, 6, 7, 8), z],
+        )));
+        let trace = write_trace::<CpuBackend>(&values);
+        let interaction_trace = RangeCheckTraceGenerator::<CpuBackend, LOG_RANGE>::new()
+            .write_interaction_trace(&trace.iter().collect(), &elements);
+        let [trace_polys, interaction_polys] = [trace, interaction_trace].map(|evals| {
+            evals
+                .into_iter()
+                .map(|eval| eval.interpolate())
+                .collect_vec()
+        });
+        let component_trace = ComponentTrace::new(
+            TreeVec::new(vec![
+                trace_polys.iter().collect(),
+                interaction_polys.iter().collect(),
+            ]),
+            TreeVec::new(vec![vec![], vec![]]),
+        );
+
+        let lookup_values = component.lookup_values(&component_trace);
+
+        let expected_sum: SecureField = values.iter().map(|&v| (v - z).inverse()).sum();
+        assert_eq!(component.logup().claimed_sum(&lookup_values), expected_sum);
+    }
+
+    #[test]
+    fn test_wrong_claimed_sum_fails_verification() {
+        let air = RangeCheckAir {
+            component: RangeCheckComponent,
+            values: random_values(100),
+        };
+        let trace = write_trace::<CpuBackend>(&air.values);
+        let mut proof = prove::<CpuBackend, _>(&air, &mut test_channel(), trace).unwrap();
+        let claimed_sum_id = air.component.logup().claimed_sum_ids()[0].clone();
+        let claimed_sum = proof.lookup_values[&claimed_sum_id];
+        proof
+            .lookup_values
+            .extend(LookupValues::new(BTreeMap::from([(
+                claimed_sum_id,
+                claimed_sum + BaseField::from(1),
+            )])));
+
+        let result = verify(proof, &air, &mut test_channel());
+
+        // The claimed sum no longer balances the values the verifier looks up.
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the range")]
+    fn test_out_of_range_input_panics() {
+        let mut generator = RangeCheckTraceGenerator::<CpuBackend, LOG_RANGE>::new();
+
+        generator.add_inputs(&vec![BaseField::from(1 << LOG_RANGE)]);
+    }
+
+    #[test]
+    fn test_multiplicities() {
+        let mut generator = RangeCheckTraceGenerator::<CpuBackend, LOG_RANGE>::new();
+
+        generator.add_inputs(&vec![
+            BaseField::zero(),
+            BaseField::from(3),
+            BaseField::zero(),
+        ]);
+
+        assert_eq!(generator.multiplicities()[..4], [2, 0, 0, 1]);
+    }
+}
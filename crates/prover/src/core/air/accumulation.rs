@@ -3,42 +3,49 @@
 //! defined as
 //!   f(p) = sum_i alpha^{N-1-i} u_i(P).
 
+use std::array;
+use std::iter::zip;
+
 use itertools::Itertools;
 use tracing::{span, Level};
 
 use crate::core::backend::{Backend, CpuBackend};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::domain_denom_inverses;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SecureColumn;
-use crate::core::fields::FieldOps;
-use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly, SecureCirclePoly};
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::{FieldOps, SecureExtensionField};
+use crate::core::poly::circle::{
+    CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, SecureCirclePoly,
+};
 use crate::core::poly::BitReversedOrder;
-use crate::core::utils::generate_secure_powers;
+use crate::core::utils::generate_powers;
 
 /// Accumulates N evaluations of u_i(P0) at a single point.
 /// Computes f(P0), the combined polynomial at that point.
 /// For n accumulated evaluations, the i'th evaluation is multiplied by alpha^(N-1-i).
-pub struct PointEvaluationAccumulator {
-    random_coeff: SecureField,
-    accumulation: SecureField,
+pub struct PointEvaluationAccumulator<F: SecureExtensionField = SecureField> {
+    random_coeff: F,
+    accumulation: F,
 }
 
-impl PointEvaluationAccumulator {
+impl<F: SecureExtensionField> PointEvaluationAccumulator<F> {
     /// Creates a new accumulator.
     /// `random_coeff` should be a secure random field element, drawn from the channel.
-    pub fn new(random_coeff: SecureField) -> Self {
+    pub fn new(random_coeff: F) -> Self {
         Self {
             random_coeff,
-            accumulation: SecureField::default(),
+            accumulation: F::default(),
         }
     }
 
     /// Accumulates u_i(P0), a polynomial evaluation at a P0 in reverse order.
-    pub fn accumulate(&mut self, evaluation: SecureField) {
+    pub fn accumulate(&mut self, evaluation: F) {
         self.accumulation = self.accumulation * self.random_coeff + evaluation;
     }
 
-    pub fn finalize(self) -> SecureField {
+    pub fn finalize(self) -> F {
         self.accumulation
     }
 }
@@ -46,23 +53,23 @@ impl PointEvaluationAccumulator {
 // TODO(ShaharS), rename terminology to constraints instead of columns.
 /// Accumulates evaluations of u_i(P), each at an evaluation domain of the size of that polynomial.
 /// Computes the coefficients of f(P).
-pub struct DomainEvaluationAccumulator<B: Backend> {
-    random_coeff_powers: Vec<SecureField>,
+pub struct DomainEvaluationAccumulator<B: Backend, F: SecureExtensionField = SecureField> {
+    random_coeff_powers: Vec<F>,
     /// Accumulated evaluations for each log_size.
     /// Each `sub_accumulation` holds the sum over all columns i of that log_size, of
     /// `evaluation_i * alpha^(N - 1 - i)`
     /// where `N` is the total number of evaluations.
-    sub_accumulations: Vec<Option<SecureColumn<B>>>,
+    sub_accumulations: Vec<Option<F::Column<B>>>,
 }
 
-impl<B: Backend> DomainEvaluationAccumulator<B> {
+impl<B: Backend, F: SecureExtensionField> DomainEvaluationAccumulator<B, F> {
     /// Creates a new accumulator.
     /// `random_coeff` should be a secure random field element, drawn from the channel.
     /// `max_log_size` is the maximum log_size of the accumulated evaluations.
-    pub fn new(random_coeff: SecureField, max_log_size: u32, total_columns: usize) -> Self {
+    pub fn new(random_coeff: F, max_log_size: u32, total_columns: usize) -> Self {
         let max_log_size = max_log_size as usize;
         Self {
-            random_coeff_powers: generate_secure_powers(random_coeff, total_columns),
+            random_coeff_powers: generate_powers(random_coeff, total_columns),
             sub_accumulations: (0..(max_log_size + 1)).map(|_| None).collect(),
         }
     }
@@ -75,7 +82,7 @@ impl<B: Backend> DomainEvaluationAccumulator<B> {
     pub fn columns<const N: usize>(
         &mut self,
         n_cols_per_size: [(u32, usize); N],
-    ) -> [ColumnAccumulator<'_, B>; N] {
+    ) -> [ColumnAccumulator<'_, B, F>; N] {
         self.sub_accumulations
             .get_many_mut(n_cols_per_size.map(|(log_size, _)| log_size as usize))
             .unwrap_or_else(|e| panic!("invalid log_sizes: {}", e))
@@ -87,7 +94,7 @@ impl<B: Backend> DomainEvaluationAccumulator<B> {
                     .split_off(self.random_coeff_powers.len() - n_cols);
                 ColumnAccumulator {
                     random_coeff_powers: random_coeffs,
-                    col: col.get_or_insert_with(|| SecureColumn::zeros(1 << log_size)),
+                    col: col.get_or_insert_with(|| F::Column::zeros(1 << log_size)),
                 }
             })
             .collect_vec()
@@ -101,59 +108,109 @@ impl<B: Backend> DomainEvaluationAccumulator<B> {
     }
 }
 
-pub trait AccumulationOps: FieldOps<BaseField> + Sized {
+pub trait AccumulationOps<F: SecureExtensionField = SecureField>:
+    FieldOps<BaseField> + Sized
+{
     /// Accumulates other into column:
     ///   column = column + other.
-    fn accumulate(column: &mut SecureColumn<Self>, other: &SecureColumn<Self>);
+    fn accumulate(column: &mut F::Column<Self>, other: &F::Column<Self>);
 }
 
-impl<B: Backend> DomainEvaluationAccumulator<B> {
+impl<B: Backend + AccumulationOps<F>, F: SecureExtensionField> DomainEvaluationAccumulator<B, F> {
     /// Computes f(P) as coefficients.
-    pub fn finalize(self) -> SecureCirclePoly<B> {
+    pub fn finalize(self) -> SecureCirclePoly<B, F> {
         assert_eq!(
             self.random_coeff_powers.len(),
             0,
             "not all random coefficients were used"
         );
         let _span = span!(Level::INFO, "Constraints interpolation").entered();
-        let mut res_coeffs = SecureColumn::<B>::zeros(1 << self.log_size());
+        let mut res_coeffs = F::Column::<B>::zeros(1 << self.log_size());
         let res_log_size = self.log_size();
 
         for (log_size, values) in self.sub_accumulations.into_iter().enumerate().skip(1) {
             let Some(values) = values else {
                 continue;
             };
-            let coeffs = SecureColumn::<B> {
-                columns: values.columns.map(|c| {
-                    CircleEvaluation::<B, BaseField, BitReversedOrder>::new(
-                        CanonicCoset::new(log_size as u32).circle_domain(),
-                        c,
-                    )
-                    .interpolate()
-                    .extend(res_log_size)
-                    .coeffs
-                }),
-            };
+            let coeffs = F::Column::<B>::from_columns(
+                values
+                    .into_columns()
+                    .into_iter()
+                    .map(|c| {
+                        CircleEvaluation::<B, BaseField, BitReversedOrder>::new(
+                            CanonicCoset::new(log_size as u32).circle_domain(),
+                            c,
+                        )
+                        .interpolate()
+                        .extend(res_log_size)
+                        .coeffs
+                    })
+                    .collect(),
+            );
             // Add column coefficients into result coefficients, element-wise, in-place.
-            B::accumulate(&mut res_coeffs, &coeffs);
+            <B as AccumulationOps<F>>::accumulate(&mut res_coeffs, &coeffs);
         }
 
-        SecureCirclePoly(res_coeffs.columns.map(CirclePoly::new))
+        SecureCirclePoly::new(
+            res_coeffs
+                .into_columns()
+                .into_iter()
+                .map(CirclePoly::new)
+                .collect(),
+        )
     }
 }
 
 /// A domain accumulator for polynomials of a single size.
-pub struct ColumnAccumulator<'a, B: Backend> {
-    pub random_coeff_powers: Vec<SecureField>,
-    pub col: &'a mut SecureColumn<B>,
+pub struct ColumnAccumulator<'a, B: Backend, F: SecureExtensionField = SecureField> {
+    pub random_coeff_powers: Vec<F>,
+    pub col: &'a mut F::Column<B>,
 }
-impl<'a> ColumnAccumulator<'a, CpuBackend> {
-    pub fn accumulate(&mut self, index: usize, evaluation: SecureField) {
+impl<'a, F: SecureExtensionField> ColumnAccumulator<'a, CpuBackend, F> {
+    pub fn accumulate(&mut self, index: usize, evaluation: F) {
         let val = self.col.at(index) + evaluation;
         self.col.set(index, val);
     }
 }
 
+impl<'a, B: Backend + AccumulationOps<F>, F: SecureExtensionField> ColumnAccumulator<'a, B, F> {
+    /// Accumulates constraint quotients computed row by row on the CPU, over `domain`.
+    /// `denoms(point)` returns the distinct denominators of the constraints at a point, which are
+    /// inverted in a batch over the domain, see [domain_denom_inverses].
+    /// `row_quotients(i, denom_inverses)` returns the quotients of the constraints at the `i`-th
+    /// point of the bit reversed domain, given the inverses of the denominators there. The j-th
+    /// quotient is multiplied by alpha^(n_cols - 1 - j).
+    pub fn accumulate_row_quotients<const N: usize, Q>(
+        self,
+        domain: CircleDomain,
+        denoms: impl Fn(CirclePoint<BaseField>) -> [(BaseField, BaseField); N],
+        row_quotients: impl Fn(usize, [BaseField; N]) -> Q,
+    ) where
+        Q: IntoIterator,
+        Q::Item: Into<F>,
+    {
+        assert_eq!(domain.size(), self.col.len());
+        let denom_inverses = domain_denom_inverses::<CpuBackend, N>(domain, denoms);
+        let quotients = F::Column::<CpuBackend>::from_values((0..domain.size()).map(|i| {
+            let row_denom_inverses = array::from_fn(|j| denom_inverses[j][i]);
+            zip(
+                row_quotients(i, row_denom_inverses),
+                self.random_coeff_powers.iter().rev(),
+            )
+            .map(|(quotient, &coeff)| quotient.into() * coeff)
+            .sum()
+        }));
+        let quotients = F::Column::<B>::from_columns(
+            quotients
+                .into_columns()
+                .into_iter()
+                .map(|column| column.into_iter().collect())
+                .collect(),
+        );
+        <B as AccumulationOps<F>>::accumulate(self.col, &quotients);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::array;
@@ -164,7 +221,6 @@ mod tests {
 
     use super::*;
     use crate::core::backend::cpu::CpuCircleEvaluation;
-    use crate::core::circle::CirclePoint;
     use crate::core::fields::m31::{M31, P};
     use crate::qm31;
 
@@ -1,18 +1,20 @@
 use itertools::{zip_eq, Itertools};
 
-use super::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use super::accumulation::{
+    AccumulationOps, DomainEvaluationAccumulator, PointEvaluationAccumulator,
+};
 use super::{Air, AirProver, ComponentTrace};
 use crate::core::backend::Backend;
 use crate::core::circle::CirclePoint;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::fields::SecureExtensionField;
 use crate::core::pcs::{CommitmentTreeProver, TreeVec};
 use crate::core::poly::circle::SecureCirclePoly;
 use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
 use crate::core::vcs::ops::MerkleOps;
 use crate::core::{ColumnVec, InteractionElements, LookupValues};
 
-pub trait AirExt: Air {
+pub trait AirExt<F: SecureExtensionField = SecureField>: Air<F> {
     fn composition_log_degree_bound(&self) -> u32 {
         self.components()
             .iter()
@@ -29,10 +31,7 @@ pub trait AirExt: Air {
             .unwrap()
     }
 
-    fn mask_points(
-        &self,
-        point: CirclePoint<SecureField>,
-    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+    fn mask_points(&self, point: CirclePoint<F>) -> TreeVec<ColumnVec<Vec<CirclePoint<F>>>> {
         let mut air_points = TreeVec::default();
         for component in self.components() {
             let component_points = component.mask_points(point);
@@ -46,18 +45,18 @@ pub trait AirExt: Air {
             );
         }
         // Add the composition polynomial mask points.
-        air_points.push(vec![vec![point]; SECURE_EXTENSION_DEGREE]);
+        air_points.push(vec![vec![point]; F::EXTENSION_DEGREE]);
         air_points
     }
 
     fn eval_composition_polynomial_at_point(
         &self,
-        point: CirclePoint<SecureField>,
-        mask_values: &Vec<TreeVec<Vec<Vec<SecureField>>>>,
-        random_coeff: SecureField,
+        point: CirclePoint<F>,
+        mask_values: &Vec<TreeVec<Vec<Vec<F>>>>,
+        random_coeff: F,
         interaction_elements: &InteractionElements,
         lookup_values: &LookupValues,
-    ) -> SecureField {
+    ) -> F {
         let mut evaluation_accumulator = PointEvaluationAccumulator::new(random_coeff);
         zip_eq(self.components(), mask_values).for_each(|(component, mask)| {
             component.evaluate_constraint_quotients_at_point(
@@ -127,16 +126,18 @@ pub trait AirExt: Air {
     }
 }
 
-impl<A: Air + ?Sized> AirExt for A {}
+impl<F: SecureExtensionField, A: Air<F> + ?Sized> AirExt<F> for A {}
 
-pub trait AirProverExt<B: Backend>: AirProver<B> {
+pub trait AirProverExt<B: Backend + AccumulationOps<F>, F: SecureExtensionField = SecureField>:
+    AirProver<B, F>
+{
     fn compute_composition_polynomial(
         &self,
-        random_coeff: SecureField,
+        random_coeff: F,
         component_traces: &[ComponentTrace<'_, B>],
         interaction_elements: &InteractionElements,
         lookup_values: &LookupValues,
-    ) -> SecureCirclePoly<B> {
+    ) -> SecureCirclePoly<B, F> {
         let total_constraints: usize = self
             .prover_components()
             .iter()
@@ -166,4 +167,7 @@ pub trait AirProverExt<B: Backend>: AirProver<B> {
     }
 }
 
-impl<B: Backend, A: AirProver<B>> AirProverExt<B> for A {}
+impl<B: Backend + AccumulationOps<F>, F: SecureExtensionField, A: AirProver<B, F>>
+    AirProverExt<B, F> for A
+{
+}
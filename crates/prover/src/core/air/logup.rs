@@ -0,0 +1,763 @@
+//! A LogUp lookup argument, shared by the components of an AIR.
+//!
+//! A component declares [LookupEntry]s: tuples of its base trace columns that it yields to, or uses
+//! from, a relation, with a multiplicity. Each tuple `v` is combined with the interaction elements
+//! of its relation into a denominator `d = sum_i alpha^(n-1-i) v_i - z`, and contributes the
+//! fraction `m / d` (or `-m / d` for a use) in every row. [Logup] writes these fractions to the
+//! interaction trace of the component, followed by their cumulative sum over the rows, and
+//! constrains both. The total sum of the component (its claimed sum) is exposed through
+//! [LookupValues], and the lookups of the AIR hold if the claimed sums of all its components add up
+//! to zero, as checked by [verify_balanced].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter::{zip, Sum};
+use std::ops::{Mul, Neg, Sub};
+
+use itertools::{izip, Itertools};
+use num_traits::{One, Zero};
+use thiserror::Error;
+
+use super::accumulation::{
+    ColumnAccumulator, DomainEvaluationAccumulator, PointEvaluationAccumulator,
+};
+use super::ComponentTrace;
+use crate::core::backend::{Backend, Col, Column};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::{
+    coset_vanishing, domain_denom_inverses, point_excluder, point_vanishing_fraction,
+};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::{
+    bit_reverse, circle_domain_order_to_coset_order, prefix_sums, shifted_secure_combination,
+};
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+
+/// The number of distinct denominators of the constraints of a [Logup], see
+/// [Logup::constraint_denoms].
+pub const N_LOGUP_DENOMS: usize = 4;
+
+/// The multiplicity of the tuples of a [LookupEntry], in each row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Multiplicity {
+    One,
+    /// The value of a base trace column.
+    Column(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LookupDirection {
+    /// Adds the tuples to the relation.
+    Yield,
+    /// Removes the tuples from the relation.
+    Use,
+}
+
+/// A tuple of base trace columns yielded to, or used from, a relation in every row of a component.
+#[derive(Clone, Debug)]
+pub struct LookupEntry {
+    pub relation: String,
+    pub direction: LookupDirection,
+    pub values: Vec<usize>,
+    pub multiplicity: Multiplicity,
+}
+
+impl LookupEntry {
+    pub fn yields(
+        relation: impl Into<String>,
+        values: Vec<usize>,
+        multiplicity: Multiplicity,
+    ) -> Self {
+        Self {
+            relation: relation.into(),
+            direction: LookupDirection::Yield,
+            values,
+            multiplicity,
+        }
+    }
+
+    pub fn uses(
+        relation: impl Into<String>,
+        values: Vec<usize>,
+        multiplicity: Multiplicity,
+    ) -> Self {
+        Self {
+            relation: relation.into(),
+            direction: LookupDirection::Use,
+            values,
+            multiplicity,
+        }
+    }
+}
+
+/// Returns the ids of the interaction elements `alpha` and `z` of a relation.
+pub fn relation_element_ids(relation: &str) -> [String; 2] {
+    [format!("{relation}_alpha"), format!("{relation}_z")]
+}
+
+/// The LogUp interaction trace and constraints of the [LookupEntry]s of a component.
+///
+/// The interaction trace consists of [SECURE_EXTENSION_DEGREE] columns for the fractions of each
+/// entry, followed by [SECURE_EXTENSION_DEGREE] columns for their cumulative sum. They are read
+/// from the start of the interaction trace given to the methods, so a component combining several
+/// arguments passes the relevant part of its interaction trace. The base trace columns of the
+/// entries must be of size `2^log_size`, and their first mask point must be the sampled point
+/// itself.
+#[derive(Clone, Debug)]
+pub struct Logup {
+    /// A name unique among the components of the AIR, used for the ids of the claimed sum.
+    pub name: String,
+    pub log_size: u32,
+    pub entries: Vec<LookupEntry>,
+}
+
+impl Logup {
+    pub fn new(name: impl Into<String>, log_size: u32, entries: Vec<LookupEntry>) -> Self {
+        Self {
+            name: name.into(),
+            log_size,
+            entries,
+        }
+    }
+
+    /// Returns the ids of the interaction elements of the relations of the entries.
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| entry.relation.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .flat_map(relation_element_ids)
+            .collect()
+    }
+
+    /// Returns the ids of the lookup values holding the coordinates of the claimed sum.
+    pub fn claimed_sum_ids(&self) -> [String; SECURE_EXTENSION_DEGREE] {
+        std::array::from_fn(|i| format!("{}_claimed_sum_{i}", self.name))
+    }
+
+    /// Returns the claimed sum of the fractions of all the rows.
+    pub fn claimed_sum(&self, lookup_values: &LookupValues) -> SecureField {
+        let ids = self.claimed_sum_ids();
+        SecureField::from_m31_array(std::array::from_fn(|i| lookup_values[&ids[i]]))
+    }
+
+    /// Returns the number of constraints: one per entry, and three on the cumulative sum.
+    pub fn n_constraints(&self) -> usize {
+        self.entries.len() + 3
+    }
+
+    pub fn max_constraint_log_degree_bound(&self) -> u32 {
+        // The constraints are of degree 2.
+        self.log_size + 1
+    }
+
+    pub fn n_interaction_columns(&self) -> usize {
+        (self.entries.len() + 1) * SECURE_EXTENSION_DEGREE
+    }
+
+    pub fn interaction_log_degree_bounds(&self) -> ColumnVec<u32> {
+        vec![self.log_size; self.n_interaction_columns()]
+    }
+
+    /// Returns the mask points of the interaction trace.
+    pub fn interaction_mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> ColumnVec<Vec<CirclePoint<SecureField>>> {
+        let prev_point = point - CanonicCoset::new(self.log_size).step().into_ef();
+        let mut points = vec![vec![point]; self.entries.len() * SECURE_EXTENSION_DEGREE];
+        points.extend(vec![vec![point, prev_point]; SECURE_EXTENSION_DEGREE]);
+        points
+    }
+
+    /// Accumulates the constraint quotients at a point, after those of the component.
+    pub fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let secure_value = |column: usize, offset: usize| {
+            SecureField::from_partial_evals(std::array::from_fn(|i| {
+                mask[INTERACTION_TRACE][column * SECURE_EXTENSION_DEGREE + i][offset]
+            }))
+        };
+        let base = |column: usize| mask[BASE_TRACE][column][0];
+        let elements = self.elements(interaction_elements);
+        let row = LogupRow {
+            denoms: zip(&self.entries, &elements)
+                .map(|(entry, &(alpha, z))| {
+                    let values = entry.values.iter().map(|&column| base(column));
+                    shifted_secure_combination(&values.collect_vec(), alpha, z)
+                })
+                .collect(),
+            multiplicities: self
+                .entries
+                .iter()
+                .map(|entry| signed_multiplicity(entry, base))
+                .collect(),
+            fractions: (0..self.entries.len())
+                .map(|i| secure_value(i, 0))
+                .collect(),
+            sum: secure_value(self.entries.len(), 0),
+            prev_sum: secure_value(self.entries.len(), 1),
+        };
+        let denom_inverses = self
+            .constraint_denoms(point)
+            .map(|(numerator, denominator)| denominator / numerator);
+        let claimed_sum = self.claimed_sum(lookup_values);
+        for quotient in self.constraint_quotients(&row, claimed_sum, denom_inverses) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+    }
+
+    /// Accumulates the constraint quotients on the evaluation domain, after those of the
+    /// component.
+    pub fn evaluate_constraint_quotients_on_domain<B: LogupOps>(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[INTERACTION_TRACE][0].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let denom_inverses = domain_denom_inverses::<B, N_LOGUP_DENOMS>(eval_domain, |point| {
+            self.constraint_denoms(point)
+        });
+        let elements = self.elements(interaction_elements);
+        let claimed_sum = self.claimed_sum(lookup_values);
+
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, self.n_constraints())]);
+        B::accumulate_logup_quotients(self, trace, accum, &denom_inverses, &elements, claimed_sum);
+    }
+
+    /// Returns the claimed sum, read from the last row of the cumulative sum.
+    pub fn lookup_values<B: Backend>(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        let domain = CanonicCoset::new(self.log_size);
+        let last_point = domain.at(domain.size() - 1).into_ef();
+        let claimed_sum = trace.polys[INTERACTION_TRACE]
+            [self.entries.len() * SECURE_EXTENSION_DEGREE..]
+            .iter()
+            .map(|poly| poly.eval_at_point(last_point).try_into().unwrap())
+            .collect_vec();
+        LookupValues::new(BTreeMap::from_iter(zip(
+            self.claimed_sum_ids(),
+            claimed_sum,
+        )))
+    }
+
+    /// Writes the interaction trace from the base trace of the component.
+    pub fn write_interaction_trace<B: Backend>(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        interaction_elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let base_values: BTreeMap<usize, Vec<BaseField>> = self
+            .base_columns()
+            .into_iter()
+            .map(|column| {
+                assert_eq!(trace[column].domain.log_size(), self.log_size);
+                let mut values = trace[column].values.to_cpu();
+                bit_reverse(&mut values);
+                (column, circle_domain_order_to_coset_order(&values))
+            })
+            .collect();
+        let elements = self.elements(interaction_elements);
+
+        let mut fractions = zip(&self.entries, &elements)
+            .map(|(entry, &(alpha, z))| {
+                let denoms = (0..1 << self.log_size)
+                    .map(|row| {
+                        let values = entry
+                            .values
+                            .iter()
+                            .map(|column| base_values[column][row])
+                            .collect_vec();
+                        shifted_secure_combination(&values, alpha, z)
+                    })
+                    .collect_vec();
+                let mut fractions = vec![SecureField::zero(); denoms.len()];
+                SecureField::batch_inverse(&denoms, &mut fractions);
+                for (row, fraction) in fractions.iter_mut().enumerate() {
+                    *fraction *= signed_multiplicity(entry, |column| {
+                        SecureField::from(base_values[&column][row])
+                    });
+                }
+                fractions
+            })
+            .collect_vec();
+        let row_sums = (0..1 << self.log_size)
+            .map(|row| fractions.iter().map(|fractions| fractions[row]).sum())
+            .collect_vec();
+        fractions.push(prefix_sums(&row_sums));
+
+        let domain = CanonicCoset::new(self.log_size);
+        fractions
+            .iter()
+            .flat_map(|values| {
+                (0..SECURE_EXTENSION_DEGREE).map(move |i| {
+                    let coordinates = values.iter().map(|value| value.to_m31_array()[i]);
+                    CircleEvaluation::new_canonical_ordered(domain, coordinates.collect())
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the base trace columns read by the entries.
+    fn base_columns(&self) -> BTreeSet<usize> {
+        self.entries
+            .iter()
+            .flat_map(|entry| {
+                let multiplicity = match entry.multiplicity {
+                    Multiplicity::One => None,
+                    Multiplicity::Column(column) => Some(column),
+                };
+                entry.values.iter().copied().chain(multiplicity)
+            })
+            .collect()
+    }
+
+    /// Returns `(alpha, z)` for each entry.
+    fn elements(
+        &self,
+        interaction_elements: &InteractionElements,
+    ) -> Vec<(SecureField, SecureField)> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let [alpha_id, z_id] = relation_element_ids(&entry.relation);
+                (interaction_elements[&alpha_id], interaction_elements[&z_id])
+            })
+            .collect()
+    }
+
+    /// Returns the denominators of the constraints at a point, as fractions `(numerator,
+    /// denominator)`, see [domain_denom_inverses]. They are those of the constraints on every row,
+    /// on the first row, on all the rows but the first (the step constraint), and on the last row.
+    pub fn constraint_denoms<F: ExtensionOf<BaseField>>(
+        &self,
+        point: CirclePoint<F>,
+    ) -> [(F, F); N_LOGUP_DENOMS] {
+        let domain = CanonicCoset::new(self.log_size).coset;
+        let first_point = domain.at(0);
+        let domain_vanishing = coset_vanishing(domain, point);
+        [
+            (domain_vanishing, F::one()),
+            point_vanishing_fraction(first_point, point),
+            (domain_vanishing, point_excluder(first_point, point)),
+            point_vanishing_fraction(domain.at(domain.size() - 1), point),
+        ]
+    }
+
+    /// Returns the constraint quotients of a row, in the order they are accumulated, given the
+    /// inverses of the denominators of [Logup::constraint_denoms] at its point.
+    pub fn constraint_quotients<F, D>(
+        &self,
+        row: &LogupRow<F>,
+        claimed_sum: F,
+        [domain_inverse, first_inverse, step_inverse, last_inverse]: [D; N_LOGUP_DENOMS],
+    ) -> Vec<F>
+    where
+        F: Copy + Sub<Output = F> + Mul<Output = F> + Mul<D, Output = F> + Sum,
+        D: Copy,
+    {
+        let mut quotients = izip!(&row.fractions, &row.denoms, &row.multiplicities)
+            .map(|(&fraction, &denom, &multiplicity)| {
+                (fraction * denom - multiplicity) * domain_inverse
+            })
+            .collect_vec();
+        let row_sum: F = row.fractions.iter().copied().sum();
+        quotients.extend([
+            (row.sum - row_sum) * first_inverse,
+            (row.sum - row.prev_sum - row_sum) * step_inverse,
+            (row.sum - claimed_sum) * last_inverse,
+        ]);
+        quotients
+    }
+}
+
+/// The values needed to evaluate the constraints of a [Logup] in a row, or in a vector of rows.
+pub struct LogupRow<F> {
+    /// The combined tuple `sum_i alpha^(n-1-i) v_i - z` of each entry.
+    pub denoms: Vec<F>,
+    /// The multiplicity of each entry, negated for a use.
+    pub multiplicities: Vec<F>,
+    pub fractions: Vec<F>,
+    pub sum: F,
+    pub prev_sum: F,
+}
+
+/// Returns the multiplicity of an entry in a row, negated for a use.
+pub fn signed_multiplicity<F: One + Neg<Output = F>>(
+    entry: &LookupEntry,
+    base: impl Fn(usize) -> F,
+) -> F {
+    let multiplicity = match entry.multiplicity {
+        Multiplicity::One => F::one(),
+        Multiplicity::Column(column) => base(column),
+    };
+    match entry.direction {
+        LookupDirection::Yield => multiplicity,
+        LookupDirection::Use => -multiplicity,
+    }
+}
+
+pub trait LogupOps: Backend {
+    /// Accumulates the constraint quotients of `logup` on the evaluation domain, given the inverses
+    /// of the denominators of [Logup::constraint_denoms] on it, and `(alpha, z)` for each entry.
+    fn accumulate_logup_quotients(
+        logup: &Logup,
+        trace: &ComponentTrace<'_, Self>,
+        accumulator: ColumnAccumulator<'_, Self>,
+        denom_inverses: &[Col<Self, BaseField>; N_LOGUP_DENOMS],
+        elements: &[(SecureField, SecureField)],
+        claimed_sum: SecureField,
+    );
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum LookupError {
+    #[error("The claimed sums of the lookups add up to {0} instead of zero.")]
+    NotBalanced(SecureField),
+    #[error("Invalid public claim: {0}.")]
+    InvalidClaim(String),
+}
+
+/// Checks that the claimed sums of `logups` add up to zero, i.e. that every used tuple is yielded.
+pub fn verify_balanced<'a>(
+    logups: impl IntoIterator<Item = &'a Logup>,
+    lookup_values: &LookupValues,
+) -> Result<(), LookupError> {
+    verify_balanced_with_public(logups, SecureField::zero(), lookup_values)
+}
+
+/// Checks that the claimed sums of `logups` and `public_sum` add up to zero, where `public_sum` is
+/// the sum of the fractions of tuples the verifier yields or uses itself, see [public_sum].
+pub fn verify_balanced_with_public<'a>(
+    logups: impl IntoIterator<Item = &'a Logup>,
+    public_sum: SecureField,
+    lookup_values: &LookupValues,
+) -> Result<(), LookupError> {
+    let sum: SecureField = logups
+        .into_iter()
+        .map(|logup| logup.claimed_sum(lookup_values))
+        .sum::<SecureField>()
+        + public_sum;
+    if !sum.is_zero() {
+        return Err(LookupError::NotBalanced(sum));
+    }
+    Ok(())
+}
+
+/// Returns the sum of the fractions of tuples of the statement, e.g. public inputs and outputs,
+/// yielded to or used from `relation` once each.
+pub fn public_sum(
+    relation: &str,
+    direction: LookupDirection,
+    tuples: impl IntoIterator<Item = Vec<BaseField>>,
+    interaction_elements: &InteractionElements,
+) -> SecureField {
+    let [alpha_id, z_id] = relation_element_ids(relation);
+    let (alpha, z) = (interaction_elements[&alpha_id], interaction_elements[&z_id]);
+    let sum: SecureField = tuples
+        .into_iter()
+        .map(|tuple| shifted_secure_combination(&tuple, alpha, z).inverse())
+        .sum();
+    match direction {
+        LookupDirection::Yield => sum,
+        LookupDirection::Use => -sum,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{verify_balanced, Logup, LogupOps, LookupEntry, LookupError, Multiplicity};
+    use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+    use crate::core::air::{Air, AirExt, AirProver, Component, ComponentProver, ComponentTrace};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Backend, CpuBackend};
+    use crate::core::channel::Blake2sChannel;
+    use crate::core::circle::CirclePoint;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::prover::{prove, verify, VerificationError};
+    use crate::core::test_utils::{draw_interaction_elements, prove_and_verify, test_channel};
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::ops::MerkleOps;
+    use crate::core::{ColumnVec, InteractionElements, LookupValues};
+    use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier};
+
+    const RELATION: &str = "squares";
+    const PRODUCER_LOG_SIZE: u32 = 4;
+    const CONSUMER_LOG_SIZE: u32 = 6;
+
+    /// A component whose only constraints are those of its lookups.
+    #[derive(Clone)]
+    struct LookupComponent {
+        logup: Logup,
+        n_columns: usize,
+    }
+
+    impl Component for LookupComponent {
+        fn n_constraints(&self) -> usize {
+            self.logup.n_constraints()
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.logup.max_constraint_log_degree_bound()
+        }
+
+        fn n_interaction_phases(&self) -> u32 {
+            2
+        }
+
+        fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+            TreeVec::new(vec![
+                vec![self.logup.log_size; self.n_columns],
+                self.logup.interaction_log_degree_bounds(),
+            ])
+        }
+
+        fn mask_points(
+            &self,
+            point: CirclePoint<SecureField>,
+        ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+            TreeVec::new(vec![
+                vec![vec![point]; self.n_columns],
+                self.logup.interaction_mask_points(point),
+            ])
+        }
+
+        fn evaluate_constraint_quotients_at_point(
+            &self,
+            point: CirclePoint<SecureField>,
+            mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+            evaluation_accumulator: &mut PointEvaluationAccumulator,
+            interaction_elements: &InteractionElements,
+            lookup_values: &LookupValues,
+        ) {
+            self.logup.evaluate_constraint_quotients_at_point(
+                point,
+                mask,
+                evaluation_accumulator,
+                interaction_elements,
+                lookup_values,
+            );
+        }
+    }
+
+    impl<B: LogupOps> ComponentProver<B> for LookupComponent {
+        fn evaluate_constraint_quotients_on_domain(
+            &self,
+            trace: &ComponentTrace<'_, B>,
+            evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+            interaction_elements: &InteractionElements,
+            lookup_values: &LookupValues,
+        ) {
+            self.logup.evaluate_constraint_quotients_on_domain(
+                trace,
+                evaluation_accumulator,
+                interaction_elements,
+                lookup_values,
+            );
+        }
+
+        fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+            self.logup.lookup_values(trace)
+        }
+    }
+
+    #[derive(Clone)]
+    struct LookupAir {
+        components: Vec<LookupComponent>,
+    }
+
+    impl Air for LookupAir {
+        fn components(&self) -> Vec<&dyn Component> {
+            self.components
+                .iter()
+                .map(|component| component as &dyn Component)
+                .collect()
+        }
+    }
+
+    impl AirTraceVerifier for LookupAir {
+        fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+            draw_interaction_elements(
+                channel,
+                self.components
+                    .iter()
+                    .flat_map(|component| component.logup.interaction_element_ids()),
+            )
+        }
+
+        fn verify_lookups(
+            &self,
+            _interaction_elements: &InteractionElements,
+            lookup_values: &LookupValues,
+        ) -> Result<(), LookupError> {
+            verify_balanced(
+                self.components.iter().map(|component| &component.logup),
+                lookup_values,
+            )
+        }
+    }
+
+    impl<B: LogupOps> AirTraceGenerator<B> for LookupAir {
+        fn interact(
+            &self,
+            trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+            elements: &InteractionElements,
+        ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+            let mut trace = trace.iter();
+            self.components
+                .iter()
+                .flat_map(|component| {
+                    let component_trace = trace.by_ref().take(component.n_columns).collect();
+                    component
+                        .logup
+                        .write_interaction_trace(&component_trace, elements)
+                })
+                .collect()
+        }
+
+        fn to_air_prover(&self) -> impl AirProver<B> {
+            self.clone()
+        }
+
+        fn composition_log_degree_bound(&self) -> u32 {
+            AirExt::composition_log_degree_bound(self)
+        }
+    }
+
+    impl<B: LogupOps> AirProver<B> for LookupAir {
+        fn prover_components(&self) -> Vec<&dyn ComponentProver<B>> {
+            self.components
+                .iter()
+                .map(|component| component as &dyn ComponentProver<B>)
+                .collect()
+        }
+    }
+
+    /// Returns an AIR where a producer yields the pairs `(x, x^2)` of a table, and a consumer of a
+    /// different size uses them.
+    fn squares_air() -> LookupAir {
+        let producer = LookupComponent {
+            logup: Logup::new(
+                "producer",
+                PRODUCER_LOG_SIZE,
+                vec![LookupEntry::yields(
+                    RELATION,
+                    vec![0, 1],
+                    Multiplicity::Column(2),
+                )],
+            ),
+            n_columns: 3,
+        };
+        let consumer = LookupComponent {
+            logup: Logup::new(
+                "consumer",
+                CONSUMER_LOG_SIZE,
+                vec![
+                    LookupEntry::uses(RELATION, vec![0, 1], Multiplicity::One),
+                    LookupEntry::uses(RELATION, vec![2, 3], Multiplicity::One),
+                ],
+            ),
+            n_columns: 4,
+        };
+        LookupAir {
+            components: vec![producer, consumer],
+        }
+    }
+
+    /// Writes the trace of [squares_air], where the consumer uses `consumed(row)` in each row.
+    fn squares_trace<B: Backend>(
+        consumed: impl Fn(u32) -> [u32; 2],
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let table_size = 1 << PRODUCER_LOG_SIZE;
+        let mut multiplicities = vec![0; table_size as usize];
+        let mut consumer_columns = vec![vec![]; 4];
+        for row in 0..1 << CONSUMER_LOG_SIZE {
+            for (i, value) in consumed(row).into_iter().enumerate() {
+                multiplicities[(value % table_size) as usize] += 1;
+                consumer_columns[2 * i].push(value);
+                consumer_columns[2 * i + 1].push(value * value);
+            }
+        }
+        let producer_columns = vec![
+            (0..table_size).collect_vec(),
+            (0..table_size).map(|x| x * x).collect_vec(),
+            multiplicities,
+        ];
+        [
+            (PRODUCER_LOG_SIZE, producer_columns),
+            (CONSUMER_LOG_SIZE, consumer_columns),
+        ]
+        .into_iter()
+        .flat_map(|(log_size, columns)| {
+            columns.into_iter().map(move |column| {
+                CircleEvaluation::new_canonical_ordered(
+                    CanonicCoset::new(log_size),
+                    column.into_iter().map(BaseField::from).collect(),
+                )
+            })
+        })
+        .collect()
+    }
+
+    fn test_balanced_lookups<B: LogupOps + MerkleOps<Blake2sMerkleHasher>>() {
+        let air = squares_air();
+        let trace = squares_trace::<B>(|row| [row % 16, (3 * row + 1) % 16]);
+
+        prove_and_verify(&air, trace);
+    }
+
+    #[test]
+    fn test_balanced_lookups_cpu() {
+        test_balanced_lookups::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_balanced_lookups_simd() {
+        test_balanced_lookups::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_unbalanced_lookups_fail_verification() {
+        let air = squares_air();
+        // Uses (16, 256), which is not in the table.
+        let trace = squares_trace::<CpuBackend>(|row| [row % 16, if row == 5 { 16 } else { 0 }]);
+
+        let proof = prove::<CpuBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        assert!(matches!(
+            verify(proof, &air, &mut test_channel()),
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    fn test_interaction_element_ids() {
+        let air = squares_air();
+
+        let ids = air.components[1].logup.interaction_element_ids();
+
+        assert_eq!(ids, ["squares_alpha", "squares_z"]);
+    }
+}
@@ -4,7 +4,8 @@ use std::vec;
 use itertools::Itertools;
 
 use crate::core::circle::CirclePoint;
-use crate::core::fields::qm31::SecureField;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::ExtensionOf;
 use crate::core::poly::circle::CanonicCoset;
 use crate::core::ColumnVec;
 
@@ -14,10 +15,10 @@ type Mask = ColumnVec<Vec<usize>>;
 
 /// Returns the same point for each mask item.
 /// Should be used where all the mask items has no shift from the constraint point.
-pub fn fixed_mask_points(
+pub fn fixed_mask_points<F: ExtensionOf<BaseField>>(
     mask: &Mask,
-    point: CirclePoint<SecureField>,
-) -> ColumnVec<Vec<CirclePoint<SecureField>>> {
+    point: CirclePoint<F>,
+) -> ColumnVec<Vec<CirclePoint<F>>> {
     assert_eq!(
         mask.iter()
             .flat_map(|mask_entry| mask_entry.iter().collect::<HashSet<_>>())
@@ -33,11 +34,11 @@ pub fn fixed_mask_points(
 
 /// For each mask item returns the point shifted by the domain initial point of the column.
 /// Should be used where the mask items are shifted from the constraint point.
-pub fn shifted_mask_points(
+pub fn shifted_mask_points<F: ExtensionOf<BaseField>>(
     mask: &Mask,
     domains: &[CanonicCoset],
-    point: CirclePoint<SecureField>,
-) -> ColumnVec<Vec<CirclePoint<SecureField>>> {
+    point: CirclePoint<F>,
+) -> ColumnVec<Vec<CirclePoint<F>>> {
     mask.iter()
         .zip(domains.iter())
         .map(|(mask_entry, domain)| {
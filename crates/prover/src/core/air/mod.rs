@@ -3,6 +3,7 @@ use super::backend::Backend;
 use super::circle::CirclePoint;
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
+use super::fields::SecureExtensionField;
 use super::pcs::TreeVec;
 use super::poly::circle::{CircleEvaluation, CirclePoly};
 use super::poly::BitReversedOrder;
@@ -10,7 +11,9 @@ use super::{ColumnVec, InteractionElements, LookupValues};
 
 pub mod accumulation;
 mod air_ext;
+pub mod logup;
 pub mod mask;
+pub mod permutation;
 
 pub use air_ext::{AirExt, AirProverExt};
 
@@ -20,17 +23,17 @@ pub use air_ext::{AirExt, AirProverExt};
 /// For instance, all interaction elements are assumed to be present in it.
 /// Therefore, an AIR is generated only after the initial trace commitment phase.
 // TODO(spapini): consider renaming this struct.
-pub trait Air {
-    fn components(&self) -> Vec<&dyn Component>;
+pub trait Air<F: SecureExtensionField = SecureField> {
+    fn components(&self) -> Vec<&dyn Component<F>>;
 }
 
-pub trait AirProver<B: Backend>: Air {
-    fn prover_components(&self) -> Vec<&dyn ComponentProver<B>>;
+pub trait AirProver<B: Backend, F: SecureExtensionField = SecureField>: Air<F> {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<B, F>>;
 }
 
 /// A component is a set of trace columns of various sizes along with a set of
-/// constraints on them.
-pub trait Component {
+/// constraints on them, over the secure field `F`.
+pub trait Component<F: SecureExtensionField = SecureField> {
     fn n_constraints(&self) -> usize;
 
     fn max_constraint_log_degree_bound(&self) -> u32;
@@ -44,29 +47,26 @@ pub trait Component {
 
     /// Returns the mask points for each trace column. The returned TreeVec should be of size
     /// `n_interaction_phases`.
-    fn mask_points(
-        &self,
-        point: CirclePoint<SecureField>,
-    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>>;
+    fn mask_points(&self, point: CirclePoint<F>) -> TreeVec<ColumnVec<Vec<CirclePoint<F>>>>;
 
     /// Evaluates the constraint quotients combination of the component at a point.
     fn evaluate_constraint_quotients_at_point(
         &self,
-        point: CirclePoint<SecureField>,
-        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
-        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        point: CirclePoint<F>,
+        mask: &TreeVec<ColumnVec<Vec<F>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator<F>,
         interaction_elements: &InteractionElements,
         lookup_values: &LookupValues,
     );
 }
 
-pub trait ComponentProver<B: Backend>: Component {
+pub trait ComponentProver<B: Backend, F: SecureExtensionField = SecureField>: Component<F> {
     /// Evaluates the constraint quotients of the component on the evaluation domain.
     /// Accumulates quotients in `evaluation_accumulator`.
     fn evaluate_constraint_quotients_on_domain(
         &self,
         trace: &ComponentTrace<'_, B>,
-        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B, F>,
         interaction_elements: &InteractionElements,
         lookup_values: &LookupValues,
     );
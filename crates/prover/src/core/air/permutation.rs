@@ -0,0 +1,400 @@
+//! A grand product argument, proving that two tuples of base trace columns of a component hold the
+//! same multiset of rows, i.e. that they are permutations of each other.
+//!
+//! The `i`-th row of each side is combined with the interaction elements `alpha` and `z` of the
+//! [Permutation] into `a_i = sum_j alpha^(k-1-j) lhs_j[i] - z` (and `b_i` for the right hand
+//! side). The interaction trace holds the running product `P_i = prod_{j <= i} a_j / b_j`, in coset
+//! order, constrained by
+//!   `P_0 * b_0 = a_0`,
+//!   `P_i * b_i = P_{i-1} * a_i` for `i > 0`,
+//!   `P_{n-1} = 1`.
+//! The product ends at 1 if the sides are permutations of each other, and otherwise only with
+//! negligible probability over the choice of `alpha` and `z`.
+
+use std::ops::Sub;
+
+use super::accumulation::{
+    ColumnAccumulator, DomainEvaluationAccumulator, PointEvaluationAccumulator,
+};
+use super::ComponentTrace;
+use crate::core::backend::{Backend, Col};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::{
+    coset_vanishing, domain_denom_inverses, point_excluder, point_vanishing_fraction,
+};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::{SecureColumn, SECURE_EXTENSION_DEGREE};
+use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::shifted_secure_combination;
+use crate::core::{ColumnVec, InteractionElements};
+
+pub const N_PERMUTATION_CONSTRAINTS: usize = 3;
+
+/// The grand product argument of a component, asserting that the rows of the `lhs` base trace
+/// columns are a permutation of the rows of the `rhs` ones.
+///
+/// The running product is read from the start of the interaction trace given to the methods, see
+/// [Logup](super::logup::Logup), and its constraints are accumulated after those of the component.
+/// The base trace columns must be of size `2^log_size`, and their first mask point must be the
+/// sampled point itself.
+#[derive(Clone, Debug)]
+pub struct Permutation {
+    /// A name unique among the components of the AIR, used for the ids of the interaction
+    /// elements.
+    pub name: String,
+    pub log_size: u32,
+    pub lhs: Vec<usize>,
+    pub rhs: Vec<usize>,
+}
+
+impl Permutation {
+    pub fn new(name: impl Into<String>, log_size: u32, lhs: Vec<usize>, rhs: Vec<usize>) -> Self {
+        assert_eq!(lhs.len(), rhs.len(), "the sides must have the same width");
+        Self {
+            name: name.into(),
+            log_size,
+            lhs,
+            rhs,
+        }
+    }
+
+    /// Returns the ids of the interaction elements `alpha` and `z`.
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        vec![format!("{}_alpha", self.name), format!("{}_z", self.name)]
+    }
+
+    pub fn n_constraints(&self) -> usize {
+        N_PERMUTATION_CONSTRAINTS
+    }
+
+    pub fn max_constraint_log_degree_bound(&self) -> u32 {
+        // The constraints are of degree 2.
+        self.log_size + 1
+    }
+
+    pub fn interaction_log_degree_bounds(&self) -> ColumnVec<u32> {
+        vec![self.log_size; SECURE_EXTENSION_DEGREE]
+    }
+
+    /// Returns the mask points of the interaction trace.
+    pub fn interaction_mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> ColumnVec<Vec<CirclePoint<SecureField>>> {
+        let prev_point = point - CanonicCoset::new(self.log_size).step().into_ef();
+        vec![vec![point, prev_point]; SECURE_EXTENSION_DEGREE]
+    }
+
+    /// Accumulates the constraint quotients at a point, after those of the component.
+    pub fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+    ) {
+        let (alpha, z) = self.elements(interaction_elements);
+        let combine = |columns: &[usize]| {
+            let values = columns
+                .iter()
+                .map(|&column| mask[BASE_TRACE][column][0])
+                .collect::<Vec<_>>();
+            shifted_secure_combination(&values, alpha, z)
+        };
+        let product = |offset: usize| {
+            SecureField::from_partial_evals(std::array::from_fn(|i| {
+                mask[INTERACTION_TRACE][i][offset]
+            }))
+        };
+        let numerators = self.constraint_numerators(
+            combine(&self.lhs),
+            combine(&self.rhs),
+            product(0),
+            product(1),
+        );
+        let denom_inverses = self
+            .constraint_denoms(point)
+            .map(|(numerator, denominator)| denominator / numerator);
+        for (numerator, denom_inverse) in numerators.into_iter().zip(denom_inverses) {
+            evaluation_accumulator.accumulate(numerator * denom_inverse);
+        }
+    }
+
+    /// Accumulates the constraint quotients on the evaluation domain, after those of the
+    /// component.
+    pub fn evaluate_constraint_quotients_on_domain<B: PermutationOps>(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+    ) {
+        let eval_domain = trace.evals[INTERACTION_TRACE][0].domain;
+        let denom_inverses =
+            domain_denom_inverses::<B, N_PERMUTATION_CONSTRAINTS>(eval_domain, |point| {
+                self.constraint_denoms(point)
+            });
+        let (alpha, z) = self.elements(interaction_elements);
+        let [accum] = evaluation_accumulator
+            .columns([(self.max_constraint_log_degree_bound(), self.n_constraints())]);
+        B::accumulate_permutation_quotients(self, trace, accum, &denom_inverses, alpha, z);
+    }
+
+    /// Writes the running product from the base trace of the component.
+    pub fn write_interaction_trace<B: PermutationOps>(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        interaction_elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let (alpha, z) = self.elements(interaction_elements);
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        B::grand_product(self, trace, alpha, z)
+            .columns
+            .into_iter()
+            .map(|column| CircleEvaluation::new(domain, column))
+            .collect()
+    }
+
+    /// Returns `(alpha, z)`.
+    pub fn elements(
+        &self,
+        interaction_elements: &InteractionElements,
+    ) -> (SecureField, SecureField) {
+        let [alpha_id, z_id] = &self.interaction_element_ids()[..] else {
+            unreachable!()
+        };
+        (interaction_elements[alpha_id], interaction_elements[z_id])
+    }
+
+    /// Returns the numerators of the constraints, given the combined rows `a` and `b` of the sides
+    /// and the running product at a point and at the previous point.
+    pub fn constraint_numerators<F>(
+        &self,
+        a: F,
+        b: F,
+        product: F,
+        prev_product: F,
+    ) -> [F; N_PERMUTATION_CONSTRAINTS]
+    where
+        F: FieldExpOps + Sub<Output = F>,
+    {
+        [
+            product * b - a,
+            product * b - prev_product * a,
+            product - F::one(),
+        ]
+    }
+
+    /// Returns the denominators of the constraints at a point, as fractions `(numerator,
+    /// denominator)`, see [domain_denom_inverses].
+    pub fn constraint_denoms<F>(&self, point: CirclePoint<F>) -> [(F, F); N_PERMUTATION_CONSTRAINTS]
+    where
+        F: ExtensionOf<BaseField>,
+    {
+        let domain = CanonicCoset::new(self.log_size).coset;
+        let first_point = domain.at(0);
+        [
+            point_vanishing_fraction(first_point, point),
+            // The step constraint holds on all the rows except the first.
+            (
+                coset_vanishing(domain, point),
+                point_excluder(first_point, point),
+            ),
+            point_vanishing_fraction(domain.at(domain.size() - 1), point),
+        ]
+    }
+}
+
+pub trait PermutationOps: Backend {
+    /// Returns the running product of `permutation`, in bit reversed circle domain order, given the
+    /// base trace of its component.
+    fn grand_product(
+        permutation: &Permutation,
+        trace: &ColumnVec<&CircleEvaluation<Self, BaseField, BitReversedOrder>>,
+        alpha: SecureField,
+        z: SecureField,
+    ) -> SecureColumn<Self>;
+
+    /// Accumulates the constraint quotients of `permutation` on the evaluation domain, given the
+    /// inverses of the denominators of [Permutation::constraint_denoms] on it.
+    fn accumulate_permutation_quotients(
+        permutation: &Permutation,
+        trace: &ComponentTrace<'_, Self>,
+        accumulator: ColumnAccumulator<'_, Self>,
+        denom_inverses: &[Col<Self, BaseField>; N_PERMUTATION_CONSTRAINTS],
+        alpha: SecureField,
+        z: SecureField,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use itertools::{zip_eq, Itertools};
+    use num_traits::One;
+    use rand::rngs::SmallRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    use super::{Permutation, PermutationOps};
+    use crate::core::air::accumulation::DomainEvaluationAccumulator;
+    use crate::core::air::ComponentTrace;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Column, CpuBackend};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::utils::coset_index_to_bit_reversed_circle_domain_index;
+    use crate::core::{ColumnVec, InteractionElements};
+    use crate::qm31;
+
+    const LOG_SIZE: u32 = 5;
+
+    fn permutation() -> Permutation {
+        Permutation::new("permutation", LOG_SIZE, vec![0, 1], vec![2, 3])
+    }
+
+    fn interaction_elements() -> InteractionElements {
+        InteractionElements::new(BTreeMap::from_iter(zip_eq(
+            permutation().interaction_element_ids(),
+            [qm31!(1, 2, 3, 4), qm31!(5, 6, 7, 8)],
+        )))
+    }
+
+    /// Returns columns `[x, y, x', y']`, in coset order, where the rows of `(x', y')` are a
+    /// shuffle of the rows of `(x, y)`.
+    fn shuffled_columns() -> Vec<Vec<BaseField>> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let rows = (0..1 << LOG_SIZE)
+            .map(|_| [rng.gen::<BaseField>(), rng.gen()])
+            .collect_vec();
+        let mut shuffled_rows = rows.clone();
+        shuffled_rows.shuffle(&mut rng);
+        (0..4)
+            .map(|column| {
+                let rows = if column < 2 { &rows } else { &shuffled_rows };
+                rows.iter().map(|row| row[column % 2]).collect()
+            })
+            .collect()
+    }
+
+    fn write_trace<B: PermutationOps>(
+        columns: &[Vec<BaseField>],
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        columns
+            .iter()
+            .map(|column| {
+                CircleEvaluation::new_canonical_ordered(
+                    CanonicCoset::new(LOG_SIZE),
+                    column.iter().copied().collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn last_product<B: PermutationOps>(columns: &[Vec<BaseField>]) -> SecureField {
+        let trace = write_trace::<B>(columns);
+        let interaction_trace =
+            permutation().write_interaction_trace(&trace.iter().collect(), &interaction_elements());
+        let last_index =
+            coset_index_to_bit_reversed_circle_domain_index((1 << LOG_SIZE) - 1, LOG_SIZE);
+        SecureField::from_m31_array(std::array::from_fn(|i| {
+            interaction_trace[i].values.at(last_index)
+        }))
+    }
+
+    #[test]
+    fn test_grand_product_of_permutation_is_one() {
+        let columns = shuffled_columns();
+
+        assert_eq!(last_product::<CpuBackend>(&columns), SecureField::one());
+        assert_eq!(last_product::<SimdBackend>(&columns), SecureField::one());
+    }
+
+    #[test]
+    fn test_grand_product_of_non_permutation_is_not_one() {
+        let mut columns = shuffled_columns();
+        columns[3][7] += BaseField::one();
+
+        assert_ne!(last_product::<CpuBackend>(&columns), SecureField::one());
+        assert_ne!(last_product::<SimdBackend>(&columns), SecureField::one());
+    }
+
+    #[test]
+    fn test_simd_grand_product_matches_cpu() {
+        let columns = shuffled_columns();
+        let elements = interaction_elements();
+
+        let cpu_trace = write_trace::<CpuBackend>(&columns);
+        let simd_trace = write_trace::<SimdBackend>(&columns);
+        let cpu_product =
+            permutation().write_interaction_trace(&cpu_trace.iter().collect(), &elements);
+        let simd_product =
+            permutation().write_interaction_trace(&simd_trace.iter().collect(), &elements);
+
+        for (cpu_column, simd_column) in zip_eq(cpu_product, simd_product) {
+            assert_eq!(cpu_column.values, simd_column.values.to_cpu());
+        }
+    }
+
+    /// Evaluates the constraint quotients of [permutation] on the trace of `columns`, and returns
+    /// the coefficients of the combined quotient.
+    fn quotient_coeffs<B: PermutationOps>(columns: &[Vec<BaseField>]) -> Vec<Vec<BaseField>> {
+        let permutation = permutation();
+        let elements = interaction_elements();
+        let trace = write_trace::<B>(columns);
+        let interaction_trace =
+            permutation.write_interaction_trace(&trace.iter().collect(), &elements);
+        let eval_domain =
+            CanonicCoset::new(permutation.max_constraint_log_degree_bound()).circle_domain();
+        let [polys, evals] = [trace, interaction_trace].map(|trace| {
+            let polys = trace
+                .into_iter()
+                .map(|eval| eval.interpolate())
+                .collect_vec();
+            let evals = polys
+                .iter()
+                .map(|poly| poly.evaluate(eval_domain))
+                .collect_vec();
+            (polys, evals)
+        });
+        let component_trace = ComponentTrace::new(
+            TreeVec::new(vec![polys.0.iter().collect(), evals.0.iter().collect()]),
+            TreeVec::new(vec![polys.1.iter().collect(), evals.1.iter().collect()]),
+        );
+        let mut accumulator = DomainEvaluationAccumulator::new(
+            qm31!(3, 1, 4, 1),
+            permutation.max_constraint_log_degree_bound(),
+            permutation.n_constraints(),
+        );
+
+        permutation.evaluate_constraint_quotients_on_domain(
+            &component_trace,
+            &mut accumulator,
+            &elements,
+        );
+
+        accumulator
+            .finalize()
+            .iter()
+            .map(|poly| poly.coeffs.to_cpu())
+            .collect()
+    }
+
+    #[test]
+    fn test_simd_quotients_match_cpu() {
+        let columns = shuffled_columns();
+
+        assert_eq!(
+            quotient_coeffs::<CpuBackend>(&columns),
+            quotient_coeffs::<SimdBackend>(&columns)
+        );
+    }
+}
@@ -0,0 +1,558 @@
+//! Conformance checks for [Backend] implementations.
+//!
+//! Each `check_*` function runs the operations of one backend trait on random inputs of many
+//! sizes, including sizes smaller than a SIMD vector, and panics if a result differs from the
+//! [CpuBackend] reference implementation. A new backend can validate itself by calling
+//! [check_backend] from its tests.
+//!
+//! Field elements are compared modulo P, since backends may return them in a redundant
+//! representation (i.e. P for zero).
+
+use std::fmt::Debug;
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use super::{Backend, Col, Column, ColumnOps, CpuBackend};
+use crate::core::air::accumulation::AccumulationOps;
+use crate::core::circle::{
+    CirclePoint, CirclePointIndex, Coset, M31_CIRCLE_LOG_ORDER, SECURE_FIELD_CIRCLE_ORDER,
+};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::FieldOps;
+use crate::core::fri::FriOps;
+use crate::core::pcs::quotients::{ColumnSampleBatch, QuotientOps};
+use crate::core::poly::circle::{
+    CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, PolyOps, SecureEvaluation,
+};
+use crate::core::poly::line::{LineDomain, LineEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::LOG_BLOWUP_FACTOR;
+use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+use crate::core::vcs::ops::{MerkleHasher, MerkleOps};
+
+/// The largest log size of the inputs.
+const MAX_LOG_SIZE: u32 = 10;
+
+/// Runs all the conformance checks on `B`.
+pub fn check_backend<B: Backend + MerkleOps<Blake2sMerkleHasher>>() {
+    check_column_ops::<B>();
+    check_field_ops::<B>();
+    check_poly_ops::<B>();
+    check_fri_ops::<B>();
+    check_quotient_ops::<B>();
+    check_accumulation_ops::<B>();
+    check_merkle_ops::<B, Blake2sMerkleHasher>();
+}
+
+/// Checks [ColumnOps] and [Column] for base field and secure field columns.
+pub fn check_column_ops<B: ColumnOps<BaseField> + ColumnOps<SecureField>>() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in 0..=MAX_LOG_SIZE {
+        check_column::<B, BaseField>(rng, log_size, canonical);
+        check_column::<B, SecureField>(rng, log_size, canonical_secure);
+    }
+}
+
+fn check_column<B: ColumnOps<T>, T: Copy + Default + PartialEq + Debug + Send + Sync>(
+    rng: &mut SmallRng,
+    log_size: u32,
+    canonical: fn(T) -> T,
+) where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    let values = (0..1 << log_size).map(|_| rng.gen()).collect_vec();
+    let mut column = values.iter().copied().collect::<Col<B, T>>();
+
+    assert_eq!(column.len(), values.len(), "len, log_size={log_size}");
+    assert_eq!(column.to_cpu(), values, "to_cpu, log_size={log_size}");
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(column.at(i), value, "at, log_size={log_size}");
+    }
+    let zeros = Col::<B, T>::zeros(values.len()).to_cpu();
+    assert_eq!(
+        zeros.into_iter().map(canonical).collect_vec(),
+        vec![T::default(); values.len()]
+    );
+
+    let mut expected = values;
+    CpuBackend::bit_reverse_column(&mut expected);
+    B::bit_reverse_column(&mut column);
+    assert_eq!(
+        column.to_cpu(),
+        expected,
+        "bit_reverse_column, log_size={log_size}"
+    );
+}
+
+/// Checks [FieldOps] for the base field and the secure field.
+pub fn check_field_ops<B: FieldOps<BaseField> + FieldOps<SecureField>>() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in 0..=MAX_LOG_SIZE {
+        let values = random_values::<BaseField>(rng, 1 << log_size);
+        let mut expected = vec![BaseField::default(); values.len()];
+        CpuBackend::batch_inverse(&values, &mut expected);
+        let mut dst = Col::<B, BaseField>::zeros(values.len());
+        <B as FieldOps<BaseField>>::batch_inverse(&to_col::<B, _>(&values), &mut dst);
+        assert_eq_base(&dst.to_cpu(), &expected, "batch_inverse", log_size);
+
+        let values = random_values::<SecureField>(rng, 1 << log_size);
+        let mut expected = vec![SecureField::default(); values.len()];
+        CpuBackend::batch_inverse(&values, &mut expected);
+        let mut dst = Col::<B, SecureField>::zeros(values.len());
+        <B as FieldOps<SecureField>>::batch_inverse(&to_col::<B, _>(&values), &mut dst);
+        assert_eq_secure(&dst.to_cpu(), &expected, "secure batch_inverse", log_size);
+    }
+}
+
+/// Checks [PolyOps], on canonic and non-canonic domains.
+pub fn check_poly_ops<B: PolyOps>() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in 1..=MAX_LOG_SIZE {
+        let coset = CanonicCoset::new(log_size);
+        let values = random_values(rng, coset.size());
+        let expected = CpuBackend::new_canonical_ordered(coset, values.clone());
+        let eval = B::new_canonical_ordered(coset, to_col::<B, _>(&values));
+        assert_eq!(eval.domain, expected.domain);
+        assert_eq_base(
+            &eval.values.to_cpu(),
+            &expected.values,
+            "new_canonical_ordered",
+            log_size,
+        );
+
+        let random_domain = CircleDomain::new(random_coset(rng, log_size - 1));
+        for domain in [coset.circle_domain(), random_domain] {
+            check_fft::<B>(rng, domain);
+        }
+
+        let cpu_poly = CirclePoly::<CpuBackend>::new(random_values(rng, 1 << log_size));
+        let poly = to_poly::<B>(&cpu_poly);
+        let point = random_point(rng);
+        assert_eq!(
+            B::eval_at_point(&poly, point),
+            CpuBackend::eval_at_point(&cpu_poly, point),
+            "eval_at_point, log_size={log_size}"
+        );
+        let extended = B::extend(&poly, log_size + 2);
+        let cpu_extended = CpuBackend::extend(&cpu_poly, log_size + 2);
+        assert_eq_base(
+            &extended.coeffs.to_cpu(),
+            &cpu_extended.coeffs,
+            "extend",
+            log_size,
+        );
+
+        check_poly_arithmetic::<B>(rng, &cpu_poly, log_size);
+    }
+}
+
+/// Checks interpolation and evaluation on `domain`, with twiddles of the domain and of a larger
+/// coset tower.
+fn check_fft<B: PolyOps>(rng: &mut SmallRng, domain: CircleDomain) {
+    let log_size = domain.log_size();
+    let cpu_twiddles = CpuBackend::precompute_twiddles(domain.half_coset);
+    let twiddles = B::precompute_twiddles(domain.half_coset);
+    let large_twiddles = B::precompute_twiddles(random_coset(rng, log_size + 1));
+    let subtree = large_twiddles.subtree(large_twiddles.root_coset.repeated_double(2));
+    let twiddles_of_subtree = B::precompute_twiddles(subtree.root_coset);
+
+    let values = random_values(rng, domain.size());
+    let expected =
+        CpuBackend::interpolate(CircleEvaluation::new(domain, values.clone()), &cpu_twiddles);
+    let poly = B::interpolate(
+        CircleEvaluation::new(domain, to_col::<B, _>(&values)),
+        &twiddles,
+    );
+    assert_eq_base(
+        &poly.coeffs.to_cpu(),
+        &expected.coeffs,
+        "interpolate",
+        log_size,
+    );
+
+    for poly_log_size in 0..=log_size {
+        let cpu_poly = CirclePoly::<CpuBackend>::new(random_values(rng, 1 << poly_log_size));
+        let poly = to_poly::<B>(&cpu_poly);
+        let expected = CpuBackend::evaluate(&cpu_poly, domain, &cpu_twiddles);
+        let eval = B::evaluate(&poly, domain, &twiddles);
+        assert_eq_base(
+            &eval.values.to_cpu(),
+            &expected.values,
+            "evaluate",
+            log_size,
+        );
+
+        // Evaluate on the domain of the subtree, with the subtree and with fresh twiddles.
+        let subtree_domain = CircleDomain::new(subtree.root_coset);
+        if poly_log_size <= subtree_domain.log_size() {
+            let expected = B::evaluate(&poly, subtree_domain, &twiddles_of_subtree);
+            let eval = B::evaluate(&poly, subtree_domain, &subtree);
+            assert_eq_base(
+                &eval.values.to_cpu(),
+                &expected.values.to_cpu(),
+                "evaluate with twiddle_subtree",
+                log_size,
+            );
+        }
+    }
+}
+
+/// Checks the [PolyOps] arithmetic operations.
+fn check_poly_arithmetic<B: PolyOps>(
+    rng: &mut SmallRng,
+    cpu_poly: &CirclePoly<CpuBackend>,
+    log_size: u32,
+) {
+    let poly = to_poly::<B>(cpu_poly);
+    let cpu_other = CirclePoly::<CpuBackend>::new(random_values(rng, 1 << log_size));
+    let other = to_poly::<B>(&cpu_other);
+    let factor = rng.gen();
+    assert_eq_base(
+        &B::add(&poly, &other).coeffs.to_cpu(),
+        &CpuBackend::add(cpu_poly, &cpu_other).coeffs,
+        "add",
+        log_size,
+    );
+    assert_eq_base(
+        &B::sub(&poly, &other).coeffs.to_cpu(),
+        &CpuBackend::sub(cpu_poly, &cpu_other).coeffs,
+        "sub",
+        log_size,
+    );
+    assert_eq_base(
+        &B::scale(&poly, factor).coeffs.to_cpu(),
+        &CpuBackend::scale(cpu_poly, factor).coeffs,
+        "scale",
+        log_size,
+    );
+
+    let domain = CanonicCoset::new(log_size + 1).circle_domain();
+    let (lhs, rhs) = (
+        random_values(rng, domain.size()),
+        random_values(rng, domain.size()),
+    );
+    let expected = CpuBackend::mul_evaluations(
+        &CircleEvaluation::new(domain, lhs.clone()),
+        &CircleEvaluation::new(domain, rhs.clone()),
+    );
+    let eval = B::mul_evaluations(
+        &CircleEvaluation::new(domain, to_col::<B, _>(&lhs)),
+        &CircleEvaluation::new(domain, to_col::<B, _>(&rhs)),
+    );
+    assert_eq_base(
+        &eval.values.to_cpu(),
+        &expected.values,
+        "mul_evaluations",
+        log_size,
+    );
+
+    let vanishing_coset = CanonicCoset::new(log_size).coset();
+    let expected = CpuBackend::div_by_coset_vanishing(
+        &CircleEvaluation::new(domain, lhs.clone()),
+        vanishing_coset,
+    );
+    let eval = B::div_by_coset_vanishing(
+        &CircleEvaluation::new(domain, to_col::<B, _>(&lhs)),
+        vanishing_coset,
+    );
+    assert_eq_base(
+        &eval.values.to_cpu(),
+        &expected.values,
+        "div_by_coset_vanishing",
+        log_size,
+    );
+}
+
+/// Checks [FriOps].
+pub fn check_fri_ops<B: FriOps>() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in 1..=MAX_LOG_SIZE {
+        let alpha = rng.gen();
+
+        let line_domain = LineDomain::new(Coset::half_odds(log_size));
+        let values = random_values(rng, line_domain.size());
+        let expected = CpuBackend::fold_line(
+            &LineEvaluation::new(line_domain, values.iter().copied().collect()),
+            alpha,
+            &CpuBackend::precompute_twiddles(line_domain.coset()),
+        );
+        let folded = B::fold_line(
+            &LineEvaluation::new(line_domain, to_secure_col::<B>(&values)),
+            alpha,
+            &B::precompute_twiddles(line_domain.coset()),
+        );
+        assert_eq_secure(
+            &folded.values.to_cpu().to_vec(),
+            &expected.values.to_vec(),
+            "fold_line",
+            log_size,
+        );
+
+        let circle_domain = CanonicCoset::new(log_size + 1).circle_domain();
+        let line_domain = LineDomain::new(circle_domain.half_coset);
+        let src = random_values(rng, circle_domain.size());
+        let dst = random_values(rng, line_domain.size());
+        let mut expected = LineEvaluation::new(line_domain, dst.iter().copied().collect());
+        CpuBackend::fold_circle_into_line(
+            &mut expected,
+            &SecureEvaluation {
+                domain: circle_domain,
+                values: src.iter().copied().collect(),
+            },
+            alpha,
+            &CpuBackend::precompute_twiddles(circle_domain.half_coset),
+        );
+        let mut folded = LineEvaluation::new(line_domain, to_secure_col::<B>(&dst));
+        B::fold_circle_into_line(
+            &mut folded,
+            &SecureEvaluation {
+                domain: circle_domain,
+                values: to_secure_col::<B>(&src),
+            },
+            alpha,
+            &B::precompute_twiddles(circle_domain.half_coset),
+        );
+        assert_eq_secure(
+            &folded.values.to_cpu().to_vec(),
+            &expected.values.to_vec(),
+            "fold_circle_into_line",
+            log_size,
+        );
+
+        let domain = CanonicCoset::new(log_size).circle_domain();
+        let values = random_values(rng, domain.size());
+        let (expected, expected_lambda) = CpuBackend::decompose(&SecureEvaluation {
+            domain,
+            values: values.iter().copied().collect(),
+        });
+        let (decomposed, lambda) = B::decompose(&SecureEvaluation {
+            domain,
+            values: to_secure_col::<B>(&values),
+        });
+        assert_eq!(
+            canonical_secure(lambda),
+            canonical_secure(expected_lambda),
+            "decompose, log_size={log_size}"
+        );
+        assert_eq_secure(
+            &decomposed.values.to_cpu().to_vec(),
+            &expected.values.to_vec(),
+            "decompose",
+            log_size,
+        );
+    }
+}
+
+/// Checks [QuotientOps].
+///
+/// The columns are blown-up evaluations of random polynomials, sampled at their true values, as
+/// in a proof. Backends may rely on the resulting quotients being low degree.
+pub fn check_quotient_ops<B: QuotientOps>() {
+    const N_COLUMNS: usize = 3;
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in LOG_BLOWUP_FACTOR + 1..=MAX_LOG_SIZE {
+        let domain = CanonicCoset::new(log_size).circle_domain();
+        let polys = (0..N_COLUMNS)
+            .map(|_| {
+                CirclePoly::<CpuBackend>::new(random_values(
+                    rng,
+                    1 << (log_size - LOG_BLOWUP_FACTOR),
+                ))
+            })
+            .collect_vec();
+        let columns = polys
+            .iter()
+            .map(|poly| poly.evaluate(domain).values)
+            .collect_vec();
+        let sample_batches = [vec![0, 2], vec![1], vec![0, 1, 2]]
+            .into_iter()
+            .map(|column_indices| {
+                let point = random_point(rng);
+                ColumnSampleBatch {
+                    point,
+                    columns_and_values: column_indices
+                        .into_iter()
+                        .map(|i| (i, polys[i].eval_at_point(point)))
+                        .collect(),
+                }
+            })
+            .collect_vec();
+        let random_coeff = rng.gen();
+
+        let cpu_columns = columns
+            .iter()
+            .map(|values| CircleEvaluation::new(domain, values.clone()))
+            .collect_vec();
+        let expected = CpuBackend::accumulate_quotients(
+            domain,
+            &cpu_columns.iter().collect_vec(),
+            random_coeff,
+            &sample_batches,
+        );
+        let columns = columns
+            .iter()
+            .map(|values| {
+                CircleEvaluation::<B, BaseField, BitReversedOrder>::new(
+                    domain,
+                    to_col::<B, _>(values),
+                )
+            })
+            .collect_vec();
+        let quotients = B::accumulate_quotients(
+            domain,
+            &columns.iter().collect_vec(),
+            random_coeff,
+            &sample_batches,
+        );
+        assert_eq_secure(
+            &quotients.values.to_cpu().to_vec(),
+            &expected.values.to_vec(),
+            "accumulate_quotients",
+            log_size,
+        );
+    }
+}
+
+/// Checks [AccumulationOps].
+pub fn check_accumulation_ops<B: AccumulationOps>() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in 0..=MAX_LOG_SIZE {
+        let column = random_values(rng, 1 << log_size);
+        let other = random_values(rng, 1 << log_size);
+        let mut expected: SecureColumn<CpuBackend> = column.iter().copied().collect();
+        <CpuBackend as AccumulationOps>::accumulate(
+            &mut expected,
+            &other.iter().copied().collect::<SecureColumn<CpuBackend>>(),
+        );
+        let mut accumulated = to_secure_col::<B>(&column);
+        B::accumulate(&mut accumulated, &to_secure_col::<B>(&other));
+        assert_eq_secure(
+            &accumulated.to_cpu().to_vec(),
+            &expected.to_vec(),
+            "accumulate",
+            log_size,
+        );
+    }
+}
+
+/// Checks [MerkleOps] for the hasher `H`.
+pub fn check_merkle_ops<B: MerkleOps<H>, H: MerkleHasher>()
+where
+    CpuBackend: MerkleOps<H>,
+{
+    let rng = &mut SmallRng::seed_from_u64(0);
+    for log_size in 0..=MAX_LOG_SIZE {
+        let prev_layer_column = random_values::<BaseField>(rng, 2 << log_size);
+        let prev_layer = <CpuBackend as MerkleOps<H>>::commit_on_layer(
+            log_size + 1,
+            None,
+            &[&prev_layer_column],
+        );
+        for n_columns in [0, 1, 3] {
+            let columns = (0..n_columns)
+                .map(|_| random_values::<BaseField>(rng, 1 << log_size))
+                .collect_vec();
+            let backend_columns = columns.iter().map(|c| to_col::<B, _>(c)).collect_vec();
+            let backend_prev_layer = prev_layer.to_cpu().into_iter().collect::<Col<B, H::Hash>>();
+            for with_prev_layer in [false, true] {
+                if n_columns == 0 && !with_prev_layer {
+                    continue;
+                }
+                let expected = <CpuBackend as MerkleOps<H>>::commit_on_layer(
+                    log_size,
+                    with_prev_layer.then_some(&prev_layer),
+                    &columns.iter().collect_vec(),
+                );
+                let layer = B::commit_on_layer(
+                    log_size,
+                    with_prev_layer.then_some(&backend_prev_layer),
+                    &backend_columns.iter().collect_vec(),
+                );
+                assert_eq!(
+                    layer.to_cpu(),
+                    expected.to_cpu(),
+                    "commit_on_layer, log_size={log_size}, n_columns={n_columns}, \
+                     with_prev_layer={with_prev_layer}"
+                );
+            }
+        }
+    }
+}
+
+fn random_values<T>(rng: &mut SmallRng, len: usize) -> Vec<T>
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+fn random_coset(rng: &mut SmallRng, log_size: u32) -> Coset {
+    Coset::new(
+        CirclePointIndex(rng.gen_range(0..1 << M31_CIRCLE_LOG_ORDER)),
+        log_size,
+    )
+}
+
+fn random_point(rng: &mut SmallRng) -> CirclePoint<SecureField> {
+    CirclePoint::get_point(rng.gen::<u128>() % SECURE_FIELD_CIRCLE_ORDER)
+}
+
+fn to_col<B: ColumnOps<T>, T: Copy>(values: &[T]) -> Col<B, T> {
+    values.iter().copied().collect()
+}
+
+fn to_secure_col<B: FieldOps<BaseField>>(values: &[SecureField]) -> SecureColumn<B> {
+    let cpu_column: SecureColumn<CpuBackend> = values.iter().copied().collect();
+    SecureColumn {
+        columns: cpu_column.columns.map(|column| to_col::<B, _>(&column)),
+    }
+}
+
+fn to_poly<B: PolyOps>(poly: &CirclePoly<CpuBackend>) -> CirclePoly<B> {
+    CirclePoly::new(to_col::<B, _>(&poly.coeffs))
+}
+
+fn canonical(value: BaseField) -> BaseField {
+    BaseField::partial_reduce(value.0)
+}
+
+fn canonical_secure(value: SecureField) -> SecureField {
+    SecureField::from_m31_array(value.to_m31_array().map(canonical))
+}
+
+fn assert_eq_base(values: &[BaseField], expected: &[BaseField], op: &str, log_size: u32) {
+    assert_eq!(
+        values.iter().copied().map(canonical).collect_vec(),
+        expected.iter().copied().map(canonical).collect_vec(),
+        "{op}, log_size={log_size}"
+    );
+}
+
+fn assert_eq_secure(values: &[SecureField], expected: &[SecureField], op: &str, log_size: u32) {
+    assert_eq!(
+        values.iter().copied().map(canonical_secure).collect_vec(),
+        expected.iter().copied().map(canonical_secure).collect_vec(),
+        "{op}, log_size={log_size}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_backend;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::CpuBackend;
+
+    #[test]
+    fn test_cpu_backend_conformance() {
+        check_backend::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_simd_backend_conformance() {
+        check_backend::<SimdBackend>();
+    }
+}
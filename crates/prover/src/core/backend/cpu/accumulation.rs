@@ -1,9 +1,10 @@
 use super::CpuBackend;
 use crate::core::air::accumulation::AccumulationOps;
-use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::SecureExtensionField;
 
-impl AccumulationOps for CpuBackend {
-    fn accumulate(column: &mut SecureColumn<Self>, other: &SecureColumn<Self>) {
+impl<F: SecureExtensionField> AccumulationOps<F> for CpuBackend {
+    fn accumulate(column: &mut F::Column<Self>, other: &F::Column<Self>) {
         for i in 0..column.len() {
             let res_coeff = column.at(i) + other.at(i);
             column.set(i, res_coeff);
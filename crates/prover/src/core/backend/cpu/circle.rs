@@ -1,8 +1,12 @@
+use std::iter::zip;
+
+use itertools::Itertools;
 use num_traits::Zero;
 
 use super::CpuBackend;
 use crate::core::backend::{Col, ColumnOps};
 use crate::core::circle::{CirclePoint, Coset};
+use crate::core::constraints::coset_vanishing;
 use crate::core::fft::{butterfly, ibutterfly};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
@@ -13,7 +17,7 @@ use crate::core::poly::circle::{
 use crate::core::poly::twiddles::TwiddleTree;
 use crate::core::poly::utils::{domain_line_twiddles_from_tree, fold};
 use crate::core::poly::BitReversedOrder;
-use crate::core::utils::{bit_reverse, coset_order_to_circle_domain_order};
+use crate::core::utils::{bit_reverse, bit_reverse_index, coset_order_to_circle_domain_order};
 
 impl PolyOps for CpuBackend {
     type Twiddles = Vec<BaseField>;
@@ -50,9 +54,9 @@ impl PolyOps for CpuBackend {
             return CirclePoly::new(values);
         };
 
-        let circle_twiddles = circle_twiddles_from_line_twiddles(line_twiddles[0]);
+        let circle_itwiddles = circle_twiddles(eval.domain, line_twiddles[0], true);
 
-        for (h, t) in circle_twiddles.enumerate() {
+        for (h, t) in circle_itwiddles.into_iter().enumerate() {
             fft_layer_loop(&mut values, 0, h, t, ibutterfly);
         }
         for (layer, layer_twiddles) in line_twiddles.into_iter().enumerate() {
@@ -71,15 +75,7 @@ impl PolyOps for CpuBackend {
     }
 
     fn eval_at_point(poly: &CirclePoly<Self>, point: CirclePoint<SecureField>) -> SecureField {
-        // TODO(Andrew): Allocation here expensive for small polynomials.
-        let mut mappings = vec![point.y, point.x];
-        let mut x = point.x;
-        for _ in 2..poly.log_size() {
-            x = CirclePoint::double_x(x);
-            mappings.push(x);
-        }
-        mappings.reverse();
-        fold(&poly.coeffs, &mappings)
+        fold(&poly.coeffs, &eval_folding_factors(point, poly.log_size()))
     }
 
     fn extend(poly: &CirclePoly<Self>, log_size: u32) -> CirclePoly<Self> {
@@ -102,24 +98,63 @@ impl PolyOps for CpuBackend {
 
         if domain.log_size() == 1 {
             let (mut val0, mut val1) = (values[0], values[1]);
-            butterfly(&mut val0, &mut val1, domain.half_coset.initial.y.inverse());
+            butterfly(&mut val0, &mut val1, domain.half_coset.initial.y);
+            (values[0], values[1]) = (val0, val1);
             return CircleEvaluation::new(domain, values);
         };
 
-        let circle_twiddles = circle_twiddles_from_line_twiddles(line_twiddles[0]);
+        let circle_twiddles = circle_twiddles(domain, line_twiddles[0], false);
 
         for (layer, layer_twiddles) in line_twiddles.iter().enumerate().rev() {
             for (h, &t) in layer_twiddles.iter().enumerate() {
                 fft_layer_loop(&mut values, layer + 1, h, t, butterfly);
             }
         }
-        for (h, t) in circle_twiddles.enumerate() {
+        for (h, t) in circle_twiddles.into_iter().enumerate() {
             fft_layer_loop(&mut values, 0, h, t, butterfly);
         }
 
         CircleEvaluation::new(domain, values)
     }
 
+    fn add(a: &CirclePoly<Self>, b: &CirclePoly<Self>) -> CirclePoly<Self> {
+        assert_eq!(a.log_size(), b.log_size());
+        CirclePoly::new(zip(&a.coeffs, &b.coeffs).map(|(&a, &b)| a + b).collect())
+    }
+
+    fn sub(a: &CirclePoly<Self>, b: &CirclePoly<Self>) -> CirclePoly<Self> {
+        assert_eq!(a.log_size(), b.log_size());
+        CirclePoly::new(zip(&a.coeffs, &b.coeffs).map(|(&a, &b)| a - b).collect())
+    }
+
+    fn scale(poly: &CirclePoly<Self>, factor: BaseField) -> CirclePoly<Self> {
+        CirclePoly::new(poly.coeffs.iter().map(|&c| c * factor).collect())
+    }
+
+    fn mul_evaluations(
+        a: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        b: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
+        assert_eq!(a.domain, b.domain);
+        CircleEvaluation::new(
+            a.domain,
+            zip(&a.values, &b.values).map(|(&a, &b)| a * b).collect(),
+        )
+    }
+
+    fn div_by_coset_vanishing(
+        eval: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        coset: Coset,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
+        let inverses = coset_vanishing_inverses(eval.domain, coset);
+        CircleEvaluation::new(
+            eval.domain,
+            zip(&eval.values, inverses)
+                .map(|(&v, inv)| v * inv)
+                .collect(),
+        )
+    }
+
     fn precompute_twiddles(mut coset: Coset) -> TwiddleTree<Self> {
         const CHUNK_LOG_SIZE: usize = 12;
         const CHUNK_SIZE: usize = 1 << CHUNK_LOG_SIZE;
@@ -165,6 +200,55 @@ impl PolyOps for CpuBackend {
             itwiddles,
         }
     }
+
+    fn twiddle_subtree(twiddles: &TwiddleTree<Self>, coset: Coset) -> TwiddleTree<Self> {
+        assert!(coset.is_doubling_of(twiddles.root_coset));
+        // The twiddles of each coset in the tower are a suffix of the twiddles of the larger ones.
+        let n_twiddles = coset.size();
+        TwiddleTree {
+            root_coset: coset,
+            twiddles: twiddles.twiddles[twiddles.twiddles.len() - n_twiddles..].to_vec(),
+            itwiddles: twiddles.itwiddles[twiddles.itwiddles.len() - n_twiddles..].to_vec(),
+        }
+    }
+}
+
+/// Returns the factors folding the coefficients of a polynomial of size `2^log_size` to its value
+/// at `point`. See [fold].
+pub(crate) fn eval_folding_factors<F: ExtensionOf<BaseField>>(
+    point: CirclePoint<F>,
+    log_size: u32,
+) -> Vec<F> {
+    // TODO(Andrew): Allocation here expensive for small polynomials.
+    let mut mappings = vec![point.y, point.x];
+    let mut x = point.x;
+    for _ in 2..log_size {
+        x = CirclePoint::double_x(x);
+        mappings.push(x);
+    }
+    // Polynomials of log size less than 2 only depend on y, or are constant.
+    mappings.truncate(log_size as usize);
+    mappings.reverse();
+    mappings
+}
+
+/// Returns the inverses of the vanishing polynomial of `coset` on `domain`, in bit reversed order.
+///
+/// # Panics
+///
+/// Panics if `domain` intersects `coset`.
+pub(crate) fn coset_vanishing_inverses(domain: CircleDomain, coset: Coset) -> Vec<BaseField> {
+    let log_size = domain.log_size();
+    let denominators = (0..domain.size())
+        .map(|i| coset_vanishing(coset, domain.at(bit_reverse_index(i, log_size))))
+        .collect_vec();
+    assert!(
+        denominators.iter().all(|d| !d.is_zero()),
+        "The domain intersects the vanishing coset"
+    );
+    let mut inverses = vec![BaseField::zero(); denominators.len()];
+    BaseField::batch_inverse(&denominators, &mut inverses);
+    inverses
 }
 
 fn fft_layer_loop(
@@ -183,6 +267,27 @@ fn fft_layer_loop(
     }
 }
 
+/// Computes the circle twiddles layer (layer 0) of `domain`, given its first line twiddles layer
+/// (layer 1). If `inverse` is set, the line twiddles and the result are inverse twiddles.
+fn circle_twiddles(
+    domain: CircleDomain,
+    first_line_twiddles: &[BaseField],
+    inverse: bool,
+) -> Vec<BaseField> {
+    if domain.log_size() > 2 {
+        return circle_twiddles_from_line_twiddles(first_line_twiddles).collect();
+    }
+
+    // The first line twiddles layer of a domain of size 4 consists of a single twiddle, which is
+    // not enough to derive the circle twiddles of a non-canonic domain. Compute them directly: the
+    // twiddles are the y coordinates of the points `p` in the butterfly pairs `(p, conj(p))`.
+    let log_size = domain.log_size();
+    (0..domain.size() / 2)
+        .map(|h| domain.at(bit_reverse_index(h << 1, log_size)).y)
+        .map(|y| if inverse { y.inverse() } else { y })
+        .collect()
+}
+
 /// Computes the circle twiddles layer (layer 0) from the first line twiddles layer (layer 1).
 fn circle_twiddles_from_line_twiddles(
     first_line_twiddles: &[BaseField],
@@ -1,33 +1,33 @@
 use super::CpuBackend;
 use crate::core::fields::m31::BaseField;
-use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::SecureExtensionField;
 use crate::core::fri::{fold_circle_into_line, fold_line, FriOps};
 use crate::core::poly::circle::SecureEvaluation;
 use crate::core::poly::line::LineEvaluation;
 use crate::core::poly::twiddles::TwiddleTree;
 
 // TODO(spapini): Optimized these functions as well.
-impl FriOps for CpuBackend {
+impl<F: SecureExtensionField> FriOps<F> for CpuBackend {
     fn fold_line(
-        eval: &LineEvaluation<Self>,
-        alpha: SecureField,
+        eval: &LineEvaluation<Self, F>,
+        alpha: F,
         _twiddles: &TwiddleTree<Self>,
-    ) -> LineEvaluation<Self> {
+    ) -> LineEvaluation<Self, F> {
         fold_line(eval, alpha)
     }
     fn fold_circle_into_line(
-        dst: &mut LineEvaluation<Self>,
-        src: &SecureEvaluation<Self>,
-        alpha: SecureField,
+        dst: &mut LineEvaluation<Self, F>,
+        src: &SecureEvaluation<Self, F>,
+        alpha: F,
         _twiddles: &TwiddleTree<Self>,
     ) {
         fold_circle_into_line(dst, src, alpha)
     }
 
-    fn decompose(eval: &SecureEvaluation<Self>) -> (SecureEvaluation<Self>, SecureField) {
+    fn decompose(eval: &SecureEvaluation<Self, F>) -> (SecureEvaluation<Self, F>, F) {
         let lambda = Self::decomposition_coefficient(eval);
-        let mut g_values = SecureColumn::<Self>::zeros(eval.len());
+        let mut g_values = F::Column::<Self>::zeros(eval.len());
 
         let domain_size = eval.len();
         let half_domain_size = domain_size / 2;
@@ -67,18 +67,16 @@ impl CpuBackend {
     /// This function assumes the blowupfactor is 2
     ///
     /// [`CirclePoly`]: crate::core::poly::circle::CirclePoly
-    fn decomposition_coefficient(eval: &SecureEvaluation<Self>) -> SecureField {
+    fn decomposition_coefficient<F: SecureExtensionField>(eval: &SecureEvaluation<Self, F>) -> F {
         let domain_size = 1 << eval.domain.log_size();
         let half_domain_size = domain_size / 2;
 
         // eval is in bit-reverse, hence all the positive factors are in the first half, opposite to
         // the latter.
-        let a_sum = (0..half_domain_size)
-            .map(|i| eval.values.at(i))
-            .sum::<SecureField>();
+        let a_sum = (0..half_domain_size).map(|i| eval.values.at(i)).sum::<F>();
         let b_sum = (half_domain_size..domain_size)
             .map(|i| eval.values.at(i))
-            .sum::<SecureField>();
+            .sum::<F>();
 
         // lambda = sum(+-f(p)) / 2N.
         (a_sum - b_sum) / BaseField::from_u32_unchecked(domain_size as u32)
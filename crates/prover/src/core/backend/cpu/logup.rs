@@ -0,0 +1,66 @@
+use std::iter::zip;
+
+use itertools::Itertools;
+
+use super::CpuBackend;
+use crate::core::air::accumulation::ColumnAccumulator;
+use crate::core::air::logup::{signed_multiplicity, Logup, LogupOps, LogupRow, N_LOGUP_DENOMS};
+use crate::core::air::ComponentTrace;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::{previous_bit_reversed_circle_domain_index, shifted_secure_combination};
+
+impl LogupOps for CpuBackend {
+    fn accumulate_logup_quotients(
+        logup: &Logup,
+        trace: &ComponentTrace<'_, Self>,
+        mut accumulator: ColumnAccumulator<'_, Self>,
+        denom_inverses: &[Vec<BaseField>; N_LOGUP_DENOMS],
+        elements: &[(SecureField, SecureField)],
+        claimed_sum: SecureField,
+    ) {
+        let eval_domain = trace.evals[INTERACTION_TRACE][0].domain;
+        let eval_log_size = eval_domain.log_size();
+        let interaction = &trace.evals[INTERACTION_TRACE];
+        let secure_value = |column: usize, i: usize| {
+            SecureField::from_m31_array(std::array::from_fn(|j| {
+                interaction[column * SECURE_EXTENSION_DEGREE + j].values[i]
+            }))
+        };
+        let n_entries = logup.entries.len();
+        for i in 0..eval_domain.size() {
+            let base = |column: usize| trace.evals[BASE_TRACE][column].values[i];
+            let prev_i =
+                previous_bit_reversed_circle_domain_index(i, logup.log_size, eval_log_size);
+            let row = LogupRow {
+                denoms: zip(&logup.entries, elements)
+                    .map(|(entry, &(alpha, z))| {
+                        let values = entry.values.iter().map(|&column| base(column));
+                        shifted_secure_combination(&values.collect_vec(), alpha, z)
+                    })
+                    .collect(),
+                multiplicities: logup
+                    .entries
+                    .iter()
+                    .map(|entry| signed_multiplicity(entry, |column| base(column).into()))
+                    .collect(),
+                fractions: (0..n_entries)
+                    .map(|column| secure_value(column, i))
+                    .collect(),
+                sum: secure_value(n_entries, i),
+                prev_sum: secure_value(n_entries, prev_i),
+            };
+            let row_denom_inverses = std::array::from_fn(|j| denom_inverses[j][i]);
+            // The j-th constraint is multiplied by alpha^(n_constraints - 1 - j).
+            let res = zip(
+                logup.constraint_quotients(&row, claimed_sum, row_denom_inverses),
+                accumulator.random_coeff_powers.iter().rev(),
+            )
+            .map(|(quotient, &coeff)| quotient * coeff)
+            .sum();
+            accumulator.accumulate(i, res);
+        }
+    }
+}
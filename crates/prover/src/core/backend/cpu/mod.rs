@@ -1,8 +1,10 @@
 mod accumulation;
 mod blake2s;
-mod circle;
+pub(crate) mod circle;
 mod fri;
+mod logup;
 mod lookups;
+mod permutation;
 pub mod quotients;
 
 use std::fmt::Debug;
@@ -18,7 +20,7 @@ pub struct CpuBackend;
 
 impl Backend for CpuBackend {}
 
-impl<T: Debug + Clone + Default> ColumnOps<T> for CpuBackend {
+impl<T: Debug + Clone + Default + Send + Sync> ColumnOps<T> for CpuBackend {
     type Column = Vec<T>;
 
     fn bit_reverse_column(column: &mut Self::Column) {
@@ -34,7 +36,7 @@ impl<F: Field> FieldOps<F> for CpuBackend {
     }
 }
 
-impl<T: Debug + Clone + Default> Column<T> for Vec<T> {
+impl<T: Debug + Clone + Default + Send + Sync> Column<T> for Vec<T> {
     fn zeros(len: usize) -> Self {
         vec![T::default(); len]
     }
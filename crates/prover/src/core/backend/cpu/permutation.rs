@@ -0,0 +1,97 @@
+use itertools::Itertools;
+use num_traits::{One, Zero};
+
+use super::CpuBackend;
+use crate::core::air::accumulation::ColumnAccumulator;
+use crate::core::air::permutation::{Permutation, PermutationOps, N_PERMUTATION_CONSTRAINTS};
+use crate::core::air::ComponentTrace;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::FieldExpOps;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::{
+    coset_index_to_bit_reversed_circle_domain_index, previous_bit_reversed_circle_domain_index,
+    shifted_secure_combination,
+};
+use crate::core::ColumnVec;
+
+impl PermutationOps for CpuBackend {
+    fn grand_product(
+        permutation: &Permutation,
+        trace: &ColumnVec<&CircleEvaluation<Self, BaseField, BitReversedOrder>>,
+        alpha: SecureField,
+        z: SecureField,
+    ) -> SecureColumn<Self> {
+        let size = 1 << permutation.log_size;
+        let combine = |columns: &[usize], i: usize| {
+            let values = columns
+                .iter()
+                .map(|&column| trace[column].values[i])
+                .collect_vec();
+            shifted_secure_combination(&values, alpha, z)
+        };
+        let denoms = (0..size)
+            .map(|i| combine(&permutation.rhs, i))
+            .collect_vec();
+        let mut ratios = vec![SecureField::zero(); size];
+        SecureField::batch_inverse(&denoms, &mut ratios);
+        for (i, ratio) in ratios.iter_mut().enumerate() {
+            *ratio *= combine(&permutation.lhs, i);
+        }
+
+        // The product runs over the rows in coset order.
+        let mut res = SecureColumn::zeros(size);
+        let mut product = SecureField::one();
+        for row in 0..size {
+            let i = coset_index_to_bit_reversed_circle_domain_index(row, permutation.log_size);
+            product *= ratios[i];
+            res.set(i, product);
+        }
+        res
+    }
+
+    fn accumulate_permutation_quotients(
+        permutation: &Permutation,
+        trace: &ComponentTrace<'_, Self>,
+        mut accumulator: ColumnAccumulator<'_, Self>,
+        denom_inverses: &[Vec<BaseField>; N_PERMUTATION_CONSTRAINTS],
+        alpha: SecureField,
+        z: SecureField,
+    ) {
+        let eval_domain = trace.evals[INTERACTION_TRACE][0].domain;
+        let eval_log_size = eval_domain.log_size();
+        let combine = |columns: &[usize], i: usize| {
+            let values = columns
+                .iter()
+                .map(|&column| trace.evals[BASE_TRACE][column].values[i])
+                .collect_vec();
+            shifted_secure_combination(&values, alpha, z)
+        };
+        let product = |i: usize| {
+            SecureField::from_m31_array(std::array::from_fn(|j| {
+                trace.evals[INTERACTION_TRACE][j].values[i]
+            }))
+        };
+        for i in 0..eval_domain.size() {
+            let prev_i =
+                previous_bit_reversed_circle_domain_index(i, permutation.log_size, eval_log_size);
+            let numerators = permutation.constraint_numerators(
+                combine(&permutation.lhs, i),
+                combine(&permutation.rhs, i),
+                product(i),
+                product(prev_i),
+            );
+            // The i-th constraint is multiplied by alpha^(n_constraints - 1 - i).
+            let res = numerators
+                .into_iter()
+                .zip(denom_inverses)
+                .zip(accumulator.random_coeff_powers.iter().rev())
+                .map(|((numerator, denom_inverses), &coeff)| numerator * denom_inverses[i] * coeff)
+                .sum();
+            accumulator.accumulate(i, res);
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use itertools::{izip, zip_eq};
-use num_traits::{One, Zero};
 
 use super::CpuBackend;
 use crate::core::backend::{Backend, Col};
@@ -7,21 +6,21 @@ use crate::core::circle::CirclePoint;
 use crate::core::constraints::{complex_conjugate_line_coeffs, pair_vanishing};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SecureColumn;
-use crate::core::fields::{ComplexConjugate, FieldExpOps};
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::{ComplexConjugate, FieldOps, SecureExtensionField};
 use crate::core::pcs::quotients::{ColumnSampleBatch, PointSample, QuotientOps};
 use crate::core::poly::circle::{CircleDomain, CircleEvaluation, SecureEvaluation};
 use crate::core::poly::BitReversedOrder;
 use crate::core::utils::{bit_reverse, bit_reverse_index};
 
-impl QuotientOps for CpuBackend {
+impl<F: SecureExtensionField> QuotientOps<F> for CpuBackend {
     fn accumulate_quotients(
         domain: CircleDomain,
         columns: &[&CircleEvaluation<Self, BaseField, BitReversedOrder>],
-        random_coeff: SecureField,
-        sample_batches: &[ColumnSampleBatch],
-    ) -> SecureEvaluation<Self> {
-        let mut values = SecureColumn::zeros(domain.size());
+        random_coeff: F,
+        sample_batches: &[ColumnSampleBatch<F>],
+    ) -> SecureEvaluation<Self, F> {
+        let mut values = F::Column::zeros(domain.size());
         let quotient_constants = quotient_constants(sample_batches, random_coeff, domain);
 
         // TODO(spapini): bit reverse iterator.
@@ -41,25 +40,25 @@ impl QuotientOps for CpuBackend {
     }
 }
 
-pub fn accumulate_row_quotients(
-    sample_batches: &[ColumnSampleBatch],
+pub fn accumulate_row_quotients<F: SecureExtensionField>(
+    sample_batches: &[ColumnSampleBatch<F>],
     columns: &[&CircleEvaluation<CpuBackend, BaseField, BitReversedOrder>],
-    quotient_constants: &QuotientConstants<CpuBackend>,
+    quotient_constants: &QuotientConstants<CpuBackend, F>,
     row: usize,
     domain_point: CirclePoint<BaseField>,
-) -> SecureField {
-    let mut row_accumulator = SecureField::zero();
+) -> F {
+    let mut row_accumulator = F::zero();
     for (sample_batch, line_coeffs, batch_coeff, denominator_inverses) in izip!(
         sample_batches,
         &quotient_constants.line_coeffs,
         &quotient_constants.batch_random_coeffs,
         &quotient_constants.denominator_inverses
     ) {
-        let mut numerator = SecureField::zero();
+        let mut numerator = F::zero();
         for ((column_index, _), (a, b, c)) in zip_eq(&sample_batch.columns_and_values, line_coeffs)
         {
             let column = &columns[*column_index];
-            let value = column[row] * *c;
+            let value = *c * column[row];
             // The numerator is a line equation passing through
             //   (sample_point.y, sample_value), (conj(sample_point), conj(sample_value))
             // evaluated at (domain_point.y, value).
@@ -79,14 +78,14 @@ pub fn accumulate_row_quotients(
 /// Specifically, for the i-th (in a sample batch) column's numerator term
 /// `alpha^i * (c * F(p) - (a * p.y + b))`, we precompute and return the constants:
 /// (`alpha^i * a`, `alpha^i * b`, `alpha^i * c`).
-pub fn column_line_coeffs(
-    sample_batches: &[ColumnSampleBatch],
-    random_coeff: SecureField,
-) -> Vec<Vec<(SecureField, SecureField, SecureField)>> {
+pub fn column_line_coeffs<F: SecureExtensionField>(
+    sample_batches: &[ColumnSampleBatch<F>],
+    random_coeff: F,
+) -> Vec<Vec<(F, F, F)>> {
     sample_batches
         .iter()
         .map(|sample_batch| {
-            let mut alpha = SecureField::one();
+            let mut alpha = F::one();
             sample_batch
                 .columns_and_values
                 .iter()
@@ -106,20 +105,20 @@ pub fn column_line_coeffs(
 /// Precompute the random coefficients used to linearly combine the batched quotients.
 /// Specifically, for each sample batch we compute random_coeff^(number of columns in the batch),
 /// which is used to linearly combine the batch with the next one.
-pub fn batch_random_coeffs(
-    sample_batches: &[ColumnSampleBatch],
-    random_coeff: SecureField,
-) -> Vec<SecureField> {
+pub fn batch_random_coeffs<F: SecureExtensionField>(
+    sample_batches: &[ColumnSampleBatch<F>],
+    random_coeff: F,
+) -> Vec<F> {
     sample_batches
         .iter()
         .map(|sb| random_coeff.pow(sb.columns_and_values.len() as u128))
         .collect()
 }
 
-fn denominator_inverses(
-    sample_batches: &[ColumnSampleBatch],
+fn denominator_inverses<F: SecureExtensionField>(
+    sample_batches: &[ColumnSampleBatch<F>],
     domain: CircleDomain,
-) -> Vec<Col<CpuBackend, SecureField>> {
+) -> Vec<Col<CpuBackend, F>> {
     let mut flat_denominators = Vec::with_capacity(sample_batches.len() * domain.size());
     for sample_batch in sample_batches {
         for row in 0..domain.size() {
@@ -133,8 +132,8 @@ fn denominator_inverses(
         }
     }
 
-    let mut flat_denominator_inverses = vec![SecureField::zero(); flat_denominators.len()];
-    SecureField::batch_inverse(&flat_denominators, &mut flat_denominator_inverses);
+    let mut flat_denominator_inverses = vec![F::zero(); flat_denominators.len()];
+    F::batch_inverse(&flat_denominators, &mut flat_denominator_inverses);
 
     flat_denominator_inverses
         .chunks_mut(domain.size())
@@ -145,11 +144,11 @@ fn denominator_inverses(
         .collect()
 }
 
-pub fn quotient_constants(
-    sample_batches: &[ColumnSampleBatch],
-    random_coeff: SecureField,
+pub fn quotient_constants<F: SecureExtensionField>(
+    sample_batches: &[ColumnSampleBatch<F>],
+    random_coeff: F,
     domain: CircleDomain,
-) -> QuotientConstants<CpuBackend> {
+) -> QuotientConstants<CpuBackend, F> {
     let line_coeffs = column_line_coeffs(sample_batches, random_coeff);
     let batch_random_coeffs = batch_random_coeffs(sample_batches, random_coeff);
     let denominator_inverses = denominator_inverses(sample_batches, domain);
@@ -161,15 +160,15 @@ pub fn quotient_constants(
 }
 
 /// Holds the precomputed constant values used in each quotient evaluation.
-pub struct QuotientConstants<B: Backend> {
+pub struct QuotientConstants<B: Backend + FieldOps<F>, F: SecureExtensionField = SecureField> {
     /// The line coefficients for each quotient numerator term. For more details see
     /// [self::column_line_coeffs].
-    pub line_coeffs: Vec<Vec<(SecureField, SecureField, SecureField)>>,
+    pub line_coeffs: Vec<Vec<(F, F, F)>>,
     /// The random coefficients used to linearly combine the batched quotients For more details see
     /// [self::batch_random_coeffs].
-    pub batch_random_coeffs: Vec<SecureField>,
+    pub batch_random_coeffs: Vec<F>,
     /// The inverses of the denominators of the quotients.
-    pub denominator_inverses: Vec<Col<B, SecureField>>,
+    pub denominator_inverses: Vec<Col<B, F>>,
 }
 
 #[cfg(test)]
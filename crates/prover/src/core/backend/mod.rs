@@ -5,11 +5,12 @@ pub use cpu::CpuBackend;
 use super::air::accumulation::AccumulationOps;
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
-use super::fields::FieldOps;
+use super::fields::{FieldOps, SecureExtensionField};
 use super::fri::FriOps;
 use super::pcs::quotients::QuotientOps;
-use super::poly::circle::PolyOps;
+use super::poly::circle::{ExtensionPolyOps, PolyOps};
 
+pub mod conformance;
 pub mod cpu;
 pub mod simd;
 
@@ -17,6 +18,8 @@ pub trait Backend:
     Copy
     + Clone
     + Debug
+    + Send
+    + Sync
     + FieldOps<BaseField>
     + FieldOps<SecureField>
     + PolyOps
@@ -26,6 +29,22 @@ pub trait Backend:
 {
 }
 
+/// A [Backend] that can prove over the secure extension field `F`.
+pub trait ExtensionBackend<F: SecureExtensionField>:
+    Backend + FieldOps<F> + ExtensionPolyOps<F> + QuotientOps<F> + FriOps<F> + AccumulationOps<F>
+{
+}
+
+impl<B, F: SecureExtensionField> ExtensionBackend<F> for B where
+    B: Backend
+        + FieldOps<F>
+        + ExtensionPolyOps<F>
+        + QuotientOps<F>
+        + FriOps<F>
+        + AccumulationOps<F>
+{
+}
+
 pub trait ColumnOps<T> {
     type Column: Column<T>;
     fn bit_reverse_column(column: &mut Self::Column);
@@ -34,7 +53,7 @@ pub trait ColumnOps<T> {
 pub type Col<B, T> = <B as ColumnOps<T>>::Column;
 
 // TODO(spapini): Consider removing the generic parameter and only support BaseField.
-pub trait Column<T>: Clone + Debug + FromIterator<T> {
+pub trait Column<T>: Clone + Debug + Send + Sync + FromIterator<T> {
     /// Creates a new column of zeros with the given length.
     fn zeros(len: usize) -> Self;
     /// Returns a cpu vector of the column.
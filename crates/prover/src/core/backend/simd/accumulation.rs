@@ -1,12 +1,24 @@
+use std::iter::zip;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 use super::SimdBackend;
 use crate::core::air::accumulation::AccumulationOps;
 use crate::core::fields::secure_column::SecureColumn;
 
 impl AccumulationOps for SimdBackend {
     fn accumulate(column: &mut SecureColumn<Self>, other: &SecureColumn<Self>) {
-        for i in 0..column.packed_len() {
-            let res_coeff = unsafe { column.packed_at(i) + other.packed_at(i) };
-            unsafe { column.set_packed(i, res_coeff) };
+        // Secure field addition is coordinate-wise, so each coordinate column is accumulated
+        // separately.
+        for (column, other) in zip(&mut column.columns, &other.columns) {
+            #[cfg(not(feature = "parallel"))]
+            let iter = column.data.iter_mut().zip(&other.data);
+
+            #[cfg(feature = "parallel")]
+            let iter = column.data.par_iter_mut().zip(&other.data);
+
+            iter.for_each(|(res, other)| *res += *other);
         }
     }
 }
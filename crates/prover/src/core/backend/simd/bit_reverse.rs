@@ -3,7 +3,7 @@ use std::array;
 use super::column::{BaseFieldVec, SecureFieldVec};
 use super::m31::PackedBaseField;
 use super::SimdBackend;
-use crate::core::backend::ColumnOps;
+use crate::core::backend::{Column, ColumnOps};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::utils::{bit_reverse as cpu_bit_reverse, bit_reverse_index};
@@ -31,8 +31,11 @@ impl ColumnOps<BaseField> for SimdBackend {
 impl ColumnOps<SecureField> for SimdBackend {
     type Column = SecureFieldVec;
 
-    fn bit_reverse_column(_column: &mut SecureFieldVec) {
-        todo!()
+    fn bit_reverse_column(column: &mut SecureFieldVec) {
+        // TODO(spapini): Vectorize.
+        let mut values = column.to_cpu();
+        cpu_bit_reverse(&mut values);
+        *column = values.into_iter().collect();
     }
 }
 
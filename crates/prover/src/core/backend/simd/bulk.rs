@@ -0,0 +1,400 @@
+//! Bulk operations on [BaseFieldVec] and [SecureFieldVec].
+//!
+//! The full vectors are processed with packed arithmetic. The values of a partial last vector
+//! (when the length is not a multiple of [N_LANES]) are processed with the scalar implementations
+//! in [crate::core::utils], so the padding lanes never affect the results.
+
+use std::array;
+use std::iter::{successors, zip};
+use std::simd::cmp::SimdOrd;
+use std::simd::{Mask, Simd};
+
+use itertools::Itertools;
+use num_traits::{One, Zero};
+
+use super::column::{BaseFieldVec, SecureFieldVec};
+use super::m31::{PackedBaseField, LOG_N_LANES, MODULUS, N_LANES};
+use super::qm31::PackedSecureField;
+use crate::core::backend::Column;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::FieldExpOps;
+use crate::core::utils::{dot_product, generate_powers, horner_eval, prefix_products, prefix_sums};
+
+impl BaseFieldVec {
+    /// Creates a column from arbitrary `u32` values, reducing them modulo P.
+    pub fn from_u32_slice(values: &[u32]) -> Self {
+        let mut chunks = values.array_chunks::<N_LANES>();
+        let mut data = chunks
+            .by_ref()
+            .map(|chunk| reduce_u32s(Simd::from_array(*chunk)))
+            .collect_vec();
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut last = [0; N_LANES];
+            last[..remainder.len()].copy_from_slice(remainder);
+            data.push(reduce_u32s(Simd::from_array(last)));
+        }
+        Self {
+            data,
+            length: values.len(),
+        }
+    }
+
+    /// Returns the sum of the pointwise products with `other`.
+    pub fn dot(&self, other: &Self) -> BaseField {
+        assert_eq!(self.len(), other.len());
+        let n_full = self.len() / N_LANES;
+        let full_dot = zip(&self.data[..n_full], &other.data[..n_full])
+            .fold(PackedBaseField::zero(), |acc, (&a, &b)| acc + a * b)
+            .pointwise_sum();
+        full_dot + dot_product(&self.tail(), &other.tail())
+    }
+
+    /// Evaluates the univariate polynomial whose coefficients are the values of the column,
+    /// ordered from the lowest degree, at `point`.
+    pub fn eval_polynomial(&self, point: SecureField) -> SecureField {
+        let n_full = self.len() / N_LANES;
+        let point_powers = generate_powers(point, N_LANES + 1);
+        let step = PackedSecureField::broadcast(point_powers[N_LANES]);
+        // Lane i evaluates the coefficients i, i + N_LANES, i + 2 * N_LANES, ... at
+        // point^N_LANES.
+        let lane_evals = self.data[..n_full]
+            .iter()
+            .rfold(PackedSecureField::zero(), |acc, &coeffs| {
+                acc * step + coeffs
+            });
+        let full_eval = dot_product(&lane_evals.to_array(), &point_powers[..N_LANES]);
+        let tail_eval = horner_eval(&self.tail(), point);
+        full_eval + point.pow((n_full * N_LANES) as u128) * tail_eval
+    }
+
+    /// Returns the column `[1, base, base^2, ..., base^(n_powers - 1)]`.
+    pub fn powers(base: BaseField, n_powers: usize) -> Self {
+        let first = PackedBaseField::from_array(generate_powers(base, N_LANES).try_into().unwrap());
+        let step = PackedBaseField::broadcast(base.pow(N_LANES as u128));
+        let data = successors(Some(first), |&powers| Some(powers * step))
+            .take(n_powers.div_ceil(N_LANES))
+            .collect();
+        let mut res = Self {
+            data,
+            length: n_powers,
+        };
+        res.zero_padding();
+        res
+    }
+
+    /// Returns the inclusive prefix sums of the column.
+    pub fn prefix_sum(&self) -> Self {
+        let n_full = self.len() / N_LANES;
+        let mut carry = BaseField::zero();
+        let mut data = Vec::with_capacity(self.data.len());
+        for &values in &self.data[..n_full] {
+            let sums = scan_lanes(values, BaseField::zero(), |a, b| a + b)
+                + PackedBaseField::broadcast(carry);
+            carry = sums.to_array()[N_LANES - 1];
+            data.push(sums);
+        }
+        let tail = prefix_sums(&self.tail()).into_iter().map(|v| v + carry);
+        data.extend(pack_tail(tail.collect()));
+        Self {
+            data,
+            length: self.len(),
+        }
+    }
+
+    /// Returns the inclusive prefix products of the column.
+    pub fn prefix_product(&self) -> Self {
+        let n_full = self.len() / N_LANES;
+        let mut carry = BaseField::one();
+        let mut data = Vec::with_capacity(self.data.len());
+        for &values in &self.data[..n_full] {
+            let products = scan_lanes(values, BaseField::one(), |a, b| a * b)
+                * PackedBaseField::broadcast(carry);
+            carry = products.to_array()[N_LANES - 1];
+            data.push(products);
+        }
+        let tail = prefix_products(&self.tail()).into_iter().map(|v| v * carry);
+        data.extend(pack_tail(tail.collect()));
+        Self {
+            data,
+            length: self.len(),
+        }
+    }
+
+    /// Returns the values of the partial last vector.
+    fn tail(&self) -> Vec<BaseField> {
+        self.as_slice()[self.len() / N_LANES * N_LANES..].to_vec()
+    }
+
+    /// Sets the padding lanes of the last vector to zero.
+    fn zero_padding(&mut self) {
+        let n_values = self.length % N_LANES;
+        if let (true, Some(last)) = (n_values != 0, self.data.last_mut()) {
+            let mut values = last.to_array();
+            values[n_values..].fill(BaseField::zero());
+            *last = PackedBaseField::from_array(values);
+        }
+    }
+}
+
+impl SecureFieldVec {
+    /// Returns the sum of the pointwise products with `other`.
+    pub fn dot(&self, other: &Self) -> SecureField {
+        assert_eq!(self.len(), other.len());
+        let n_full = self.len() / N_LANES;
+        let full_dot = zip(&self.data[..n_full], &other.data[..n_full])
+            .fold(PackedSecureField::zero(), |acc, (&a, &b)| acc + a * b)
+            .pointwise_sum();
+        full_dot + dot_product(&self.tail(), &other.tail())
+    }
+
+    /// Evaluates the univariate polynomial whose coefficients are the values of the column,
+    /// ordered from the lowest degree, at `point`.
+    pub fn eval_polynomial(&self, point: SecureField) -> SecureField {
+        let n_full = self.len() / N_LANES;
+        let point_powers = generate_powers(point, N_LANES + 1);
+        let step = PackedSecureField::broadcast(point_powers[N_LANES]);
+        // Lane i evaluates the coefficients i, i + N_LANES, i + 2 * N_LANES, ... at
+        // point^N_LANES.
+        let lane_evals = self.data[..n_full]
+            .iter()
+            .rfold(PackedSecureField::zero(), |acc, &coeffs| {
+                acc * step + coeffs
+            });
+        let full_eval = dot_product(&lane_evals.to_array(), &point_powers[..N_LANES]);
+        let tail_eval = horner_eval(&self.tail(), point);
+        full_eval + point.pow((n_full * N_LANES) as u128) * tail_eval
+    }
+
+    /// Returns the column `[1, base, base^2, ..., base^(n_powers - 1)]`.
+    pub fn powers(base: SecureField, n_powers: usize) -> Self {
+        let first =
+            PackedSecureField::from_array(generate_powers(base, N_LANES).try_into().unwrap());
+        let step = PackedSecureField::broadcast(base.pow(N_LANES as u128));
+        let data = successors(Some(first), |&powers| Some(powers * step))
+            .take(n_powers.div_ceil(N_LANES))
+            .collect();
+        let mut res = Self {
+            data,
+            length: n_powers,
+        };
+        res.zero_padding();
+        res
+    }
+
+    /// Returns the inclusive prefix sums of the column.
+    pub fn prefix_sum(&self) -> Self {
+        let n_full = self.len() / N_LANES;
+        let mut carry = SecureField::zero();
+        let mut data = Vec::with_capacity(self.data.len());
+        for &values in &self.data[..n_full] {
+            let sums = scan_secure_lanes(values, SecureField::zero(), |a, b| a + b)
+                + PackedSecureField::broadcast(carry);
+            carry = sums.to_array()[N_LANES - 1];
+            data.push(sums);
+        }
+        let tail = prefix_sums(&self.tail()).into_iter().map(|v| v + carry);
+        data.extend(pack_secure_tail(tail.collect()));
+        Self {
+            data,
+            length: self.len(),
+        }
+    }
+
+    /// Returns the inclusive prefix products of the column.
+    pub fn prefix_product(&self) -> Self {
+        let n_full = self.len() / N_LANES;
+        let mut carry = SecureField::one();
+        let mut data = Vec::with_capacity(self.data.len());
+        for &values in &self.data[..n_full] {
+            let products = scan_secure_lanes(values, SecureField::one(), |a, b| a * b)
+                * PackedSecureField::broadcast(carry);
+            carry = products.to_array()[N_LANES - 1];
+            data.push(products);
+        }
+        let tail = prefix_products(&self.tail()).into_iter().map(|v| v * carry);
+        data.extend(pack_secure_tail(tail.collect()));
+        Self {
+            data,
+            length: self.len(),
+        }
+    }
+
+    /// Returns the values of the partial last vector.
+    fn tail(&self) -> Vec<SecureField> {
+        let n_full = self.len() / N_LANES;
+        match self.data.get(n_full) {
+            Some(last) => last.to_array()[..self.len() - n_full * N_LANES].to_vec(),
+            None => vec![],
+        }
+    }
+
+    /// Sets the padding lanes of the last vector to zero.
+    fn zero_padding(&mut self) {
+        let n_values = self.length % N_LANES;
+        if let (true, Some(last)) = (n_values != 0, self.data.last_mut()) {
+            let mut values = last.to_array();
+            values[n_values..].fill(SecureField::zero());
+            *last = PackedSecureField::from_array(values);
+        }
+    }
+}
+
+/// Reduces arbitrary `u32` values modulo P.
+fn reduce_u32s(values: Simd<u32, N_LANES>) -> PackedBaseField {
+    // x = 2^31 * hi + lo = hi + lo (mod P), and hi + lo is in [0, P + 1].
+    let sum = (values & MODULUS) + (values >> 31);
+    // Reduce [0, P + 1] to [0, P).
+    unsafe { PackedBaseField::from_simd_unchecked(Simd::simd_min(sum, sum - MODULUS)) }
+}
+
+/// Moves each lane `n_lanes` lanes up, filling the first `n_lanes` lanes with `fill`. The scans
+/// only shift by powers of two below [N_LANES].
+fn shift_lanes(values: PackedBaseField, n_lanes: usize, fill: BaseField) -> PackedBaseField {
+    let values = values.into_simd();
+    let rotated = match n_lanes {
+        1 => values.rotate_elements_right::<1>(),
+        2 => values.rotate_elements_right::<2>(),
+        4 => values.rotate_elements_right::<4>(),
+        8 => values.rotate_elements_right::<8>(),
+        _ => unreachable!("shift by {n_lanes} lanes"),
+    };
+    let mask = Mask::<i32, N_LANES>::from_array(array::from_fn(|i| i < n_lanes));
+    unsafe { PackedBaseField::from_simd_unchecked(mask.select(Simd::splat(fill.0), rotated)) }
+}
+
+/// Computes the inclusive scan of the lanes under `op`, whose identity is `identity`, in
+/// [LOG_N_LANES] steps.
+fn scan_lanes(
+    mut values: PackedBaseField,
+    identity: BaseField,
+    op: impl Fn(PackedBaseField, PackedBaseField) -> PackedBaseField,
+) -> PackedBaseField {
+    for log_shift in 0..LOG_N_LANES {
+        values = op(values, shift_lanes(values, 1 << log_shift, identity));
+    }
+    values
+}
+
+/// Like [scan_lanes], for packed secure field elements.
+fn scan_secure_lanes(
+    mut values: PackedSecureField,
+    identity: SecureField,
+    op: impl Fn(PackedSecureField, PackedSecureField) -> PackedSecureField,
+) -> PackedSecureField {
+    let identity = identity.to_m31_array();
+    for log_shift in 0..LOG_N_LANES {
+        let coordinates = values.into_packed_m31s();
+        let shifted = PackedSecureField::from_packed_m31s(array::from_fn(|i| {
+            shift_lanes(coordinates[i], 1 << log_shift, identity[i])
+        }));
+        values = op(values, shifted);
+    }
+    values
+}
+
+/// Packs less than [N_LANES] values into a vector padded with zeros, if there are any values.
+fn pack_tail(values: Vec<BaseField>) -> Option<PackedBaseField> {
+    (!values.is_empty()).then(|| {
+        let mut padded = [BaseField::zero(); N_LANES];
+        padded[..values.len()].copy_from_slice(&values);
+        PackedBaseField::from_array(padded)
+    })
+}
+
+/// Like [pack_tail], for secure field values.
+fn pack_secure_tail(values: Vec<SecureField>) -> Option<PackedSecureField> {
+    (!values.is_empty()).then(|| {
+        let mut padded = [SecureField::zero(); N_LANES];
+        padded[..values.len()].copy_from_slice(&values);
+        PackedSecureField::from_array(padded)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::core::backend::simd::column::{BaseFieldVec, SecureFieldVec};
+    use crate::core::backend::Column;
+    use crate::core::fields::m31::{BaseField, P};
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::utils::{
+        dot_product, generate_powers, horner_eval, prefix_products, prefix_sums,
+    };
+
+    const LENGTHS: [usize; 6] = [0, 1, 15, 16, 17, 100];
+
+    #[test]
+    fn test_from_u32_slice() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut values = vec![0, 1, P - 1, P, P + 1, 2 * P, 2 * P + 1, u32::MAX];
+        values.extend((0..30).map(|_| rng.gen::<u32>()));
+
+        let column = BaseFieldVec::from_u32_slice(&values);
+
+        assert_eq!(
+            column.to_cpu(),
+            values
+                .iter()
+                .map(|&v| BaseField::from_u32_unchecked(v % P))
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_base_field_bulk_ops() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for len in LENGTHS {
+            let lhs = (0..len).map(|_| rng.gen()).collect_vec();
+            let rhs = (0..len).map(|_| rng.gen()).collect_vec();
+            let base: BaseField = rng.gen();
+            let point: SecureField = rng.gen();
+            let column: BaseFieldVec = lhs.iter().copied().collect();
+            let other: BaseFieldVec = rhs.iter().copied().collect();
+
+            assert_eq!(column.dot(&other), dot_product(&lhs, &rhs));
+            assert_eq!(column.eval_polynomial(point), horner_eval(&lhs, point));
+            assert_eq!(
+                BaseFieldVec::powers(base, len).to_cpu(),
+                generate_powers(base, len)
+            );
+            assert_eq!(column.prefix_sum().to_cpu(), prefix_sums(&lhs));
+            assert_eq!(column.prefix_product().to_cpu(), prefix_products(&lhs));
+        }
+    }
+
+    #[test]
+    fn test_secure_field_bulk_ops() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for len in LENGTHS {
+            let lhs = (0..len).map(|_| rng.gen()).collect_vec();
+            let rhs = (0..len).map(|_| rng.gen()).collect_vec();
+            let base: SecureField = rng.gen();
+            let point: SecureField = rng.gen();
+            let column: SecureFieldVec = lhs.iter().copied().collect();
+            let other: SecureFieldVec = rhs.iter().copied().collect();
+
+            assert_eq!(column.dot(&other), dot_product(&lhs, &rhs));
+            assert_eq!(column.eval_polynomial(point), horner_eval(&lhs, point));
+            assert_eq!(
+                SecureFieldVec::powers(base, len).to_cpu(),
+                generate_powers(base, len)
+            );
+            assert_eq!(column.prefix_sum().to_cpu(), prefix_sums(&lhs));
+            assert_eq!(column.prefix_product().to_cpu(), prefix_products(&lhs));
+        }
+    }
+
+    #[test]
+    fn test_powers_padding_is_zero() {
+        let column = BaseFieldVec::powers(BaseField::from(3), 5);
+
+        assert!(column.data[0].to_array()[5..]
+            .iter()
+            .all(|&v| v == BaseField::from(0)));
+    }
+}
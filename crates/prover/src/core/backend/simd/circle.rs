@@ -1,13 +1,14 @@
 use std::iter::zip;
 use std::mem::transmute;
 
-use bytemuck::{cast_slice, Zeroable};
-use num_traits::One;
+use bytemuck::Zeroable;
+use num_traits::{One, Zero};
 
-use super::fft::{ifft, rfft, CACHED_FFT_LOG_SIZE};
+use super::fft::{ifft, rfft, CACHED_FFT_LOG_SIZE, MIN_FFT_LOG_SIZE};
 use super::m31::{PackedBaseField, LOG_N_LANES, N_LANES};
 use super::qm31::PackedSecureField;
 use super::SimdBackend;
+use crate::core::backend::cpu::circle::coset_vanishing_inverses;
 use crate::core::backend::simd::column::BaseFieldVec;
 use crate::core::backend::{Col, CpuBackend};
 use crate::core::circle::{CirclePoint, Coset};
@@ -143,6 +144,10 @@ impl PolyOps for SimdBackend {
         eval: CircleEvaluation<Self, BaseField, BitReversedOrder>,
         twiddles: &TwiddleTree<Self>,
     ) -> CirclePoly<Self> {
+        if eval.domain.log_size() < MIN_FFT_LOG_SIZE {
+            return interpolate_small(eval);
+        }
+
         let mut values = eval.values;
         let log_size = values.length.ilog2();
 
@@ -222,7 +227,6 @@ impl PolyOps for SimdBackend {
         twiddles: &TwiddleTree<Self>,
     ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
         // TODO(spapini): Precompute twiddles.
-        // TODO(spapini): Handle small cases.
         let log_size = domain.log_size();
         let fft_log_size = poly.log_size();
         assert!(
@@ -230,6 +234,13 @@ impl PolyOps for SimdBackend {
             "Can only evaluate on larger domains"
         );
 
+        if log_size < MIN_FFT_LOG_SIZE {
+            return evaluate_small(poly, domain);
+        }
+        if fft_log_size < MIN_FFT_LOG_SIZE {
+            return Self::evaluate(&pad_small(poly, MIN_FFT_LOG_SIZE), domain, twiddles);
+        }
+
         let twiddles = domain_line_twiddles_from_tree(domain, &twiddles.twiddles);
 
         // Evaluate on a big domains by evaluating on several subdomains.
@@ -275,6 +286,66 @@ impl PolyOps for SimdBackend {
         )
     }
 
+    fn add(a: &CirclePoly<Self>, b: &CirclePoly<Self>) -> CirclePoly<Self> {
+        assert_eq!(a.log_size(), b.log_size());
+        CirclePoly::new(BaseFieldVec {
+            data: zip(&a.coeffs.data, &b.coeffs.data)
+                .map(|(&a, &b)| a + b)
+                .collect(),
+            length: a.coeffs.length,
+        })
+    }
+
+    fn sub(a: &CirclePoly<Self>, b: &CirclePoly<Self>) -> CirclePoly<Self> {
+        assert_eq!(a.log_size(), b.log_size());
+        CirclePoly::new(BaseFieldVec {
+            data: zip(&a.coeffs.data, &b.coeffs.data)
+                .map(|(&a, &b)| a - b)
+                .collect(),
+            length: a.coeffs.length,
+        })
+    }
+
+    fn scale(poly: &CirclePoly<Self>, factor: BaseField) -> CirclePoly<Self> {
+        let factor = PackedBaseField::broadcast(factor);
+        CirclePoly::new(BaseFieldVec {
+            data: poly.coeffs.data.iter().map(|&c| c * factor).collect(),
+            length: poly.coeffs.length,
+        })
+    }
+
+    fn mul_evaluations(
+        a: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        b: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
+        assert_eq!(a.domain, b.domain);
+        CircleEvaluation::new(
+            a.domain,
+            BaseFieldVec {
+                data: zip(&a.values.data, &b.values.data)
+                    .map(|(&a, &b)| a * b)
+                    .collect(),
+                length: a.values.length,
+            },
+        )
+    }
+
+    fn div_by_coset_vanishing(
+        eval: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        coset: Coset,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
+        let inverses = BaseFieldVec::from_iter(coset_vanishing_inverses(eval.domain, coset));
+        CircleEvaluation::new(
+            eval.domain,
+            BaseFieldVec {
+                data: zip(&eval.values.data, &inverses.data)
+                    .map(|(&v, &inv)| v * inv)
+                    .collect(),
+                length: eval.values.length,
+            },
+        )
+    }
+
     fn precompute_twiddles(coset: Coset) -> TwiddleTree<Self> {
         let mut twiddles = Vec::with_capacity(coset.size());
         let mut itwiddles = Vec::with_capacity(coset.size());
@@ -283,14 +354,15 @@ impl PolyOps for SimdBackend {
         for layer in &rfft::get_twiddle_dbls(coset) {
             twiddles.extend(layer);
         }
-        // Pad by any value, to make the size a power of 2.
-        twiddles.push(1);
+        // Pad to make the size a power of 2. Any value works, the double of the CPU backend's
+        // padding makes the conversions between the backends' twiddles lossless.
+        twiddles.push(2);
         assert_eq!(twiddles.len(), coset.size());
         for layer in &ifft::get_itwiddle_dbls(coset) {
             itwiddles.extend(layer);
         }
-        // Pad by any value, to make the size a power of 2.
-        itwiddles.push(1);
+        // Pad to make the size a power of 2, as above.
+        itwiddles.push(2);
         assert_eq!(itwiddles.len(), coset.size());
 
         TwiddleTree {
@@ -299,6 +371,49 @@ impl PolyOps for SimdBackend {
             itwiddles,
         }
     }
+
+    fn twiddle_subtree(twiddles: &TwiddleTree<Self>, coset: Coset) -> TwiddleTree<Self> {
+        assert!(coset.is_doubling_of(twiddles.root_coset));
+        // The twiddles of each coset in the tower are a suffix of the twiddles of the larger ones.
+        let n_twiddles = coset.size();
+        TwiddleTree {
+            root_coset: coset,
+            twiddles: twiddles.twiddles[twiddles.twiddles.len() - n_twiddles..].to_vec(),
+            itwiddles: twiddles.itwiddles[twiddles.itwiddles.len() - n_twiddles..].to_vec(),
+        }
+    }
+}
+
+/// Interpolates on a domain too small for the vectorized ifft, using the CPU backend.
+fn interpolate_small(
+    eval: CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>,
+) -> CirclePoly<SimdBackend> {
+    let twiddles = CpuBackend::precompute_twiddles(eval.domain.half_coset);
+    let eval = CircleEvaluation::new(eval.domain, eval.values.into_cpu_vec());
+    let poly = CpuBackend::interpolate(eval, &twiddles);
+    CirclePoly::new(poly.coeffs.into_iter().collect())
+}
+
+/// Evaluates on a domain too small for the vectorized fft, using the CPU backend.
+fn evaluate_small(
+    poly: &CirclePoly<SimdBackend>,
+    domain: CircleDomain,
+) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+    let twiddles = CpuBackend::precompute_twiddles(domain.half_coset);
+    let poly = CirclePoly::<CpuBackend>::new(poly.coeffs.as_slice().to_vec());
+    let eval = CpuBackend::evaluate(&poly, domain, &twiddles);
+    CircleEvaluation::new(domain, eval.values.into_iter().collect())
+}
+
+/// Extends a small polynomial to `log_size` by padding its coefficients with zeros.
+///
+/// Only valid up to [CACHED_FFT_LOG_SIZE], where the coefficients are laid out as on the CPU
+/// backend.
+fn pad_small(poly: &CirclePoly<SimdBackend>, log_size: u32) -> CirclePoly<SimdBackend> {
+    assert!(log_size <= CACHED_FFT_LOG_SIZE);
+    let mut coeffs = poly.coeffs.as_slice().to_vec();
+    coeffs.resize(1 << log_size, BaseField::zero());
+    CirclePoly::new(coeffs.into_iter().collect())
 }
 
 fn slow_eval_at_point(
@@ -311,6 +426,8 @@ fn slow_eval_at_point(
         x = CirclePoint::double_x(x);
         mappings.push(x);
     }
+    // Polynomials of log size less than 2 only depend on y, or are constant.
+    mappings.truncate(poly.log_size() as usize);
     mappings.reverse();
 
     // If the polynomial is large, the fft does a transpose in the middle.
@@ -323,7 +440,7 @@ fn slow_eval_at_point(
         // Swap content of a,c.
         a.swap_with_slice(&mut c[0..n0]);
     }
-    fold(cast_slice::<_, BaseField>(&poly.coeffs.data), &mappings)
+    fold(poly.coeffs.as_slice(), &mappings)
 }
 
 #[cfg(test)]
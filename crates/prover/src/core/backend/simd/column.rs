@@ -1,4 +1,5 @@
-use std::mem;
+use std::iter::zip;
+use std::{array, mem};
 
 use bytemuck::{cast_slice, cast_slice_mut, Zeroable};
 use itertools::{izip, Itertools};
@@ -6,12 +7,14 @@ use num_traits::Zero;
 
 use super::cm31::PackedCM31;
 use super::m31::{PackedBaseField, N_LANES};
+use super::om31::PackedOM31;
 use super::qm31::{PackedQM31, PackedSecureField};
 use super::SimdBackend;
 use crate::core::backend::{Column, CpuBackend};
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::om31::OM31;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::secure_column::{ExtensionColumn, SecureColumn};
 use crate::core::fields::{FieldExpOps, FieldOps};
 
 impl FieldOps<BaseField> for SimdBackend {
@@ -200,6 +203,30 @@ impl SecureColumn<SimdBackend> {
     }
 }
 
+impl ExtensionColumn<SimdBackend, OM31> {
+    pub fn packed_len(&self) -> usize {
+        self.columns[0].data.len()
+    }
+
+    /// # Safety
+    ///
+    /// `vec_index` must be a valid index.
+    pub unsafe fn packed_at(&self, vec_index: usize) -> PackedOM31 {
+        PackedOM31::from_packed_m31s(array::from_fn(|i| {
+            *self.columns[i].data.get_unchecked(vec_index)
+        }))
+    }
+
+    /// # Safety
+    ///
+    /// `vec_index` must be a valid index.
+    pub unsafe fn set_packed(&mut self, vec_index: usize, value: PackedOM31) {
+        for (column, value) in zip(&mut self.columns, value.into_packed_m31s()) {
+            *column.data.get_unchecked_mut(vec_index) = value;
+        }
+    }
+}
+
 impl FromIterator<SecureField> for SecureColumn<SimdBackend> {
     fn from_iter<I: IntoIterator<Item = SecureField>>(iter: I) -> Self {
         let cpu_col = SecureColumn::<CpuBackend>::from_iter(iter);
@@ -208,6 +235,23 @@ impl FromIterator<SecureField> for SecureColumn<SimdBackend> {
     }
 }
 
+impl FromIterator<PackedSecureField> for SecureColumn<SimdBackend> {
+    fn from_iter<I: IntoIterator<Item = PackedSecureField>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut columns: [BaseFieldVec; 4] = array::from_fn(|_| BaseFieldVec {
+            data: Vec::with_capacity(iter.size_hint().0),
+            length: 0,
+        });
+        for PackedQM31([PackedCM31([a, b]), PackedCM31([c, d])]) in iter {
+            for (column, value) in zip(&mut columns, [a, b, c, d]) {
+                column.data.push(value);
+                column.length += N_LANES;
+            }
+        }
+        SecureColumn { columns }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::array;
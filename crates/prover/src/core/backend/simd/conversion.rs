@@ -0,0 +1,290 @@
+//! Conversions of columns, evaluations, polynomials and twiddles between [CpuBackend] and
+//! [SimdBackend].
+//!
+//! This allows, for example, generating a trace on the CPU backend and proving it on the SIMD
+//! backend. Field elements are reduced when converted to the CPU backend, so the conversions are
+//! lossless in both directions.
+
+use super::column::{BaseFieldVec, SecureFieldVec};
+use super::fft::CACHED_FFT_LOG_SIZE;
+use super::m31::LOG_N_LANES;
+use super::SimdBackend;
+use crate::core::backend::{Col, CpuBackend};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::{ExtensionOf, FieldOps};
+use crate::core::poly::circle::{CircleEvaluation, CirclePoly};
+use crate::core::poly::line::LineEvaluation;
+use crate::core::poly::twiddles::TwiddleTree;
+
+impl From<Vec<BaseField>> for BaseFieldVec {
+    fn from(values: Vec<BaseField>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<BaseFieldVec> for Vec<BaseField> {
+    fn from(column: BaseFieldVec) -> Self {
+        column
+            .data
+            .iter()
+            .flat_map(|packed| packed.to_array())
+            .take(column.length)
+            .collect()
+    }
+}
+
+impl From<Vec<SecureField>> for SecureFieldVec {
+    fn from(values: Vec<SecureField>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<SecureFieldVec> for Vec<SecureField> {
+    fn from(column: SecureFieldVec) -> Self {
+        column
+            .data
+            .iter()
+            .flat_map(|packed| packed.to_array())
+            .take(column.length)
+            .collect()
+    }
+}
+
+impl From<SecureColumn<CpuBackend>> for SecureColumn<SimdBackend> {
+    fn from(column: SecureColumn<CpuBackend>) -> Self {
+        SecureColumn {
+            columns: column.columns.map(BaseFieldVec::from),
+        }
+    }
+}
+
+impl From<SecureColumn<SimdBackend>> for SecureColumn<CpuBackend> {
+    fn from(column: SecureColumn<SimdBackend>) -> Self {
+        SecureColumn {
+            columns: column.columns.map(Vec::from),
+        }
+    }
+}
+
+impl<F: ExtensionOf<BaseField>, EvalOrder> From<CircleEvaluation<CpuBackend, F, EvalOrder>>
+    for CircleEvaluation<SimdBackend, F, EvalOrder>
+where
+    SimdBackend: FieldOps<F>,
+    Col<SimdBackend, F>: From<Vec<F>>,
+{
+    fn from(eval: CircleEvaluation<CpuBackend, F, EvalOrder>) -> Self {
+        CircleEvaluation::new(eval.domain, eval.values.into())
+    }
+}
+
+impl<F: ExtensionOf<BaseField>, EvalOrder> From<CircleEvaluation<SimdBackend, F, EvalOrder>>
+    for CircleEvaluation<CpuBackend, F, EvalOrder>
+where
+    SimdBackend: FieldOps<F>,
+    Vec<F>: From<Col<SimdBackend, F>>,
+{
+    fn from(eval: CircleEvaluation<SimdBackend, F, EvalOrder>) -> Self {
+        CircleEvaluation::new(eval.domain, eval.values.into())
+    }
+}
+
+impl From<CirclePoly<CpuBackend>> for CirclePoly<SimdBackend> {
+    fn from(poly: CirclePoly<CpuBackend>) -> Self {
+        CirclePoly::new(transpose_coeffs(&poly.coeffs).into())
+    }
+}
+
+impl From<CirclePoly<SimdBackend>> for CirclePoly<CpuBackend> {
+    fn from(poly: CirclePoly<SimdBackend>) -> Self {
+        CirclePoly::new(transpose_coeffs(&Vec::from(poly.coeffs)))
+    }
+}
+
+impl From<LineEvaluation<CpuBackend>> for LineEvaluation<SimdBackend> {
+    fn from(eval: LineEvaluation<CpuBackend>) -> Self {
+        LineEvaluation::new(eval.domain(), eval.values.into())
+    }
+}
+
+impl From<LineEvaluation<SimdBackend>> for LineEvaluation<CpuBackend> {
+    fn from(eval: LineEvaluation<SimdBackend>) -> Self {
+        LineEvaluation::new(eval.domain(), eval.values.into())
+    }
+}
+
+impl From<TwiddleTree<CpuBackend>> for TwiddleTree<SimdBackend> {
+    fn from(tree: TwiddleTree<CpuBackend>) -> Self {
+        // The SIMD backend stores the doubles of the twiddles, in the same layout.
+        let to_dbls = |twiddles: Vec<BaseField>| twiddles.into_iter().map(|t| t.0 * 2).collect();
+        TwiddleTree {
+            root_coset: tree.root_coset,
+            twiddles: to_dbls(tree.twiddles),
+            itwiddles: to_dbls(tree.itwiddles),
+        }
+    }
+}
+
+impl From<TwiddleTree<SimdBackend>> for TwiddleTree<CpuBackend> {
+    fn from(tree: TwiddleTree<SimdBackend>) -> Self {
+        let from_dbls = |twiddles: Vec<u32>| {
+            twiddles
+                .into_iter()
+                .map(|t| BaseField::partial_reduce(t / 2))
+                .collect()
+        };
+        TwiddleTree {
+            root_coset: tree.root_coset,
+            twiddles: from_dbls(tree.twiddles),
+            itwiddles: from_dbls(tree.itwiddles),
+        }
+    }
+}
+
+/// Permutes the coefficients of a polynomial between the CPU layout and the SIMD layout.
+///
+/// Above [CACHED_FFT_LOG_SIZE], the SIMD fft transposes the coefficients in the middle, which
+/// swaps the two blocks of index bits above the [LOG_N_LANES] lowest bits. This permutation is
+/// its own inverse.
+fn transpose_coeffs(coeffs: &[BaseField]) -> Vec<BaseField> {
+    let log_size = coeffs.len().ilog2();
+    if log_size <= CACHED_FFT_LOG_SIZE {
+        return coeffs.to_vec();
+    }
+    let n_swapped_bits = (log_size - LOG_N_LANES) / 2;
+    let high_shift = log_size - n_swapped_bits;
+    let block_mask = (1 << n_swapped_bits) - 1;
+    let swapped_mask = (block_mask << LOG_N_LANES) | (block_mask << high_shift);
+    (0..coeffs.len())
+        .map(|i| {
+            let low_block = (i >> LOG_N_LANES) & block_mask;
+            let high_block = i >> high_shift;
+            coeffs[(i & !swapped_mask) | (high_block << LOG_N_LANES) | (low_block << high_shift)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use num_traits::Zero;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::core::backend::simd::column::BaseFieldVec;
+    use crate::core::backend::simd::m31::{PackedBaseField, N_LANES};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Column, CpuBackend};
+    use crate::core::circle::SECURE_FIELD_CIRCLE_GEN;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::secure_column::SecureColumn;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly, PolyOps};
+    use crate::core::poly::line::{LineDomain, LineEvaluation};
+    use crate::core::poly::twiddles::TwiddleTree;
+    use crate::core::poly::BitReversedOrder;
+
+    #[test]
+    fn test_column_round_trip() {
+        let rng = &mut SmallRng::seed_from_u64(0);
+        for len in [0, 1, 7, 16, 33] {
+            let values: Vec<BaseField> = (0..len).map(|_| rng.gen()).collect();
+            let secure_values: Vec<SecureField> = (0..len).map(|_| rng.gen()).collect();
+
+            let column = BaseFieldVec::from(values.clone());
+            let secure_column = SecureColumn::<SimdBackend>::from(
+                secure_values
+                    .iter()
+                    .copied()
+                    .collect::<SecureColumn<CpuBackend>>(),
+            );
+
+            assert_eq!(column.to_cpu(), values);
+            assert_eq!(Vec::from(column), values);
+            assert_eq!(
+                SecureColumn::<CpuBackend>::from(secure_column).to_vec(),
+                secure_values
+            );
+        }
+    }
+
+    #[test]
+    fn test_simd_column_to_cpu_is_reduced() {
+        // The SIMD backend may represent zero as P.
+        let column = BaseFieldVec {
+            data: vec![-PackedBaseField::zero()],
+            length: N_LANES,
+        };
+
+        assert_eq!(Vec::from(column), vec![BaseField::zero(); N_LANES]);
+    }
+
+    #[test]
+    fn test_evaluation_conversion_commutes_with_interpolation() {
+        let rng = &mut SmallRng::seed_from_u64(0);
+        for log_size in [1, 4, 5, 16, 17, 18] {
+            let domain = CanonicCoset::new(log_size).circle_domain();
+            let values = (0..domain.size()).map(|_| rng.gen()).collect_vec();
+            let cpu_eval =
+                CircleEvaluation::<CpuBackend, BaseField, BitReversedOrder>::new(domain, values);
+            let simd_eval =
+                CircleEvaluation::<SimdBackend, _, BitReversedOrder>::from(cpu_eval.clone());
+
+            let cpu_poly = cpu_eval.clone().interpolate();
+            let simd_poly = simd_eval.clone().interpolate();
+
+            assert_eq!(
+                CirclePoly::<CpuBackend>::from(simd_poly.clone()).coeffs,
+                cpu_poly.coeffs
+            );
+            assert_eq!(
+                CirclePoly::<SimdBackend>::from(cpu_poly.clone())
+                    .coeffs
+                    .to_cpu(),
+                simd_poly.coeffs.to_cpu()
+            );
+            assert_eq!(
+                simd_poly.eval_at_point(SECURE_FIELD_CIRCLE_GEN),
+                cpu_poly.eval_at_point(SECURE_FIELD_CIRCLE_GEN)
+            );
+            assert_eq!(
+                CircleEvaluation::<CpuBackend, _, BitReversedOrder>::from(simd_eval).values,
+                cpu_eval.values
+            );
+        }
+    }
+
+    #[test]
+    fn test_line_evaluation_round_trip() {
+        let rng = &mut SmallRng::seed_from_u64(0);
+        let domain = LineDomain::new(CanonicCoset::new(6).half_coset());
+        let values: SecureColumn<CpuBackend> = (0..domain.size()).map(|_| rng.gen()).collect();
+        let eval = LineEvaluation::<CpuBackend>::new(domain, values.clone());
+
+        let eval = LineEvaluation::<CpuBackend>::from(LineEvaluation::<SimdBackend>::from(eval));
+
+        assert_eq!(eval.domain().coset(), domain.coset());
+        assert_eq!(eval.values.to_vec(), values.to_vec());
+    }
+
+    #[test]
+    fn test_twiddle_tree_conversion() {
+        for log_size in [8, 14] {
+            let coset = CanonicCoset::new(log_size).half_coset();
+            let cpu_twiddles = CpuBackend::precompute_twiddles(coset);
+            let simd_twiddles = SimdBackend::precompute_twiddles(coset);
+
+            let converted: TwiddleTree<SimdBackend> = CpuBackend::precompute_twiddles(coset).into();
+            let converted_back: TwiddleTree<CpuBackend> =
+                SimdBackend::precompute_twiddles(coset).into();
+
+            assert_eq!(converted.root_coset, coset);
+            assert_eq!(converted.twiddles, simd_twiddles.twiddles);
+            assert_eq!(converted.itwiddles, simd_twiddles.itwiddles);
+            assert_eq!(converted_back.root_coset, coset);
+            assert_eq!(converted_back.twiddles, cpu_twiddles.twiddles);
+            assert_eq!(converted_back.itwiddles, cpu_twiddles.itwiddles);
+        }
+    }
+}
@@ -2,13 +2,15 @@ use std::array;
 use std::simd::u32x8;
 
 use num_traits::Zero;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use super::m31::{PackedBaseField, LOG_N_LANES, N_LANES};
 use super::SimdBackend;
 use crate::core::backend::simd::fft::compute_first_twiddles;
 use crate::core::backend::simd::fft::ifft::simd_ibutterfly;
 use crate::core::backend::simd::qm31::PackedSecureField;
-use crate::core::backend::Column;
+use crate::core::backend::{Column, CpuBackend};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::fields::secure_column::SecureColumn;
@@ -33,24 +35,13 @@ impl FriOps for SimdBackend {
         let domain = eval.domain();
         let itwiddles = domain_line_twiddles_from_tree(domain, &twiddles.itwiddles)[0];
 
-        let mut folded_values = SecureColumn::<Self>::zeros(1 << (log_size - 1));
-
-        for vec_index in 0..(1 << (log_size - 1 - LOG_N_LANES)) {
-            let value = unsafe {
-                let twiddle_dbl: [u32; 16] =
-                    array::from_fn(|i| *itwiddles.get_unchecked(vec_index * 16 + i));
-                let val0 = eval.values.packed_at(vec_index * 2).into_packed_m31s();
-                let val1 = eval.values.packed_at(vec_index * 2 + 1).into_packed_m31s();
-                let pairs: [_; 4] = array::from_fn(|i| {
-                    let (a, b) = val0[i].deinterleave(val1[i]);
-                    simd_ibutterfly(a, b, std::mem::transmute(twiddle_dbl))
-                });
-                let val0 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].0));
-                let val1 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].1));
-                val0 + PackedSecureField::broadcast(alpha) * val1
-            };
-            unsafe { folded_values.set_packed(vec_index, value) };
-        }
+        let n_vecs = 1 << (log_size - 1 - LOG_N_LANES);
+
+        #[cfg(not(feature = "parallel"))]
+        let folded_values = fold_line_serial(&eval.values, alpha, itwiddles, n_vecs);
+
+        #[cfg(feature = "parallel")]
+        let folded_values = fold_line_parallel(&eval.values, alpha, itwiddles, n_vecs);
 
         LineEvaluation::new(domain.double(), folded_values)
     }
@@ -62,41 +53,44 @@ impl FriOps for SimdBackend {
         twiddles: &TwiddleTree<Self>,
     ) {
         let log_size = src.len().ilog2();
-        assert!(log_size > LOG_N_LANES, "Evaluation too small");
+        if log_size <= LOG_N_LANES {
+            let mut cpu_dst = dst.to_cpu();
+            let cpu_src = SecureEvaluation {
+                domain: src.domain,
+                values: src.values.to_cpu(),
+            };
+            fri::fold_circle_into_line(&mut cpu_dst, &cpu_src, alpha);
+            *dst = LineEvaluation::new(cpu_dst.domain(), cpu_dst.values.into_iter().collect());
+            return;
+        }
 
         let domain = src.domain;
-        let alpha_sq = alpha * alpha;
         let itwiddles = domain_line_twiddles_from_tree(domain, &twiddles.itwiddles)[0];
 
-        for vec_index in 0..(1 << (log_size - 1 - LOG_N_LANES)) {
-            let value = unsafe {
-                // The 16 twiddles of the circle domain can be derived from the 8 twiddles of the
-                // next line domain. See `compute_first_twiddles()`.
-                let twiddle_dbl = u32x8::from_array(array::from_fn(|i| {
-                    *itwiddles.get_unchecked(vec_index * 8 + i)
-                }));
-                let (t0, _) = compute_first_twiddles(twiddle_dbl);
-                let val0 = src.values.packed_at(vec_index * 2).into_packed_m31s();
-                let val1 = src.values.packed_at(vec_index * 2 + 1).into_packed_m31s();
-                let pairs: [_; 4] = array::from_fn(|i| {
-                    let (a, b) = val0[i].deinterleave(val1[i]);
-                    simd_ibutterfly(a, b, t0)
-                });
-                let val0 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].0));
-                let val1 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].1));
-                val0 + PackedSecureField::broadcast(alpha) * val1
+        assert_eq!(dst.values.packed_len(), 1 << (log_size - 1 - LOG_N_LANES));
+
+        #[cfg(not(feature = "parallel"))]
+        fold_circle_into_line_serial(&mut dst.values, &src.values, alpha, itwiddles);
+
+        #[cfg(feature = "parallel")]
+        fold_circle_into_line_parallel(&mut dst.values, &src.values, alpha, itwiddles);
+    }
+
+    fn decompose(eval: &SecureEvaluation<Self>) -> (SecureEvaluation<Self>, SecureField) {
+        // Each half of the evaluation must fill whole vectors.
+        if eval.len() < 2 * N_LANES {
+            let cpu_eval: SecureEvaluation<CpuBackend> = SecureEvaluation {
+                domain: eval.domain,
+                values: eval.values.to_cpu(),
             };
-            unsafe {
-                dst.values.set_packed(
-                    vec_index,
-                    dst.values.packed_at(vec_index) * PackedSecureField::broadcast(alpha_sq)
-                        + value,
-                )
+            let (g, lambda) = CpuBackend::decompose(&cpu_eval);
+            let g = SecureEvaluation {
+                domain: g.domain,
+                values: g.values.into_iter().collect(),
             };
+            return (g, lambda);
         }
-    }
 
-    fn decompose(eval: &SecureEvaluation<Self>) -> (SecureEvaluation<Self>, SecureField) {
         let lambda = decomposition_coefficient(eval);
         let broadcasted_lambda = PackedSecureField::broadcast(lambda);
         let mut g_values = SecureColumn::<Self>::zeros(eval.len());
@@ -151,6 +145,140 @@ fn decomposition_coefficient(eval: &SecureEvaluation<SimdBackend>) -> SecureFiel
     SecureField::from_m31(x, y, z, w) / BaseField::from_u32_unchecked(1 << eval.domain.log_size())
 }
 
+/// Folds the `vec_index`-th pair of vectors of a line evaluation, whose line twiddles are
+/// `itwiddles`.
+///
+/// # Safety
+///
+/// `vec_index` must be below half the number of vectors of `values`.
+unsafe fn fold_line_vec(
+    values: &SecureColumn<SimdBackend>,
+    alpha: SecureField,
+    itwiddles: &[u32],
+    vec_index: usize,
+) -> PackedSecureField {
+    let twiddle_dbl: [u32; 16] = array::from_fn(|i| *itwiddles.get_unchecked(vec_index * 16 + i));
+    let val0 = values.packed_at(vec_index * 2).into_packed_m31s();
+    let val1 = values.packed_at(vec_index * 2 + 1).into_packed_m31s();
+    let pairs: [_; 4] = array::from_fn(|i| {
+        let (a, b) = val0[i].deinterleave(val1[i]);
+        simd_ibutterfly(a, b, std::mem::transmute(twiddle_dbl))
+    });
+    let val0 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].0));
+    let val1 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].1));
+    val0 + PackedSecureField::broadcast(alpha) * val1
+}
+
+/// Folds the `vec_index`-th pair of vectors of a circle evaluation into a line, where `itwiddles`
+/// are the twiddles of the line.
+///
+/// # Safety
+///
+/// `vec_index` must be below half the number of vectors of `values`.
+unsafe fn fold_circle_vec(
+    values: &SecureColumn<SimdBackend>,
+    alpha: SecureField,
+    itwiddles: &[u32],
+    vec_index: usize,
+) -> PackedSecureField {
+    // The 16 twiddles of the circle domain can be derived from the 8 twiddles of the next line
+    // domain. See `compute_first_twiddles()`.
+    let twiddle_dbl = u32x8::from_array(array::from_fn(|i| {
+        *itwiddles.get_unchecked(vec_index * 8 + i)
+    }));
+    let (t0, _) = compute_first_twiddles(twiddle_dbl);
+    let val0 = values.packed_at(vec_index * 2).into_packed_m31s();
+    let val1 = values.packed_at(vec_index * 2 + 1).into_packed_m31s();
+    let pairs: [_; 4] = array::from_fn(|i| {
+        let (a, b) = val0[i].deinterleave(val1[i]);
+        simd_ibutterfly(a, b, t0)
+    });
+    let val0 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].0));
+    let val1 = PackedSecureField::from_packed_m31s(array::from_fn(|i| pairs[i].1));
+    val0 + PackedSecureField::broadcast(alpha) * val1
+}
+
+#[cfg(any(not(feature = "parallel"), test))]
+fn fold_line_serial(
+    values: &SecureColumn<SimdBackend>,
+    alpha: SecureField,
+    itwiddles: &[u32],
+    n_vecs: usize,
+) -> SecureColumn<SimdBackend> {
+    let mut folded_values = SecureColumn::<SimdBackend>::zeros(n_vecs * N_LANES);
+    for vec_index in 0..n_vecs {
+        unsafe {
+            let value = fold_line_vec(values, alpha, itwiddles, vec_index);
+            folded_values.set_packed(vec_index, value);
+        }
+    }
+    folded_values
+}
+
+#[cfg(feature = "parallel")]
+fn fold_line_parallel(
+    values: &SecureColumn<SimdBackend>,
+    alpha: SecureField,
+    itwiddles: &[u32],
+    n_vecs: usize,
+) -> SecureColumn<SimdBackend> {
+    let mut folded_values = SecureColumn::<SimdBackend>::zeros(n_vecs * N_LANES);
+    let [c0, c1, c2, c3] = &mut folded_values.columns;
+    (
+        c0.data.par_iter_mut(),
+        c1.data.par_iter_mut(),
+        c2.data.par_iter_mut(),
+        c3.data.par_iter_mut(),
+    )
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(vec_index, (v0, v1, v2, v3))| {
+            let value = unsafe { fold_line_vec(values, alpha, itwiddles, vec_index) };
+            [*v0, *v1, *v2, *v3] = value.into_packed_m31s();
+        });
+    folded_values
+}
+
+#[cfg(any(not(feature = "parallel"), test))]
+fn fold_circle_into_line_serial(
+    dst: &mut SecureColumn<SimdBackend>,
+    src: &SecureColumn<SimdBackend>,
+    alpha: SecureField,
+    itwiddles: &[u32],
+) {
+    let alpha_sq = PackedSecureField::broadcast(alpha * alpha);
+    for vec_index in 0..dst.packed_len() {
+        unsafe {
+            let value = fold_circle_vec(src, alpha, itwiddles, vec_index);
+            dst.set_packed(vec_index, dst.packed_at(vec_index) * alpha_sq + value);
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn fold_circle_into_line_parallel(
+    dst: &mut SecureColumn<SimdBackend>,
+    src: &SecureColumn<SimdBackend>,
+    alpha: SecureField,
+    itwiddles: &[u32],
+) {
+    let alpha_sq = PackedSecureField::broadcast(alpha * alpha);
+    let [c0, c1, c2, c3] = &mut dst.columns;
+    (
+        c0.data.par_iter_mut(),
+        c1.data.par_iter_mut(),
+        c2.data.par_iter_mut(),
+        c3.data.par_iter_mut(),
+    )
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(vec_index, (v0, v1, v2, v3))| {
+            let value = unsafe { fold_circle_vec(src, alpha, itwiddles, vec_index) };
+            let prev = PackedSecureField::from_packed_m31s([*v0, *v1, *v2, *v3]);
+            [*v0, *v1, *v2, *v3] = (prev * alpha_sq + value).into_packed_m31s();
+        });
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -158,7 +286,14 @@ mod tests {
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
 
+    #[cfg(feature = "parallel")]
+    use super::{
+        fold_circle_into_line_parallel, fold_circle_into_line_serial, fold_line_parallel,
+        fold_line_serial,
+    };
     use crate::core::backend::simd::column::BaseFieldVec;
+    #[cfg(feature = "parallel")]
+    use crate::core::backend::simd::m31::LOG_N_LANES;
     use crate::core::backend::simd::SimdBackend;
     use crate::core::backend::{Column, CpuBackend};
     use crate::core::fields::m31::BaseField;
@@ -167,6 +302,8 @@ mod tests {
     use crate::core::fri::FriOps;
     use crate::core::poly::circle::{CanonicCoset, CirclePoly, PolyOps, SecureEvaluation};
     use crate::core::poly::line::{LineDomain, LineEvaluation};
+    #[cfg(feature = "parallel")]
+    use crate::core::poly::utils::domain_line_twiddles_from_tree;
     use crate::qm31;
 
     #[test]
@@ -227,6 +364,35 @@ mod tests {
         assert_eq!(cpu_fold.values.to_vec(), simd_fold.values.to_vec());
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_fold_matches_serial() {
+        const LOG_SIZE: u32 = 10;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let alpha = rng.gen();
+        let src: SecureColumn<SimdBackend> = (0..1 << LOG_SIZE)
+            .map(|_| rng.gen::<SecureField>())
+            .collect();
+        let dst: SecureColumn<SimdBackend> = (0..1 << (LOG_SIZE - 1))
+            .map(|_| rng.gen::<SecureField>())
+            .collect();
+        let circle_domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        let line_domain = LineDomain::new(circle_domain.half_coset);
+        let twiddles = SimdBackend::precompute_twiddles(line_domain.coset());
+        let itwiddles = domain_line_twiddles_from_tree(line_domain, &twiddles.itwiddles)[0];
+        let n_vecs = 1 << (LOG_SIZE - 1 - LOG_N_LANES);
+        let mut serial_dst = dst.clone();
+        let mut parallel_dst = dst;
+
+        let serial_fold = fold_line_serial(&src, alpha, itwiddles, n_vecs);
+        let parallel_fold = fold_line_parallel(&src, alpha, itwiddles, n_vecs);
+        fold_circle_into_line_serial(&mut serial_dst, &src, alpha, itwiddles);
+        fold_circle_into_line_parallel(&mut parallel_dst, &src, alpha, itwiddles);
+
+        assert_eq!(serial_fold.to_vec(), parallel_fold.to_vec());
+        assert_eq!(serial_dst.to_vec(), parallel_dst.to_vec());
+    }
+
     #[test]
     fn decomposition_test() {
         const DOMAIN_LOG_SIZE: u32 = 5;
@@ -246,7 +412,7 @@ mod tests {
                 values.values.clone(),
             ],
         };
-        let avx_eval = SecureEvaluation {
+        let avx_eval = SecureEvaluation::<SimdBackend> {
             domain,
             values: avx_column.clone(),
         };
@@ -0,0 +1,95 @@
+use std::iter::zip;
+
+use itertools::Itertools;
+
+use super::column::BaseFieldVec;
+use super::m31::{LOG_N_LANES, N_LANES};
+use super::permutation::combine;
+use super::qm31::PackedSecureField;
+use super::SimdBackend;
+use crate::core::air::accumulation::ColumnAccumulator;
+use crate::core::air::logup::{signed_multiplicity, Logup, LogupOps, LogupRow, N_LOGUP_DENOMS};
+use crate::core::air::ComponentTrace;
+use crate::core::backend::Column;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::previous_bit_reversed_circle_domain_index;
+
+impl LogupOps for SimdBackend {
+    fn accumulate_logup_quotients(
+        logup: &Logup,
+        trace: &ComponentTrace<'_, Self>,
+        accumulator: ColumnAccumulator<'_, Self>,
+        denom_inverses: &[BaseFieldVec; N_LOGUP_DENOMS],
+        elements: &[(SecureField, SecureField)],
+        claimed_sum: SecureField,
+    ) {
+        let eval_domain = trace.evals[INTERACTION_TRACE][0].domain;
+        let eval_log_size = eval_domain.log_size();
+        assert!(eval_log_size >= LOG_N_LANES);
+
+        let interaction = &trace.evals[INTERACTION_TRACE];
+        let secure_value = |column: usize, vec_index: usize| {
+            PackedSecureField::from_packed_m31s(std::array::from_fn(|j| {
+                interaction[column * SECURE_EXTENSION_DEGREE + j]
+                    .values
+                    .data[vec_index]
+            }))
+        };
+        let n_entries = logup.entries.len();
+        let claimed_sum = PackedSecureField::broadcast(claimed_sum);
+        let coeffs = accumulator
+            .random_coeff_powers
+            .iter()
+            .rev()
+            .map(|&coeff| PackedSecureField::broadcast(coeff))
+            .collect_vec();
+        for vec_index in 0..1 << (eval_log_size - LOG_N_LANES) {
+            let base = |column: usize| trace.evals[BASE_TRACE][column].values.data[vec_index];
+            // The previous rows are not contiguous, so they are gathered.
+            let prev_sum = PackedSecureField::from_array(std::array::from_fn(|lane| {
+                let prev_i = previous_bit_reversed_circle_domain_index(
+                    vec_index * N_LANES + lane,
+                    logup.log_size,
+                    eval_log_size,
+                );
+                SecureField::from_m31_array(std::array::from_fn(|j| {
+                    interaction[n_entries * SECURE_EXTENSION_DEGREE + j]
+                        .values
+                        .at(prev_i)
+                }))
+            }));
+            let row = LogupRow {
+                denoms: zip(&logup.entries, elements)
+                    .map(|(entry, &(alpha, z))| {
+                        combine(entry.values.iter().map(|&column| base(column)), alpha, z)
+                    })
+                    .collect(),
+                multiplicities: logup
+                    .entries
+                    .iter()
+                    .map(|entry| signed_multiplicity(entry, |column| base(column).into()))
+                    .collect(),
+                fractions: (0..n_entries)
+                    .map(|column| secure_value(column, vec_index))
+                    .collect(),
+                sum: secure_value(n_entries, vec_index),
+                prev_sum,
+            };
+            let row_denom_inverses = std::array::from_fn(|j| denom_inverses[j].data[vec_index]);
+            // The j-th constraint is multiplied by alpha^(n_constraints - 1 - j).
+            let res: PackedSecureField = zip(
+                logup.constraint_quotients(&row, claimed_sum, row_denom_inverses),
+                &coeffs,
+            )
+            .map(|(quotient, &coeff)| quotient * coeff)
+            .sum();
+            unsafe {
+                accumulator
+                    .col
+                    .set_packed(vec_index, accumulator.col.packed_at(vec_index) + res);
+            }
+        }
+    }
+}
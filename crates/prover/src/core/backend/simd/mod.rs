@@ -3,12 +3,17 @@ use super::Backend;
 pub mod accumulation;
 pub mod bit_reverse;
 pub mod blake2s;
+mod bulk;
 pub mod circle;
 pub mod cm31;
 pub mod column;
+mod conversion;
 pub mod fft;
 pub mod fri;
+mod logup;
 pub mod m31;
+pub mod om31;
+mod permutation;
 pub mod qm31;
 pub mod quotients;
 mod utils;
@@ -0,0 +1,322 @@
+use std::array;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use bytemuck::{Pod, Zeroable};
+use num_traits::{One, Zero};
+use rand::distributions::{Distribution, Standard};
+
+use super::cm31::PackedCM31;
+use super::m31::{PackedM31, N_LANES};
+use super::qm31::PackedQM31;
+use crate::core::fields::om31::{OM31, OM31_EXTENSION_DEGREE};
+use crate::core::fields::FieldExpOps;
+
+/// SIMD implementation of [`OM31`].
+#[derive(Copy, Clone, Debug)]
+pub struct PackedOM31(pub [PackedQM31; 2]);
+
+impl PackedOM31 {
+    /// Constructs a new instance with all vector elements set to `value`.
+    pub fn broadcast(value: OM31) -> Self {
+        Self([
+            PackedQM31::broadcast(value.0),
+            PackedQM31::broadcast(value.1),
+        ])
+    }
+
+    /// Returns all `a` values such that each vector element is represented as `a + bv`.
+    pub fn a(&self) -> PackedQM31 {
+        self.0[0]
+    }
+
+    /// Returns all `b` values such that each vector element is represented as `a + bv`.
+    pub fn b(&self) -> PackedQM31 {
+        self.0[1]
+    }
+
+    pub fn to_array(&self) -> [OM31; N_LANES] {
+        let a = self.a().to_array();
+        let b = self.b().to_array();
+        array::from_fn(|i| OM31(a[i], b[i]))
+    }
+
+    pub fn from_array(values: [OM31; N_LANES]) -> Self {
+        let a = values.map(|v| v.0);
+        let b = values.map(|v| v.1);
+        Self([PackedQM31::from_array(a), PackedQM31::from_array(b)])
+    }
+
+    /// Sums all the elements in the vector.
+    pub fn pointwise_sum(self) -> OM31 {
+        self.to_array().into_iter().sum()
+    }
+
+    /// Doubles each element in the vector.
+    pub fn double(self) -> Self {
+        let Self([a, b]) = self;
+        Self([a.double(), b.double()])
+    }
+
+    /// Returns the vectors of the [`OM31_EXTENSION_DEGREE`] coordinates of the elements.
+    pub fn into_packed_m31s(self) -> [PackedM31; OM31_EXTENSION_DEGREE] {
+        let [a, b, c, d] = self.a().into_packed_m31s();
+        let [e, f, g, h] = self.b().into_packed_m31s();
+        [a, b, c, d, e, f, g, h]
+    }
+
+    /// Creates an instance from the vectors of the [`OM31_EXTENSION_DEGREE`] coordinates of the
+    /// elements.
+    pub fn from_packed_m31s([a, b, c, d, e, f, g, h]: [PackedM31; OM31_EXTENSION_DEGREE]) -> Self {
+        Self([
+            PackedQM31::from_packed_m31s([a, b, c, d]),
+            PackedQM31::from_packed_m31s([e, f, g, h]),
+        ])
+    }
+}
+
+/// Multiplies by u, the root of the irreducible polynomial of [`OM31`].
+fn mul_by_u(x: PackedQM31) -> PackedQM31 {
+    // u * (a + bu) = (2+i)b + au = (2b.a - b.b) + (b.a + 2b.b)i + au.
+    let PackedQM31([a, b]) = x;
+    let r_b = PackedCM31([b.a().double() - b.b(), b.a() + b.b().double()]);
+    PackedQM31([r_b, a])
+}
+
+impl Add for PackedOM31 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self([self.a() + rhs.a(), self.b() + rhs.b()])
+    }
+}
+
+impl Sub for PackedOM31 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self([self.a() - rhs.a(), self.b() - rhs.b()])
+    }
+}
+
+impl Mul for PackedOM31 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Compute using Karatsuba.
+        //   (a + vb) * (c + vd) = (ac + ubd) + (ad + bc)v.
+        let ac = self.a() * rhs.a();
+        let bd = self.b() * rhs.b();
+        let ad_p_bc = (self.a() + self.b()) * (rhs.a() + rhs.b()) - ac - bd;
+        Self([ac + mul_by_u(bd), ad_p_bc])
+    }
+}
+
+impl Zero for PackedOM31 {
+    fn zero() -> Self {
+        Self([PackedQM31::zero(), PackedQM31::zero()])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.a().is_zero() && self.b().is_zero()
+    }
+}
+
+impl One for PackedOM31 {
+    fn one() -> Self {
+        Self([PackedQM31::one(), PackedQM31::zero()])
+    }
+}
+
+impl AddAssign for PackedOM31 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl MulAssign for PackedOM31 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl FieldExpOps for PackedOM31 {
+    fn inverse(&self) -> Self {
+        assert!(!self.is_zero(), "0 has no inverse");
+        // (a + bv)^-1 = (a - bv) / (a^2 - ub^2).
+        let denom = self.a().square() - mul_by_u(self.b().square());
+        let denom_inverse = denom.inverse();
+        Self([self.a() * denom_inverse, -self.b() * denom_inverse])
+    }
+}
+
+impl Add<PackedM31> for PackedOM31 {
+    type Output = Self;
+
+    fn add(self, rhs: PackedM31) -> Self::Output {
+        Self([self.a() + rhs, self.b()])
+    }
+}
+
+impl Mul<PackedM31> for PackedOM31 {
+    type Output = Self;
+
+    fn mul(self, rhs: PackedM31) -> Self::Output {
+        let Self([a, b]) = self;
+        Self([a * rhs, b * rhs])
+    }
+}
+
+impl Sub<PackedM31> for PackedOM31 {
+    type Output = Self;
+
+    fn sub(self, rhs: PackedM31) -> Self::Output {
+        let Self([a, b]) = self;
+        Self([a - rhs, b])
+    }
+}
+
+impl Mul<PackedQM31> for PackedOM31 {
+    type Output = Self;
+
+    fn mul(self, rhs: PackedQM31) -> Self::Output {
+        let Self([a, b]) = self;
+        Self([a * rhs, b * rhs])
+    }
+}
+
+impl SubAssign for PackedOM31 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+unsafe impl Pod for PackedOM31 {}
+
+unsafe impl Zeroable for PackedOM31 {
+    fn zeroed() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
+
+impl Sum for PackedOM31 {
+    fn sum<I>(mut iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        let first = iter.next().unwrap_or_else(Self::zero);
+        iter.fold(first, |a, b| a + b)
+    }
+}
+
+impl<'a> Sum<&'a Self> for PackedOM31 {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Self>,
+    {
+        iter.copied().sum()
+    }
+}
+
+impl Neg for PackedOM31 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let Self([a, b]) = self;
+        Self([-a, -b])
+    }
+}
+
+impl Distribution<PackedOM31> for Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> PackedOM31 {
+        PackedOM31::from_array(rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::array;
+
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::core::backend::simd::om31::PackedOM31;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::fields::om31::OM31;
+    use crate::core::fields::secure_column::ExtensionColumn;
+    use crate::core::fields::FieldExpOps;
+
+    #[test]
+    fn addition_works() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let lhs = rng.gen();
+        let rhs = rng.gen();
+        let packed_lhs = PackedOM31::from_array(lhs);
+        let packed_rhs = PackedOM31::from_array(rhs);
+
+        let res = packed_lhs + packed_rhs;
+
+        assert_eq!(res.to_array(), array::from_fn(|i| lhs[i] + rhs[i]));
+    }
+
+    #[test]
+    fn subtraction_works() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let lhs = rng.gen();
+        let rhs = rng.gen();
+        let packed_lhs = PackedOM31::from_array(lhs);
+        let packed_rhs = PackedOM31::from_array(rhs);
+
+        let res = packed_lhs - packed_rhs;
+
+        assert_eq!(res.to_array(), array::from_fn(|i| lhs[i] - rhs[i]));
+    }
+
+    #[test]
+    fn multiplication_works() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let lhs = rng.gen();
+        let rhs = rng.gen();
+        let packed_lhs = PackedOM31::from_array(lhs);
+        let packed_rhs = PackedOM31::from_array(rhs);
+
+        let res = packed_lhs * packed_rhs;
+
+        assert_eq!(res.to_array(), array::from_fn(|i| lhs[i] * rhs[i]));
+    }
+
+    #[test]
+    fn negation_works() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values = rng.gen();
+        let packed_values = PackedOM31::from_array(values);
+
+        let res = -packed_values;
+
+        assert_eq!(res.to_array(), values.map(|v| -v));
+    }
+
+    #[test]
+    fn inverse_works() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values: [OM31; 16] = rng.gen();
+        let packed_values = PackedOM31::from_array(values);
+
+        let res = packed_values.inverse();
+
+        assert_eq!(res.to_array(), values.map(|v| v.inverse()));
+    }
+
+    #[test]
+    fn extension_column_packed_access_works() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values: Vec<OM31> = (0..64).map(|_| rng.gen()).collect();
+        let mut column: ExtensionColumn<SimdBackend, OM31> = values.iter().copied().collect();
+        let packed_value: PackedOM31 = rng.gen();
+
+        unsafe { column.set_packed(2, packed_value) };
+
+        assert_eq!(unsafe { column.packed_at(0) }.to_array(), values[..16]);
+        assert_eq!(column.to_cpu().to_vec()[32..48], packed_value.to_array());
+    }
+}
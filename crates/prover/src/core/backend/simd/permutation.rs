@@ -0,0 +1,140 @@
+use itertools::Itertools;
+use num_traits::{One, Zero};
+
+use super::column::BaseFieldVec;
+use super::m31::{PackedBaseField, LOG_N_LANES, N_LANES};
+use super::qm31::PackedSecureField;
+use super::SimdBackend;
+use crate::core::air::accumulation::ColumnAccumulator;
+use crate::core::air::permutation::{Permutation, PermutationOps, N_PERMUTATION_CONSTRAINTS};
+use crate::core::air::ComponentTrace;
+use crate::core::backend::Column;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SecureColumn;
+use crate::core::fields::FieldExpOps;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::{
+    coset_index_to_bit_reversed_circle_domain_index, previous_bit_reversed_circle_domain_index,
+};
+use crate::core::ColumnVec;
+
+/// Combines packed rows of `k` columns into `sum_j alpha^(k-1-j) columns_j - z`.
+pub(super) fn combine(
+    columns: impl IntoIterator<Item = PackedBaseField>,
+    alpha: SecureField,
+    z: SecureField,
+) -> PackedSecureField {
+    let alpha = PackedSecureField::broadcast(alpha);
+    columns
+        .into_iter()
+        .fold(PackedSecureField::zero(), |acc, value| acc * alpha + value)
+        - PackedSecureField::broadcast(z)
+}
+
+impl PermutationOps for SimdBackend {
+    fn grand_product(
+        permutation: &Permutation,
+        trace: &ColumnVec<&CircleEvaluation<Self, BaseField, BitReversedOrder>>,
+        alpha: SecureField,
+        z: SecureField,
+    ) -> SecureColumn<Self> {
+        assert!(permutation.log_size >= LOG_N_LANES);
+        let packed_row = |columns: &[usize], vec_index: usize| {
+            let values = columns
+                .iter()
+                .map(|&column| trace[column].values.data[vec_index]);
+            combine(values, alpha, z)
+        };
+        let n_vecs = 1 << (permutation.log_size - LOG_N_LANES);
+        let denoms = (0..n_vecs)
+            .map(|vec_index| packed_row(&permutation.rhs, vec_index))
+            .collect_vec();
+        let mut ratios = vec![PackedSecureField::zero(); n_vecs];
+        PackedSecureField::batch_inverse(&denoms, &mut ratios);
+        let ratios = ratios
+            .into_iter()
+            .enumerate()
+            .flat_map(|(vec_index, ratio)| {
+                (ratio * packed_row(&permutation.lhs, vec_index)).to_array()
+            })
+            .collect_vec();
+
+        // The product runs over the rows in coset order, which is not vectorizable.
+        let mut products = vec![SecureField::zero(); ratios.len()];
+        let mut product = SecureField::one();
+        for row in 0..ratios.len() {
+            let i = coset_index_to_bit_reversed_circle_domain_index(row, permutation.log_size);
+            product *= ratios[i];
+            products[i] = product;
+        }
+        products.into_iter().collect()
+    }
+
+    fn accumulate_permutation_quotients(
+        permutation: &Permutation,
+        trace: &ComponentTrace<'_, Self>,
+        accumulator: ColumnAccumulator<'_, Self>,
+        denom_inverses: &[BaseFieldVec; N_PERMUTATION_CONSTRAINTS],
+        alpha: SecureField,
+        z: SecureField,
+    ) {
+        let eval_domain = trace.evals[INTERACTION_TRACE][0].domain;
+        let eval_log_size = eval_domain.log_size();
+        assert!(eval_log_size >= LOG_N_LANES);
+
+        let interaction = &trace.evals[INTERACTION_TRACE];
+        let product = |vec_index: usize| {
+            PackedSecureField::from_packed_m31s(std::array::from_fn(|j| {
+                interaction[j].values.data[vec_index]
+            }))
+        };
+        let coeffs = accumulator
+            .random_coeff_powers
+            .iter()
+            .rev()
+            .map(|&coeff| PackedSecureField::broadcast(coeff))
+            .collect_vec();
+        for vec_index in 0..1 << (eval_log_size - LOG_N_LANES) {
+            let packed_row = |columns: &[usize]| {
+                let values = columns
+                    .iter()
+                    .map(|&column| trace.evals[BASE_TRACE][column].values.data[vec_index]);
+                combine(values, alpha, z)
+            };
+            // The previous rows are not contiguous, so they are gathered.
+            let prev_product = PackedSecureField::from_array(std::array::from_fn(|lane| {
+                let prev_i = previous_bit_reversed_circle_domain_index(
+                    vec_index * N_LANES + lane,
+                    permutation.log_size,
+                    eval_log_size,
+                );
+                SecureField::from_m31_array(std::array::from_fn(|j| {
+                    interaction[j].values.at(prev_i)
+                }))
+            }));
+            let numerators = permutation.constraint_numerators(
+                packed_row(&permutation.lhs),
+                packed_row(&permutation.rhs),
+                product(vec_index),
+                prev_product,
+            );
+            // The i-th constraint is multiplied by alpha^(n_constraints - 1 - i).
+            let res: PackedSecureField = numerators
+                .into_iter()
+                .zip(denom_inverses)
+                .zip(&coeffs)
+                .map(|((numerator, denom_inverses), &coeff)| {
+                    numerator * denom_inverses.data[vec_index] * coeff
+                })
+                .sum();
+            unsafe {
+                accumulator
+                    .col
+                    .set_packed(vec_index, accumulator.col.packed_at(vec_index) + res);
+            }
+        }
+    }
+}
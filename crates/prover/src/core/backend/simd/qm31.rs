@@ -189,6 +189,17 @@ impl Mul<PackedM31> for PackedQM31 {
     }
 }
 
+impl From<PackedM31> for PackedQM31 {
+    fn from(value: PackedM31) -> Self {
+        Self::from_packed_m31s([
+            value,
+            PackedM31::zero(),
+            PackedM31::zero(),
+            PackedM31::zero(),
+        ])
+    }
+}
+
 impl Sub<PackedM31> for PackedQM31 {
     type Output = Self;
 
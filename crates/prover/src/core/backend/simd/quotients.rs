@@ -1,5 +1,7 @@
 use itertools::{izip, zip_eq, Itertools};
 use num_traits::Zero;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use tracing::{span, Level};
 
 use super::column::SecureFieldVec;
@@ -9,7 +11,7 @@ use super::SimdBackend;
 use crate::core::backend::cpu::quotients::{
     batch_random_coeffs, column_line_coeffs, QuotientConstants,
 };
-use crate::core::backend::{Col, Column};
+use crate::core::backend::{Col, Column, CpuBackend};
 use crate::core::circle::CirclePoint;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
@@ -28,6 +30,11 @@ impl QuotientOps for SimdBackend {
         random_coeff: SecureField,
         sample_batches: &[ColumnSampleBatch],
     ) -> SecureEvaluation<Self> {
+        // The subdomain must hold at least 4 vectors.
+        if domain.log_size() < LOG_N_LANES + 2 + LOG_BLOWUP_FACTOR {
+            return accumulate_quotients_small(domain, columns, random_coeff, sample_batches);
+        }
+
         // Split the domain into a subdomain and a shift coset.
         // TODO(spapini): Move to the caller when Columns support slices.
         let (subdomain, mut subdomain_shifts) = domain.split(LOG_BLOWUP_FACTOR);
@@ -76,6 +83,29 @@ impl QuotientOps for SimdBackend {
     }
 }
 
+/// Accumulates the quotients on the CPU, for domains too small for the vectorized implementation.
+fn accumulate_quotients_small(
+    domain: CircleDomain,
+    columns: &[&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>],
+    random_coeff: SecureField,
+    sample_batches: &[ColumnSampleBatch],
+) -> SecureEvaluation<SimdBackend> {
+    let columns = columns
+        .iter()
+        .map(|column| CircleEvaluation::new(column.domain, column.values.to_cpu()))
+        .collect_vec();
+    let eval = CpuBackend::accumulate_quotients(
+        domain,
+        &columns.iter().collect_vec(),
+        random_coeff,
+        sample_batches,
+    );
+    SecureEvaluation {
+        domain,
+        values: eval.values.into_iter().collect(),
+    }
+}
+
 fn accumulate_quotients_on_subdomain(
     subdomain: CircleDomain,
     sample_batches: &[ColumnSampleBatch],
@@ -88,33 +118,14 @@ fn accumulate_quotients_on_subdomain(
     [crate::core::poly::circle::CirclePoly<SimdBackend>; 4],
 ) {
     assert!(subdomain.log_size() >= LOG_N_LANES + 2);
-    let mut values = SecureColumn::<SimdBackend>::zeros(subdomain.size());
     let quotient_constants = quotient_constants(sample_batches, random_coeff, subdomain);
 
     let span = span!(Level::INFO, "Quotient accumulation").entered();
-    // TODO(spapini): bit reverse iterator.
-    for quad_row in 0..1 << (subdomain.log_size() - LOG_N_LANES - 2) {
-        // TODO(spapini): Use optimized domain iteration.
-        let spaced_ys = PackedBaseField::from_array(std::array::from_fn(|i| {
-            subdomain
-                .at(bit_reverse_index(
-                    (quad_row << (LOG_N_LANES + 2)) + (i << 2),
-                    subdomain.log_size(),
-                ))
-                .y
-        }));
-        let row_accumulator = accumulate_row_quotients(
-            sample_batches,
-            columns,
-            &quotient_constants,
-            quad_row,
-            spaced_ys,
-        );
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..4 {
-            unsafe { values.set_packed((quad_row << 2) + i, row_accumulator[i]) };
-        }
-    }
+    #[cfg(not(feature = "parallel"))]
+    let values = accumulate_rows_serial(subdomain, sample_batches, columns, &quotient_constants);
+
+    #[cfg(feature = "parallel")]
+    let values = accumulate_rows_parallel(subdomain, sample_batches, columns, &quotient_constants);
     span.exit();
     let span = span!(Level::INFO, "Quotient extension").entered();
 
@@ -132,6 +143,89 @@ fn accumulate_quotients_on_subdomain(
     (span, extended_eval, subeval_polys)
 }
 
+/// Accumulates the quotients of the `quad_row`-th 4 vectors of rows of `subdomain`.
+fn accumulate_quad_row_quotients(
+    subdomain: CircleDomain,
+    sample_batches: &[ColumnSampleBatch],
+    columns: &[&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>],
+    quotient_constants: &QuotientConstants<SimdBackend>,
+    quad_row: usize,
+) -> [PackedSecureField; 4] {
+    // TODO(spapini): Use optimized domain iteration.
+    let spaced_ys = PackedBaseField::from_array(std::array::from_fn(|i| {
+        subdomain
+            .at(bit_reverse_index(
+                (quad_row << (LOG_N_LANES + 2)) + (i << 2),
+                subdomain.log_size(),
+            ))
+            .y
+    }));
+    accumulate_row_quotients(
+        sample_batches,
+        columns,
+        quotient_constants,
+        quad_row,
+        spaced_ys,
+    )
+}
+
+#[cfg(any(not(feature = "parallel"), test))]
+fn accumulate_rows_serial(
+    subdomain: CircleDomain,
+    sample_batches: &[ColumnSampleBatch],
+    columns: &[&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>],
+    quotient_constants: &QuotientConstants<SimdBackend>,
+) -> SecureColumn<SimdBackend> {
+    let mut values = SecureColumn::<SimdBackend>::zeros(subdomain.size());
+    // TODO(spapini): bit reverse iterator.
+    for quad_row in 0..1 << (subdomain.log_size() - LOG_N_LANES - 2) {
+        let row_accumulator = accumulate_quad_row_quotients(
+            subdomain,
+            sample_batches,
+            columns,
+            quotient_constants,
+            quad_row,
+        );
+        #[allow(clippy::needless_range_loop)]
+        for i in 0..4 {
+            unsafe { values.set_packed((quad_row << 2) + i, row_accumulator[i]) };
+        }
+    }
+    values
+}
+
+#[cfg(feature = "parallel")]
+fn accumulate_rows_parallel(
+    subdomain: CircleDomain,
+    sample_batches: &[ColumnSampleBatch],
+    columns: &[&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>],
+    quotient_constants: &QuotientConstants<SimdBackend>,
+) -> SecureColumn<SimdBackend> {
+    let mut values = SecureColumn::<SimdBackend>::zeros(subdomain.size());
+    let [c0, c1, c2, c3] = &mut values.columns;
+    (
+        c0.data.par_chunks_mut(4),
+        c1.data.par_chunks_mut(4),
+        c2.data.par_chunks_mut(4),
+        c3.data.par_chunks_mut(4),
+    )
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(quad_row, (v0, v1, v2, v3))| {
+            let row_accumulator = accumulate_quad_row_quotients(
+                subdomain,
+                sample_batches,
+                columns,
+                quotient_constants,
+                quad_row,
+            );
+            for (i, value) in row_accumulator.into_iter().enumerate() {
+                [v0[i], v1[i], v2[i], v3[i]] = value.into_packed_m31s();
+            }
+        });
+    values
+}
+
 /// Accumulates the quotients for 4 * N_LANES rows at a time.
 /// spaced_ys - y values for N_LANES points in the domain, in jumps of 4.
 pub fn accumulate_row_quotients(
@@ -261,6 +355,8 @@ fn quotient_constants(
 mod tests {
     use itertools::Itertools;
 
+    #[cfg(feature = "parallel")]
+    use super::{accumulate_rows_parallel, accumulate_rows_serial, quotient_constants};
     use crate::core::backend::simd::column::BaseFieldVec;
     use crate::core::backend::simd::SimdBackend;
     use crate::core::backend::{Column, CpuBackend};
@@ -324,4 +420,31 @@ mod tests {
 
         assert_eq!(res, cpu_result);
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_accumulation_matches_serial() {
+        const LOG_SIZE: u32 = 8;
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        let columns = (0..3)
+            .map(|j| {
+                let values = (0..domain.size())
+                    .map(|i| BaseField::from(i * i + j))
+                    .collect();
+                CircleEvaluation::<SimdBackend, BaseField, BitReversedOrder>::new(domain, values)
+            })
+            .collect_vec();
+        let sample_batches = vec![ColumnSampleBatch {
+            point: SECURE_FIELD_CIRCLE_GEN,
+            columns_and_values: vec![(0, qm31!(1, 2, 3, 4)), (2, qm31!(5, 6, 7, 8))],
+        }];
+        let quotient_constants = quotient_constants(&sample_batches, qm31!(1, 2, 3, 4), domain);
+        let columns = columns.iter().collect_vec();
+
+        let serial = accumulate_rows_serial(domain, &sample_batches, &columns, &quotient_constants);
+        let parallel =
+            accumulate_rows_parallel(domain, &sample_batches, &columns, &quotient_constants);
+
+        assert_eq!(serial.to_vec(), parallel.to_vec());
+    }
 }
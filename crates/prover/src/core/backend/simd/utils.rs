@@ -14,6 +14,35 @@ impl<const N: usize> Swizzle<N> for InterleaveOdds {
     const INDEX: [usize; N] = parity_interleave(true);
 }
 
+/// A raw mutable pointer that can be shared between threads.
+///
+/// Used to run kernels that write to disjoint parts of a buffer in parallel. Access the pointer
+/// with [UnsafeMut::get], so that closures capture the wrapper and not the raw pointer.
+#[derive(Clone, Copy, Debug)]
+pub struct UnsafeMut<T: ?Sized>(pub *mut T);
+
+impl<T: ?Sized> UnsafeMut<T> {
+    pub fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+unsafe impl<T: ?Sized> Send for UnsafeMut<T> {}
+unsafe impl<T: ?Sized> Sync for UnsafeMut<T> {}
+
+/// A raw const pointer that can be shared between threads. See [UnsafeMut].
+#[derive(Clone, Copy, Debug)]
+pub struct UnsafeConst<T: ?Sized>(pub *const T);
+
+impl<T: ?Sized> UnsafeConst<T> {
+    pub fn get(&self) -> *const T {
+        self.0
+    }
+}
+
+unsafe impl<T: ?Sized> Send for UnsafeConst<T> {}
+unsafe impl<T: ?Sized> Sync for UnsafeConst<T> {}
+
 const fn parity_interleave<const N: usize>(odd: bool) -> [usize; N] {
     let mut res = [0; N];
     let mut i = 0;
@@ -124,9 +124,10 @@ mod tests {
 
     use crate::core::channel::blake2s::Blake2sChannel;
     use crate::core::channel::Channel;
+    use crate::core::fields::om31::OM31;
     use crate::core::fields::qm31::SecureField;
     use crate::core::vcs::blake2_hash::Blake2sHash;
-    use crate::m31;
+    use crate::{m31, qm31};
 
     #[test]
     fn test_initialize_channel() {
@@ -229,4 +230,51 @@ mod tests {
 
         assert_ne!(initial_digest, channel.digest);
     }
+
+    #[test]
+    pub fn test_draw_extension_felts() {
+        let initial_digest = Blake2sHash::from(vec![2; 32]);
+        let mut channel = Blake2sChannel::new(initial_digest);
+        let mut expected_channel = Blake2sChannel::new(initial_digest);
+
+        let felts: Vec<OM31> = channel.draw_extension_felts(3);
+
+        // Each OM31 is made of the coordinates of two consecutive secure felts.
+        let secure_felts = expected_channel.draw_felts(6);
+        assert_eq!(
+            felts,
+            secure_felts
+                .chunks(2)
+                .map(|chunk| OM31(chunk[0], chunk[1]))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(channel.digest, expected_channel.digest);
+        assert_ne!(felts[0], felts[1]);
+    }
+
+    #[test]
+    pub fn test_mix_extension_felts() {
+        let initial_digest = Blake2sHash::from(vec![0; 32]);
+        let mut channel = Blake2sChannel::new(initial_digest);
+        let mut expected_channel = Blake2sChannel::new(initial_digest);
+        let felts = [OM31(qm31!(1, 2, 3, 4), qm31!(5, 6, 7, 8))];
+
+        channel.mix_extension_felts(&felts);
+        expected_channel.mix_felts(&[qm31!(8, 1, 0, 0), qm31!(1, 2, 3, 4), qm31!(5, 6, 7, 8)]);
+
+        assert_eq!(channel.digest, expected_channel.digest);
+        assert_ne!(initial_digest, channel.digest);
+    }
+
+    #[test]
+    pub fn test_mix_extension_felts_is_domain_separated() {
+        let initial_digest = Blake2sHash::from(vec![0; 32]);
+        let mut channel = Blake2sChannel::new(initial_digest);
+        let mut halves_channel = Blake2sChannel::new(initial_digest);
+
+        channel.mix_extension_felts(&[OM31(qm31!(1, 2, 3, 4), qm31!(5, 6, 7, 8))]);
+        halves_channel.mix_extension_felts(&[qm31!(1, 2, 3, 4), qm31!(5, 6, 7, 8)]);
+
+        assert_ne!(channel.digest, halves_channel.digest);
+    }
 }
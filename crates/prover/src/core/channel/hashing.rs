@@ -0,0 +1,160 @@
+//! Domain-separated hashing of arbitrary bytes to field elements and circle points, through a
+//! [Channel].
+//!
+//! The input is mixed into the channel before drawing, so the outputs are bound to the transcript
+//! so far. To hash independently of any transcript, use a freshly initialized channel.
+
+use itertools::Itertools;
+
+use super::Channel;
+use crate::core::circle::CirclePoint;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+
+/// Number of bytes packed into each base field element. 3 bytes always fit below P.
+const BYTES_PER_BASE_FELT: usize = 3;
+const BYTES_PER_SECURE_FELT: usize = BYTES_PER_BASE_FELT * SECURE_EXTENSION_DEGREE;
+
+/// Mixes arbitrary bytes into the channel.
+///
+/// The length is mixed first, so the encoding is injective even though the last element is
+/// padded with zeros.
+pub fn mix_bytes<C: Channel>(channel: &mut C, bytes: &[u8]) {
+    channel.mix_nonce(bytes.len() as u64);
+    let felts = bytes
+        .chunks(BYTES_PER_SECURE_FELT)
+        .map(|chunk| {
+            let mut padded = [0; BYTES_PER_SECURE_FELT];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            SecureField::from_m31_array(
+                padded
+                    .chunks_exact(BYTES_PER_BASE_FELT)
+                    .map(|limb| {
+                        BaseField::from_u32_unchecked(u32::from_le_bytes([
+                            limb[0], limb[1], limb[2], 0,
+                        ]))
+                    })
+                    .collect_vec()
+                    .try_into()
+                    .unwrap(),
+            )
+        })
+        .collect_vec();
+    channel.mix_felts(&felts);
+}
+
+/// Mixes the domain separator `dst` and then `data` into the channel.
+fn mix_domain_separated<C: Channel>(channel: &mut C, dst: &[u8], data: &[u8]) {
+    mix_bytes(channel, dst);
+    mix_bytes(channel, data);
+}
+
+/// Hashes `data` under the domain separator `dst` to a uniform [BaseField] element.
+pub fn hash_to_base_field<C: Channel>(channel: &mut C, dst: &[u8], data: &[u8]) -> BaseField {
+    mix_domain_separated(channel, dst, data);
+    // Each coordinate of a uniform secure field element is uniform.
+    channel.draw_felt().to_m31_array()[0]
+}
+
+/// Hashes `data` under the domain separator `dst` to a uniform [SecureField] element.
+pub fn hash_to_secure_field<C: Channel>(channel: &mut C, dst: &[u8], data: &[u8]) -> SecureField {
+    mix_domain_separated(channel, dst, data);
+    channel.draw_felt()
+}
+
+/// Hashes `data` under the domain separator `dst` to a uniform point on the secure field circle.
+pub fn hash_to_circle_point<C: Channel>(
+    channel: &mut C,
+    dst: &[u8],
+    data: &[u8],
+) -> CirclePoint<SecureField> {
+    mix_domain_separated(channel, dst, data);
+    CirclePoint::get_random_point(channel)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::{hash_to_base_field, hash_to_circle_point, hash_to_secure_field};
+    use crate::core::channel::{Blake2sChannel, Channel};
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::vcs::blake2_hash::Blake2sHash;
+
+    const DST: &[u8] = b"test-domain";
+
+    fn new_channel() -> Blake2sChannel {
+        Blake2sChannel::new(Blake2sHash::default())
+    }
+
+    #[test]
+    fn test_hash_to_secure_field_is_deterministic() {
+        let data = b"some data";
+
+        let felt = hash_to_secure_field(&mut new_channel(), DST, data);
+
+        assert_eq!(felt, hash_to_secure_field(&mut new_channel(), DST, data));
+        assert!(!felt.is_zero());
+    }
+
+    #[test]
+    fn test_hash_to_field_is_domain_separated() {
+        let data = b"some data";
+
+        let felt = hash_to_secure_field(&mut new_channel(), DST, data);
+
+        assert_ne!(
+            felt,
+            hash_to_secure_field(&mut new_channel(), b"other-domain", data)
+        );
+        // Moving bytes between the separator and the data changes the output.
+        assert_ne!(
+            felt,
+            hash_to_secure_field(&mut new_channel(), b"test-domai", b"nsome data")
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field_depends_on_length() {
+        let short = hash_to_base_field(&mut new_channel(), DST, &[0]);
+        let long = hash_to_base_field(&mut new_channel(), DST, &[0, 0]);
+
+        assert_ne!(short, long);
+    }
+
+    #[test]
+    fn test_hash_to_field_depends_on_transcript() {
+        let data = b"some data";
+        let mut channel = new_channel();
+        channel.mix_felts(&[SecureField::one()]);
+
+        assert_ne!(
+            hash_to_secure_field(&mut channel, DST, data),
+            hash_to_secure_field(&mut new_channel(), DST, data)
+        );
+    }
+
+    #[test]
+    fn test_hash_to_circle_point() {
+        let point = hash_to_circle_point(&mut new_channel(), DST, b"some data");
+
+        assert_eq!(point.x * point.x + point.y * point.y, SecureField::one());
+        assert_ne!(
+            point,
+            hash_to_circle_point(&mut new_channel(), DST, b"other data")
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_hash_with_poseidon_channel() {
+        use crate::core::channel::poseidon252::Poseidon252Channel;
+
+        let mut channel = Poseidon252Channel::new(Default::default());
+
+        let point = hash_to_circle_point(&mut channel, DST, b"some data");
+
+        assert_eq!(point.x * point.x + point.y * point.y, SecureField::one());
+    }
+}
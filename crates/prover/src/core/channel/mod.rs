@@ -1,10 +1,18 @@
+use itertools::Itertools;
+use num_traits::Zero;
+
+use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
+use super::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use super::fields::SecureExtensionField;
 
 mod blake2s;
+mod hashing;
 #[cfg(not(target_arch = "wasm32"))]
 mod poseidon252;
 
 pub use blake2s::Blake2sChannel;
+pub use hashing::{hash_to_base_field, hash_to_circle_point, hash_to_secure_field, mix_bytes};
 
 pub const EXTENSION_FELTS_PER_HASH: usize = 2;
 
@@ -44,4 +52,52 @@ pub trait Channel {
     fn draw_felts(&mut self, n_felts: usize) -> Vec<SecureField>;
     /// Returns a vector of random bytes of length `BYTES_PER_HASH`.
     fn draw_random_bytes(&mut self) -> Vec<u8>;
+
+    /// Mixes elements of any extension field `F`, packed into [SecureField]s.
+    ///
+    /// The packed coordinates are preceded by a header holding the extension degree and the
+    /// number of elements, so that the zero padding of the last [SecureField] is unambiguous.
+    fn mix_extension_felts<F: SecureExtensionField>(&mut self, felts: &[F]) {
+        let header = SecureField::from_m31_array([
+            BaseField::from(F::EXTENSION_DEGREE),
+            BaseField::from(felts.len()),
+            BaseField::zero(),
+            BaseField::zero(),
+        ]);
+        let coordinates = felts
+            .iter()
+            .flat_map(|felt| (0..F::EXTENSION_DEGREE).map(|i| felt.coordinate(i)))
+            .collect_vec();
+        let secure_felts = [header]
+            .into_iter()
+            .chain(coordinates.chunks(SECURE_EXTENSION_DEGREE).map(|chunk| {
+                SecureField::from_m31_array(std::array::from_fn(|i| {
+                    chunk.get(i).copied().unwrap_or_else(BaseField::zero)
+                }))
+            }))
+            .collect_vec();
+        self.mix_felts(&secure_felts);
+    }
+
+    /// Generates a uniform random element of any extension field `F`, see
+    /// [Self::draw_extension_felts].
+    fn draw_extension_felt<F: SecureExtensionField>(&mut self) -> F {
+        self.draw_extension_felts(1)[0]
+    }
+
+    /// Generates a uniform random vector of elements of any extension field `F`, from the
+    /// coordinates of uniform [SecureField]s.
+    fn draw_extension_felts<F: SecureExtensionField>(&mut self, n_felts: usize) -> Vec<F> {
+        let n_secure_felts = (n_felts * F::EXTENSION_DEGREE).div_ceil(SECURE_EXTENSION_DEGREE);
+        let coordinates = self
+            .draw_felts(n_secure_felts)
+            .into_iter()
+            .flat_map(|felt| felt.to_m31_array())
+            .collect_vec();
+        coordinates
+            .chunks_exact(F::EXTENSION_DEGREE)
+            .take(n_felts)
+            .map(|chunk| F::from_coordinates(|i| chunk[i]))
+            .collect()
+    }
 }
@@ -1,5 +1,7 @@
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
+use serde::{Deserialize, Serialize};
+
 use super::fields::m31::{BaseField, M31};
 use super::fields::qm31::SecureField;
 use super::fields::{ComplexConjugate, ExtensionOf, Field};
@@ -8,7 +10,9 @@ use crate::core::fields::qm31::P4;
 use crate::math::utils::egcd;
 
 /// A point on the complex circle. Treaed as an additive group.
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub struct CirclePoint<F: Field> {
     pub x: F,
     pub y: F,
@@ -208,7 +212,7 @@ pub const SECURE_FIELD_CIRCLE_ORDER: u128 = P4 - 1;
 
 /// Integer i that represent the circle point i * CIRCLE_GEN. Treated as an
 /// additive ring modulo `1 << M31_CIRCLE_LOG_ORDER`.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct CirclePointIndex(pub usize);
 
 impl CirclePointIndex {
@@ -290,7 +294,7 @@ impl Neg for CirclePointIndex {
 }
 
 /// Represents the coset initial + \<step\>.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Coset {
     pub initial_index: CirclePointIndex,
     pub initial: CirclePoint<M31>,
@@ -1,10 +1,16 @@
-use num_traits::One;
+use std::array;
+use std::iter::zip;
 
+use num_traits::{One, Zero};
+
+use super::backend::{Backend, Col};
 use super::circle::{CirclePoint, Coset};
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
-use super::fields::ExtensionOf;
+use super::fields::{ExtensionOf, FieldExpOps, SecureExtensionField};
 use super::pcs::quotients::PointSample;
+use super::poly::circle::CircleDomain;
+use super::utils::bit_reverse_index;
 use crate::core::fields::ComplexConjugate;
 
 /// Evaluates a vanishing polynomial of the coset at a point.
@@ -67,8 +73,43 @@ pub fn point_vanishing<F: ExtensionOf<BaseField>, EF: ExtensionOf<F>>(
     vanish_point: CirclePoint<F>,
     p: CirclePoint<EF>,
 ) -> EF {
+    let (numerator, denominator) = point_vanishing_fraction(vanish_point, p);
+    numerator / denominator
+}
+
+/// Evaluates the vanishing polynomial of the vanish_point at a point, as a fraction `(numerator,
+/// denominator)`, so that it can be inverted in a batch. See [point_vanishing].
+pub fn point_vanishing_fraction<F: ExtensionOf<BaseField>, EF: ExtensionOf<F>>(
+    vanish_point: CirclePoint<F>,
+    p: CirclePoint<EF>,
+) -> (EF, EF) {
     let h = p - vanish_point.into_ef();
-    h.y / (EF::one() + h.x)
+    (h.y, EF::one() + h.x)
+}
+
+/// Evaluates the inverses of `N` constraint denominators on the points of `domain`, in bit reversed
+/// order, with one batch inversion each. `denoms(p)` returns each denominator as a fraction
+/// `(numerator, denominator)`, whose inverse `denominator / numerator` is written to its column.
+pub fn domain_denom_inverses<B: Backend, const N: usize>(
+    domain: CircleDomain,
+    denoms: impl Fn(CirclePoint<BaseField>) -> [(BaseField, BaseField); N],
+) -> [Col<B, BaseField>; N] {
+    let mut numerators: [Vec<BaseField>; N] = array::from_fn(|_| vec![]);
+    let mut denominators: [Vec<BaseField>; N] = array::from_fn(|_| vec![]);
+    for i in 0..domain.size() {
+        let p = domain.at(bit_reverse_index(i, domain.log_size()));
+        for (j, (numerator, denominator)) in denoms(p).into_iter().enumerate() {
+            numerators[j].push(numerator);
+            denominators[j].push(denominator);
+        }
+    }
+    array::from_fn(|j| {
+        let mut inverses = vec![BaseField::zero(); domain.size()];
+        BaseField::batch_inverse(&numerators[j], &mut inverses);
+        zip(inverses, &denominators[j])
+            .map(|(inverse, &denominator)| inverse * denominator)
+            .collect()
+    })
 }
 
 /// Evaluates a point on a line between a point and its complex conjugate.
@@ -95,10 +136,10 @@ pub fn complex_conjugate_line(
 /// (conj(sample.y), conj(sample.value)).
 /// Relies on the fact that every polynomial F over the base
 /// field holds: F(p*) == F(p)* (* being the complex conjugate).
-pub fn complex_conjugate_line_coeffs(
-    sample: &PointSample,
-    alpha: SecureField,
-) -> (SecureField, SecureField, SecureField) {
+pub fn complex_conjugate_line_coeffs<F: SecureExtensionField>(
+    sample: &PointSample<F>,
+    alpha: F,
+) -> (F, F, F) {
     // TODO(AlonH): This assertion will fail at a probability of 1 to 2^62. Use a better solution.
     assert_ne!(
         sample.point.y,
@@ -114,10 +155,15 @@ pub fn complex_conjugate_line_coeffs(
 
 #[cfg(test)]
 mod tests {
-    use num_traits::Zero;
+    use num_traits::{One, Zero};
 
-    use super::{coset_vanishing, point_excluder, point_vanishing};
+    use super::{
+        coset_vanishing, domain_denom_inverses, point_excluder, point_vanishing,
+        point_vanishing_fraction,
+    };
     use crate::core::backend::cpu::{CpuCircleEvaluation, CpuCirclePoly};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::Column;
     use crate::core::circle::{CirclePoint, CirclePointIndex, Coset};
     use crate::core::constraints::{complex_conjugate_line, pair_vanishing};
     use crate::core::fields::m31::{BaseField, M31};
@@ -126,6 +172,7 @@ mod tests {
     use crate::core::poly::circle::CanonicCoset;
     use crate::core::poly::NaturalOrder;
     use crate::core::test_utils::secure_eval_to_base_eval;
+    use crate::core::utils::bit_reverse_index;
     use crate::m31;
 
     #[test]
@@ -195,6 +242,29 @@ mod tests {
         point_vanishing(point, point.antipode());
     }
 
+    #[test]
+    fn test_domain_denom_inverses() {
+        let domain = CanonicCoset::new(6).circle_domain();
+        let coset = CanonicCoset::new(5).coset;
+
+        let [coset_inverses, point_inverses] =
+            domain_denom_inverses::<SimdBackend, 2>(domain, |p| {
+                [
+                    (coset_vanishing(coset, p), BaseField::one()),
+                    point_vanishing_fraction(coset.at(0), p),
+                ]
+            });
+
+        for i in 0..domain.size() {
+            let p = domain.at(bit_reverse_index(i, domain.log_size()));
+            assert_eq!(coset_inverses.at(i), coset_vanishing(coset, p).inverse());
+            assert_eq!(
+                point_inverses.at(i),
+                point_vanishing(coset.at(0), p).inverse()
+            );
+        }
+    }
+
     #[test]
     fn test_complex_conjugate_symmetry() {
         // Create a polynomial over a base circle domain.
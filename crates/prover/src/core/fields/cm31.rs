@@ -3,16 +3,20 @@ use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
+use serde::{Deserialize, Serialize};
+
 use super::{ComplexConjugate, FieldExpOps};
 use crate::core::fields::m31::M31;
 use crate::{impl_extension_field, impl_field};
 
 pub const P2: u64 = 4611686014132420609; // (2 ** 31 - 1) ** 2
+/// The inverse of 2 in [M31].
+const HALF: M31 = M31::from_u32_unchecked(1 << 30);
 
 /// Complex extension field of M31.
 /// Equivalent to M31\[x\] over (x^2 + 1) as the irreducible polynomial.
 /// Represented as (a, b) of a + bi.
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct CM31(pub M31, pub M31);
 
 impl_field!(CM31, P2);
@@ -26,6 +30,41 @@ impl CM31 {
     pub fn from_m31(a: M31, b: M31) -> CM31 {
         Self(a, b)
     }
+
+    /// Returns the norm a^2 + b^2 of a + bi over [M31].
+    pub fn norm(&self) -> M31 {
+        self.0.square() + self.1.square()
+    }
+
+    /// Returns the Legendre symbol of the element: 0 for zero, 1 for a non-zero square and -1
+    /// otherwise.
+    pub fn legendre_symbol(&self) -> i32 {
+        // An element of a quadratic extension is a square iff its norm is a square.
+        self.norm().legendre_symbol()
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre_symbol() >= 0
+    }
+
+    /// Returns a square root of the element, if it is a square.
+    pub fn sqrt(&self) -> Option<Self> {
+        let Self(a, b) = *self;
+        if b.is_zero() {
+            // Every element of M31 is a square in CM31, since -1 is not a square in M31.
+            return Some(match a.sqrt() {
+                Some(c) => Self(c, M31::zero()),
+                None => Self(M31::zero(), (-a).sqrt().unwrap()),
+            });
+        }
+        // (c + di)^2 = a + bi iff c^2 - d^2 = a and 2cd = b, so c^2 = (a ± n) / 2 where n^2 is
+        // the norm. Since b is non-zero, c is non-zero.
+        let n = self.norm().sqrt()?;
+        let c = ((a + n) * HALF)
+            .sqrt()
+            .or_else(|| ((a - n) * HALF).sqrt())?;
+        Some(Self(c, b / (c + c)))
+    }
 }
 
 impl Display for CM31 {
@@ -81,11 +120,12 @@ macro_rules! cm31 {
 
 #[cfg(test)]
 mod tests {
+    use num_traits::{One, Zero};
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
 
-    use super::CM31;
-    use crate::core::fields::m31::P;
+    use super::{CM31, P2};
+    use crate::core::fields::m31::{M31, P};
     use crate::core::fields::{FieldExpOps, IntoSlice};
     use crate::m31;
 
@@ -115,6 +155,52 @@ mod tests {
         assert_eq!(cm1 / m, cm1 / cm);
     }
 
+    #[test]
+    fn test_legendre_symbol_matches_euler_criterion() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x: CM31 = rng.gen();
+            let euler = match x.pow((P2 as u128 - 1) / 2) {
+                v if v.is_zero() => 0,
+                v if v.is_one() => 1,
+                _ => -1,
+            };
+
+            assert_eq!(x.legendre_symbol(), euler);
+            assert_eq!(x.is_square(), euler >= 0);
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        // Not a square, by the irreducibility of the next extension.
+        let non_square = cm31!(2, 1);
+        assert_eq!(non_square.legendre_symbol(), -1);
+        assert_eq!(CM31::zero().sqrt(), Some(CM31::zero()));
+        for _ in 0..100 {
+            let x: CM31 = rng.gen();
+            let square = x.square();
+
+            let root = square.sqrt().unwrap();
+
+            assert!(root == x || root == -x);
+            assert_eq!((square * non_square).sqrt(), None);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_of_subfield_elements() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x = CM31::from(rng.gen::<M31>());
+
+            let root = x.sqrt().unwrap();
+
+            assert_eq!(root.square(), x);
+        }
+    }
+
     #[test]
     fn test_into_slice() {
         let mut rng = SmallRng::seed_from_u64(0);
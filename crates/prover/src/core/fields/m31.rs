@@ -5,6 +5,8 @@ use std::ops::{
 
 use bytemuck::{Pod, Zeroable};
 use rand::distributions::{Distribution, Standard};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
 
 use super::{ComplexConjugate, FieldExpOps};
 use crate::impl_field;
@@ -14,18 +16,49 @@ pub const N_BYTES_FELT: usize = 4;
 pub const P: u32 = 2147483647; // 2 ** 31 - 1
 
 #[repr(transparent)]
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Pod, Zeroable)]
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Pod, Zeroable, Serialize,
+)]
+#[serde(transparent)]
 pub struct M31(pub u32);
 pub type BaseField = M31;
 
 impl_field!(M31, P);
 
+/// Deserializes a reduced element, rejecting values that are not below `P`.
+impl<'de> Deserialize<'de> for M31 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u32::deserialize(deserializer)?;
+        if value >= P {
+            return Err(D::Error::custom(format!(
+                "{value} is not reduced modulo {P}"
+            )));
+        }
+        Ok(Self(value))
+    }
+}
+
 impl M31 {
     pub fn sqrt(&self) -> Option<Self> {
         let result = self.pow(1 << 29);
         (result.square() == *self).then_some(result)
     }
 
+    /// Returns the Legendre symbol of the element: 0 for zero, 1 for a non-zero square and -1
+    /// otherwise.
+    pub fn legendre_symbol(&self) -> i32 {
+        // Euler's criterion.
+        match self.pow(((P - 1) / 2) as u128) {
+            Self(0) => 0,
+            Self(1) => 1,
+            _ => -1,
+        }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre_symbol() >= 0
+    }
+
     /// Assumes that `val` is in the range [0, 2 * `P`) and returns `val` % `P`.
     pub fn partial_reduce(val: u32) -> Self {
         Self(val.checked_sub(P).unwrap_or(val))
@@ -168,6 +201,8 @@ fn sqn<const N: usize, T: FieldExpOps>(mut v: T) -> T {
 mod tests {
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
+    use serde::de::value::{Error as ValueError, U32Deserializer};
+    use serde::Deserialize;
 
     use super::{M31, P};
     use crate::core::fields::m31::{pow2147483645, BaseField};
@@ -201,6 +236,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_legendre_symbol() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x: M31 = rng.gen();
+            // -1 is not a square, since P = 3 (mod 4).
+            let expected = if x.0 == 0 { 0 } else { 1 };
+
+            assert_eq!(x.square().legendre_symbol(), expected);
+            assert_eq!((-x.square()).legendre_symbol(), -expected);
+            assert_eq!(x.is_square(), x.sqrt().is_some());
+        }
+    }
+
     #[test]
     fn test_into_slice() {
         let mut rng = SmallRng::seed_from_u64(0);
@@ -224,4 +273,13 @@ mod tests {
 
         assert_eq!(pow2147483645(v), v.pow(2147483645));
     }
+
+    #[test]
+    fn test_deserialize_rejects_unreduced() {
+        let deserialize = |value| M31::deserialize(U32Deserializer::<ValueError>::new(value));
+
+        assert_eq!(deserialize(P - 1), Ok(M31(P - 1)));
+        assert!(deserialize(P).is_err());
+        assert!(deserialize(u32::MAX).is_err());
+    }
 }
@@ -1,13 +1,21 @@
 use std::fmt::{Debug, Display};
+use std::hash::Hash;
 use std::iter::{Product, Sum};
 use std::ops::{Mul, MulAssign, Neg};
 
-use num_traits::{NumAssign, NumAssignOps, NumOps, One};
+use num_traits::{NumAssign, NumAssignOps, NumOps, One, Zero};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
+use self::m31::M31;
+use self::secure_column::ExtensionColumnOps;
 use super::backend::ColumnOps;
+use super::channel::Channel;
+use super::circle::CirclePoint;
 
 pub mod cm31;
 pub mod m31;
+pub mod om31;
 pub mod qm31;
 pub mod secure_column;
 
@@ -160,6 +168,62 @@ impl<F: Field> ExtensionOf<F> for F {
     const EXTENSION_DEGREE: usize = 1;
 }
 
+/// An extension of the base field that random challenges can be drawn from, see
+/// [Channel::draw_extension_felts].
+///
+/// The prover, FRI and the commitment scheme are generic over their secure field, which is
+/// [SecureField](qm31::SecureField) by default. Every backend supports
+/// [SecureField](qm31::SecureField), and the [CpuBackend](crate::core::backend::CpuBackend) also
+/// supports [OM31](om31::OM31), see [ExtensionBackend](crate::core::backend::ExtensionBackend).
+/// The interaction elements of the AIRs are drawn from [SecureField](qm31::SecureField) for any
+/// secure field.
+pub trait SecureExtensionField:
+    ExtensionOf<M31> + ComplexConjugate + Eq + Ord + Hash + Serialize + DeserializeOwned
+{
+    /// The column of elements of the field, stored coordinate-wise on a backend `B`.
+    type Column<B: FieldOps<M31>>: ExtensionColumnOps<B, Self>;
+
+    /// Creates an element from its `EXTENSION_DEGREE` coordinates over the base field, where
+    /// `coordinate(i)` is the i-th coordinate.
+    fn from_coordinates(coordinate: impl FnMut(usize) -> M31) -> Self;
+
+    /// Returns the coordinate at `index`, in the order of [Self::from_coordinates].
+    fn coordinate(&self, index: usize) -> M31;
+
+    /// Returns the combined value, given the values of its `EXTENSION_DEGREE` composing base field
+    /// polynomials at a point.
+    fn from_partial_evals(evals: &[Self]) -> Self {
+        assert_eq!(evals.len(), Self::EXTENSION_DEGREE);
+        evals
+            .iter()
+            .enumerate()
+            .map(|(i, &eval)| {
+                eval * Self::from_coordinates(|j| if i == j { M31::one() } else { M31::zero() })
+            })
+            .sum()
+    }
+
+    /// Draws a random point of the circle over the field.
+    ///
+    /// The point is the image of a random element `t` under the rational parametrization
+    /// `t -> ((1 - t^2) / (1 + t^2), 2t / (1 + t^2))` of the circle.
+    fn draw_circle_point(channel: &mut impl Channel) -> CirclePoint<Self> {
+        loop {
+            let t = channel.draw_extension_felt::<Self>();
+            let t_square = t.square();
+            let denominator = Self::one() + t_square;
+            if denominator.is_zero() {
+                continue;
+            }
+            let denominator_inverse = denominator.inverse();
+            return CirclePoint {
+                x: (Self::one() - t_square) * denominator_inverse,
+                y: t.double() * denominator_inverse,
+            };
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! impl_field {
     ($field_name: ty, $field_size: ident) => {
@@ -0,0 +1,220 @@
+use std::fmt::{Debug, Display};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::qm31::{QM31, R};
+use super::secure_column::ExtensionColumn;
+use super::{ComplexConjugate, FieldExpOps, FieldOps, SecureExtensionField};
+use crate::core::fields::m31::M31;
+use crate::{impl_extension_field, impl_field};
+
+/// (2 ** 31 - 1) ** 8, as little-endian 128-bit limbs.
+pub const P8: [u128; 2] = [
+    127605887040754786328042323130892419073,
+    1329227990833155723832736485415583748,
+];
+pub const OM31_EXTENSION_DEGREE: usize = 8;
+
+/// Extension field of QM31, of ~248 bits.
+/// Equivalent to QM31\[x\] over (x^2 - u) as the irreducible polynomial.
+/// Represented as (a, b) of a + bv, where a and b are [QM31] elements.
+///
+/// The prover supports OM31 as its secure field on the
+/// [CpuBackend](crate::core::backend::CpuBackend) only, see [SecureExtensionField].
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct OM31(pub QM31, pub QM31);
+
+impl_field!(OM31, P8);
+impl_extension_field!(OM31, QM31);
+
+impl OM31 {
+    pub const fn from_u32_unchecked(values: [u32; OM31_EXTENSION_DEGREE]) -> Self {
+        let [a, b, c, d, e, f, g, h] = values;
+        Self(
+            QM31::from_u32_unchecked(a, b, c, d),
+            QM31::from_u32_unchecked(e, f, g, h),
+        )
+    }
+
+    pub fn from_m31_array(array: [M31; OM31_EXTENSION_DEGREE]) -> Self {
+        Self(
+            QM31::from_m31_array(array[..4].try_into().unwrap()),
+            QM31::from_m31_array(array[4..].try_into().unwrap()),
+        )
+    }
+
+    pub fn to_m31_array(self) -> [M31; OM31_EXTENSION_DEGREE] {
+        let [a, b, c, d] = self.0.to_m31_array();
+        let [e, f, g, h] = self.1.to_m31_array();
+        [a, b, c, d, e, f, g, h]
+    }
+
+    /// Returns the combined value, given the values of its composing base field polynomials at that
+    /// point.
+    pub fn from_partial_evals(evals: [Self; OM31_EXTENSION_DEGREE]) -> Self {
+        evals
+            .into_iter()
+            .enumerate()
+            .map(|(i, eval)| {
+                let mut basis = [0; OM31_EXTENSION_DEGREE];
+                basis[i] = 1;
+                eval * Self::from_u32_unchecked(basis)
+            })
+            .sum()
+    }
+}
+
+/// Multiplies by u, the root of the irreducible polynomial of [OM31].
+fn mul_by_u(x: QM31) -> QM31 {
+    // u * (a + bu) = (2+i)b + au.
+    QM31(R * x.1, x.0)
+}
+
+impl Display for OM31 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}) + ({})v", self.0, self.1)
+    }
+}
+
+impl Debug for OM31 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}) + ({})v", self.0, self.1)
+    }
+}
+
+impl Mul for OM31 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // (a + bv) * (c + dv) = (ac + ubd) + (ad + bc)v.
+        Self(
+            self.0 * rhs.0 + mul_by_u(self.1 * rhs.1),
+            self.0 * rhs.1 + self.1 * rhs.0,
+        )
+    }
+}
+
+impl From<QM31> for OM31 {
+    fn from(x: QM31) -> Self {
+        Self(x, QM31::zero())
+    }
+}
+
+impl FieldExpOps for OM31 {
+    fn inverse(&self) -> Self {
+        assert!(!self.is_zero(), "0 has no inverse");
+        // (a + bv)^-1 = (a - bv) / (a^2 - ub^2).
+        let denom = self.0.square() - mul_by_u(self.1.square());
+        let denom_inverse = denom.inverse();
+        Self(self.0 * denom_inverse, -self.1 * denom_inverse)
+    }
+}
+
+impl SecureExtensionField for OM31 {
+    type Column<B: FieldOps<M31>> = ExtensionColumn<B, Self>;
+
+    fn from_coordinates(coordinate: impl FnMut(usize) -> M31) -> Self {
+        Self::from_m31_array(std::array::from_fn(coordinate))
+    }
+
+    fn coordinate(&self, index: usize) -> M31 {
+        self.to_m31_array()[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::One;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{OM31, OM31_EXTENSION_DEGREE};
+    use crate::core::fields::m31::{M31, P};
+    use crate::core::fields::qm31::{P4, QM31};
+    use crate::core::fields::{ExtensionOf, FieldExpOps, SecureExtensionField};
+    use crate::{m31, qm31};
+
+    #[test]
+    fn test_irreducible_polynomial() {
+        // x^2 - u is irreducible over QM31 iff u is not a square.
+        let u = qm31!(0, 0, 1, 0);
+
+        assert_eq!(u.pow((P4 - 1) / 2), -QM31::one());
+    }
+
+    #[test]
+    fn test_extension_degree() {
+        assert_eq!(
+            <OM31 as ExtensionOf<M31>>::EXTENSION_DEGREE,
+            OM31_EXTENSION_DEGREE
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let x: OM31 = rng.gen();
+
+            assert_eq!(x * x.inverse(), OM31::one());
+        }
+    }
+
+    #[test]
+    fn test_ops() {
+        let om0 = OM31::from_u32_unchecked([1, 2, 3, 4, 5, 6, 7, 8]);
+        let om1 = OM31::from_u32_unchecked([4, 5, 6, 7, 8, 9, 10, 11]);
+        let m = m31!(8);
+        let om = OM31::from(m);
+        let qm = qm31!(1, 2, 3, 4);
+
+        assert_eq!(
+            om0 + om1,
+            OM31::from_u32_unchecked([5, 7, 9, 11, 13, 15, 17, 19])
+        );
+        assert_eq!(om1 + m, om1 + om);
+        assert_eq!(om1 * m, om1 * om);
+        assert_eq!(
+            -om0,
+            OM31::from_u32_unchecked([P - 1, P - 2, P - 3, P - 4, P - 5, P - 6, P - 7, P - 8])
+        );
+        assert_eq!(om0 - om1, OM31::from_u32_unchecked([P - 3; 8]));
+        assert_eq!(om1 - m, om1 - om);
+        assert_eq!(om0 * om1 / om1, om0);
+        assert_eq!(om1 / m, om1 / om);
+        // QM31 is a subfield.
+        assert_eq!(OM31::from(qm) * OM31::from(qm), OM31::from(qm * qm));
+    }
+
+    #[test]
+    fn test_mul_is_associative_and_distributive() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (a, b, c): (OM31, OM31, OM31) = (rng.gen(), rng.gen(), rng.gen());
+
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a * (b + c), a * b + a * c);
+    }
+
+    #[test]
+    fn test_from_partial_evals() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let x: OM31 = rng.gen();
+        let evals = x.to_m31_array().map(OM31::from);
+
+        assert_eq!(OM31::from_partial_evals(evals), x);
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let x: OM31 = rng.gen();
+
+        let coordinates = x.to_m31_array();
+
+        assert_eq!(OM31::from_coordinates(|i| coordinates[i]), x);
+        assert_eq!(x.coordinate(5), coordinates[5]);
+    }
+}
@@ -3,20 +3,28 @@ use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
-use super::secure_column::SECURE_EXTENSION_DEGREE;
-use super::{ComplexConjugate, FieldExpOps};
+use serde::{Deserialize, Serialize};
+
+use super::secure_column::{SecureColumn, SECURE_EXTENSION_DEGREE};
+use super::{ComplexConjugate, FieldExpOps, FieldOps, SecureExtensionField};
+use crate::core::channel::Channel;
+use crate::core::circle::CirclePoint;
 use crate::core::fields::cm31::CM31;
 use crate::core::fields::m31::M31;
 use crate::{impl_extension_field, impl_field};
 
 pub const P4: u128 = 21267647892944572736998860269687930881; // (2 ** 31 - 1) ** 4
 pub const R: CM31 = CM31::from_u32_unchecked(2, 1);
+/// The inverse of 2 in [CM31].
+const HALF: CM31 = CM31::from_u32_unchecked(1 << 30, 0);
 
 /// Extension field of CM31.
 /// Equivalent to CM31\[x\] over (x^2 - 2 - i) as the irreducible polynomial.
 /// Represented as ((a, b), (c, d)) of (a + bi) + (c + di)u.
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct QM31(pub CM31, pub CM31);
+/// The field the random challenges of the prover are drawn from, and that the out of domain
+/// samples and FRI are computed over.
 pub type SecureField = QM31;
 
 impl_field!(QM31, P4);
@@ -42,6 +50,41 @@ impl QM31 {
         [self.0 .0, self.0 .1, self.1 .0, self.1 .1]
     }
 
+    /// Returns the norm a^2 - rb^2 of a + bu over [CM31].
+    pub fn norm(&self) -> CM31 {
+        self.0.square() - R * self.1.square()
+    }
+
+    /// Returns the Legendre symbol of the element: 0 for zero, 1 for a non-zero square and -1
+    /// otherwise.
+    pub fn legendre_symbol(&self) -> i32 {
+        // An element of a quadratic extension is a square iff its norm is a square.
+        self.norm().legendre_symbol()
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre_symbol() >= 0
+    }
+
+    /// Returns a square root of the element, if it is a square.
+    pub fn sqrt(&self) -> Option<Self> {
+        let Self(a, b) = *self;
+        if b.is_zero() {
+            // a is a square in QM31 iff either a or a / r is a square in CM31, since (du)^2 = rd^2.
+            return match a.sqrt() {
+                Some(c) => Some(Self(c, CM31::zero())),
+                None => Some(Self(CM31::zero(), (a / R).sqrt()?)),
+            };
+        }
+        // (c + du)^2 = a + bu iff c^2 + rd^2 = a and 2cd = b, so c^2 = (a ± n) / 2 where n^2 is
+        // the norm. Since b is non-zero, c is non-zero.
+        let n = self.norm().sqrt()?;
+        let c = ((a + n) * HALF)
+            .sqrt()
+            .or_else(|| ((a - n) * HALF).sqrt())?;
+        Some(Self(c, b / (c + c)))
+    }
+
     /// Returns the combined value, given the values of its composing base field polynomials at that
     /// point.
     pub fn from_partial_evals(evals: [Self; SECURE_EXTENSION_DEGREE]) -> Self {
@@ -100,6 +143,22 @@ impl FieldExpOps for QM31 {
     }
 }
 
+impl SecureExtensionField for QM31 {
+    type Column<B: FieldOps<M31>> = SecureColumn<B>;
+
+    fn from_coordinates(coordinate: impl FnMut(usize) -> M31) -> Self {
+        Self::from_m31_array(std::array::from_fn(coordinate))
+    }
+
+    fn coordinate(&self, index: usize) -> M31 {
+        self.to_m31_array()[index]
+    }
+
+    fn draw_circle_point(channel: &mut impl Channel) -> CirclePoint<Self> {
+        CirclePoint::get_random_point(channel)
+    }
+}
+
 #[cfg(test)]
 #[macro_export]
 macro_rules! qm31 {
@@ -111,11 +170,12 @@ macro_rules! qm31 {
 
 #[cfg(test)]
 mod tests {
-    use num_traits::One;
+    use num_traits::{One, Zero};
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
 
-    use super::QM31;
+    use super::{P4, QM31};
+    use crate::core::fields::cm31::CM31;
     use crate::core::fields::m31::P;
     use crate::core::fields::{FieldExpOps, IntoSlice};
     use crate::m31;
@@ -146,6 +206,52 @@ mod tests {
         assert_eq!(qm1 / m, qm1 / qm);
     }
 
+    #[test]
+    fn test_legendre_symbol_matches_euler_criterion() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x: QM31 = rng.gen();
+            let euler = match x.pow((P4 - 1) / 2) {
+                v if v.is_zero() => 0,
+                v if v.is_one() => 1,
+                _ => -1,
+            };
+
+            assert_eq!(x.legendre_symbol(), euler);
+            assert_eq!(x.is_square(), euler >= 0);
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        // Not a square, by the irreducibility of the next extension.
+        let non_square = qm31!(0, 0, 1, 0);
+        assert_eq!(non_square.legendre_symbol(), -1);
+        assert_eq!(QM31::zero().sqrt(), Some(QM31::zero()));
+        for _ in 0..100 {
+            let x: QM31 = rng.gen();
+            let square = x.square();
+
+            let root = square.sqrt().unwrap();
+
+            assert!(root == x || root == -x);
+            assert_eq!((square * non_square).sqrt(), None);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_of_subfield_elements() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x = QM31(rng.gen::<CM31>(), CM31::zero());
+
+            let root = x.sqrt().unwrap();
+
+            assert_eq!(root.square(), x);
+        }
+    }
+
     #[test]
     fn test_into_slice() {
         let mut rng = SmallRng::seed_from_u64(0);
@@ -1,6 +1,10 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::IndexMut;
+
 use super::m31::BaseField;
 use super::qm31::SecureField;
-use super::{ExtensionOf, FieldOps};
+use super::{ExtensionOf, FieldOps, SecureExtensionField};
 use crate::core::backend::{Col, Column, CpuBackend};
 use crate::core::utils::IteratorMutExt;
 
@@ -9,10 +13,24 @@ pub const SECURE_EXTENSION_DEGREE: usize =
 
 /// An array of `SECURE_EXTENSION_DEGREE` base field columns, that represents a column of secure
 /// field elements.
-#[derive(Clone, Debug)]
 pub struct SecureColumn<B: FieldOps<BaseField>> {
     pub columns: [Col<B, BaseField>; SECURE_EXTENSION_DEGREE],
 }
+// Implemented by hand, as deriving would require the backend to implement the traits.
+impl<B: FieldOps<BaseField>> Clone for SecureColumn<B> {
+    fn clone(&self) -> Self {
+        Self {
+            columns: self.columns.clone(),
+        }
+    }
+}
+impl<B: FieldOps<BaseField>> Debug for SecureColumn<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecureColumn")
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
 impl SecureColumn<CpuBackend> {
     pub fn set(&mut self, index: usize, value: SecureField) {
         self.columns
@@ -97,3 +115,194 @@ impl From<SecureColumn<CpuBackend>> for Vec<SecureField> {
         column.into_iter().collect()
     }
 }
+
+/// A column of elements of the extension field `F`, stored as `F::EXTENSION_DEGREE` base field
+/// columns. Generalizes [SecureColumn] to any [SecureExtensionField].
+pub struct ExtensionColumn<B: FieldOps<BaseField>, F: SecureExtensionField> {
+    pub columns: Vec<Col<B, BaseField>>,
+    _field: PhantomData<F>,
+}
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> Clone for ExtensionColumn<B, F> {
+    fn clone(&self) -> Self {
+        Self::new(self.columns.clone())
+    }
+}
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> Debug for ExtensionColumn<B, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtensionColumn")
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> ExtensionColumn<B, F> {
+    pub fn new(columns: Vec<Col<B, BaseField>>) -> Self {
+        assert_eq!(columns.len(), F::EXTENSION_DEGREE);
+        assert!(columns.iter().all(|c| c.len() == columns[0].len()));
+        Self {
+            columns,
+            _field: PhantomData,
+        }
+    }
+
+    pub fn at(&self, index: usize) -> F {
+        F::from_coordinates(|i| self.columns[i].at(index))
+    }
+
+    pub fn zeros(len: usize) -> Self {
+        Self::new(
+            (0..F::EXTENSION_DEGREE)
+                .map(|_| Col::<B, BaseField>::zeros(len))
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns[0].is_empty()
+    }
+
+    pub fn to_cpu(&self) -> ExtensionColumn<CpuBackend, F> {
+        ExtensionColumn::new(self.columns.iter().map(|c| c.to_cpu()).collect())
+    }
+}
+impl<F: SecureExtensionField> ExtensionColumn<CpuBackend, F> {
+    pub fn set(&mut self, index: usize, value: F) {
+        for (i, column) in self.columns.iter_mut().enumerate() {
+            column[index] = value.coordinate(i);
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<F> {
+        (0..self.len()).map(|i| self.at(i)).collect()
+    }
+}
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> FromIterator<F> for ExtensionColumn<B, F> {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        let mut columns = vec![vec![]; F::EXTENSION_DEGREE];
+        for value in iter.into_iter() {
+            for (i, column) in columns.iter_mut().enumerate() {
+                column.push(value.coordinate(i));
+            }
+        }
+        Self::new(
+            columns
+                .into_iter()
+                .map(|c| c.into_iter().collect())
+                .collect(),
+        )
+    }
+}
+impl<B: FieldOps<BaseField>> From<SecureColumn<B>> for ExtensionColumn<B, SecureField> {
+    fn from(column: SecureColumn<B>) -> Self {
+        Self::new(column.columns.into())
+    }
+}
+
+/// A column of elements of the secure field `F`, stored as `F::EXTENSION_DEGREE` base field
+/// columns. See [SecureExtensionField::Column].
+pub trait ExtensionColumnOps<B: FieldOps<BaseField>, F: SecureExtensionField>:
+    Clone + Debug + Sized
+{
+    /// Creates a column from its `F::EXTENSION_DEGREE` coordinate columns.
+    fn from_columns(columns: Vec<Col<B, BaseField>>) -> Self;
+
+    /// Returns the coordinate columns.
+    fn columns(&self) -> &[Col<B, BaseField>];
+
+    /// Returns the coordinate columns, mutably.
+    fn columns_mut(&mut self) -> &mut [Col<B, BaseField>];
+
+    fn into_columns(self) -> Vec<Col<B, BaseField>>;
+
+    fn at(&self, index: usize) -> F {
+        F::from_coordinates(|i| self.columns()[i].at(index))
+    }
+
+    fn zeros(len: usize) -> Self {
+        Self::from_columns(
+            (0..F::EXTENSION_DEGREE)
+                .map(|_| Col::<B, BaseField>::zeros(len))
+                .collect(),
+        )
+    }
+
+    fn len(&self) -> usize {
+        self.columns()[0].len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.columns()[0].is_empty()
+    }
+
+    fn to_cpu(&self) -> F::Column<CpuBackend> {
+        ExtensionColumnOps::from_columns(self.columns().iter().map(|c| c.to_cpu()).collect())
+    }
+
+    fn to_vec(&self) -> Vec<F> {
+        (0..self.len()).map(|i| self.at(i)).collect()
+    }
+
+    /// Creates a column from its values.
+    fn from_values(values: impl IntoIterator<Item = F>) -> Self {
+        Self::from_columns(
+            values
+                .into_iter()
+                .collect::<ExtensionColumn<B, F>>()
+                .columns,
+        )
+    }
+
+    fn set(&mut self, index: usize, value: F)
+    where
+        Col<B, BaseField>: IndexMut<usize, Output = BaseField>,
+    {
+        for (i, column) in self.columns_mut().iter_mut().enumerate() {
+            column[index] = value.coordinate(i);
+        }
+    }
+}
+
+impl<B: FieldOps<BaseField>> ExtensionColumnOps<B, SecureField> for SecureColumn<B> {
+    fn from_columns(columns: Vec<Col<B, BaseField>>) -> Self {
+        Self {
+            columns: columns
+                .try_into()
+                .unwrap_or_else(|_| panic!("expected {SECURE_EXTENSION_DEGREE} columns")),
+        }
+    }
+
+    fn columns(&self) -> &[Col<B, BaseField>] {
+        &self.columns
+    }
+
+    fn columns_mut(&mut self) -> &mut [Col<B, BaseField>] {
+        &mut self.columns
+    }
+
+    fn into_columns(self) -> Vec<Col<B, BaseField>> {
+        self.columns.into()
+    }
+}
+
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> ExtensionColumnOps<B, F>
+    for ExtensionColumn<B, F>
+{
+    fn from_columns(columns: Vec<Col<B, BaseField>>) -> Self {
+        Self::new(columns)
+    }
+
+    fn columns(&self) -> &[Col<B, BaseField>] {
+        &self.columns
+    }
+
+    fn columns_mut(&mut self) -> &mut [Col<B, BaseField>] {
+        &mut self.columns
+    }
+
+    fn into_columns(self) -> Vec<Col<B, BaseField>> {
+        self.columns
+    }
+}
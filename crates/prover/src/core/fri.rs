@@ -5,7 +5,7 @@ use std::iter::zip;
 use std::ops::RangeInclusive;
 
 use itertools::Itertools;
-use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{span, Level};
 
@@ -13,8 +13,8 @@ use super::backend::CpuBackend;
 use super::channel::Channel;
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
-use super::fields::secure_column::{SecureColumn, SECURE_EXTENSION_DEGREE};
-use super::fields::FieldOps;
+use super::fields::secure_column::ExtensionColumnOps;
+use super::fields::{FieldOps, SecureExtensionField};
 use super::poly::circle::{CircleEvaluation, PolyOps, SecureEvaluation};
 use super::poly::line::{LineEvaluation, LinePoly};
 use super::poly::twiddles::TwiddleTree;
@@ -33,11 +33,12 @@ use crate::core::vcs::verifier::{MerkleVerificationError, MerkleVerifier};
 
 /// FRI proof config
 // TODO(andrew): Support different step sizes.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedFriConfig")]
 pub struct FriConfig {
-    log_blowup_factor: u32,
-    log_last_layer_degree_bound: u32,
-    n_queries: usize,
+    pub log_blowup_factor: u32,
+    pub log_last_layer_degree_bound: u32,
+    pub n_queries: usize,
     // TODO(andrew): fold_steps.
 }
 
@@ -74,7 +75,50 @@ impl FriConfig {
     }
 }
 
-pub trait FriOps: FieldOps<BaseField> + PolyOps + Sized + FieldOps<SecureField> {
+/// A deserialized [FriConfig], whose parameters are not checked yet.
+#[derive(Deserialize)]
+struct UncheckedFriConfig {
+    log_blowup_factor: u32,
+    log_last_layer_degree_bound: u32,
+    n_queries: usize,
+}
+
+impl TryFrom<UncheckedFriConfig> for FriConfig {
+    type Error = InvalidFriConfig;
+
+    fn try_from(config: UncheckedFriConfig) -> Result<Self, Self::Error> {
+        let UncheckedFriConfig {
+            log_blowup_factor,
+            log_last_layer_degree_bound,
+            n_queries,
+        } = config;
+        if !Self::LOG_LAST_LAYER_DEGREE_BOUND_RANGE.contains(&log_last_layer_degree_bound) {
+            return Err(InvalidFriConfig::LogLastLayerDegreeBound(
+                log_last_layer_degree_bound,
+            ));
+        }
+        if !Self::LOG_BLOWUP_FACTOR_RANGE.contains(&log_blowup_factor) {
+            return Err(InvalidFriConfig::LogBlowupFactor(log_blowup_factor));
+        }
+        Ok(Self::new(
+            log_last_layer_degree_bound,
+            log_blowup_factor,
+            n_queries,
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum InvalidFriConfig {
+    #[error("log last layer degree bound {0} is not in {range:?}", range = FriConfig::LOG_LAST_LAYER_DEGREE_BOUND_RANGE)]
+    LogLastLayerDegreeBound(u32),
+    #[error("log blowup factor {0} is not in {range:?}", range = FriConfig::LOG_BLOWUP_FACTOR_RANGE)]
+    LogBlowupFactor(u32),
+}
+
+pub trait FriOps<F: SecureExtensionField = SecureField>:
+    FieldOps<BaseField> + PolyOps + Sized + FieldOps<F>
+{
     /// Folds a degree `d` polynomial into a degree `d/2` polynomial.
     ///
     /// Let `eval` be a polynomial evaluated on a [LineDomain] `E`, `alpha` be a random field
@@ -86,10 +130,10 @@ pub trait FriOps: FieldOps<BaseField> + PolyOps + Sized + FieldOps<SecureField>
     ///
     /// Panics if there are less than two evaluations.
     fn fold_line(
-        eval: &LineEvaluation<Self>,
-        alpha: SecureField,
+        eval: &LineEvaluation<Self, F>,
+        alpha: F,
         twiddles: &TwiddleTree<Self>,
-    ) -> LineEvaluation<Self>;
+    ) -> LineEvaluation<Self, F>;
 
     /// Folds and accumulates a degree `d` circle polynomial into a degree `d/2` univariate
     /// polynomial.
@@ -108,9 +152,9 @@ pub trait FriOps: FieldOps<BaseField> + PolyOps + Sized + FieldOps<SecureField>
     // TODO(andrew): Make folding factor generic.
     // TODO(andrew): Fold directly into FRI layer to prevent allocation.
     fn fold_circle_into_line(
-        dst: &mut LineEvaluation<Self>,
-        src: &SecureEvaluation<Self>,
-        alpha: SecureField,
+        dst: &mut LineEvaluation<Self, F>,
+        src: &SecureEvaluation<Self, F>,
+        alpha: F,
         twiddles: &TwiddleTree<Self>,
     );
 
@@ -119,18 +163,22 @@ pub trait FriOps: FieldOps<BaseField> + PolyOps + Sized + FieldOps<SecureField>
     /// FRI-space: polynomials of total degree n/2.
     /// Based on lemma #12 from the CircleStark paper: f(P) = g(P)+ lambda * alternating(P),
     /// where lambda is the cosset diff of eval, and g is a polynomial in the fft-space.
-    fn decompose(eval: &SecureEvaluation<Self>) -> (SecureEvaluation<Self>, SecureField);
+    fn decompose(eval: &SecureEvaluation<Self, F>) -> (SecureEvaluation<Self, F>, F);
 }
 /// A FRI prover that applies the FRI protocol to prove a set of polynomials are of low degree.
-pub struct FriProver<B: FriOps + MerkleOps<H>, H: MerkleHasher> {
+pub struct FriProver<
+    B: FriOps<F> + MerkleOps<H>,
+    H: MerkleHasher,
+    F: SecureExtensionField = SecureField,
+> {
     config: FriConfig,
-    inner_layers: Vec<FriLayerProver<B, H>>,
-    last_layer_poly: LinePoly,
+    inner_layers: Vec<FriLayerProver<B, H, F>>,
+    last_layer_poly: LinePoly<F>,
     /// Unique sizes of committed columns sorted in descending order.
     column_log_sizes: Vec<u32>,
 }
 
-impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
+impl<B: FriOps<F> + MerkleOps<H>, H: MerkleHasher, F: SecureExtensionField> FriProver<B, H, F> {
     /// Commits to multiple [CircleEvaluation]s.
     ///
     /// `columns` must be provided in descending order by size.
@@ -154,7 +202,7 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
     pub fn commit(
         channel: &mut impl Channel<Digest = H::Hash>,
         config: FriConfig,
-        columns: &[SecureEvaluation<B>],
+        columns: &[SecureEvaluation<B, F>],
         twiddles: &TwiddleTree<B>,
     ) -> Self {
         let _span = span!(Level::INFO, "FRI commitment").entered();
@@ -183,14 +231,15 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
     /// All `columns` must be provided in descending order by size.
     ///
     /// Returns all inner layers and the evaluation of the last layer.
+    #[allow(clippy::type_complexity)]
     fn commit_inner_layers(
         channel: &mut impl Channel<Digest = H::Hash>,
         config: FriConfig,
-        columns: &[SecureEvaluation<B>],
+        columns: &[SecureEvaluation<B, F>],
         twiddles: &TwiddleTree<B>,
-    ) -> (Vec<FriLayerProver<B, H>>, LineEvaluation<B>) {
+    ) -> (Vec<FriLayerProver<B, H, F>>, LineEvaluation<B, F>) {
         // Returns the length of the [LineEvaluation] a [CircleEvaluation] gets folded into.
-        let folded_len = |e: &SecureEvaluation<B>| e.len() >> CIRCLE_TO_LINE_FOLD_STEP;
+        let folded_len = |e: &SecureEvaluation<B, F>| e.len() >> CIRCLE_TO_LINE_FOLD_STEP;
 
         let first_layer_size = folded_len(&columns[0]);
         let first_layer_domain = LineDomain::new(Coset::half_odds(first_layer_size.ilog2()));
@@ -201,7 +250,7 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
         let mut layers = Vec::new();
 
         // Circle polynomials can all be folded with the same alpha.
-        let circle_poly_alpha = channel.draw_felt();
+        let circle_poly_alpha = channel.draw_extension_felt::<F>();
 
         while layer_evaluation.len() > config.last_layer_domain_size() {
             let mut layer_lambda_acc = PointEvaluationAccumulator::new(circle_poly_alpha.square());
@@ -224,8 +273,8 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
             let layer_lambda = layer_lambda_acc.finalize();
             let layer = FriLayerProver::new(layer_evaluation, layer_lambda);
             channel.mix_digest(layer.merkle_tree.root());
-            channel.mix_felts(&[layer_lambda]);
-            let folding_alpha = channel.draw_felt();
+            channel.mix_extension_felts(&[layer_lambda]);
+            let folding_alpha = channel.draw_extension_felt();
             let folded_layer_evaluation = B::fold_line(&layer.evaluation, folding_alpha, twiddles);
 
             layer_evaluation = folded_layer_evaluation;
@@ -251,8 +300,8 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
     fn commit_last_layer(
         channel: &mut impl Channel<Digest = H::Hash>,
         config: FriConfig,
-        evaluation: LineEvaluation<B>,
-    ) -> LinePoly {
+        evaluation: LineEvaluation<B, F>,
+    ) -> LinePoly<F> {
         assert_eq!(evaluation.len(), config.last_layer_domain_size());
 
         let evaluation = evaluation.to_cpu();
@@ -260,10 +309,10 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
 
         let last_layer_degree_bound = 1 << config.log_last_layer_degree_bound;
         let zeros = coeffs.split_off(last_layer_degree_bound);
-        assert!(zeros.iter().all(SecureField::is_zero), "invalid degree");
+        assert!(zeros.iter().all(F::is_zero), "invalid degree");
 
         let last_layer_poly = LinePoly::from_ordered_coefficients(coeffs);
-        channel.mix_felts(&last_layer_poly);
+        channel.mix_extension_felts(&last_layer_poly);
 
         last_layer_poly
     }
@@ -274,7 +323,7 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
     pub fn decommit(
         self,
         channel: &mut impl Channel<Digest = H::Hash>,
-    ) -> (FriProof<H>, BTreeMap<u32, SparseSubCircleDomain>) {
+    ) -> (FriProof<H, F>, BTreeMap<u32, SparseSubCircleDomain>) {
         let max_column_log_size = self.column_log_sizes[0];
         let queries = Queries::generate(channel, max_column_log_size, self.config.n_queries);
         let positions = get_opening_positions(&queries, &self.column_log_sizes);
@@ -285,7 +334,7 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
     /// # Panics
     ///
     /// Panics if the queries were sampled on the wrong domain size.
-    fn decommit_on_queries(self, queries: &Queries) -> FriProof<H> {
+    fn decommit_on_queries(self, queries: &Queries) -> FriProof<H, F> {
         let max_column_log_size = self.column_log_sizes[0];
         assert_eq!(queries.log_domain_size, max_column_log_size);
         let first_layer_queries = queries.fold(CIRCLE_TO_LINE_FOLD_STEP);
@@ -308,23 +357,23 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriProver<B, H> {
     }
 }
 
-pub struct FriVerifier<H: MerkleHasher> {
+pub struct FriVerifier<H: MerkleHasher, F: SecureExtensionField = SecureField> {
     config: FriConfig,
     /// Alpha used to fold all circle polynomials to univariate polynomials.
-    circle_poly_alpha: SecureField,
+    circle_poly_alpha: F,
     /// Domain size queries should be sampled from.
     expected_query_log_domain_size: u32,
     /// The list of degree bounds of all committed circle polynomials.
     column_bounds: Vec<CirclePolyDegreeBound>,
-    inner_layers: Vec<FriLayerVerifier<H>>,
+    inner_layers: Vec<FriLayerVerifier<H, F>>,
     last_layer_domain: LineDomain,
-    last_layer_poly: LinePoly,
+    last_layer_poly: LinePoly<F>,
     /// The queries used for decommitment. Initialized when calling
     /// [`FriVerifier::column_opening_positions`].
     queries: Option<Queries>,
 }
 
-impl<H: MerkleHasher> FriVerifier<H> {
+impl<H: MerkleHasher, F: SecureExtensionField> FriVerifier<H, F> {
     /// Verifies the commitment stage of FRI.
     ///
     /// `column_bounds` should be the committed circle polynomial degree bounds in descending order.
@@ -344,7 +393,7 @@ impl<H: MerkleHasher> FriVerifier<H> {
     pub fn commit(
         channel: &mut impl Channel<Digest = H::Hash>,
         config: FriConfig,
-        proof: FriProof<H>,
+        proof: FriProof<H, F>,
         column_bounds: Vec<CirclePolyDegreeBound>,
     ) -> Result<Self, FriVerificationError> {
         assert!(column_bounds.is_sorted_by_key(|b| Reverse(*b)));
@@ -354,7 +403,7 @@ impl<H: MerkleHasher> FriVerifier<H> {
             max_column_bound.log_degree_bound + config.log_blowup_factor;
 
         // Circle polynomials can all be folded with the same alpha.
-        let circle_poly_alpha = channel.draw_felt();
+        let circle_poly_alpha = channel.draw_extension_felt();
 
         let mut inner_layers = Vec::new();
         let mut layer_bound = max_column_bound.fold_to_line();
@@ -367,9 +416,9 @@ impl<H: MerkleHasher> FriVerifier<H> {
 
             // The merkle verification, combined with the decomposition being unique, asserts the
             // decomposition correctness.
-            channel.mix_felts(&[proof.decomposition_coeff]);
+            channel.mix_extension_felts(&[proof.decomposition_coeff]);
 
-            let folding_alpha = channel.draw_felt();
+            let folding_alpha = channel.draw_extension_felt();
 
             inner_layers.push(FriLayerVerifier {
                 degree_bound: layer_bound,
@@ -396,7 +445,7 @@ impl<H: MerkleHasher> FriVerifier<H> {
             return Err(FriVerificationError::LastLayerDegreeInvalid);
         }
 
-        channel.mix_felts(&last_layer_poly);
+        channel.mix_extension_felts(&last_layer_poly);
 
         Ok(Self {
             config,
@@ -423,7 +472,7 @@ impl<H: MerkleHasher> FriVerifier<H> {
     // TODO(andrew): Finish docs.
     pub fn decommit(
         mut self,
-        decommitted_values: Vec<SparseCircleEvaluation>,
+        decommitted_values: Vec<SparseCircleEvaluation<F>>,
     ) -> Result<(), FriVerificationError> {
         let queries = self.queries.take().expect("queries not sampled");
         self.decommit_on_queries(&queries, decommitted_values)
@@ -432,7 +481,7 @@ impl<H: MerkleHasher> FriVerifier<H> {
     fn decommit_on_queries(
         self,
         queries: &Queries,
-        decommitted_values: Vec<SparseCircleEvaluation>,
+        decommitted_values: Vec<SparseCircleEvaluation<F>>,
     ) -> Result<(), FriVerificationError> {
         assert_eq!(queries.log_domain_size, self.expected_query_log_domain_size);
         assert_eq!(decommitted_values.len(), self.column_bounds.len());
@@ -449,15 +498,15 @@ impl<H: MerkleHasher> FriVerifier<H> {
     fn decommit_inner_layers(
         &self,
         queries: &Queries,
-        decommitted_values: Vec<SparseCircleEvaluation>,
-    ) -> Result<(Queries, Vec<SecureField>), FriVerificationError> {
+        decommitted_values: Vec<SparseCircleEvaluation<F>>,
+    ) -> Result<(Queries, Vec<F>), FriVerificationError> {
         let circle_poly_alpha = self.circle_poly_alpha;
         let circle_poly_alpha_sq = circle_poly_alpha * circle_poly_alpha;
 
         let mut decommitted_values = decommitted_values.into_iter();
         let mut column_bounds = self.column_bounds.iter().copied().peekable();
         let mut layer_queries = queries.fold(CIRCLE_TO_LINE_FOLD_STEP);
-        let mut layer_query_evals = vec![SecureField::zero(); layer_queries.len()];
+        let mut layer_query_evals = vec![F::zero(); layer_queries.len()];
 
         for layer in self.inner_layers.iter() {
             if column_bounds
@@ -508,8 +557,8 @@ impl<H: MerkleHasher> FriVerifier<H> {
     // TODO(Ohad): generalize this.
     fn project_to_fft_space(
         layer_queries: &Queries,
-        evals: &mut SparseCircleEvaluation,
-        lambda: SecureField,
+        evals: &mut SparseCircleEvaluation<F>,
+        lambda: F,
     ) {
         let domain_size = 1 << layer_queries.log_domain_size;
         layer_queries
@@ -527,7 +576,7 @@ impl<H: MerkleHasher> FriVerifier<H> {
     fn decommit_last_layer(
         self,
         queries: Queries,
-        query_evals: Vec<SecureField>,
+        query_evals: Vec<F>,
     ) -> Result<(), FriVerificationError> {
         let Self {
             last_layer_domain: domain,
@@ -670,10 +719,11 @@ impl LinePolyDegreeBound {
 }
 
 /// A FRI proof.
-#[derive(Debug)]
-pub struct FriProof<H: MerkleHasher> {
-    pub inner_layers: Vec<FriLayerProof<H>>,
-    pub last_layer_poly: LinePoly,
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FriProof<H: MerkleHasher, F: SecureExtensionField = SecureField> {
+    pub inner_layers: Vec<FriLayerProof<H, F>>,
+    pub last_layer_poly: LinePoly<F>,
 }
 
 /// Number of folds for univariate polynomials.
@@ -686,25 +736,26 @@ pub const CIRCLE_TO_LINE_FOLD_STEP: u32 = 1;
 /// Stores a subset of evaluations in a fri layer with their corresponding merkle decommitments.
 ///
 /// The subset corresponds to the set of evaluations needed by a FRI verifier.
-#[derive(Debug)]
-pub struct FriLayerProof<H: MerkleHasher> {
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FriLayerProof<H: MerkleHasher, F: SecureExtensionField = SecureField> {
     /// The subset stored corresponds to the set of evaluations the verifier doesn't have but needs
     /// to fold and verify the merkle decommitment.
-    pub evals_subset: Vec<SecureField>,
+    pub evals_subset: Vec<F>,
     pub decommitment: MerkleDecommitment<H>,
-    pub decomposition_coeff: SecureField,
+    pub decomposition_coeff: F,
     pub commitment: H::Hash,
 }
 
-struct FriLayerVerifier<H: MerkleHasher> {
+struct FriLayerVerifier<H: MerkleHasher, F: SecureExtensionField> {
     degree_bound: LinePolyDegreeBound,
     domain: LineDomain,
-    folding_alpha: SecureField,
+    folding_alpha: F,
     layer_index: usize,
-    proof: FriLayerProof<H>,
+    proof: FriLayerProof<H, F>,
 }
 
-impl<H: MerkleHasher> FriLayerVerifier<H> {
+impl<H: MerkleHasher, F: SecureExtensionField> FriLayerVerifier<H, F> {
     /// Verifies the layer's merkle decommitment and returns the the folded queries and query evals.
     ///
     /// # Errors
@@ -719,8 +770,8 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
     fn verify_and_fold(
         &self,
         queries: Queries,
-        evals_at_queries: Vec<SecureField>,
-    ) -> Result<(Queries, Vec<SecureField>), FriVerificationError> {
+        evals_at_queries: Vec<F>,
+    ) -> Result<(Queries, Vec<F>), FriVerificationError> {
         let decommitment = self.proof.decommitment.clone();
         let commitment = self.proof.commitment;
 
@@ -728,11 +779,12 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
         let sparse_evaluation = self.extract_evaluation(&queries, &evals_at_queries)?;
 
         // TODO: When leaf values are removed from the decommitment, also remove this block.
-        let actual_decommitment_evals: SecureColumn<CpuBackend> = sparse_evaluation
-            .subline_evals
-            .iter()
-            .flat_map(|e| e.values.into_iter())
-            .collect();
+        let actual_decommitment_evals = F::Column::<CpuBackend>::from_values(
+            sparse_evaluation
+                .subline_evals
+                .iter()
+                .flat_map(|e| e.values.to_vec()),
+        );
 
         let folded_queries = queries.fold(FOLD_STEP);
 
@@ -748,7 +800,7 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
 
         let merkle_verifier = MerkleVerifier::new(
             commitment,
-            vec![self.domain.log_size(); SECURE_EXTENSION_DEGREE],
+            vec![self.domain.log_size(); F::EXTENSION_DEGREE],
         );
         // TODO(spapini): Propagate error.
         merkle_verifier
@@ -756,7 +808,7 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
                 [(self.domain.log_size(), decommitment_positions)]
                     .into_iter()
                     .collect(),
-                actual_decommitment_evals.columns.to_vec(),
+                actual_decommitment_evals.columns().to_vec(),
                 decommitment,
             )
             .map_err(|e| FriVerificationError::InnerLayerCommitmentInvalid {
@@ -781,8 +833,8 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
     fn extract_evaluation(
         &self,
         queries: &Queries,
-        evals_at_queries: &[SecureField],
-    ) -> Result<SparseLineEvaluation, FriVerificationError> {
+        evals_at_queries: &[F],
+    ) -> Result<SparseLineEvaluation<F>, FriVerificationError> {
         // Evals provided by the verifier.
         let mut evals_at_queries = evals_at_queries.iter().copied();
 
@@ -821,7 +873,7 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
 
             all_subline_evals.push(LineEvaluation::new(
                 subline_domain,
-                subline_evals.into_iter().collect(),
+                F::Column::from_values(subline_evals),
             ));
         }
 
@@ -841,17 +893,19 @@ impl<H: MerkleHasher> FriLayerVerifier<H> {
 /// The polynomial evaluations are viewed as evaluation of a polynomial on multiple distinct cosets
 /// of size two. Each leaf of the merkle tree commits to a single coset evaluation.
 // TODO(andrew): Support different step sizes.
-struct FriLayerProver<B: FriOps + MerkleOps<H>, H: MerkleHasher> {
-    evaluation: LineEvaluation<B>,
-    decomposition_coeff: SecureField,
+struct FriLayerProver<B: FriOps<F> + MerkleOps<H>, H: MerkleHasher, F: SecureExtensionField> {
+    evaluation: LineEvaluation<B, F>,
+    decomposition_coeff: F,
     merkle_tree: MerkleProver<B, H>,
 }
 
-impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriLayerProver<B, H> {
-    fn new(evaluation: LineEvaluation<B>, decomposition_coeff: SecureField) -> Self {
+impl<B: FriOps<F> + MerkleOps<H>, H: MerkleHasher, F: SecureExtensionField>
+    FriLayerProver<B, H, F>
+{
+    fn new(evaluation: LineEvaluation<B, F>, decomposition_coeff: F) -> Self {
         // TODO(spapini): Commit on slice.
         // TODO(spapini): Merkle tree in backend.
-        let merkle_tree = MerkleProver::commit(evaluation.values.columns.iter().collect_vec());
+        let merkle_tree = MerkleProver::commit(evaluation.values.columns().iter().collect_vec());
         #[allow(unreachable_code)]
         FriLayerProver {
             evaluation,
@@ -861,7 +915,7 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriLayerProver<B, H> {
     }
 
     /// Generates a decommitment of the subline evaluations at the specified positions.
-    fn decommit(self, queries: &Queries) -> FriLayerProof<H> {
+    fn decommit(self, queries: &Queries) -> FriLayerProof<H, F> {
         let mut decommit_positions = Vec::new();
         let mut evals_subset = Vec::new();
 
@@ -893,7 +947,7 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriLayerProver<B, H> {
             [(self.evaluation.len().ilog2(), decommit_positions)]
                 .into_iter()
                 .collect(),
-            self.evaluation.values.columns.iter().collect_vec(),
+            self.evaluation.values.columns().iter().collect_vec(),
         );
         let decomposition_coeff = self.decomposition_coeff;
 
@@ -908,23 +962,21 @@ impl<B: FriOps + MerkleOps<H>, H: MerkleHasher> FriLayerProver<B, H> {
 
 /// Holds a foldable subset of circle polynomial evaluations.
 #[derive(Debug, Clone)]
-pub struct SparseCircleEvaluation {
-    subcircle_evals: Vec<CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>>,
+pub struct SparseCircleEvaluation<F: SecureExtensionField = SecureField> {
+    subcircle_evals: Vec<CircleEvaluation<CpuBackend, F, BitReversedOrder>>,
 }
 
-impl SparseCircleEvaluation {
+impl<F: SecureExtensionField> SparseCircleEvaluation<F> {
     /// # Panics
     ///
     /// Panics if the evaluation domain sizes don't equal the folding factor.
-    pub fn new(
-        subcircle_evals: Vec<CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>>,
-    ) -> Self {
+    pub fn new(subcircle_evals: Vec<CircleEvaluation<CpuBackend, F, BitReversedOrder>>) -> Self {
         let folding_factor = 1 << CIRCLE_TO_LINE_FOLD_STEP;
         assert!(subcircle_evals.iter().all(|e| e.len() == folding_factor));
         Self { subcircle_evals }
     }
 
-    fn fold(self, alpha: SecureField) -> Vec<SecureField> {
+    fn fold(self, alpha: F) -> Vec<F> {
         self.subcircle_evals
             .into_iter()
             .map(|e| {
@@ -934,7 +986,7 @@ impl SparseCircleEvaluation {
                     &mut buffer,
                     &SecureEvaluation {
                         domain: e.domain,
-                        values: e.values.into_iter().collect(),
+                        values: F::Column::from_values(e.values),
                     },
                     alpha,
                 );
@@ -944,7 +996,7 @@ impl SparseCircleEvaluation {
     }
 
     /// Computes `self = self * alpha + rhs`.
-    fn accumulate(&mut self, rhs: Self, alpha: SecureField) {
+    fn accumulate(&mut self, rhs: Self, alpha: F) {
         assert_eq!(self.subcircle_evals.len(), rhs.subcircle_evals.len());
         for (lhs, rhs) in self.subcircle_evals.iter_mut().zip(rhs.subcircle_evals) {
             assert_eq!(lhs.len(), rhs.len());
@@ -955,34 +1007,33 @@ impl SparseCircleEvaluation {
     }
 }
 
-impl<'a> IntoIterator for &'a mut SparseCircleEvaluation {
-    type Item = &'a mut CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>;
-    type IntoIter =
-        std::slice::IterMut<'a, CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>>;
+impl<'a, F: SecureExtensionField> IntoIterator for &'a mut SparseCircleEvaluation<F> {
+    type Item = &'a mut CircleEvaluation<CpuBackend, F, BitReversedOrder>;
+    type IntoIter = std::slice::IterMut<'a, CircleEvaluation<CpuBackend, F, BitReversedOrder>>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.subcircle_evals.iter_mut()
     }
 }
 
-/// Holds a small foldable subset of univariate SecureField polynomial evaluations.
+/// Holds a small foldable subset of univariate secure field polynomial evaluations.
 /// Evaluation is held at the CPU backend.
 #[derive(Debug, Clone)]
-struct SparseLineEvaluation {
-    subline_evals: Vec<LineEvaluation<CpuBackend>>,
+struct SparseLineEvaluation<F: SecureExtensionField> {
+    subline_evals: Vec<LineEvaluation<CpuBackend, F>>,
 }
 
-impl SparseLineEvaluation {
+impl<F: SecureExtensionField> SparseLineEvaluation<F> {
     /// # Panics
     ///
     /// Panics if the evaluation domain sizes don't equal the folding factor.
-    fn new(subline_evals: Vec<LineEvaluation<CpuBackend>>) -> Self {
+    fn new(subline_evals: Vec<LineEvaluation<CpuBackend, F>>) -> Self {
         let folding_factor = 1 << FOLD_STEP;
         assert!(subline_evals.iter().all(|e| e.len() == folding_factor));
         Self { subline_evals }
     }
 
-    fn fold(self, alpha: SecureField) -> Vec<SecureField> {
+    fn fold(self, alpha: F) -> Vec<F> {
         self.subline_evals
             .into_iter()
             .map(|e| fold_line(&e, alpha).values.at(0))
@@ -992,18 +1043,17 @@ impl SparseLineEvaluation {
 
 /// Folds a degree `d` polynomial into a degree `d/2` polynomial.
 /// See [`FriOps::fold_line`].
-pub fn fold_line(
-    eval: &LineEvaluation<CpuBackend>,
-    alpha: SecureField,
-) -> LineEvaluation<CpuBackend> {
+pub fn fold_line<F: SecureExtensionField>(
+    eval: &LineEvaluation<CpuBackend, F>,
+    alpha: F,
+) -> LineEvaluation<CpuBackend, F> {
     let n = eval.len();
     assert!(n >= 2, "Evaluation too small");
 
     let domain = eval.domain();
 
-    let folded_values = eval
-        .values
-        .into_iter()
+    let folded_values = (0..n)
+        .map(|i| eval.values.at(i))
         .array_chunks()
         .enumerate()
         .map(|(i, [f_x, f_neg_x])| {
@@ -1013,26 +1063,26 @@ pub fn fold_line(
             let (mut f0, mut f1) = (f_x, f_neg_x);
             ibutterfly(&mut f0, &mut f1, x.inverse());
             f0 + alpha * f1
-        })
-        .collect();
+        });
 
-    LineEvaluation::new(domain.double(), folded_values)
+    LineEvaluation::new(domain.double(), F::Column::from_values(folded_values))
 }
 
 /// Folds and accumulates a degree `d` circle polynomial into a degree `d/2` univariate
 /// polynomial.
 /// See [`FriOps::fold_circle_into_line`].
-pub fn fold_circle_into_line(
-    dst: &mut LineEvaluation<CpuBackend>,
-    src: &SecureEvaluation<CpuBackend>,
-    alpha: SecureField,
+pub fn fold_circle_into_line<F: SecureExtensionField>(
+    dst: &mut LineEvaluation<CpuBackend, F>,
+    src: &SecureEvaluation<CpuBackend, F>,
+    alpha: F,
 ) {
     assert_eq!(src.len() >> CIRCLE_TO_LINE_FOLD_STEP, dst.len());
 
     let domain = src.domain;
     let alpha_sq = alpha * alpha;
 
-    src.into_iter()
+    (0..src.len())
+        .map(|i| src.at(i))
         .array_chunks()
         .enumerate()
         .for_each(|(i, [f_p, f_neg_p])| {
@@ -1057,8 +1107,12 @@ mod tests {
 
     use itertools::Itertools;
     use num_traits::{One, Zero};
+    use serde::de::value::{Error as ValueError, MapDeserializer};
+    use serde::Deserialize;
 
-    use super::{get_opening_positions, FriVerificationError, SparseCircleEvaluation};
+    use super::{
+        get_opening_positions, FriVerificationError, InvalidFriConfig, SparseCircleEvaluation,
+    };
     use crate::core::backend::cpu::{CpuCircleEvaluation, CpuCirclePoly};
     use crate::core::backend::{Col, Column, ColumnOps, CpuBackend};
     use crate::core::circle::{CirclePointIndex, Coset};
@@ -1084,6 +1138,33 @@ mod tests {
 
     type FriProver = super::FriProver<CpuBackend, Blake2sMerkleHasher>;
 
+    fn deserialize_config(
+        log_blowup_factor: u32,
+        log_last_layer_degree_bound: u32,
+    ) -> Result<FriConfig, ValueError> {
+        FriConfig::deserialize(MapDeserializer::new(
+            [
+                ("log_blowup_factor", log_blowup_factor),
+                ("log_last_layer_degree_bound", log_last_layer_degree_bound),
+                ("n_queries", 3),
+            ]
+            .into_iter(),
+        ))
+    }
+
+    #[test]
+    fn deserializing_config_checks_parameters() {
+        assert_eq!(deserialize_config(1, 2), Ok(FriConfig::new(2, 1, 3)));
+        assert_eq!(
+            deserialize_config(0, 2).unwrap_err().to_string(),
+            InvalidFriConfig::LogBlowupFactor(0).to_string()
+        );
+        assert_eq!(
+            deserialize_config(1, 11).unwrap_err().to_string(),
+            InvalidFriConfig::LogLastLayerDegreeBound(11).to_string()
+        );
+    }
+
     #[test]
     fn fold_line_works() {
         const DEGREE: usize = 8;
@@ -1105,7 +1186,7 @@ mod tests {
             .map(|p| poly.eval_at_point(p.into()))
             .collect();
         CpuBackend::bit_reverse_column(&mut values);
-        let evals = LineEvaluation::new(domain, values.into_iter().collect());
+        let evals = LineEvaluation::<CpuBackend>::new(domain, values.into_iter().collect());
 
         let drp_evals = fold_line(&evals, alpha);
         let mut drp_evals = drp_evals.values.into_iter().collect_vec();
@@ -1156,7 +1237,7 @@ mod tests {
     fn committing_evaluation_from_invalid_domain_fails() {
         let invalid_domain = CircleDomain::new(Coset::new(CirclePointIndex::generator(), 3));
         assert!(!invalid_domain.is_canonic(), "must be an invalid domain");
-        let evaluation = SecureEvaluation {
+        let evaluation = SecureEvaluation::<CpuBackend> {
             domain: invalid_domain,
             values: vec![SecureField::one(); 1 << 4].into_iter().collect(),
         };
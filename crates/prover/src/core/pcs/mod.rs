@@ -6,11 +6,40 @@
 //! the existence of such polynomials, and are ok with having a small decoding list.
 //! Note: Opened points cannot come from the commitment domain.
 
+mod out_of_core;
 mod prover;
 pub mod quotients;
 mod utils;
 mod verifier;
 
-pub use self::prover::{CommitmentSchemeProof, CommitmentSchemeProver, CommitmentTreeProver};
+use serde::{Deserialize, Serialize};
+
+pub use self::out_of_core::{DiskColumn, OutOfCoreTreeProver};
+pub use self::prover::{
+    prove_aggregated_values, CommitmentSchemeOpening, CommitmentSchemeProof,
+    CommitmentSchemeProver, CommitmentTreeProver, TreeProver,
+};
 pub use self::utils::TreeVec;
-pub use self::verifier::CommitmentSchemeVerifier;
+pub use self::verifier::{verify_aggregated_values, CommitmentSchemeVerifier};
+use super::fri::FriConfig;
+use super::prover::{
+    LOG_BLOWUP_FACTOR, LOG_LAST_LAYER_DEGREE_BOUND, N_QUERIES, PROOF_OF_WORK_BITS,
+};
+
+/// Security parameters of the commitment scheme.
+/// The prover and the verifier must use the same configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcsConfig {
+    /// Proof of work difficulty, ground before the FRI queries are drawn.
+    pub pow_bits: u32,
+    pub fri_config: FriConfig,
+}
+
+impl Default for PcsConfig {
+    fn default() -> Self {
+        Self {
+            pow_bits: PROOF_OF_WORK_BITS,
+            fri_config: FriConfig::new(LOG_LAST_LAYER_DEGREE_BOUND, LOG_BLOWUP_FACTOR, N_QUERIES),
+        }
+    }
+}
@@ -0,0 +1,611 @@
+//! Out-of-core commitment trees, for traces whose evaluations do not fit in memory.
+//!
+//! An [OutOfCoreTreeProver] commits to polynomials like a
+//! [CommitmentTreeProver](super::CommitmentTreeProver), but writes the coefficients and the
+//! evaluations of the polynomials and the layers of the Merkle tree to files. The polynomials are
+//! evaluated one at a time, the Merkle layers are hashed chunk by chunk, and proving only rereads
+//! the files chunk by chunk or at the queried rows.
+//! Out-of-core trees are committed by a [CommitmentSchemeProver] of the [CpuBackend], see
+//! [CommitmentSchemeProver::new_out_of_core]. Only the FRI quotients, whose size does not depend on
+//! the number of columns, are held in memory while proving.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::iter::{self, zip};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use bytemuck::Pod;
+use itertools::{Either, Itertools};
+
+use super::quotients::{ColumnSampleBatch, PointSample, QuotientOps};
+use super::{CommitmentSchemeProof, CommitmentSchemeProver, PcsConfig, TreeProver, TreeVec};
+use crate::core::backend::cpu::circle::eval_folding_factors;
+use crate::core::backend::{CpuBackend, ExtensionBackend};
+use crate::core::channel::{Blake2sChannel, Channel};
+use crate::core::circle::CirclePoint;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::SecureExtensionField;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly, SecureEvaluation};
+use crate::core::poly::twiddles::TwiddleTree;
+use crate::core::poly::utils::fold;
+use crate::core::queries::SubCircleDomain;
+use crate::core::utils::PeekableExt;
+use crate::core::vcs::blake2_hash::Blake2sHash;
+use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+use crate::core::vcs::ops::MerkleHasher;
+use crate::core::vcs::prover::{decommit_on_layers, MerkleDecommitment, ReadColumn};
+use crate::core::ColumnVec;
+
+/// The number of values read at a time.
+const CHUNK_SIZE: usize = 1 << 12;
+
+/// A column of values stored in a file. The file is removed when the column is dropped.
+pub struct DiskColumn<T: Pod> {
+    file: Mutex<File>,
+    path: PathBuf,
+    len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T: Pod> DiskColumn<T> {
+    /// Writes `values` to a new file in `dir`.
+    pub fn create(dir: &Path, values: impl IntoIterator<Item = T>) -> io::Result<Self> {
+        Self::try_create(dir, values.into_iter().map(Ok))
+    }
+
+    /// Writes `values` to a new file in `dir`, failing on the first error of `values`.
+    fn try_create(dir: &Path, values: impl IntoIterator<Item = io::Result<T>>) -> io::Result<Self> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let path = dir.join(format!(
+            "stwo-{}-{}.col",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        // Wrap the file first, so that it is removed if writing fails.
+        let mut column = Self {
+            file: Mutex::new(file),
+            path,
+            len: 0,
+            phantom: PhantomData,
+        };
+        let mut writer = BufWriter::new(column.file.get_mut().unwrap());
+        for value in values {
+            writer.write_all(bytemuck::bytes_of(&value?))?;
+            column.len += 1;
+        }
+        writer.flush()?;
+        drop(writer);
+        Ok(column)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads `n` values, starting at index `start`.
+    pub fn read_range(&self, start: usize, n: usize) -> io::Result<Vec<T>> {
+        assert!(start + n <= self.len, "range out of bounds");
+        let mut values = vec![T::zeroed(); n];
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start((start * std::mem::size_of::<T>()) as u64))?;
+        file.read_exact(bytemuck::cast_slice_mut(&mut values))?;
+        Ok(values)
+    }
+
+    /// Returns an iterator over consecutive chunks of `chunk_size` values.
+    fn chunks(&self, chunk_size: usize) -> impl Iterator<Item = io::Result<Vec<T>>> + '_ {
+        (0..self.len)
+            .step_by(chunk_size)
+            .map(move |start| self.read_range(start, chunk_size.min(self.len - start)))
+    }
+}
+
+impl<T: Pod> ReadColumn<T> for DiskColumn<T> {
+    type Error = io::Error;
+
+    fn read_len(&self) -> usize {
+        self.len
+    }
+
+    fn read_at(&self, index: usize) -> io::Result<T> {
+        Ok(self.read_range(index, 1)?[0])
+    }
+}
+
+impl<T: Pod> Drop for DiskColumn<T> {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// A commitment tree whose polynomials, evaluations and Merkle layers are stored in files, and
+/// reread chunk by chunk or at the queried rows.
+pub struct OutOfCoreTreeProver {
+    /// The coefficients of the committed polynomials, as in [CirclePoly].
+    pub polynomials: ColumnVec<DiskColumn<BaseField>>,
+    /// The evaluations of the committed polynomials on their commitment domains, in bit reversed
+    /// order.
+    pub evaluations: ColumnVec<DiskColumn<BaseField>>,
+    /// Layers of the Merkle tree, from the root layer to the largest layer.
+    pub layers: Vec<DiskColumn<Blake2sHash>>,
+    root: Blake2sHash,
+}
+
+impl OutOfCoreTreeProver {
+    /// Commits to `polynomials`, writing the files to `dir`, and mixes the root into `channel`.
+    /// The polynomials are consumed one at a time, so they may be generated lazily.
+    pub fn new(
+        polynomials: impl IntoIterator<Item = CirclePoly<CpuBackend>>,
+        log_blowup_factor: u32,
+        channel: &mut Blake2sChannel,
+        twiddles: &TwiddleTree<CpuBackend>,
+        dir: &Path,
+    ) -> io::Result<Self> {
+        let (polynomials, evaluations): (Vec<_>, Vec<_>) = polynomials
+            .into_iter()
+            .map(|poly| {
+                let coeffs = DiskColumn::create(dir, poly.coeffs.iter().copied())?;
+                let eval = poly.evaluate_with_twiddles(
+                    CanonicCoset::new(poly.log_size() + log_blowup_factor).circle_domain(),
+                    twiddles,
+                );
+                drop(poly);
+                Ok((coeffs, DiskColumn::create(dir, eval.values)?))
+            })
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let layers = commit(&evaluations, dir)?;
+        let root = layers[0].read_at(0)?;
+        channel.mix_digest(root);
+
+        Ok(Self {
+            polynomials,
+            evaluations,
+            layers,
+            root,
+        })
+    }
+
+    pub fn root(&self) -> Blake2sHash {
+        self.root
+    }
+
+    /// Decommits the merkle tree on the given query positions.
+    /// Returns the values at the queried positions and the decommitment.
+    /// The queries are given as a mapping from the log size of the layer size to the queried
+    /// positions on each column of that size.
+    pub fn decommit(
+        &self,
+        queries: BTreeMap<u32, Vec<usize>>,
+    ) -> io::Result<(
+        ColumnVec<Vec<BaseField>>,
+        MerkleDecommitment<Blake2sMerkleHasher>,
+    )> {
+        decommit_on_layers(&self.layers, queries, self.evaluations.iter().collect())
+    }
+}
+
+impl TreeProver<CpuBackend> for OutOfCoreTreeProver {
+    type Error = io::Error;
+
+    fn root(&self) -> Blake2sHash {
+        self.root
+    }
+
+    fn sample_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: &ColumnVec<Vec<CirclePoint<F>>>,
+    ) -> io::Result<ColumnVec<Vec<PointSample<F>>>>
+    where
+        CpuBackend: ExtensionBackend<F>,
+    {
+        zip(&self.polynomials, sampled_points)
+            .map(|(coeffs, points)| {
+                points
+                    .iter()
+                    .map(|&point| {
+                        Ok(PointSample {
+                            point,
+                            value: eval_at_point(coeffs, point)?,
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Computes the quotients as [compute_fri_quotients](super::quotients::compute_fri_quotients)
+    /// does, on the chunks of the commitment domains. An aligned chunk of a bit reversed
+    /// evaluation is the bit reversed evaluation on a [SubCircleDomain].
+    fn compute_fri_quotients<F: SecureExtensionField>(
+        trees: &[&Self],
+        samples: &[Vec<PointSample<F>>],
+        random_coeff: F,
+    ) -> io::Result<Vec<SecureEvaluation<CpuBackend, F>>>
+    where
+        CpuBackend: ExtensionBackend<F>,
+    {
+        let columns = trees.iter().flat_map(|tree| &tree.evaluations);
+        zip(columns, samples)
+            .sorted_by_key(|(c, _)| Reverse(c.len()))
+            .group_by(|(c, _)| c.len().ilog2())
+            .into_iter()
+            .map(|(log_size, tuples)| {
+                let (columns, samples): (Vec<_>, Vec<_>) = tuples.unzip();
+                let commitment_domain = CanonicCoset::new(log_size).circle_domain();
+                let sample_batches = ColumnSampleBatch::new_vec(&samples);
+                let chunk_log_size = CHUNK_SIZE.ilog2().min(log_size);
+                let mut values = F::Column::<CpuBackend>::zeros(0);
+                for coset_index in 0..1 << (log_size - chunk_log_size) {
+                    let domain = SubCircleDomain {
+                        coset_index,
+                        log_size: chunk_log_size,
+                    }
+                    .to_circle_domain(&commitment_domain);
+                    let chunks = columns
+                        .iter()
+                        .map(|column| {
+                            let values =
+                                column.read_range(coset_index * domain.size(), domain.size())?;
+                            Ok(CircleEvaluation::new(domain, values))
+                        })
+                        .collect::<io::Result<Vec<_>>>()?;
+                    let quotients = CpuBackend::accumulate_quotients(
+                        domain,
+                        &chunks.iter().collect_vec(),
+                        random_coeff,
+                        &sample_batches,
+                    );
+                    for (column, chunk) in
+                        zip(values.columns_mut(), quotients.values.into_columns())
+                    {
+                        column.extend(chunk);
+                    }
+                }
+                Ok(SecureEvaluation {
+                    domain: commitment_domain,
+                    values,
+                })
+            })
+            .collect()
+    }
+
+    fn decommit(
+        &self,
+        queries: BTreeMap<u32, Vec<usize>>,
+    ) -> io::Result<(
+        ColumnVec<Vec<BaseField>>,
+        MerkleDecommitment<Blake2sMerkleHasher>,
+    )> {
+        OutOfCoreTreeProver::decommit(self, queries)
+    }
+}
+
+impl CommitmentSchemeProver<CpuBackend, OutOfCoreTreeProver> {
+    /// Returns a commitment scheme whose trees are stored in files, see [OutOfCoreTreeProver].
+    pub fn new_out_of_core(config: PcsConfig) -> Self {
+        Self::empty(config)
+    }
+
+    /// Commits to `polynomials`, writing the files to `dir`. See [OutOfCoreTreeProver::new].
+    pub fn commit(
+        &mut self,
+        polynomials: impl IntoIterator<Item = CirclePoly<CpuBackend>>,
+        channel: &mut Blake2sChannel,
+        twiddles: &TwiddleTree<CpuBackend>,
+        dir: &Path,
+    ) -> io::Result<()> {
+        let tree = OutOfCoreTreeProver::new(
+            polynomials,
+            self.config.fri_config.log_blowup_factor,
+            channel,
+            twiddles,
+            dir,
+        )?;
+        self.trees.push(tree);
+        Ok(())
+    }
+
+    pub fn prove_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+        channel: &mut Blake2sChannel,
+        twiddles: &TwiddleTree<CpuBackend>,
+    ) -> io::Result<CommitmentSchemeProof<F>>
+    where
+        CpuBackend: ExtensionBackend<F>,
+    {
+        let samples = self.try_sample_values(sampled_points, channel)?;
+        self.try_prove_samples(samples, channel, twiddles)
+    }
+}
+
+/// Evaluates the polynomial of coefficients `coeffs` at `point`, folding the coefficients chunk
+/// by chunk. See [PolyOps::eval_at_point](crate::core::poly::circle::PolyOps::eval_at_point).
+fn eval_at_point<F: SecureExtensionField>(
+    coeffs: &DiskColumn<BaseField>,
+    point: CirclePoint<F>,
+) -> io::Result<F> {
+    let log_size = coeffs.len().ilog2();
+    let chunk_log_size = CHUNK_SIZE.ilog2().min(log_size);
+    let folding_factors = eval_folding_factors(point, log_size);
+    // The first factors fold the chunks, indexed by the high bits of the coefficients.
+    let (chunk_factors, inner_factors) =
+        folding_factors.split_at((log_size - chunk_log_size) as usize);
+    let mut value = F::zero();
+    for (i, chunk) in coeffs.chunks(1 << chunk_log_size).enumerate() {
+        let chunk_factor = chunk_factors
+            .iter()
+            .rev()
+            .enumerate()
+            .filter(|(bit, _)| i >> bit & 1 == 1)
+            .fold(F::one(), |acc, (_, &factor)| acc * factor);
+        value += fold(&chunk?, inner_factors) * chunk_factor;
+    }
+    Ok(value)
+}
+
+/// Commits to the columns, as
+/// [MerkleProver::commit](crate::core::vcs::prover::MerkleProver::commit) does, and returns the
+/// layers of the Merkle tree.
+fn commit(
+    columns: &[DiskColumn<BaseField>],
+    dir: &Path,
+) -> io::Result<Vec<DiskColumn<Blake2sHash>>> {
+    assert!(!columns.is_empty());
+
+    let columns = &mut columns
+        .iter()
+        .sorted_by_key(|c| Reverse(c.len()))
+        .peekable();
+    let mut layers: Vec<DiskColumn<Blake2sHash>> = Vec::new();
+
+    let max_log_size = columns.peek().unwrap().len().ilog2();
+    for log_size in (0..=max_log_size).rev() {
+        // Take columns of the current log_size.
+        let layer_columns = columns
+            .peek_take_while(|column| column.len().ilog2() == log_size)
+            .collect_vec();
+
+        layers.push(commit_on_layer(
+            log_size,
+            layers.last(),
+            &layer_columns,
+            dir,
+        )?);
+    }
+    layers.reverse();
+    Ok(layers)
+}
+
+/// Hashes a layer of the Merkle tree, reading the previous layer and the columns chunk by chunk.
+/// See [MerkleOps::commit_on_layer](crate::core::vcs::ops::MerkleOps::commit_on_layer).
+fn commit_on_layer(
+    log_size: u32,
+    prev_layer: Option<&DiskColumn<Blake2sHash>>,
+    columns: &[&DiskColumn<BaseField>],
+    dir: &Path,
+) -> io::Result<DiskColumn<Blake2sHash>> {
+    let layer_size = 1 << log_size;
+    let chunk_size = CHUNK_SIZE.min(layer_size);
+    let mut prev_layer_chunks = prev_layer.map(|layer| layer.chunks(2 * chunk_size));
+    let mut column_chunks = columns
+        .iter()
+        .map(|column| column.chunks(chunk_size))
+        .collect_vec();
+
+    let mut hash_chunk = || -> io::Result<Vec<Blake2sHash>> {
+        let prev_layer_hashes = prev_layer_chunks
+            .as_mut()
+            .map(|chunks| chunks.next().unwrap())
+            .transpose()?;
+        let column_values = column_chunks
+            .iter_mut()
+            .map(|chunks| chunks.next().unwrap())
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok((0..chunk_size)
+            .map(|i| {
+                let children_hashes = prev_layer_hashes
+                    .as_ref()
+                    .map(|hashes| (hashes[2 * i], hashes[2 * i + 1]));
+                let node_values = column_values.iter().map(|values| values[i]).collect_vec();
+                Blake2sMerkleHasher::hash_node(children_hashes, &node_values)
+            })
+            .collect())
+    };
+    let hashes = (0..layer_size / chunk_size).flat_map(|_| match hash_chunk() {
+        Ok(hashes) => Either::Left(hashes.into_iter().map(Ok)),
+        Err(err) => Either::Right(iter::once(Err(err))),
+    });
+    DiskColumn::try_create(dir, hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{eval_at_point, DiskColumn, OutOfCoreTreeProver, CHUNK_SIZE};
+    use crate::core::backend::CpuBackend;
+    use crate::core::channel::Channel;
+    use crate::core::circle::SECURE_FIELD_CIRCLE_GEN;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::pcs::{CommitmentSchemeProver, CommitmentSchemeVerifier, PcsConfig, TreeVec};
+    use crate::core::poly::circle::{CanonicCoset, CirclePoly, PolyOps};
+    use crate::core::test_utils::test_channel;
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::prover::MerkleProver;
+
+    const LOG_BLOWUP_FACTOR: u32 = 1;
+
+    fn random_polys(log_sizes: &[u32]) -> Vec<CirclePoly<CpuBackend>> {
+        let rng = &mut SmallRng::seed_from_u64(0);
+        log_sizes
+            .iter()
+            .map(|&log_size| {
+                CirclePoly::new((0..1 << log_size).map(|_| rng.gen::<BaseField>()).collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_out_of_core_tree_matches_in_memory_tree() {
+        // The largest layer is larger than a chunk.
+        let max_log_size = CHUNK_SIZE.ilog2() + 1;
+        let log_sizes = [max_log_size, 3, max_log_size - 1, 3, 5];
+        let polys = random_polys(&log_sizes);
+        let twiddles = CpuBackend::precompute_twiddles(
+            CanonicCoset::new(max_log_size + LOG_BLOWUP_FACTOR)
+                .circle_domain()
+                .half_coset,
+        );
+        let mut commitment_scheme = CommitmentSchemeProver::<CpuBackend>::new(PcsConfig::default());
+        let in_memory_channel = &mut test_channel();
+        commitment_scheme.commit(polys.clone(), in_memory_channel, &twiddles);
+
+        let out_of_core_channel = &mut test_channel();
+        let tree = OutOfCoreTreeProver::new(
+            polys,
+            LOG_BLOWUP_FACTOR,
+            out_of_core_channel,
+            &twiddles,
+            &std::env::temp_dir(),
+        )
+        .unwrap();
+
+        assert_eq!(tree.root(), commitment_scheme.roots()[0]);
+        assert_eq!(
+            out_of_core_channel.get_digest(),
+            in_memory_channel.get_digest()
+        );
+    }
+
+    #[test]
+    fn test_out_of_core_tree_decommit() {
+        let log_sizes = [7, 5, 7, 4];
+        let polys = random_polys(&log_sizes);
+        let twiddles =
+            CpuBackend::precompute_twiddles(CanonicCoset::new(8).circle_domain().half_coset);
+        let tree = OutOfCoreTreeProver::new(
+            polys,
+            LOG_BLOWUP_FACTOR,
+            &mut test_channel(),
+            &twiddles,
+            &std::env::temp_dir(),
+        )
+        .unwrap();
+        let evaluations = tree
+            .evaluations
+            .iter()
+            .map(|eval| eval.read_range(0, eval.len()).unwrap())
+            .collect_vec();
+        let in_memory_tree =
+            MerkleProver::<CpuBackend, Blake2sMerkleHasher>::commit(evaluations.iter().collect());
+        let queries = BTreeMap::from([(8, vec![0, 17, 18, 200]), (6, vec![3, 40])]);
+
+        let (values, decommitment) = tree.decommit(queries.clone()).unwrap();
+
+        let (expected_values, expected_decommitment) =
+            in_memory_tree.decommit(queries, evaluations.iter().collect());
+        assert_eq!(values, expected_values);
+        assert_eq!(
+            decommitment.hash_witness,
+            expected_decommitment.hash_witness
+        );
+        assert_eq!(
+            decommitment.column_witness,
+            expected_decommitment.column_witness
+        );
+    }
+
+    #[test]
+    fn test_out_of_core_eval_at_point() {
+        let poly = random_polys(&[CHUNK_SIZE.ilog2() + 2]).pop().unwrap();
+        let coeffs = DiskColumn::create(&std::env::temp_dir(), poly.coeffs.clone()).unwrap();
+        let point = SECURE_FIELD_CIRCLE_GEN;
+
+        assert_eq!(
+            eval_at_point(&coeffs, point).unwrap(),
+            poly.eval_at_point(point)
+        );
+    }
+
+    #[test]
+    fn test_out_of_core_commitment_scheme_proof() {
+        let config = PcsConfig::default();
+        let log_blowup_factor = config.fri_config.log_blowup_factor;
+        // The largest commitment domain is larger than a chunk.
+        let max_log_size = CHUNK_SIZE.ilog2();
+        let log_sizes = [max_log_size, 3, 5, max_log_size];
+        let polys = random_polys(&log_sizes);
+        let twiddles = CpuBackend::precompute_twiddles(
+            CanonicCoset::new(max_log_size + log_blowup_factor)
+                .circle_domain()
+                .half_coset,
+        );
+        let point = SECURE_FIELD_CIRCLE_GEN;
+        let sampled_points = TreeVec::new(vec![vec![vec![point, point.double()]; 4]]);
+        let mut in_memory_scheme = CommitmentSchemeProver::<CpuBackend>::new(config);
+        let in_memory_channel = &mut test_channel();
+        in_memory_scheme.commit(polys.clone(), in_memory_channel, &twiddles);
+        in_memory_scheme.prove_values(sampled_points.clone(), in_memory_channel, &twiddles);
+
+        let mut scheme = CommitmentSchemeProver::new_out_of_core(config);
+        let channel = &mut test_channel();
+        scheme
+            .commit(polys, channel, &twiddles, &std::env::temp_dir())
+            .unwrap();
+        let proof = scheme
+            .prove_values(sampled_points.clone(), channel, &twiddles)
+            .unwrap();
+
+        assert_eq!(channel.get_digest(), in_memory_channel.get_digest());
+        let mut verifier = CommitmentSchemeVerifier::new(config);
+        let verifier_channel = &mut test_channel();
+        verifier.commit(scheme.roots()[0], &log_sizes, verifier_channel);
+        verifier
+            .verify_values(sampled_points, proof, verifier_channel)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_out_of_core_tree_removes_files() {
+        let dir = std::env::temp_dir().join(format!("stwo-out-of-core-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let twiddles =
+            CpuBackend::precompute_twiddles(CanonicCoset::new(6).circle_domain().half_coset);
+
+        let tree = OutOfCoreTreeProver::new(
+            random_polys(&[5, 4]),
+            LOG_BLOWUP_FACTOR,
+            &mut test_channel(),
+            &twiddles,
+            &dir,
+        )
+        .unwrap();
+        assert!(std::fs::read_dir(&dir).unwrap().next().is_some());
+        drop(tree);
+
+        assert!(std::fs::read_dir(&dir).unwrap().next().is_none());
+        std::fs::remove_dir(&dir).unwrap();
+    }
+}
@@ -1,25 +1,30 @@
 use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::iter::zip;
+use std::marker::PhantomData;
 
-use itertools::Itertools;
+use itertools::{zip_eq, Itertools};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use tracing::{span, Level};
 
 use super::super::channel::Blake2sChannel;
 use super::super::circle::CirclePoint;
 use super::super::fields::m31::BaseField;
 use super::super::fields::qm31::SecureField;
-use super::super::fri::{FriConfig, FriProof, FriProver};
+use super::super::fri::{FriProof, FriProver};
 use super::super::poly::circle::CanonicCoset;
 use super::super::poly::BitReversedOrder;
 use super::super::proof_of_work::{ProofOfWork, ProofOfWorkProof};
-use super::super::prover::{
-    LOG_BLOWUP_FACTOR, LOG_LAST_LAYER_DEGREE_BOUND, N_QUERIES, PROOF_OF_WORK_BITS,
-};
 use super::super::ColumnVec;
 use super::quotients::{compute_fri_quotients, PointSample};
 use super::utils::TreeVec;
-use crate::core::backend::Backend;
+use super::PcsConfig;
+use crate::core::backend::{Backend, ExtensionBackend};
 use crate::core::channel::Channel;
-use crate::core::poly::circle::{CircleEvaluation, CirclePoly};
+use crate::core::fields::SecureExtensionField;
+use crate::core::poly::circle::{CircleEvaluation, CirclePoly, SecureEvaluation};
 use crate::core::poly::twiddles::TwiddleTree;
 use crate::core::vcs::blake2_hash::Blake2sHash;
 use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
@@ -30,116 +35,308 @@ type MerkleHasher = Blake2sMerkleHasher;
 type ProofChannel = Blake2sChannel;
 
 /// The prover side of a FRI polynomial commitment scheme. See [super].
-pub struct CommitmentSchemeProver<B: Backend + MerkleOps<MerkleHasher>> {
-    pub trees: TreeVec<CommitmentTreeProver<B>>,
-    pub log_blowup_factor: u32,
+/// The committed trees are held in memory by default, or stored in files with
+/// [OutOfCoreTreeProver](super::OutOfCoreTreeProver) trees.
+pub struct CommitmentSchemeProver<B: Backend + MerkleOps<MerkleHasher>, T = CommitmentTreeProver<B>>
+{
+    pub trees: TreeVec<T>,
+    pub config: PcsConfig,
+    phantom: PhantomData<B>,
 }
 
-impl<B: Backend + MerkleOps<MerkleHasher>> CommitmentSchemeProver<B> {
-    pub fn new(log_blowup_factor: u32) -> Self {
+impl<B: Backend + MerkleOps<MerkleHasher>, T: TreeProver<B>> CommitmentSchemeProver<B, T> {
+    pub(super) fn empty(config: PcsConfig) -> Self {
         CommitmentSchemeProver {
             trees: TreeVec::default(),
-            log_blowup_factor,
+            config,
+            phantom: PhantomData,
         }
     }
 
+    pub fn roots(&self) -> TreeVec<Blake2sHash> {
+        self.trees.as_ref().map(|tree| tree.root())
+    }
+
+    /// Evaluates the committed polynomials on the sampled points, and mixes the values into the
+    /// channel.
+    #[allow(clippy::type_complexity)]
+    pub(super) fn try_sample_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+        channel: &mut ProofChannel,
+    ) -> Result<TreeVec<ColumnVec<Vec<PointSample<F>>>>, T::Error>
+    where
+        B: ExtensionBackend<F>,
+    {
+        let _span = span!(Level::INFO, "Evaluate columns out of domain").entered();
+        let samples = TreeVec::new(
+            self.trees
+                .iter()
+                .zip_eq(sampled_points.iter())
+                .map(|(tree, points)| tree.sample_values(points))
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+        let sampled_values = samples
+            .as_cols_ref()
+            .map_cols(|x| x.iter().map(|o| o.value).collect());
+        channel.mix_extension_felts(&sampled_values.flatten_cols());
+        Ok(samples)
+    }
+
+    /// Proves values sampled by [Self::try_sample_values].
+    pub(super) fn try_prove_samples<F: SecureExtensionField>(
+        &self,
+        samples: TreeVec<ColumnVec<Vec<PointSample<F>>>>,
+        channel: &mut ProofChannel,
+        twiddles: &TwiddleTree<B>,
+    ) -> Result<CommitmentSchemeProof<F>, T::Error>
+    where
+        B: ExtensionBackend<F>,
+    {
+        let (mut openings, proof_of_work, fri_proof) =
+            try_prove_aggregated_values(vec![(self, samples)], channel, twiddles)?;
+        let opening = openings.pop().unwrap();
+        Ok(CommitmentSchemeProof {
+            sampled_values: opening.sampled_values,
+            decommitments: opening.decommitments,
+            queried_values: opening.queried_values,
+            proof_of_work,
+            fri_proof,
+        })
+    }
+}
+
+impl<B: Backend + MerkleOps<MerkleHasher>> CommitmentSchemeProver<B> {
+    pub fn new(config: PcsConfig) -> Self {
+        Self::empty(config)
+    }
+
     pub fn commit(
         &mut self,
         polynomials: ColumnVec<CirclePoly<B>>,
         channel: &mut ProofChannel,
         twiddles: &TwiddleTree<B>,
     ) {
-        let tree =
-            CommitmentTreeProver::new(polynomials, self.log_blowup_factor, channel, twiddles);
+        let tree = CommitmentTreeProver::new(
+            polynomials,
+            self.config.fri_config.log_blowup_factor,
+            channel,
+            twiddles,
+        );
         self.trees.push(tree);
     }
 
-    pub fn roots(&self) -> TreeVec<Blake2sHash> {
-        self.trees.as_ref().map(|tree| tree.commitment.root())
-    }
-
     pub fn polynomials(&self) -> TreeVec<ColumnVec<&CirclePoly<B>>> {
         self.trees
             .as_ref()
             .map(|tree| tree.polynomials.iter().collect())
     }
 
-    fn evaluations(&self) -> TreeVec<ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>> {
-        self.trees
-            .as_ref()
-            .map(|tree| tree.evaluations.iter().collect())
+    pub fn prove_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+        channel: &mut ProofChannel,
+        twiddles: &TwiddleTree<B>,
+    ) -> CommitmentSchemeProof<F>
+    where
+        B: ExtensionBackend<F>,
+    {
+        let samples = self.sample_values(sampled_points, channel);
+        self.prove_samples(samples, channel, twiddles)
     }
 
-    pub fn prove_values(
+    /// Proves values sampled by [Self::sample_values].
+    pub fn prove_samples<F: SecureExtensionField>(
         &self,
-        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>>,
+        samples: TreeVec<ColumnVec<Vec<PointSample<F>>>>,
         channel: &mut ProofChannel,
         twiddles: &TwiddleTree<B>,
-    ) -> CommitmentSchemeProof {
-        // Evaluate polynomials on open points.
-        let span = span!(Level::INFO, "Evaluate columns out of domain").entered();
-        let samples = self
-            .polynomials()
-            .zip_cols(&sampled_points)
-            .map_cols(|(poly, points)| {
-                points
-                    .iter()
-                    .map(|&point| PointSample {
-                        point,
-                        value: poly.eval_at_point(point),
-                    })
-                    .collect_vec()
-            });
-        span.exit();
-        let sampled_values = samples
-            .as_cols_ref()
-            .map_cols(|x| x.iter().map(|o| o.value).collect());
-        channel.mix_felts(&sampled_values.clone().flatten_cols());
+    ) -> CommitmentSchemeProof<F>
+    where
+        B: ExtensionBackend<F>,
+    {
+        self.try_prove_samples(samples, channel, twiddles)
+            .unwrap_or_else(|e| match e {})
+    }
+
+    /// Evaluates the committed polynomials on the sampled points, and mixes the values into the
+    /// channel.
+    pub fn sample_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+        channel: &mut ProofChannel,
+    ) -> TreeVec<ColumnVec<Vec<PointSample<F>>>>
+    where
+        B: ExtensionBackend<F>,
+    {
+        self.try_sample_values(sampled_points, channel)
+            .unwrap_or_else(|e| match e {})
+    }
+}
+
+/// Proves the sampled values of several commitment schemes with a single FRI proof, on a random
+/// linear combination of the quotients of all of them. Returns an opening per scheme, the proof of
+/// work and the FRI proof.
+/// There must be at least one scheme. All the schemes must have the same configuration, and their
+/// sampled values must already be bound to `channel`. `twiddles` must cover the largest commitment
+/// domain.
+#[allow(clippy::type_complexity)]
+pub fn prove_aggregated_values<
+    B: ExtensionBackend<F> + MerkleOps<MerkleHasher>,
+    F: SecureExtensionField,
+>(
+    schemes: Vec<(
+        &CommitmentSchemeProver<B>,
+        TreeVec<ColumnVec<Vec<PointSample<F>>>>,
+    )>,
+    channel: &mut ProofChannel,
+    twiddles: &TwiddleTree<B>,
+) -> (
+    Vec<CommitmentSchemeOpening<F>>,
+    ProofOfWorkProof,
+    FriProof<MerkleHasher, F>,
+) {
+    try_prove_aggregated_values(schemes, channel, twiddles).unwrap_or_else(|e| match e {})
+}
+
+/// [prove_aggregated_values] for schemes of any [TreeProver], failing if reading the committed
+/// data fails.
+#[allow(clippy::type_complexity)]
+fn try_prove_aggregated_values<
+    B: ExtensionBackend<F> + MerkleOps<MerkleHasher>,
+    T: TreeProver<B>,
+    F: SecureExtensionField,
+>(
+    schemes: Vec<(
+        &CommitmentSchemeProver<B, T>,
+        TreeVec<ColumnVec<Vec<PointSample<F>>>>,
+    )>,
+    channel: &mut ProofChannel,
+    twiddles: &TwiddleTree<B>,
+) -> Result<
+    (
+        Vec<CommitmentSchemeOpening<F>>,
+        ProofOfWorkProof,
+        FriProof<MerkleHasher, F>,
+    ),
+    T::Error,
+> {
+    let (schemes, samples): (Vec<_>, Vec<_>) = schemes.into_iter().unzip();
+    assert!(!schemes.is_empty(), "no commitment schemes to prove");
+    let config = schemes[0].config;
+    assert!(
+        schemes.iter().all(|scheme| scheme.config == config),
+        "aggregated commitment schemes must have the same configuration"
+    );
+    let sampled_values = samples
+        .iter()
+        .map(|samples| {
+            samples
+                .as_cols_ref()
+                .map_cols(|x| x.iter().map(|o| o.value).collect())
+        })
+        .collect_vec();
 
-        // Compute oods quotients for boundary constraints on the sampled points.
-        let columns = self.evaluations().flatten();
-        let quotients = compute_fri_quotients(&columns, &samples.flatten(), channel.draw_felt());
+    // Compute oods quotients for boundary constraints on the sampled points.
+    let trees = schemes
+        .iter()
+        .flat_map(|scheme| scheme.trees.iter())
+        .collect_vec();
+    let samples = samples
+        .into_iter()
+        .flat_map(|samples| samples.flatten())
+        .collect_vec();
+    let quotients = T::compute_fri_quotients(&trees, &samples, channel.draw_extension_felt())?;
 
-        // Run FRI commitment phase on the oods quotients.
-        let fri_config = FriConfig::new(LOG_LAST_LAYER_DEGREE_BOUND, LOG_BLOWUP_FACTOR, N_QUERIES);
-        let fri_prover =
-            FriProver::<B, MerkleHasher>::commit(channel, fri_config, &quotients, twiddles);
+    // Run FRI commitment phase on the oods quotients.
+    let fri_prover =
+        FriProver::<B, MerkleHasher, F>::commit(channel, config.fri_config, &quotients, twiddles);
 
-        // Proof of work.
-        let proof_of_work = ProofOfWork::new(PROOF_OF_WORK_BITS).prove(channel);
+    // Proof of work.
+    let proof_of_work = ProofOfWork::new(config.pow_bits).prove(channel);
 
-        // FRI decommitment phase.
-        let (fri_proof, fri_query_domains) = fri_prover.decommit(channel);
+    // FRI decommitment phase.
+    let (fri_proof, fri_query_domains) = fri_prover.decommit(channel);
 
-        // Decommit the FRI queries on the merkle trees.
-        let decommitment_results = self.trees.as_ref().map(|tree| {
-            let queries = fri_query_domains
+    // Decommit the FRI queries on the merkle trees.
+    let queries: BTreeMap<_, _> = fri_query_domains
+        .iter()
+        .map(|(&log_size, domain)| (log_size, domain.flatten()))
+        .collect();
+    let openings = zip(schemes, sampled_values)
+        .map(|(scheme, sampled_values)| {
+            let (queried_values, decommitments) = scheme
+                .trees
                 .iter()
-                .map(|(&log_size, domain)| (log_size, domain.flatten()))
-                .collect();
-            tree.decommit(queries)
-        });
-
-        let queried_values = decommitment_results.as_ref().map(|(v, _)| v.clone());
-        let decommitments = decommitment_results.map(|(_, d)| d);
-
-        CommitmentSchemeProof {
-            sampled_values,
-            decommitments,
-            queried_values,
-            proof_of_work,
-            fri_proof,
-        }
-    }
+                .map(|tree| tree.decommit(queries.clone()))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .unzip();
+            Ok(CommitmentSchemeOpening {
+                sampled_values,
+                queried_values: TreeVec::new(queried_values),
+                decommitments: TreeVec::new(decommitments),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((openings, proof_of_work, fri_proof))
 }
 
-#[derive(Debug)]
-pub struct CommitmentSchemeProof {
-    pub sampled_values: TreeVec<ColumnVec<Vec<SecureField>>>,
+/// The values sampled and queried from a single commitment scheme, in a proof of several schemes
+/// (see [prove_aggregated_values]).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CommitmentSchemeOpening<F: SecureExtensionField = SecureField> {
+    pub sampled_values: TreeVec<ColumnVec<Vec<F>>>,
+    pub decommitments: TreeVec<MerkleDecommitment<MerkleHasher>>,
+    pub queried_values: TreeVec<ColumnVec<Vec<BaseField>>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CommitmentSchemeProof<F: SecureExtensionField = SecureField> {
+    pub sampled_values: TreeVec<ColumnVec<Vec<F>>>,
     pub decommitments: TreeVec<MerkleDecommitment<MerkleHasher>>,
     pub queried_values: TreeVec<ColumnVec<Vec<BaseField>>>,
     pub proof_of_work: ProofOfWorkProof,
-    pub fri_proof: FriProof<MerkleHasher>,
+    pub fri_proof: FriProof<MerkleHasher, F>,
+}
+
+/// A tree of a [CommitmentSchemeProver], committing to a set of polynomials.
+pub trait TreeProver<B: Backend> {
+    /// The error of reading the committed data.
+    type Error;
+
+    fn root(&self) -> Blake2sHash;
+
+    /// Evaluates the committed polynomials on their sampled points.
+    fn sample_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: &ColumnVec<Vec<CirclePoint<F>>>,
+    ) -> Result<ColumnVec<Vec<PointSample<F>>>, Self::Error>
+    where
+        B: ExtensionBackend<F>;
+
+    /// Computes the quotients of the columns of `trees`, in order, by their samples. See
+    /// [compute_fri_quotients].
+    fn compute_fri_quotients<F: SecureExtensionField>(
+        trees: &[&Self],
+        samples: &[Vec<PointSample<F>>],
+        random_coeff: F,
+    ) -> Result<Vec<SecureEvaluation<B, F>>, Self::Error>
+    where
+        B: ExtensionBackend<F>;
+
+    /// Decommits the merkle tree on the given query positions.
+    /// Returns the values at the queried positions and the decommitment.
+    /// The queries are given as a mapping from the log size of the layer size to the queried
+    /// positions on each column of that size.
+    #[allow(clippy::type_complexity)]
+    fn decommit(
+        &self,
+        queries: BTreeMap<u32, Vec<usize>>,
+    ) -> Result<(ColumnVec<Vec<BaseField>>, MerkleDecommitment<MerkleHasher>), Self::Error>;
 }
 
 /// Prover data for a single commitment tree in a commitment scheme. The commitment scheme allows to
@@ -158,15 +355,20 @@ impl<B: Backend + MerkleOps<MerkleHasher>> CommitmentTreeProver<B> {
         twiddles: &TwiddleTree<B>,
     ) -> Self {
         let span = span!(Level::INFO, "Commitment evaluation").entered();
-        let evaluations = polynomials
-            .iter()
+        #[cfg(not(feature = "parallel"))]
+        let iter = polynomials.iter();
+
+        #[cfg(feature = "parallel")]
+        let iter = polynomials.par_iter();
+
+        let evaluations = iter
             .map(|poly| {
                 poly.evaluate_with_twiddles(
                     CanonicCoset::new(poly.log_size() + log_blowup_factor).circle_domain(),
                     twiddles,
                 )
             })
-            .collect_vec();
+            .collect::<Vec<_>>();
 
         span.exit();
 
@@ -180,20 +382,59 @@ impl<B: Backend + MerkleOps<MerkleHasher>> CommitmentTreeProver<B> {
             commitment: tree,
         }
     }
+}
+
+impl<B: Backend + MerkleOps<MerkleHasher>> TreeProver<B> for CommitmentTreeProver<B> {
+    type Error = Infallible;
+
+    fn root(&self) -> Blake2sHash {
+        self.commitment.root()
+    }
+
+    fn sample_values<F: SecureExtensionField>(
+        &self,
+        sampled_points: &ColumnVec<Vec<CirclePoint<F>>>,
+    ) -> Result<ColumnVec<Vec<PointSample<F>>>, Infallible>
+    where
+        B: ExtensionBackend<F>,
+    {
+        Ok(zip_eq(&self.polynomials, sampled_points)
+            .map(|(poly, points)| {
+                points
+                    .iter()
+                    .map(|&point| PointSample {
+                        point,
+                        value: poly.eval_at_extension_point(point),
+                    })
+                    .collect_vec()
+            })
+            .collect())
+    }
+
+    fn compute_fri_quotients<F: SecureExtensionField>(
+        trees: &[&Self],
+        samples: &[Vec<PointSample<F>>],
+        random_coeff: F,
+    ) -> Result<Vec<SecureEvaluation<B, F>>, Infallible>
+    where
+        B: ExtensionBackend<F>,
+    {
+        let columns = trees
+            .iter()
+            .flat_map(|tree| &tree.evaluations)
+            .collect_vec();
+        Ok(compute_fri_quotients(&columns, samples, random_coeff))
+    }
 
-    /// Decommits the merkle tree on the given query positions.
-    /// Returns the values at the queried positions and the decommitment.
-    /// The queries are given as a mapping from the log size of the layer size to the queried
-    /// positions on each column of that size.
     fn decommit(
         &self,
         queries: BTreeMap<u32, Vec<usize>>,
-    ) -> (ColumnVec<Vec<BaseField>>, MerkleDecommitment<MerkleHasher>) {
+    ) -> Result<(ColumnVec<Vec<BaseField>>, MerkleDecommitment<MerkleHasher>), Infallible> {
         let eval_vec = self
             .evaluations
             .iter()
             .map(|eval| &eval.values)
             .collect_vec();
-        self.commitment.decommit(queries, eval_vec)
+        Ok(self.commitment.decommit(queries, eval_vec))
     }
 }
@@ -9,6 +9,7 @@ use crate::core::backend::cpu::quotients::{accumulate_row_quotients, quotient_co
 use crate::core::circle::CirclePoint;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
+use crate::core::fields::SecureExtensionField;
 use crate::core::fri::SparseCircleEvaluation;
 use crate::core::poly::circle::{
     CanonicCoset, CircleDomain, CircleEvaluation, PolyOps, SecureEvaluation,
@@ -18,7 +19,7 @@ use crate::core::prover::VerificationError;
 use crate::core::queries::SparseSubCircleDomain;
 use crate::core::utils::bit_reverse_index;
 
-pub trait QuotientOps: PolyOps {
+pub trait QuotientOps<F: SecureExtensionField = SecureField>: PolyOps {
     /// Accumulates the quotients of the columns at the given domain.
     /// For a column f(x), and a point sample (p,v), the quotient is
     ///   (f(x) - V0(x))/V1(x)
@@ -28,24 +29,24 @@ pub trait QuotientOps: PolyOps {
     fn accumulate_quotients(
         domain: CircleDomain,
         columns: &[&CircleEvaluation<Self, BaseField, BitReversedOrder>],
-        random_coeff: SecureField,
-        sample_batches: &[ColumnSampleBatch],
-    ) -> SecureEvaluation<Self>;
+        random_coeff: F,
+        sample_batches: &[ColumnSampleBatch<F>],
+    ) -> SecureEvaluation<Self, F>;
 }
 
 /// A batch of column samplings at a point.
-pub struct ColumnSampleBatch {
+pub struct ColumnSampleBatch<F: SecureExtensionField = SecureField> {
     /// The point at which the columns are sampled.
-    pub point: CirclePoint<SecureField>,
+    pub point: CirclePoint<F>,
     /// The sampled column indices and their values at the point.
-    pub columns_and_values: Vec<(usize, SecureField)>,
+    pub columns_and_values: Vec<(usize, F)>,
 }
 
-impl ColumnSampleBatch {
+impl<F: SecureExtensionField> ColumnSampleBatch<F> {
     /// Groups column samples by sampled point.
     /// # Arguments
     /// samples: For each column, a vector of samples.
-    pub fn new_vec(samples: &[&Vec<PointSample>]) -> Vec<Self> {
+    pub fn new_vec(samples: &[&Vec<PointSample<F>>]) -> Vec<Self> {
         // Group samples by point, and create a ColumnSampleBatch for each point.
         // This should keep a stable ordering.
         let mut grouped_samples = BTreeMap::new();
@@ -67,16 +68,16 @@ impl ColumnSampleBatch {
     }
 }
 
-pub struct PointSample {
-    pub point: CirclePoint<SecureField>,
-    pub value: SecureField,
+pub struct PointSample<F: SecureExtensionField = SecureField> {
+    pub point: CirclePoint<F>,
+    pub value: F,
 }
 
-pub fn compute_fri_quotients<B: QuotientOps>(
+pub fn compute_fri_quotients<B: QuotientOps<F>, F: SecureExtensionField>(
     columns: &[&CircleEvaluation<B, BaseField, BitReversedOrder>],
-    samples: &[Vec<PointSample>],
-    random_coeff: SecureField,
-) -> Vec<SecureEvaluation<B>> {
+    samples: &[Vec<PointSample<F>>],
+    random_coeff: F,
+) -> Vec<SecureEvaluation<B, F>> {
     let _span = span!(Level::INFO, "Compute FRI quotients").entered();
     zip(columns, samples)
         .sorted_by_key(|(c, _)| Reverse(c.domain.log_size()))
@@ -92,13 +93,13 @@ pub fn compute_fri_quotients<B: QuotientOps>(
         .collect()
 }
 
-pub fn fri_answers(
+pub fn fri_answers<F: SecureExtensionField>(
     column_log_sizes: Vec<u32>,
-    samples: &[Vec<PointSample>],
-    random_coeff: SecureField,
+    samples: &[Vec<PointSample<F>>],
+    random_coeff: F,
     query_domain_per_log_size: BTreeMap<u32, SparseSubCircleDomain>,
     queried_values_per_column: &[Vec<BaseField>],
-) -> Result<Vec<SparseCircleEvaluation>, VerificationError> {
+) -> Result<Vec<SparseCircleEvaluation<F>>, VerificationError> {
     izip!(column_log_sizes, samples, queried_values_per_column)
         .sorted_by_key(|(log_size, ..)| Reverse(*log_size))
         .group_by(|(log_size, ..)| *log_size)
@@ -117,13 +118,13 @@ pub fn fri_answers(
         .collect()
 }
 
-pub fn fri_answers_for_log_size(
+pub fn fri_answers_for_log_size<F: SecureExtensionField>(
     log_size: u32,
-    samples: &[&Vec<PointSample>],
-    random_coeff: SecureField,
+    samples: &[&Vec<PointSample<F>>],
+    random_coeff: F,
     query_domain: &SparseSubCircleDomain,
     queried_values_per_column: &[&Vec<BaseField>],
-) -> Result<SparseCircleEvaluation, VerificationError> {
+) -> Result<SparseCircleEvaluation<F>, VerificationError> {
     let commitment_domain = CanonicCoset::new(log_size).circle_domain();
     let sample_batches = ColumnSampleBatch::new_vec(samples);
     for queried_values in queried_values_per_column {
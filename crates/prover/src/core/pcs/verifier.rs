@@ -4,15 +4,12 @@ use itertools::Itertools;
 
 use super::super::channel::Blake2sChannel;
 use super::super::circle::CirclePoint;
-use super::super::fields::qm31::SecureField;
-use super::super::fri::{CirclePolyDegreeBound, FriConfig, FriVerifier};
-use super::super::proof_of_work::ProofOfWork;
-use super::super::prover::{
-    LOG_BLOWUP_FACTOR, LOG_LAST_LAYER_DEGREE_BOUND, N_QUERIES, PROOF_OF_WORK_BITS,
-};
+use super::super::fields::SecureExtensionField;
+use super::super::fri::{CirclePolyDegreeBound, FriProof, FriVerifier};
+use super::super::proof_of_work::{ProofOfWork, ProofOfWorkProof};
 use super::quotients::{fri_answers, PointSample};
 use super::utils::TreeVec;
-use super::CommitmentSchemeProof;
+use super::{CommitmentSchemeOpening, CommitmentSchemeProof, PcsConfig};
 use crate::core::channel::Channel;
 use crate::core::prover::VerificationError;
 use crate::core::vcs::blake2_hash::Blake2sHash;
@@ -23,14 +20,17 @@ use crate::core::ColumnVec;
 type ProofChannel = Blake2sChannel;
 
 /// The verifier side of a FRI polynomial commitment scheme. See [super].
-#[derive(Default)]
 pub struct CommitmentSchemeVerifier {
     pub trees: TreeVec<MerkleVerifier<Blake2sMerkleHasher>>,
+    pub config: PcsConfig,
 }
 
 impl CommitmentSchemeVerifier {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(config: PcsConfig) -> Self {
+        Self {
+            trees: TreeVec::default(),
+            config,
+        }
     }
 
     /// A [TreeVec<ColumnVec>] of the log sizes of each column in each commitment tree.
@@ -50,49 +50,99 @@ impl CommitmentSchemeVerifier {
         channel.mix_digest(commitment);
         let extended_log_sizes = log_sizes
             .iter()
-            .map(|&log_size| log_size + LOG_BLOWUP_FACTOR)
+            .map(|&log_size| log_size + self.config.fri_config.log_blowup_factor)
             .collect();
         let verifier = MerkleVerifier::new(commitment, extended_log_sizes);
         self.trees.push(verifier);
     }
 
-    pub fn verify_values(
+    pub fn verify_values<F: SecureExtensionField>(
         &self,
-        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>>,
-        proof: CommitmentSchemeProof,
+        sampled_points: TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+        proof: CommitmentSchemeProof<F>,
         channel: &mut ProofChannel,
     ) -> Result<(), VerificationError> {
-        channel.mix_felts(&proof.sampled_values.clone().flatten_cols());
-        let random_coeff = channel.draw_felt();
-
-        let bounds = self
-            .column_log_sizes()
-            .zip_cols(&sampled_points)
-            .map_cols(|(log_size, sampled_points)| {
-                vec![CirclePolyDegreeBound::new(log_size - LOG_BLOWUP_FACTOR); sampled_points.len()]
-            })
-            .flatten_cols()
-            .into_iter()
-            .sorted()
-            .rev()
-            .dedup()
-            .collect_vec();
-
-        // FRI commitment phase on OODS quotients.
-        let fri_config = FriConfig::new(LOG_LAST_LAYER_DEGREE_BOUND, LOG_BLOWUP_FACTOR, N_QUERIES);
-        let mut fri_verifier = FriVerifier::commit(channel, fri_config, proof.fri_proof, bounds)?;
-
-        // Verify proof of work.
-        ProofOfWork::new(PROOF_OF_WORK_BITS).verify(channel, &proof.proof_of_work)?;
-
-        // Get FRI query domains.
-        let fri_query_domains = fri_verifier.column_query_positions(channel);
+        channel.mix_extension_felts(&proof.sampled_values.clone().flatten_cols());
+        let opening = CommitmentSchemeOpening {
+            sampled_values: proof.sampled_values,
+            decommitments: proof.decommitments,
+            queried_values: proof.queried_values,
+        };
+        verify_aggregated_values(
+            vec![(self, sampled_points, opening)],
+            proof.proof_of_work,
+            proof.fri_proof,
+            channel,
+        )
+    }
+}
 
+/// Verifies a proof generated by [prove_aggregated_values](super::prove_aggregated_values), given
+/// each scheme with its sampled points and opening. The sampled values must already be bound to
+/// `channel`.
+#[allow(clippy::type_complexity)]
+pub fn verify_aggregated_values<F: SecureExtensionField>(
+    schemes: Vec<(
+        &CommitmentSchemeVerifier,
+        TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+        CommitmentSchemeOpening<F>,
+    )>,
+    proof_of_work: ProofOfWorkProof,
+    fri_proof: FriProof<Blake2sMerkleHasher, F>,
+    channel: &mut ProofChannel,
+) -> Result<(), VerificationError> {
+    let Some((first, ..)) = schemes.first() else {
+        return Err(VerificationError::InvalidStructure(
+            "No commitment schemes to verify".to_string(),
+        ));
+    };
+    let config = first.config;
+    if schemes.iter().any(|(scheme, ..)| scheme.config != config) {
+        return Err(VerificationError::InvalidStructure(
+            "Aggregated commitment schemes have different configurations".to_string(),
+        ));
+    }
+    let random_coeff = channel.draw_extension_felt();
+
+    let log_blowup_factor = config.fri_config.log_blowup_factor;
+    let bounds = schemes
+        .iter()
+        .flat_map(|(scheme, sampled_points, _)| {
+            scheme
+                .column_log_sizes()
+                .zip_cols(sampled_points)
+                .map_cols(|(log_size, sampled_points)| {
+                    vec![
+                        CirclePolyDegreeBound::new(log_size - log_blowup_factor);
+                        sampled_points.len()
+                    ]
+                })
+                .flatten_cols()
+        })
+        .sorted()
+        .rev()
+        .dedup()
+        .collect_vec();
+
+    // FRI commitment phase on OODS quotients.
+    let mut fri_verifier = FriVerifier::commit(channel, config.fri_config, fri_proof, bounds)?;
+
+    // Verify proof of work.
+    ProofOfWork::new(config.pow_bits).verify(channel, &proof_of_work)?;
+
+    // Get FRI query domains.
+    let fri_query_domains = fri_verifier.column_query_positions(channel);
+
+    let mut column_log_sizes = vec![];
+    let mut samples = vec![];
+    let mut queried_values = vec![];
+    for (scheme, sampled_points, opening) in schemes {
         // Verify merkle decommitments.
-        self.trees
+        scheme
+            .trees
             .as_ref()
-            .zip_eq(proof.decommitments)
-            .zip_eq(proof.queried_values.clone())
+            .zip_eq(opening.decommitments)
+            .zip_eq(opening.queried_values.clone())
             .map(|((tree, decommitment), queried_values)| {
                 let queries = fri_query_domains
                     .iter()
@@ -104,27 +154,31 @@ impl CommitmentSchemeVerifier {
             .into_iter()
             .collect::<Result<_, _>>()?;
 
-        // Answer FRI queries.
-        let samples = sampled_points
-            .zip_cols(proof.sampled_values)
-            .map_cols(|(sampled_points, sampled_values)| {
-                zip(sampled_points, sampled_values)
-                    .map(|(point, value)| PointSample { point, value })
-                    .collect_vec()
-            })
-            .flatten();
-
         // TODO(spapini): Properly defined column log size and dinstinguish between poly and
         // commitment.
-        let fri_answers = fri_answers(
-            self.column_log_sizes().flatten().into_iter().collect(),
-            &samples,
-            random_coeff,
-            fri_query_domains,
-            &proof.queried_values.flatten(),
-        )?;
-
-        fri_verifier.decommit(fri_answers)?;
-        Ok(())
+        column_log_sizes.extend(scheme.column_log_sizes().flatten());
+        samples.extend(
+            sampled_points
+                .zip_cols(opening.sampled_values)
+                .map_cols(|(sampled_points, sampled_values)| {
+                    zip(sampled_points, sampled_values)
+                        .map(|(point, value)| PointSample { point, value })
+                        .collect_vec()
+                })
+                .flatten(),
+        );
+        queried_values.extend(opening.queried_values.flatten());
     }
+
+    // Answer FRI queries.
+    let fri_answers = fri_answers(
+        column_log_sizes,
+        &samples,
+        random_coeff,
+        fri_query_domains,
+        &queried_values,
+    )?;
+
+    fri_verifier.decommit(fri_answers)?;
+    Ok(())
 }
@@ -155,13 +155,127 @@ impl<'a, F: ExtensionOf<BaseField>> Index<usize> for CosetSubEvaluation<'a, F> {
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
     use crate::core::backend::cpu::CpuCircleEvaluation;
-    use crate::core::circle::Coset;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Column, CpuBackend};
+    use crate::core::circle::{CirclePointIndex, Coset, M31_CIRCLE_LOG_ORDER};
     use crate::core::fields::m31::BaseField;
-    use crate::core::poly::circle::CanonicCoset;
-    use crate::core::poly::NaturalOrder;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::poly::circle::{
+        CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, PolyOps,
+    };
+    use crate::core::poly::{BitReversedOrder, NaturalOrder};
+    use crate::core::utils::bit_reverse_index;
     use crate::m31;
 
+    fn random_coset(rng: &mut SmallRng, log_size: u32) -> Coset {
+        Coset::new(
+            CirclePointIndex(rng.gen_range(0..1 << M31_CIRCLE_LOG_ORDER)),
+            log_size,
+        )
+    }
+
+    fn assert_evaluates_to<B: PolyOps>(
+        poly: &CirclePoly<B>,
+        eval: &CircleEvaluation<B, BaseField, BitReversedOrder>,
+    ) {
+        let log_size = eval.domain.log_size();
+        for i in 0..eval.domain.size() {
+            let point = eval.domain.at(bit_reverse_index(i, log_size));
+            assert_eq!(
+                SecureField::from(eval.values.at(i)),
+                poly.eval_at_point(point.into_ef())
+            );
+        }
+    }
+
+    fn test_evaluate_and_interpolate_on_arbitrary_domains<B: PolyOps>() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for log_size in 1..10 {
+            let shifted_canonic_domain = CanonicCoset::new(log_size)
+                .circle_domain()
+                .shift(random_coset(&mut rng, 0).initial_index);
+            let arbitrary_domain = CircleDomain::new(random_coset(&mut rng, log_size - 1));
+            for domain in [shifted_canonic_domain, arbitrary_domain] {
+                assert!(!domain.is_canonic());
+                let poly_log_size = rng.gen_range(0..=log_size);
+                let poly =
+                    CirclePoly::<B>::new((0..1 << poly_log_size).map(|_| rng.gen()).collect());
+                let point = CanonicCoset::new(20)
+                    .at(rng.gen_range(0..1 << 20))
+                    .into_ef();
+
+                let eval = poly.evaluate(domain);
+                assert_evaluates_to(&poly, &eval);
+                let interpolated = eval.interpolate();
+
+                assert_eq!(interpolated.log_size(), log_size);
+                assert_eq!(interpolated.eval_at_point(point), poly.eval_at_point(point));
+            }
+        }
+    }
+
+    fn test_evaluate_with_twiddles_of_larger_arbitrary_coset<B: PolyOps>() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let root_coset = random_coset(&mut rng, 10);
+        let twiddles = B::precompute_twiddles(root_coset);
+        for n_doublings in 0..5 {
+            let domain = CircleDomain::new(root_coset.repeated_double(n_doublings));
+            let poly = CirclePoly::<B>::new((0..domain.size()).map(|_| rng.gen()).collect());
+
+            let eval = poly.evaluate_with_twiddles(domain, &twiddles);
+            assert_evaluates_to(&poly, &eval);
+            let interpolated = eval.interpolate_with_twiddles(&twiddles);
+
+            assert_eq!(interpolated.coeffs.to_cpu(), poly.coeffs.to_cpu());
+        }
+    }
+
+    fn test_evaluate_on_split_domain<B: PolyOps>() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let domain = CanonicCoset::new(9).circle_domain();
+        let poly = CirclePoly::<B>::new((0..1 << 6).map(|_| rng.gen()).collect());
+
+        let (subdomain, shifts) = domain.split(2);
+
+        for shift in shifts {
+            assert_evaluates_to(&poly, &poly.evaluate(subdomain.shift(shift)));
+        }
+    }
+
+    #[test]
+    fn test_cpu_evaluate_and_interpolate_on_arbitrary_domains() {
+        test_evaluate_and_interpolate_on_arbitrary_domains::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_simd_evaluate_and_interpolate_on_arbitrary_domains() {
+        test_evaluate_and_interpolate_on_arbitrary_domains::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_cpu_evaluate_with_twiddles_of_larger_arbitrary_coset() {
+        test_evaluate_with_twiddles_of_larger_arbitrary_coset::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_simd_evaluate_with_twiddles_of_larger_arbitrary_coset() {
+        test_evaluate_with_twiddles_of_larger_arbitrary_coset::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_cpu_evaluate_on_split_domain() {
+        test_evaluate_on_split_domain::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_simd_evaluate_on_split_domain() {
+        test_evaluate_on_split_domain::<SimdBackend>();
+    }
+
     #[test]
     fn test_interpolate_non_canonic() {
         let domain = CanonicCoset::new(3).circle_domain();
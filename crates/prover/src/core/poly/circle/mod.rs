@@ -8,8 +8,8 @@ mod secure_poly;
 pub use canonic::CanonicCoset;
 pub use domain::{CircleDomain, MAX_CIRCLE_DOMAIN_LOG_SIZE};
 pub use evaluation::{CircleEvaluation, CosetSubEvaluation};
-pub use ops::PolyOps;
-pub use poly::CirclePoly;
+pub use ops::{ExtensionPolyOps, PolyOps};
+pub use poly::{mul_log_size, CirclePoly};
 pub use secure_poly::{SecureCirclePoly, SecureEvaluation};
 
 #[cfg(test)]
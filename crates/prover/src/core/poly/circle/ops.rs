@@ -1,17 +1,20 @@
 use super::{CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly};
-use crate::core::backend::Col;
+use crate::core::backend::cpu::circle::eval_folding_factors;
+use crate::core::backend::{Col, Column};
 use crate::core::circle::{CirclePoint, Coset};
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::om31::OM31;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::FieldOps;
+use crate::core::fields::{FieldOps, SecureExtensionField};
 use crate::core::poly::twiddles::TwiddleTree;
+use crate::core::poly::utils::fold;
 use crate::core::poly::BitReversedOrder;
 
 /// Operations on BaseField polynomials.
 pub trait PolyOps: FieldOps<BaseField> + Sized {
     // TODO(spapini): Use a column instead of this type.
     /// The type for precomputed twiddles.
-    type Twiddles;
+    type Twiddles: Send + Sync;
 
     /// Creates a [CircleEvaluation] from values ordered according to [CanonicCoset].
     /// Used by the [`CircleEvaluation::new_canonical_ordered()`] function.
@@ -22,6 +25,9 @@ pub trait PolyOps: FieldOps<BaseField> + Sized {
 
     /// Computes a minimal [CirclePoly] that evaluates to the same values as this evaluation.
     /// Used by the [`CircleEvaluation::interpolate()`] function.
+    ///
+    /// The domain may be any [CircleDomain] whose half coset is a doubling of the root coset of
+    /// `itwiddles`, canonic or not.
     fn interpolate(
         eval: CircleEvaluation<Self, BaseField, BitReversedOrder>,
         itwiddles: &TwiddleTree<Self>,
@@ -37,12 +43,82 @@ pub trait PolyOps: FieldOps<BaseField> + Sized {
 
     /// Evaluates the polynomial at all points in the domain.
     /// Used by the [`CirclePoly::evaluate()`] function.
+    ///
+    /// The domain may be any [CircleDomain] whose half coset is a doubling of the root coset of
+    /// `twiddles`, canonic or not.
     fn evaluate(
         poly: &CirclePoly<Self>,
         domain: CircleDomain,
         twiddles: &TwiddleTree<Self>,
     ) -> CircleEvaluation<Self, BaseField, BitReversedOrder>;
 
+    /// Adds two polynomials of the same size coefficient-wise.
+    /// Used by the `Add` implementation of [CirclePoly].
+    fn add(a: &CirclePoly<Self>, b: &CirclePoly<Self>) -> CirclePoly<Self>;
+
+    /// Subtracts two polynomials of the same size coefficient-wise.
+    /// Used by the `Sub` implementation of [CirclePoly].
+    fn sub(a: &CirclePoly<Self>, b: &CirclePoly<Self>) -> CirclePoly<Self>;
+
+    /// Multiplies a polynomial by a scalar.
+    /// Used by the `Mul<BaseField>` implementation of [CirclePoly].
+    fn scale(poly: &CirclePoly<Self>, factor: BaseField) -> CirclePoly<Self>;
+
+    /// Multiplies two evaluations on the same domain point-wise.
+    /// Used by the [`CirclePoly::mul()`] function.
+    fn mul_evaluations(
+        a: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        b: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder>;
+
+    /// Divides an evaluation point-wise by the vanishing polynomial of `coset` (see
+    /// [coset_vanishing](crate::core::constraints::coset_vanishing)).
+    /// Used by the [`CirclePoly::div_by_coset_vanishing()`] function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the evaluation domain intersects `coset`.
+    fn div_by_coset_vanishing(
+        eval: &CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        coset: Coset,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder>;
+
     /// Precomputes twiddles for a given coset.
+    /// The twiddles can be used for any [CircleDomain] whose half coset is a doubling of `coset`.
     fn precompute_twiddles(coset: Coset) -> TwiddleTree<Self>;
+
+    /// Extracts the twiddles of `coset` from the twiddles of a larger coset tower, without
+    /// recomputing them.
+    /// Used by the [`TwiddleTree::subtree()`] function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coset` is not a repeated doubling of the root coset of `twiddles`.
+    fn twiddle_subtree(twiddles: &TwiddleTree<Self>, coset: Coset) -> TwiddleTree<Self>;
+}
+
+/// Evaluation of [BaseField] polynomials at points over the secure field `F`.
+pub trait ExtensionPolyOps<F: SecureExtensionField>: PolyOps {
+    /// Evaluates the polynomial at a single point.
+    /// Used by the [`CirclePoly::eval_at_extension_point()`] function.
+    fn eval_at_extension_point(poly: &CirclePoly<Self>, point: CirclePoint<F>) -> F;
+}
+
+impl<B: PolyOps> ExtensionPolyOps<SecureField> for B {
+    fn eval_at_extension_point(
+        poly: &CirclePoly<Self>,
+        point: CirclePoint<SecureField>,
+    ) -> SecureField {
+        B::eval_at_point(poly, point)
+    }
+}
+
+// TODO: Evaluate on the backend instead of copying the coefficients to the CPU.
+impl<B: PolyOps> ExtensionPolyOps<OM31> for B {
+    fn eval_at_extension_point(poly: &CirclePoly<Self>, point: CirclePoint<OM31>) -> OM31 {
+        fold(
+            &poly.coeffs.to_cpu(),
+            &eval_folding_factors(point, poly.log_size()),
+        )
+    }
 }
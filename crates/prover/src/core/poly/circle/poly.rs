@@ -1,9 +1,14 @@
-use super::{CircleDomain, CircleEvaluation, PolyOps};
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Sub};
+
+use num_traits::Zero;
+
+use super::{CanonicCoset, CircleDomain, CircleEvaluation, ExtensionPolyOps, PolyOps};
 use crate::core::backend::{Col, Column};
-use crate::core::circle::CirclePoint;
+use crate::core::circle::{CirclePoint, Coset};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::FieldOps;
+use crate::core::fields::{FieldOps, SecureExtensionField};
 use crate::core::poly::twiddles::TwiddleTree;
 use crate::core::poly::BitReversedOrder;
 
@@ -43,6 +48,14 @@ impl<B: PolyOps> CirclePoly<B> {
         B::eval_at_point(self, point)
     }
 
+    /// Evaluates the polynomial at a single point over the secure field `F`.
+    pub fn eval_at_extension_point<F: SecureExtensionField>(&self, point: CirclePoint<F>) -> F
+    where
+        B: ExtensionPolyOps<F>,
+    {
+        B::eval_at_extension_point(self, point)
+    }
+
     /// Extends the polynomial to a larger degree bound.
     pub fn extend(&self, log_size: u32) -> Self {
         B::extend(self, log_size)
@@ -64,13 +77,100 @@ impl<B: PolyOps> CirclePoly<B> {
     ) -> CircleEvaluation<B, BaseField, BitReversedOrder> {
         B::evaluate(self, domain, twiddles)
     }
+
+    /// Multiplies two polynomials, by evaluating both on a domain large enough to hold the product.
+    /// The log size of the result is [mul_log_size] of the log sizes of the operands.
+    pub fn mul(&self, other: &Self) -> Self {
+        let domain = CanonicCoset::new(mul_log_size(self.log_size, other.log_size)).circle_domain();
+        let twiddles = B::precompute_twiddles(domain.half_coset);
+        let product = B::mul_evaluations(
+            &self.evaluate_with_twiddles(domain, &twiddles),
+            &other.evaluate_with_twiddles(domain, &twiddles),
+        );
+        product.interpolate_with_twiddles(&twiddles)
+    }
+
+    /// Divides the polynomial by the vanishing polynomial of `coset` (see
+    /// [coset_vanishing](crate::core::constraints::coset_vanishing)).
+    ///
+    /// Returns `None` if the division is not exact. The quotient is of log size
+    /// `max(self.log_size(), coset.log_size() + 1)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coset` intersects the canonic circle domain one size larger than the quotient.
+    /// This never happens for cosets of [CanonicCoset]s.
+    pub fn div_by_coset_vanishing(&self, coset: Coset) -> Option<Self> {
+        // The quotient fits in `log_size`, and the remainder of the division would be of degree
+        // less than `2^log_size`. Evaluating on a domain of size `2^(log_size + 1)`
+        // determines both.
+        let log_size = self.log_size.max(coset.log_size() + 1);
+        let domain = CanonicCoset::new(log_size + 1).circle_domain();
+        let twiddles = B::precompute_twiddles(domain.half_coset);
+        let quotient =
+            B::div_by_coset_vanishing(&self.evaluate_with_twiddles(domain, &twiddles), coset)
+                .interpolate_with_twiddles(&twiddles);
+
+        // Values may be in a redundant representation, where P stands for zero.
+        let coeffs = quotient.coeffs.to_cpu();
+        let (coeffs, high_coeffs) = coeffs.split_at(1 << log_size);
+        high_coeffs
+            .iter()
+            .all(|c| BaseField::partial_reduce(c.0).is_zero())
+            .then(|| Self::new(coeffs.iter().copied().collect()))
+    }
+}
+
+/// Returns the log size of the smallest [CirclePoly] that holds any product of polynomials of log
+/// sizes `a` and `b`, and can be interpolated from a [CanonicCoset] (i.e. at least 1).
+///
+/// A polynomial of log size `n > 0` has total degree at most `2^(n-1)`, and a polynomial of log
+/// size `n` holds all polynomials of total degree less than `2^(n-1)`.
+pub fn mul_log_size(a: u32, b: u32) -> u32 {
+    let degree_bound = |log_size: u32| (1 << log_size) >> 1;
+    match degree_bound(a) + degree_bound(b) {
+        0 => 1,
+        degree => (degree as u32).ilog2() + 2,
+    }
+}
+
+impl<B: PolyOps> Add for &CirclePoly<B> {
+    type Output = CirclePoly<B>;
+
+    /// Adds two polynomials, extending the smaller one to the size of the larger one.
+    fn add(self, rhs: Self) -> CirclePoly<B> {
+        match self.log_size.cmp(&rhs.log_size) {
+            Ordering::Less => B::add(&self.extend(rhs.log_size), rhs),
+            Ordering::Equal => B::add(self, rhs),
+            Ordering::Greater => B::add(self, &rhs.extend(self.log_size)),
+        }
+    }
+}
+
+impl<B: PolyOps> Sub for &CirclePoly<B> {
+    type Output = CirclePoly<B>;
+
+    /// Subtracts two polynomials, extending the smaller one to the size of the larger one.
+    fn sub(self, rhs: Self) -> CirclePoly<B> {
+        match self.log_size.cmp(&rhs.log_size) {
+            Ordering::Less => B::sub(&self.extend(rhs.log_size), rhs),
+            Ordering::Equal => B::sub(self, rhs),
+            Ordering::Greater => B::sub(self, &rhs.extend(self.log_size)),
+        }
+    }
+}
+
+impl<B: PolyOps> Mul<BaseField> for &CirclePoly<B> {
+    type Output = CirclePoly<B>;
+
+    fn mul(self, rhs: BaseField) -> CirclePoly<B> {
+        B::scale(self, rhs)
+    }
 }
 
 #[cfg(test)]
 impl crate::core::backend::cpu::CpuCirclePoly {
     pub fn is_in_fft_space(&self, log_fft_size: u32) -> bool {
-        use num_traits::Zero;
-
         let mut coeffs = self.coeffs.clone();
         while coeffs.last() == Some(&BaseField::zero()) {
             coeffs.pop();
@@ -85,8 +185,6 @@ impl crate::core::backend::cpu::CpuCirclePoly {
     /// Fri space is the space of polynomials of total degree n/2.
     /// Highest degree monomials are x^{n/2} and x^{(n/2)-1}y.
     pub fn is_in_fri_space(&self, log_fft_size: u32) -> bool {
-        use num_traits::Zero;
-
         let mut coeffs = self.coeffs.clone();
         while coeffs.last() == Some(&BaseField::zero()) {
             coeffs.pop();
@@ -100,9 +198,107 @@ impl crate::core::backend::cpu::CpuCirclePoly {
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::mul_log_size;
     use crate::core::backend::cpu::CpuCirclePoly;
-    use crate::core::circle::CirclePoint;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Col, CpuBackend};
+    use crate::core::circle::{CirclePoint, Coset, SECURE_FIELD_CIRCLE_ORDER};
+    use crate::core::constraints::coset_vanishing;
     use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly, PolyOps};
+    use crate::core::utils::bit_reverse_index;
+
+    fn random_poly<B: PolyOps>(rng: &mut SmallRng, log_size: u32) -> CirclePoly<B> {
+        CirclePoly::new((0..1 << log_size).map(|_| rng.gen()).collect())
+    }
+
+    fn random_point(rng: &mut SmallRng) -> CirclePoint<SecureField> {
+        CirclePoint::get_point(rng.gen::<u128>() % SECURE_FIELD_CIRCLE_ORDER)
+    }
+
+    /// Returns `poly` multiplied by the vanishing polynomial of `coset`.
+    fn mul_by_coset_vanishing<B: PolyOps>(poly: &CirclePoly<B>, coset: Coset) -> CirclePoly<B> {
+        let log_size = mul_log_size(poly.log_size(), coset.log_size());
+        let domain = CanonicCoset::new(log_size).circle_domain();
+        let vanishing_values: Col<B, BaseField> = (0..domain.size())
+            .map(|i| coset_vanishing(coset, domain.at(bit_reverse_index(i, log_size))))
+            .collect();
+        B::mul_evaluations(
+            &poly.evaluate(domain),
+            &CircleEvaluation::new(domain, vanishing_values),
+        )
+        .interpolate()
+    }
+
+    fn test_arithmetic<B: PolyOps>() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let (a_log_size, b_log_size) = (rng.gen_range(5..9), rng.gen_range(5..9));
+            let a = random_poly::<B>(&mut rng, a_log_size);
+            let b = random_poly::<B>(&mut rng, b_log_size);
+            let factor = rng.gen();
+            let point = random_point(&mut rng);
+            let (a_eval, b_eval) = (a.eval_at_point(point), b.eval_at_point(point));
+
+            assert_eq!((&a + &b).eval_at_point(point), a_eval + b_eval);
+            assert_eq!((&a - &b).eval_at_point(point), a_eval - b_eval);
+            assert_eq!((&a * factor).eval_at_point(point), a_eval * factor);
+            let product = a.mul(&b);
+            assert_eq!(product.log_size(), mul_log_size(a.log_size(), b.log_size()));
+            assert_eq!(product.eval_at_point(point), a_eval * b_eval);
+        }
+    }
+
+    fn test_div_by_coset_vanishing<B: PolyOps>() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let log_size = rng.gen_range(5..9);
+            let canonic_coset = CanonicCoset::new(rng.gen_range(1..log_size));
+            for coset in [canonic_coset.coset(), canonic_coset.half_coset()] {
+                let quotient = random_poly::<B>(&mut rng, log_size);
+                let poly = mul_by_coset_vanishing(&quotient, coset);
+                let point = random_point(&mut rng);
+
+                let res = poly.div_by_coset_vanishing(coset).unwrap();
+
+                assert_eq!(res.eval_at_point(point), quotient.eval_at_point(point));
+                let not_divisible = &poly + &random_poly(&mut rng, log_size);
+                assert!(not_divisible.div_by_coset_vanishing(coset).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_log_size() {
+        assert_eq!(mul_log_size(0, 0), 1);
+        assert_eq!(mul_log_size(0, 3), 4);
+        assert_eq!(mul_log_size(3, 3), 5);
+        assert_eq!(mul_log_size(5, 3), 6);
+    }
+
+    #[test]
+    fn test_cpu_circle_poly_arithmetic() {
+        test_arithmetic::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_simd_circle_poly_arithmetic() {
+        test_arithmetic::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_cpu_circle_poly_div_by_coset_vanishing() {
+        test_div_by_coset_vanishing::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_simd_circle_poly_div_by_coset_vanishing() {
+        test_div_by_coset_vanishing::<SimdBackend>();
+    }
 
     #[test]
     fn test_circle_poly_extend() {
@@ -1,32 +1,47 @@
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
-use super::{CircleDomain, CircleEvaluation, CirclePoly, PolyOps};
+use super::{CircleDomain, CircleEvaluation, CirclePoly, ExtensionPolyOps};
 use crate::core::backend::cpu::CpuCircleEvaluation;
 use crate::core::backend::CpuBackend;
 use crate::core::circle::CirclePoint;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::{SecureColumn, SECURE_EXTENSION_DEGREE};
-use crate::core::fields::FieldOps;
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::{FieldOps, SecureExtensionField};
 use crate::core::poly::BitReversedOrder;
 
-pub struct SecureCirclePoly<B: FieldOps<BaseField>>(pub [CirclePoly<B>; SECURE_EXTENSION_DEGREE]);
+/// A polynomial over the secure field `F`, stored as its `F::EXTENSION_DEGREE` coordinate
+/// polynomials over the base field.
+pub struct SecureCirclePoly<B: FieldOps<BaseField>, F: SecureExtensionField = SecureField> {
+    polys: Vec<CirclePoly<B>>,
+    _field: PhantomData<F>,
+}
+
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> SecureCirclePoly<B, F> {
+    /// Creates a polynomial from its coordinate polynomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not `F::EXTENSION_DEGREE` polynomials.
+    pub fn new(polys: Vec<CirclePoly<B>>) -> Self {
+        assert_eq!(polys.len(), F::EXTENSION_DEGREE);
+        Self {
+            polys,
+            _field: PhantomData,
+        }
+    }
+}
 
-impl<B: PolyOps> SecureCirclePoly<B> {
-    pub fn eval_at_point(&self, point: CirclePoint<SecureField>) -> SecureField {
-        SecureField::from_partial_evals(self.eval_columns_at_point(point))
+impl<B: ExtensionPolyOps<F>, F: SecureExtensionField> SecureCirclePoly<B, F> {
+    pub fn eval_at_point(&self, point: CirclePoint<F>) -> F {
+        F::from_partial_evals(&self.eval_columns_at_point(point))
     }
 
-    pub fn eval_columns_at_point(
-        &self,
-        point: CirclePoint<SecureField>,
-    ) -> [SecureField; SECURE_EXTENSION_DEGREE] {
-        [
-            self[0].eval_at_point(point),
-            self[1].eval_at_point(point),
-            self[2].eval_at_point(point),
-            self[3].eval_at_point(point),
-        ]
+    pub fn eval_columns_at_point(&self, point: CirclePoint<F>) -> Vec<F> {
+        self.iter()
+            .map(|poly| poly.eval_at_extension_point(point))
+            .collect()
     }
 
     pub fn log_size(&self) -> u32 {
@@ -34,47 +49,47 @@ impl<B: PolyOps> SecureCirclePoly<B> {
     }
 }
 
-impl<B: FieldOps<BaseField>> Deref for SecureCirclePoly<B> {
-    type Target = [CirclePoly<B>; SECURE_EXTENSION_DEGREE];
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> Deref for SecureCirclePoly<B, F> {
+    type Target = [CirclePoly<B>];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.polys
     }
 }
 
 #[derive(Clone)]
-pub struct SecureEvaluation<B: FieldOps<BaseField>> {
+pub struct SecureEvaluation<B: FieldOps<BaseField>, F: SecureExtensionField = SecureField> {
     pub domain: CircleDomain,
-    pub values: SecureColumn<B>,
+    pub values: F::Column<B>,
 }
-impl<B: FieldOps<BaseField>> Deref for SecureEvaluation<B> {
-    type Target = SecureColumn<B>;
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> Deref for SecureEvaluation<B, F> {
+    type Target = F::Column<B>;
 
     fn deref(&self) -> &Self::Target {
         &self.values
     }
 }
 
-impl<B: FieldOps<BaseField>> DerefMut for SecureEvaluation<B> {
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> DerefMut for SecureEvaluation<B, F> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.values
     }
 }
 
-impl SecureEvaluation<CpuBackend> {
+impl<F: SecureExtensionField> SecureEvaluation<CpuBackend, F> {
     // TODO(spapini): Remove when we no longer use CircleEvaluation<SecureField>.
-    pub fn to_cpu(self) -> CpuCircleEvaluation<SecureField, BitReversedOrder> {
+    pub fn to_cpu(self) -> CpuCircleEvaluation<F, BitReversedOrder> {
         CpuCircleEvaluation::new(self.domain, self.values.to_vec())
     }
 }
 
-impl From<CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>>
-    for SecureEvaluation<CpuBackend>
+impl<F: SecureExtensionField> From<CircleEvaluation<CpuBackend, F, BitReversedOrder>>
+    for SecureEvaluation<CpuBackend, F>
 {
-    fn from(evaluation: CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>) -> Self {
+    fn from(evaluation: CircleEvaluation<CpuBackend, F, BitReversedOrder>) -> Self {
         Self {
             domain: evaluation.domain,
-            values: evaluation.values.into_iter().collect(),
+            values: F::Column::from_values(evaluation.values),
         }
     }
 }
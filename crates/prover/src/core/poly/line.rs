@@ -3,8 +3,8 @@ use std::fmt::Debug;
 use std::iter::Map;
 use std::ops::{Deref, DerefMut};
 
-use itertools::Itertools;
 use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 
 use super::circle::CircleDomain;
 use super::utils::fold;
@@ -13,8 +13,8 @@ use crate::core::circle::{CirclePoint, Coset, CosetIterator};
 use crate::core::fft::ibutterfly;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SecureColumn;
-use crate::core::fields::{ExtensionOf, FieldExpOps, FieldOps};
+use crate::core::fields::secure_column::ExtensionColumnOps;
+use crate::core::fields::{ExtensionOf, FieldExpOps, FieldOps, SecureExtensionField};
 use crate::core::utils::bit_reverse;
 
 /// Domain comprising of the x-coordinates of points in a [Coset].
@@ -107,30 +107,31 @@ type LineDomainIterator =
     Map<CosetIterator<CirclePoint<BaseField>>, fn(CirclePoint<BaseField>) -> BaseField>;
 
 /// A univariate polynomial defined on a [LineDomain].
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct LinePoly {
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct LinePoly<F: SecureExtensionField = SecureField> {
     /// Coefficients of the polynomial in [line_ifft] algorithm's basis.
     ///
     /// The coefficients are stored in bit-reversed order.
-    coeffs: Vec<SecureField>,
+    coeffs: Vec<F>,
     /// The number of coefficients stored as `log2(len(coeffs))`.
     log_size: u32,
 }
 
-impl LinePoly {
+impl<F: SecureExtensionField> LinePoly<F> {
     /// Creates a new line polynomial from bit reversed coefficients.
     ///
     /// # Panics
     ///
     /// Panics if the number of coefficients is not a power of two.
-    pub fn new(coeffs: Vec<SecureField>) -> Self {
+    pub fn new(coeffs: Vec<F>) -> Self {
         assert!(coeffs.len().is_power_of_two());
         let log_size = coeffs.len().ilog2();
         Self { coeffs, log_size }
     }
 
     /// Evaluates the polynomial at a single point.
-    pub fn eval_at_point(&self, mut x: SecureField) -> SecureField {
+    pub fn eval_at_point(&self, mut x: F) -> F {
         // TODO(Andrew): Allocation here expensive for small polynomials.
         let mut doublings = Vec::new();
         for _ in 0..self.log_size {
@@ -150,7 +151,7 @@ impl LinePoly {
     }
 
     /// Returns the polynomial's coefficients in their natural order.
-    pub fn into_ordered_coefficients(mut self) -> Vec<SecureField> {
+    pub fn into_ordered_coefficients(mut self) -> Vec<F> {
         bit_reverse(&mut self.coeffs);
         self.coeffs
     }
@@ -160,22 +161,22 @@ impl LinePoly {
     /// # Panics
     ///
     /// Panics if the number of coefficients is not a power of two.
-    pub fn from_ordered_coefficients(mut coeffs: Vec<SecureField>) -> Self {
+    pub fn from_ordered_coefficients(mut coeffs: Vec<F>) -> Self {
         bit_reverse(&mut coeffs);
         Self::new(coeffs)
     }
 }
 
-impl Deref for LinePoly {
-    type Target = [SecureField];
+impl<F: SecureExtensionField> Deref for LinePoly<F> {
+    type Target = [F];
 
-    fn deref(&self) -> &[SecureField] {
+    fn deref(&self) -> &[F] {
         &self.coeffs
     }
 }
 
-impl DerefMut for LinePoly {
-    fn deref_mut(&mut self) -> &mut [SecureField] {
+impl<F: SecureExtensionField> DerefMut for LinePoly<F> {
+    fn deref_mut(&mut self) -> &mut [F] {
         &mut self.coeffs
     }
 }
@@ -185,25 +186,25 @@ impl DerefMut for LinePoly {
 // only used by FRI where evaluations are in bit-reversed order.
 // TODO(spapini): Remove pub.
 #[derive(Clone, Debug)]
-pub struct LineEvaluation<B: FieldOps<BaseField>> {
+pub struct LineEvaluation<B: FieldOps<BaseField>, F: SecureExtensionField = SecureField> {
     /// Evaluations of a univariate polynomial on `domain`.
-    pub values: SecureColumn<B>,
+    pub values: F::Column<B>,
     domain: LineDomain,
 }
 
-impl<B: FieldOps<BaseField>> LineEvaluation<B> {
+impl<B: FieldOps<BaseField>, F: SecureExtensionField> LineEvaluation<B, F> {
     /// Creates new [LineEvaluation] from a set of polynomial evaluations over a [LineDomain].
     ///
     /// # Panics
     ///
     /// Panics if the number of evaluations does not match the size of the domain.
-    pub fn new(domain: LineDomain, values: SecureColumn<B>) -> Self {
+    pub fn new(domain: LineDomain, values: F::Column<B>) -> Self {
         assert_eq!(values.len(), domain.size());
         Self { values, domain }
     }
 
     pub fn new_zero(domain: LineDomain) -> Self {
-        Self::new(domain, SecureColumn::zeros(domain.size()))
+        Self::new(domain, F::Column::zeros(domain.size()))
     }
 
     /// Returns the number of evaluations.
@@ -217,15 +218,15 @@ impl<B: FieldOps<BaseField>> LineEvaluation<B> {
     }
 
     /// Clones the values into a new line evaluation in the CPU.
-    pub fn to_cpu(&self) -> LineEvaluation<CpuBackend> {
+    pub fn to_cpu(&self) -> LineEvaluation<CpuBackend, F> {
         LineEvaluation::new(self.domain, self.values.to_cpu())
     }
 }
 
-impl LineEvaluation<CpuBackend> {
+impl<F: SecureExtensionField> LineEvaluation<CpuBackend, F> {
     /// Interpolates the polynomial as evaluations on `domain`.
-    pub fn interpolate(self) -> LinePoly {
-        let mut values = self.values.into_iter().collect_vec();
+    pub fn interpolate(self) -> LinePoly<F> {
+        let mut values = self.values.to_vec();
         CpuBackend::bit_reverse_column(&mut values);
         line_ifft(&mut values, self.domain);
         // Normalize the coefficients.
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use super::circle::PolyOps;
 use crate::core::circle::Coset;
 
@@ -11,3 +14,161 @@ pub struct TwiddleTree<B: PolyOps> {
     pub twiddles: B::Twiddles,
     pub itwiddles: B::Twiddles,
 }
+
+impl<B: PolyOps> TwiddleTree<B> {
+    /// Returns the twiddles of `coset`, a repeated doubling of the root coset, extracted from this
+    /// tree instead of being recomputed.
+    pub fn subtree(&self, coset: Coset) -> Self {
+        B::twiddle_subtree(self, coset)
+    }
+}
+
+/// A thread-safe cache of [TwiddleTree]s, keyed by their root coset.
+/// Allows reusing the twiddles of a domain across proofs, instead of recomputing them in each
+/// proof.
+pub struct TwiddleCache<B: PolyOps> {
+    trees: Mutex<HashMap<Coset, Arc<TwiddleTree<B>>>>,
+}
+
+impl<B: PolyOps> TwiddleCache<B> {
+    pub fn new() -> Self {
+        Self {
+            trees: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns twiddles that cover `coset`, i.e. a tree whose coset tower contains `coset`.
+    ///
+    /// If a cached tree covers `coset`, the smallest such tree is returned. Its root coset may be
+    /// larger than `coset`, which is fine for evaluating and interpolating (see
+    /// [TwiddleTree::subtree] for extracting the exact twiddles). Otherwise, the twiddles are
+    /// precomputed and cached.
+    pub fn get(&self, coset: Coset) -> Arc<TwiddleTree<B>> {
+        if let Some(tree) = self.get_cached(coset) {
+            return tree;
+        }
+        // Precompute without holding the lock, so that other threads can use the cache meanwhile.
+        let tree = Arc::new(B::precompute_twiddles(coset));
+        let mut trees = self.trees.lock().unwrap();
+        trees.entry(coset).or_insert(tree).clone()
+    }
+
+    fn get_cached(&self, coset: Coset) -> Option<Arc<TwiddleTree<B>>> {
+        let trees = self.trees.lock().unwrap();
+        trees
+            .values()
+            .filter(|tree| coset.is_doubling_of(tree.root_coset))
+            .min_by_key(|tree| tree.root_coset.log_size())
+            .cloned()
+    }
+
+    /// Returns the number of cached trees.
+    pub fn len(&self) -> usize {
+        self.trees.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all the cached trees.
+    pub fn clear(&self) {
+        self.trees.lock().unwrap().clear();
+    }
+}
+
+impl<B: PolyOps> Default for TwiddleCache<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::TwiddleCache;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::CpuBackend;
+    use crate::core::circle::{CirclePointIndex, Coset};
+    use crate::core::poly::circle::{CanonicCoset, PolyOps};
+
+    #[test]
+    fn test_twiddle_subtree() {
+        let root_coset = Coset::new(CirclePointIndex(12345), 8);
+        let cpu_twiddles = CpuBackend::precompute_twiddles(root_coset);
+        let simd_twiddles = SimdBackend::precompute_twiddles(root_coset);
+
+        for n_doublings in 0..=root_coset.log_size() {
+            let coset = root_coset.repeated_double(n_doublings);
+
+            let cpu_subtree = cpu_twiddles.subtree(coset);
+            let simd_subtree = simd_twiddles.subtree(coset);
+
+            let cpu_expected = CpuBackend::precompute_twiddles(coset);
+            let simd_expected = SimdBackend::precompute_twiddles(coset);
+            assert_eq!(cpu_subtree.root_coset, coset);
+            assert_eq!(cpu_subtree.twiddles, cpu_expected.twiddles);
+            assert_eq!(cpu_subtree.itwiddles, cpu_expected.itwiddles);
+            assert_eq!(simd_subtree.twiddles, simd_expected.twiddles);
+            assert_eq!(simd_subtree.itwiddles, simd_expected.itwiddles);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_twiddle_subtree_of_unrelated_coset_fails() {
+        let twiddles = CpuBackend::precompute_twiddles(CanonicCoset::new(6).half_coset());
+
+        twiddles.subtree(Coset::new(CirclePointIndex(1), 3));
+    }
+
+    #[test]
+    fn test_twiddle_cache_reuses_covering_tree() {
+        let cache = TwiddleCache::<CpuBackend>::new();
+        let large_coset = CanonicCoset::new(10).half_coset();
+        let small_coset = CanonicCoset::new(6).half_coset();
+
+        let large_tree = cache.get(large_coset);
+        let small_tree = cache.get(small_coset);
+        let large_tree_again = cache.get(large_coset);
+
+        assert!(Arc::ptr_eq(&large_tree, &small_tree));
+        assert!(Arc::ptr_eq(&large_tree, &large_tree_again));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_twiddle_cache_prefers_smallest_covering_tree() {
+        let cache = TwiddleCache::<CpuBackend>::new();
+        let small_coset = CanonicCoset::new(6).half_coset();
+        let small_tree = cache.get(small_coset);
+        let large_tree = cache.get(CanonicCoset::new(10).half_coset());
+
+        let tree = cache.get(small_coset);
+
+        assert!(!Arc::ptr_eq(&small_tree, &large_tree));
+        assert!(Arc::ptr_eq(&tree, &small_tree));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_twiddle_cache_shared_between_threads() {
+        let cache = TwiddleCache::<SimdBackend>::new();
+        let coset = CanonicCoset::new(12).half_coset();
+
+        let trees = std::thread::scope(|s| {
+            let handles = (0..4)
+                .map(|_| s.spawn(|| cache.get(coset)))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(cache.len(), 1);
+        let cached_tree = cache.get(coset);
+        assert!(trees.iter().all(|tree| Arc::ptr_eq(tree, &cached_tree)));
+    }
+}
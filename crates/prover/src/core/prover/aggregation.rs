@@ -0,0 +1,321 @@
+//! Aggregation of many proofs into one, with a single low degree test.
+//!
+//! Each proof is generated with [open](super::open), which commits on its trace and composition
+//! polynomial and samples them out of domain, on the proof's own channel. [aggregate] then proves
+//! all the samples with a single FRI proof and proof of work, on a random linear combination of
+//! the quotients of all the proofs. Verifying the aggregate proof checks the constraints of each
+//! AIR at its out of domain point, but runs FRI only once.
+
+use std::iter::zip;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    verify_oods_values, Channel, ChannelHasher, MerkleHasher, StarkProof, VerificationError,
+};
+use crate::core::backend::{Backend, ExtensionBackend};
+use crate::core::channel::Channel as ChannelTrait;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::SecureExtensionField;
+use crate::core::fri::FriProof;
+use crate::core::pcs::quotients::PointSample;
+use crate::core::pcs::{
+    prove_aggregated_values, verify_aggregated_values, CommitmentSchemeOpening,
+    CommitmentSchemeProver, PcsConfig, TreeVec,
+};
+use crate::core::poly::twiddles::TwiddleTree;
+use crate::core::proof_of_work::ProofOfWorkProof;
+use crate::core::vcs::hasher::Hasher;
+use crate::core::vcs::ops::MerkleOps;
+use crate::core::{ColumnVec, LookupValues};
+use crate::trace_generation::BatchAirTraceVerifier;
+
+/// A proof whose committed polynomials were sampled out of domain, but not yet proven to be of low
+/// degree. Generated by [open](super::open).
+pub struct OpenedProof<B: Backend + MerkleOps<MerkleHasher>, F: SecureExtensionField = SecureField>
+{
+    /// The digest of the proof's channel after the sampled values were mixed into it.
+    pub(super) channel_digest: <ChannelHasher as Hasher>::Hash,
+    pub(super) commitment_scheme: CommitmentSchemeProver<B>,
+    pub(super) twiddles: Arc<TwiddleTree<B>>,
+    pub(super) lookup_values: LookupValues,
+    pub(super) samples: TreeVec<ColumnVec<Vec<PointSample<F>>>>,
+}
+
+impl<B: ExtensionBackend<F> + MerkleOps<MerkleHasher>, F: SecureExtensionField> OpenedProof<B, F> {
+    /// Completes the proof on its own, with the channel it was opened with.
+    pub fn prove(self, channel: &mut Channel) -> StarkProof<F> {
+        let commitment_scheme_proof =
+            self.commitment_scheme
+                .prove_samples(self.samples, channel, &self.twiddles);
+        StarkProof {
+            commitments: self.commitment_scheme.roots(),
+            lookup_values: self.lookup_values,
+            commitment_scheme_proof,
+        }
+    }
+}
+
+/// The part of a [StarkProof] specific to a single proof in an [AggregateProof].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenedStarkProof {
+    pub commitments: TreeVec<<ChannelHasher as Hasher>::Hash>,
+    pub lookup_values: LookupValues,
+    pub opening: CommitmentSchemeOpening,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AggregateProof {
+    pub proofs: Vec<OpenedStarkProof>,
+    pub proof_of_work: ProofOfWorkProof,
+    pub fri_proof: FriProof<MerkleHasher>,
+}
+
+/// Aggregates proofs opened with the same configuration into a single proof.
+/// `channel` is the channel of the aggregation, independent of the channels of the proofs.
+pub fn aggregate<B: Backend + MerkleOps<MerkleHasher>>(
+    mut proofs: Vec<OpenedProof<B>>,
+    channel: &mut Channel,
+) -> AggregateProof {
+    assert!(!proofs.is_empty(), "no proofs to aggregate");
+    for proof in &proofs {
+        channel.mix_digest(proof.channel_digest);
+    }
+
+    let samples = proofs
+        .iter_mut()
+        .map(|proof| std::mem::take(&mut proof.samples))
+        .collect_vec();
+    // The twiddles of the largest proof cover the domains of all the others.
+    let twiddles = &proofs
+        .iter()
+        .max_by_key(|proof| proof.twiddles.root_coset.log_size())
+        .unwrap()
+        .twiddles;
+    let (openings, proof_of_work, fri_proof) = prove_aggregated_values(
+        zip(&proofs, samples)
+            .map(|(proof, samples)| (&proof.commitment_scheme, samples))
+            .collect(),
+        channel,
+        twiddles,
+    );
+
+    let proofs = zip(proofs, openings)
+        .map(|(proof, opening)| OpenedStarkProof {
+            commitments: proof.commitment_scheme.roots(),
+            lookup_values: proof.lookup_values,
+            opening,
+        })
+        .collect();
+    AggregateProof {
+        proofs,
+        proof_of_work,
+        fri_proof,
+    }
+}
+
+/// Verifies an [AggregateProof], where `airs[i]` and `channels[i]` are the AIR and the channel of
+/// the `i`-th aggregated proof, and `channel` is the channel of the aggregation.
+pub fn verify_aggregate(
+    proof: AggregateProof,
+    airs: &[&dyn BatchAirTraceVerifier],
+    channels: &mut [Channel],
+    channel: &mut Channel,
+    config: PcsConfig,
+) -> Result<(), VerificationError> {
+    if airs.len() != channels.len() {
+        return Err(VerificationError::InvalidStructure(format!(
+            "Expected one channel per AIR, got {} AIRs and {} channels",
+            airs.len(),
+            channels.len()
+        )));
+    }
+    if proof.proofs.len() != airs.len() || airs.is_empty() {
+        return Err(VerificationError::InvalidStructure(format!(
+            "Expected {} aggregated proofs, got {}",
+            airs.len(),
+            proof.proofs.len()
+        )));
+    }
+
+    let mut commitment_schemes = vec![];
+    let mut openings = vec![];
+    for ((proof, air), proof_channel) in zip(zip(proof.proofs, airs), channels) {
+        let (commitment_scheme, sample_points) = verify_oods_values(
+            &proof.commitments,
+            &proof.lookup_values,
+            &proof.opening.sampled_values,
+            *air,
+            proof_channel,
+            config,
+        )?;
+        proof_channel.mix_extension_felts(&proof.opening.sampled_values.clone().flatten_cols());
+        channel.mix_digest(proof_channel.get_digest());
+        commitment_schemes.push(commitment_scheme);
+        openings.push((sample_points, proof.opening));
+    }
+
+    verify_aggregated_values(
+        zip(&commitment_schemes, openings)
+            .map(|(commitment_scheme, (sample_points, opening))| {
+                (commitment_scheme, sample_points, opening)
+            })
+            .collect(),
+        proof.proof_of_work,
+        proof.fri_proof,
+        channel,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use itertools::Itertools;
+    use num_traits::One;
+
+    use super::{aggregate, verify_aggregate, AggregateProof};
+    use crate::core::backend::CpuBackend;
+    use crate::core::channel::{Blake2sChannel, Channel};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::{FieldExpOps, IntoSlice};
+    use crate::core::pcs::PcsConfig;
+    use crate::core::poly::twiddles::TwiddleCache;
+    use crate::core::prover::{open, verify, VerificationError};
+    use crate::core::test_utils::test_channel;
+    use crate::core::vcs::blake2_hash::Blake2sHasher;
+    use crate::core::vcs::hasher::Hasher;
+    use crate::examples::fibonacci::Fibonacci;
+    use crate::trace_generation::BatchAirTraceVerifier;
+
+    fn fibonacci(log_size: u32) -> Fibonacci {
+        let (mut a, mut b) = (BaseField::one(), BaseField::one());
+        for _ in 1..1 << log_size {
+            (a, b) = (b, a.square() + b.square());
+        }
+        Fibonacci::new(log_size, a)
+    }
+
+    fn fibonacci_channel(fib: &Fibonacci) -> Blake2sChannel {
+        Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[fib
+            .air
+            .component
+            .claim])))
+    }
+
+    fn prove_aggregate(fibs: &[Fibonacci]) -> AggregateProof {
+        let twiddle_cache = TwiddleCache::new();
+        let opened_proofs = fibs
+            .iter()
+            .map(|fib| {
+                open::<CpuBackend, _>(
+                    &fib.air,
+                    &mut fibonacci_channel(fib),
+                    vec![fib.get_trace()],
+                    PcsConfig::default(),
+                    &twiddle_cache,
+                )
+                .unwrap()
+            })
+            .collect();
+        aggregate(opened_proofs, &mut test_channel())
+    }
+
+    fn verify_aggregate_fibonacci(
+        proof: AggregateProof,
+        fibs: &[Fibonacci],
+    ) -> Result<(), VerificationError> {
+        verify_aggregate(
+            proof,
+            &fibs
+                .iter()
+                .map(|fib| &fib.air as &dyn BatchAirTraceVerifier)
+                .collect_vec(),
+            &mut fibs.iter().map(fibonacci_channel).collect_vec(),
+            &mut test_channel(),
+            PcsConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_aggregate_fibonacci_proofs() {
+        let fibs = [5, 7, 6].map(fibonacci);
+
+        let proof = prove_aggregate(&fibs);
+
+        verify_aggregate_fibonacci(proof, &fibs).unwrap();
+    }
+
+    #[test]
+    fn test_aggregate_with_wrong_claim_fails() {
+        let mut fibs = [5, 7, 6].map(fibonacci);
+        let proof = prove_aggregate(&fibs);
+        fibs[1].air.component.claim += BaseField::one();
+
+        let error = verify_aggregate_fibonacci(proof, &fibs).unwrap_err();
+
+        assert_matches!(error, VerificationError::OodsNotMatching);
+    }
+
+    #[test]
+    fn test_aggregate_with_missing_proof_fails() {
+        let fibs = [5, 7, 6].map(fibonacci);
+        let mut proof = prove_aggregate(&fibs);
+        proof.proofs.pop();
+
+        let error = verify_aggregate_fibonacci(proof, &fibs).unwrap_err();
+
+        assert_matches!(error, VerificationError::InvalidStructure(_));
+    }
+
+    #[test]
+    fn test_aggregate_with_missing_channel_fails() {
+        let fibs = [5, 7].map(fibonacci);
+        let proof = prove_aggregate(&fibs);
+
+        let error = verify_aggregate(
+            proof,
+            &fibs
+                .iter()
+                .map(|fib| &fib.air as &dyn BatchAirTraceVerifier)
+                .collect_vec(),
+            &mut [fibonacci_channel(&fibs[0])],
+            &mut test_channel(),
+            PcsConfig::default(),
+        )
+        .unwrap_err();
+
+        assert_matches!(error, VerificationError::InvalidStructure(_));
+    }
+
+    #[test]
+    fn test_empty_aggregate_fails() {
+        let mut proof = prove_aggregate(&[fibonacci(5)]);
+        proof.proofs.clear();
+
+        let error = verify_aggregate_fibonacci(proof, &[]).unwrap_err();
+
+        assert_matches!(error, VerificationError::InvalidStructure(_));
+    }
+
+    #[test]
+    fn test_opened_proof_proves_alone() {
+        let fib = fibonacci(5);
+        let channel = &mut fibonacci_channel(&fib);
+        let opened_proof = open::<CpuBackend, SecureField>(
+            &fib.air,
+            channel,
+            vec![fib.get_trace()],
+            PcsConfig::default(),
+            &TwiddleCache::new(),
+        )
+        .unwrap();
+
+        let proof = opened_proof.prove(channel);
+
+        verify(proof, &fib.air, &mut fibonacci_channel(&fib)).unwrap();
+    }
+}
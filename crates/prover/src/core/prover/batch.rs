@@ -0,0 +1,517 @@
+//! Proving several independent AIRs in a single proof.
+//!
+//! The traces of all the AIRs are committed into shared trees, one AIR after the other, and their
+//! constraints are combined into a single composition polynomial. Hence the batch pays for a
+//! single FRI and proof of work, instead of one per AIR.
+//!
+//! Each AIR draws its own interaction elements and has its own lookup values, so AIRs using the
+//! same element or lookup value ids may be batched together. In the proof, the lookup values of
+//! the `i`-th AIR are stored under the `"{i}:{id}"` key.
+
+use itertools::{zip_eq, Itertools};
+use tracing::{span, Level};
+
+use super::{
+    sampled_values_to_mask, Channel, MerkleHasher, ProvingError, StarkProof, VerificationError,
+    BASE_TRACE, INTERACTION_TRACE,
+};
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::{Air, AirExt, Component, ComponentTrace};
+use crate::core::backend::Backend;
+use crate::core::channel::Channel as ChannelTrait;
+use crate::core::circle::CirclePoint;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::pcs::{CommitmentSchemeProver, CommitmentSchemeVerifier, PcsConfig, TreeVec};
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, MAX_CIRCLE_DOMAIN_LOG_SIZE};
+use crate::core::poly::BitReversedOrder;
+use crate::core::vcs::ops::MerkleOps;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::{BatchAirTraceGenerator, BatchAirTraceVerifier};
+
+/// The components of several AIRs, laid out one AIR after the other.
+struct BatchAir<'a> {
+    airs: Vec<Vec<&'a dyn Component>>,
+}
+
+impl<'a> BatchAir<'a> {
+    fn new(airs: impl IntoIterator<Item = Vec<&'a dyn Component>>) -> Self {
+        Self {
+            airs: airs.into_iter().collect(),
+        }
+    }
+
+    /// Same as [AirExt::eval_composition_polynomial_at_point], where each AIR is evaluated with
+    /// its own interaction elements and lookup values.
+    fn eval_composition_polynomial_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask_values: &[TreeVec<Vec<Vec<SecureField>>>],
+        random_coeff: SecureField,
+        interaction_elements: &[InteractionElements],
+        lookup_values: &[LookupValues],
+    ) -> SecureField {
+        let mut evaluation_accumulator = PointEvaluationAccumulator::new(random_coeff);
+        let mut mask_values = mask_values.iter();
+        for ((components, interaction_elements), lookup_values) in
+            zip_eq(zip_eq(&self.airs, interaction_elements), lookup_values)
+        {
+            for component in components {
+                component.evaluate_constraint_quotients_at_point(
+                    point,
+                    mask_values.next().unwrap(),
+                    &mut evaluation_accumulator,
+                    interaction_elements,
+                    lookup_values,
+                );
+            }
+        }
+        evaluation_accumulator.finalize()
+    }
+}
+
+impl Air for BatchAir<'_> {
+    fn components(&self) -> Vec<&dyn Component> {
+        self.airs.iter().flatten().copied().collect()
+    }
+}
+
+/// Proves all the `airs` in a single [StarkProof], where `traces[i]` is the trace of `airs[i]`.
+/// The proof must be verified with [verify_batch], the same AIRs (in the same order) and the same
+/// `config`.
+pub fn prove_batch<B: Backend + MerkleOps<MerkleHasher>>(
+    airs: &[&dyn BatchAirTraceGenerator<B>],
+    channel: &mut Channel,
+    traces: Vec<ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>>,
+    config: PcsConfig,
+) -> Result<StarkProof, ProvingError> {
+    if airs.len() != traces.len() {
+        return Err(ProvingError::TraceCountMismatch {
+            n_airs: airs.len(),
+            n_traces: traces.len(),
+        });
+    }
+    let log_blowup_factor = config.fri_config.log_blowup_factor;
+
+    // Check that traces are not too big.
+    for (i, trace) in traces.iter().flatten().enumerate() {
+        if trace.domain.log_size() + log_blowup_factor > MAX_CIRCLE_DOMAIN_LOG_SIZE {
+            return Err(ProvingError::MaxTraceDegreeExceeded {
+                trace_index: i,
+                degree: trace.domain.log_size(),
+            });
+        }
+    }
+
+    let air_provers = airs.iter().map(|air| air.batch_air_prover()).collect_vec();
+    let batch_air = BatchAir::new(air_provers.iter().map(|air| air.components()));
+
+    // Check that the composition polynomial is not too big.
+    let composition_polynomial_log_degree_bound = batch_air.composition_log_degree_bound();
+    if composition_polynomial_log_degree_bound + log_blowup_factor > MAX_CIRCLE_DOMAIN_LOG_SIZE {
+        return Err(ProvingError::MaxCompositionDegreeExceeded {
+            degree: composition_polynomial_log_degree_bound,
+        });
+    }
+
+    let span = span!(Level::INFO, "Precompute twiddle").entered();
+    let twiddles = B::precompute_twiddles(
+        CanonicCoset::new(composition_polynomial_log_degree_bound + log_blowup_factor)
+            .circle_domain()
+            .half_coset,
+    );
+    span.exit();
+
+    let span = span!(Level::INFO, "Trace interpolation").entered();
+    let trace_polys = traces
+        .iter()
+        .flatten()
+        .map(|eval| eval.clone().interpolate_with_twiddles(&twiddles))
+        .collect();
+    span.exit();
+
+    let mut commitment_scheme = CommitmentSchemeProver::new(config);
+    let span = span!(Level::INFO, "Trace commitment").entered();
+    commitment_scheme.commit(trace_polys, channel, &twiddles);
+    span.exit();
+
+    let interaction_elements = airs
+        .iter()
+        .map(|air| air.interaction_elements(channel))
+        .collect_vec();
+    let interaction_trace = zip_eq(zip_eq(airs, &traces), &interaction_elements)
+        .flat_map(|((air, trace), elements)| air.batch_interact(trace, elements))
+        .collect_vec();
+    if !interaction_trace.is_empty() {
+        let span = span!(Level::INFO, "Interaction trace interpolation").entered();
+        let interaction_trace_polys = interaction_trace
+            .into_iter()
+            .map(|eval| eval.interpolate_with_twiddles(&twiddles))
+            .collect();
+        span.exit();
+        commitment_scheme.commit(interaction_trace_polys, channel, &twiddles);
+    }
+
+    // Split the component traces between the AIRs.
+    let mut component_traces = batch_air
+        .component_traces(&commitment_scheme.trees)
+        .into_iter();
+    let component_traces: Vec<Vec<ComponentTrace<'_, B>>> = air_provers
+        .iter()
+        .map(|air| {
+            component_traces
+                .by_ref()
+                .take(air.prover_components().len())
+                .collect()
+        })
+        .collect();
+
+    let lookup_values = zip_eq(&air_provers, &component_traces)
+        .map(|(air, traces)| {
+            let mut values = LookupValues::default();
+            zip_eq(air.prover_components(), traces)
+                .for_each(|(component, trace)| values.extend(component.lookup_values(trace)));
+            values
+        })
+        .collect_vec();
+    let batch_lookup_values = merge_lookup_values(&lookup_values);
+    channel.mix_felts(
+        &batch_lookup_values
+            .0
+            .values()
+            .map(|v| SecureField::from(*v))
+            .collect_vec(),
+    );
+
+    // Evaluate and commit on composition polynomial.
+    let random_coeff = channel.draw_felt();
+    let span = span!(Level::INFO, "Composition generation").entered();
+    let total_constraints = batch_air
+        .components()
+        .iter()
+        .map(|component| component.n_constraints())
+        .sum();
+    let mut accumulator = DomainEvaluationAccumulator::new(
+        random_coeff,
+        composition_polynomial_log_degree_bound,
+        total_constraints,
+    );
+    for (((air, traces), interaction_elements), lookup_values) in zip_eq(
+        zip_eq(
+            zip_eq(&air_provers, &component_traces),
+            &interaction_elements,
+        ),
+        &lookup_values,
+    ) {
+        for (component, trace) in zip_eq(air.prover_components(), traces) {
+            component.evaluate_constraint_quotients_on_domain(
+                trace,
+                &mut accumulator,
+                interaction_elements,
+                lookup_values,
+            );
+        }
+    }
+    let composition_polynomial_poly = accumulator.finalize();
+    span.exit();
+
+    let span = span!(Level::INFO, "Composition commitment").entered();
+    commitment_scheme.commit(composition_polynomial_poly.to_vec(), channel, &twiddles);
+    span.exit();
+
+    // Draw OODS point.
+    let oods_point = CirclePoint::<SecureField>::get_random_point(channel);
+
+    // Get mask sample points relative to oods point.
+    let sample_points = batch_air.mask_points(oods_point);
+
+    // Prove the trace and composition OODS values, and retrieve them.
+    let commitment_scheme_proof = commitment_scheme.prove_values(sample_points, channel, &twiddles);
+
+    // Evaluate composition polynomial at OODS point and check that it matches the trace OODS
+    // values. This is a sanity check.
+    let (trace_oods_values, composition_oods_value) =
+        sampled_values_to_mask(&batch_air, &commitment_scheme_proof.sampled_values).unwrap();
+
+    if composition_oods_value
+        != batch_air.eval_composition_polynomial_at_point(
+            oods_point,
+            &trace_oods_values,
+            random_coeff,
+            &interaction_elements,
+            &lookup_values,
+        )
+    {
+        return Err(ProvingError::ConstraintsNotSatisfied);
+    }
+
+    Ok(StarkProof {
+        commitments: commitment_scheme.roots(),
+        lookup_values: batch_lookup_values,
+        commitment_scheme_proof,
+    })
+}
+
+/// Verifies a proof generated by [prove_batch].
+pub fn verify_batch(
+    proof: StarkProof,
+    airs: &[&dyn BatchAirTraceVerifier],
+    channel: &mut Channel,
+    config: PcsConfig,
+) -> Result<(), VerificationError> {
+    let batch_air = BatchAir::new(airs.iter().map(|air| air.components()));
+    let lookup_values = split_lookup_values(&proof.lookup_values, airs.len())?;
+
+    // Read trace commitment.
+    let mut commitment_scheme = CommitmentSchemeVerifier::new(config);
+    let column_log_sizes = batch_air.column_log_sizes();
+    commitment_scheme.commit(
+        proof.commitments[BASE_TRACE],
+        &column_log_sizes[BASE_TRACE],
+        channel,
+    );
+    let interaction_elements = airs
+        .iter()
+        .map(|air| air.interaction_elements(channel))
+        .collect_vec();
+
+    if batch_air.n_interaction_phases() == 2 {
+        commitment_scheme.commit(
+            proof.commitments[INTERACTION_TRACE],
+            &column_log_sizes[INTERACTION_TRACE],
+            channel,
+        );
+    }
+
+    for ((air, elements), lookup_values) in
+        zip_eq(zip_eq(airs, &interaction_elements), &lookup_values)
+    {
+        air.verify_lookups(elements, lookup_values)?;
+    }
+    channel.mix_felts(
+        &proof
+            .lookup_values
+            .0
+            .values()
+            .map(|v| SecureField::from(*v))
+            .collect_vec(),
+    );
+    let random_coeff = channel.draw_felt();
+
+    // Read composition polynomial commitment.
+    commitment_scheme.commit(
+        *proof.commitments.last().unwrap(),
+        &[batch_air.composition_log_degree_bound(); SECURE_EXTENSION_DEGREE],
+        channel,
+    );
+
+    // Draw OODS point.
+    let oods_point = CirclePoint::<SecureField>::get_random_point(channel);
+
+    // Get mask sample points relative to oods point.
+    let sample_points = batch_air.mask_points(oods_point);
+
+    let (trace_oods_values, composition_oods_value) = sampled_values_to_mask(
+        &batch_air,
+        &proof.commitment_scheme_proof.sampled_values,
+    )
+    .map_err(|_| {
+        VerificationError::InvalidStructure("Unexpected sampled_values structure".to_string())
+    })?;
+
+    if composition_oods_value
+        != batch_air.eval_composition_polynomial_at_point(
+            oods_point,
+            &trace_oods_values,
+            random_coeff,
+            &interaction_elements,
+            &lookup_values,
+        )
+    {
+        return Err(VerificationError::OodsNotMatching);
+    }
+
+    commitment_scheme.verify_values(sample_points, proof.commitment_scheme_proof, channel)
+}
+
+/// Merges the lookup values of each AIR, prefixing their ids with the index of the AIR.
+fn merge_lookup_values(lookup_values: &[LookupValues]) -> LookupValues {
+    LookupValues::new(
+        lookup_values
+            .iter()
+            .enumerate()
+            .flat_map(|(air_index, values)| {
+                values
+                    .0
+                    .iter()
+                    .map(move |(id, value)| (format!("{air_index}:{id}"), *value))
+            })
+            .collect(),
+    )
+}
+
+/// Inverse of [merge_lookup_values]. The AIR indices must be written as [merge_lookup_values]
+/// writes them, so that each lookup value has a single id.
+fn split_lookup_values(
+    lookup_values: &LookupValues,
+    n_airs: usize,
+) -> Result<Vec<LookupValues>, VerificationError> {
+    let mut res = (0..n_airs).map(|_| LookupValues::default()).collect_vec();
+    for (key, value) in &lookup_values.0 {
+        let (air_index, id) = key
+            .split_once(':')
+            .and_then(|(prefix, id)| {
+                let air_index = prefix.parse::<usize>().ok()?;
+                (air_index.to_string() == prefix).then_some((air_index, id))
+            })
+            .filter(|(air_index, _)| *air_index < n_airs)
+            .ok_or_else(|| {
+                VerificationError::InvalidStructure(format!("Unexpected lookup value id {key}"))
+            })?;
+        res[air_index].0.insert(id.to_string(), *value);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+    use std::collections::BTreeMap;
+
+    use super::{prove_batch, split_lookup_values, verify_batch};
+    use crate::core::backend::cpu::CpuCircleEvaluation;
+    use crate::core::backend::CpuBackend;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::pcs::PcsConfig;
+    use crate::core::poly::circle::CanonicCoset;
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::prover::{ProvingError, VerificationError};
+    use crate::core::test_utils::test_channel;
+    use crate::core::{ColumnVec, LookupValues};
+    use crate::examples::fibonacci::Fibonacci;
+    use crate::examples::wide_fibonacci::component::{
+        Input, WideFibAir, WideFibComponent, LOG_N_COLUMNS,
+    };
+    use crate::examples::wide_fibonacci::constraint_eval::gen_trace;
+    use crate::m31;
+    use crate::trace_generation::{BatchAirTraceGenerator, BatchAirTraceVerifier};
+
+    fn wide_fib(
+        log_n_rows: u32,
+    ) -> (
+        WideFibAir,
+        ColumnVec<CpuCircleEvaluation<BaseField, BitReversedOrder>>,
+    ) {
+        let component = WideFibComponent {
+            log_fibonacci_size: LOG_N_COLUMNS as u32 + log_n_rows,
+            log_n_instances: 0,
+        };
+        let inputs = vec![Input {
+            a: m31!(1),
+            b: m31!(1),
+        }];
+        let trace_domain = CanonicCoset::new(component.log_column_size());
+        let trace = gen_trace(&component, inputs)
+            .into_iter()
+            .map(|column| CpuCircleEvaluation::new_canonical_ordered(trace_domain, column))
+            .collect();
+        (WideFibAir { component }, trace)
+    }
+
+    #[test]
+    fn test_batch_prove_and_verify() {
+        let fib = Fibonacci::new(5, m31!(443693538));
+        let (wide_fib_0, wide_fib_0_trace) = wide_fib(3);
+        let (wide_fib_1, wide_fib_1_trace) = wide_fib(4);
+        let traces = vec![vec![fib.get_trace()], wide_fib_0_trace, wide_fib_1_trace];
+        let config = PcsConfig::default();
+
+        let proof = prove_batch::<CpuBackend>(
+            &[
+                &fib.air as &dyn BatchAirTraceGenerator<_>,
+                &wide_fib_0,
+                &wide_fib_1,
+            ],
+            &mut test_channel(),
+            traces,
+            config,
+        )
+        .unwrap();
+
+        verify_batch(
+            proof,
+            &[
+                &fib.air as &dyn BatchAirTraceVerifier,
+                &wide_fib_0,
+                &wide_fib_1,
+            ],
+            &mut test_channel(),
+            config,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_batch_verify_with_wrong_statement_fails() {
+        let fib = Fibonacci::new(5, m31!(443693538));
+        let wrong_fib = Fibonacci::new(5, m31!(1));
+        let (wide_fib, wide_fib_trace) = wide_fib(3);
+        let config = PcsConfig::default();
+        let proof = prove_batch::<CpuBackend>(
+            &[&fib.air as &dyn BatchAirTraceGenerator<_>, &wide_fib],
+            &mut test_channel(),
+            vec![vec![fib.get_trace()], wide_fib_trace],
+            config,
+        )
+        .unwrap();
+
+        let error = verify_batch(
+            proof,
+            &[&wrong_fib.air as &dyn BatchAirTraceVerifier, &wide_fib],
+            &mut test_channel(),
+            config,
+        )
+        .unwrap_err();
+
+        assert_matches!(error, VerificationError::OodsNotMatching);
+    }
+
+    #[test]
+    fn test_batch_prove_with_missing_trace_fails() {
+        let fib = Fibonacci::new(5, m31!(443693538));
+        let (wide_fib, _) = wide_fib(3);
+
+        let error = prove_batch::<CpuBackend>(
+            &[&fib.air as &dyn BatchAirTraceGenerator<_>, &wide_fib],
+            &mut test_channel(),
+            vec![vec![fib.get_trace()]],
+            PcsConfig::default(),
+        )
+        .unwrap_err();
+
+        assert_matches!(
+            error,
+            ProvingError::TraceCountMismatch {
+                n_airs: 2,
+                n_traces: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_lookup_values_rejects_non_canonical_ids() {
+        let lookup_values =
+            |id: &str| LookupValues::new(BTreeMap::from([(id.to_string(), m31!(1))]));
+
+        let split = split_lookup_values(&lookup_values("1:x"), 2).unwrap();
+
+        assert_eq!(split[1]["x"], m31!(1));
+        for id in ["01:x", "+1:x", "2:x", "x"] {
+            assert_matches!(
+                split_lookup_values(&lookup_values(id), 2),
+                Err(VerificationError::InvalidStructure(_))
+            );
+        }
+    }
+}
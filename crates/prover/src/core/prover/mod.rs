@@ -1,14 +1,19 @@
 use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{span, Level};
 
+use super::air::logup::LookupError;
 use super::air::AirProver;
-use super::backend::Backend;
-use super::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use super::backend::{Backend, ExtensionBackend};
+use super::fields::SecureExtensionField;
 use super::fri::FriVerificationError;
-use super::pcs::{CommitmentSchemeProof, TreeVec};
+use super::pcs::quotients::PointSample;
+use super::pcs::{CommitmentSchemeProof, PcsConfig, TreeVec};
 use super::poly::circle::{CanonicCoset, MAX_CIRCLE_DOMAIN_LOG_SIZE};
-use super::poly::twiddles::TwiddleTree;
+use super::poly::twiddles::{TwiddleCache, TwiddleTree};
 use super::proof_of_work::ProofOfWorkVerificationError;
 use super::{ColumnVec, InteractionElements, LookupValues};
 use crate::core::air::{Air, AirExt, AirProverExt};
@@ -18,7 +23,7 @@ use crate::core::circle::CirclePoint;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::pcs::{CommitmentSchemeProver, CommitmentSchemeVerifier};
-use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::circle::{CircleEvaluation, CirclePoly};
 use crate::core::poly::BitReversedOrder;
 use crate::core::vcs::blake2_hash::Blake2sHasher;
 use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
@@ -27,6 +32,12 @@ use crate::core::vcs::ops::MerkleOps;
 use crate::core::vcs::verifier::MerkleVerificationError;
 use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier};
 
+mod aggregation;
+mod batch;
+
+pub use aggregation::{aggregate, verify_aggregate, AggregateProof, OpenedProof, OpenedStarkProof};
+pub use batch::{prove_batch, verify_batch};
+
 type Channel = Blake2sChannel;
 type ChannelHasher = Blake2sHasher;
 type MerkleHasher = Blake2sMerkleHasher;
@@ -39,11 +50,12 @@ pub const N_QUERIES: usize = 3;
 pub const BASE_TRACE: usize = 0;
 pub const INTERACTION_TRACE: usize = 1;
 
-#[derive(Debug)]
-pub struct StarkProof {
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct StarkProof<F: SecureExtensionField = SecureField> {
     pub commitments: TreeVec<<ChannelHasher as Hasher>::Hash>,
     pub lookup_values: LookupValues,
-    pub commitment_scheme_proof: CommitmentSchemeProof,
+    pub commitment_scheme_proof: CommitmentSchemeProof<F>,
 }
 
 #[derive(Debug)]
@@ -54,22 +66,22 @@ pub struct AdditionalProofData {
     pub oods_quotients: Vec<CircleEvaluation<CpuBackend, SecureField, BitReversedOrder>>,
 }
 
-pub fn evaluate_and_commit_on_trace<B: Backend + MerkleOps<MerkleHasher>>(
-    air: &impl AirTraceGenerator<B>,
+pub fn evaluate_and_commit_on_trace<
+    B: Backend + MerkleOps<MerkleHasher>,
+    F: SecureExtensionField,
+>(
+    air: &impl AirTraceGenerator<B, F>,
     channel: &mut Channel,
     twiddles: &TwiddleTree<B>,
     trace: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    config: PcsConfig,
 ) -> Result<(CommitmentSchemeProver<B>, InteractionElements), ProvingError> {
     let span = span!(Level::INFO, "Trace interpolation").entered();
     // TODO(AlonH): Clone only the columns needed for interaction.
-    let trace_polys = trace
-        .clone()
-        .into_iter()
-        .map(|eval| eval.interpolate_with_twiddles(twiddles))
-        .collect();
+    let trace_polys = interpolate_columns(trace.clone(), twiddles);
     span.exit();
 
-    let mut commitment_scheme = CommitmentSchemeProver::new(LOG_BLOWUP_FACTOR);
+    let mut commitment_scheme = CommitmentSchemeProver::new(config);
     let span = span!(Level::INFO, "Trace commitment").entered();
     commitment_scheme.commit(trace_polys, channel, twiddles);
     span.exit();
@@ -78,10 +90,7 @@ pub fn evaluate_and_commit_on_trace<B: Backend + MerkleOps<MerkleHasher>>(
     let interaction_trace = air.interact(&trace, &interaction_elements);
     if !interaction_trace.is_empty() {
         let span = span!(Level::INFO, "Interaction trace interpolation").entered();
-        let interaction_trace_polys = interaction_trace
-            .into_iter()
-            .map(|eval| eval.interpolate_with_twiddles(twiddles))
-            .collect();
+        let interaction_trace_polys = interpolate_columns(interaction_trace, twiddles);
         span.exit();
         commitment_scheme.commit(interaction_trace_polys, channel, twiddles);
     }
@@ -89,13 +98,56 @@ pub fn evaluate_and_commit_on_trace<B: Backend + MerkleOps<MerkleHasher>>(
     Ok((commitment_scheme, interaction_elements))
 }
 
-pub fn generate_proof<B: Backend + MerkleOps<MerkleHasher>>(
-    air: &impl AirProver<B>,
+/// Interpolates the columns, in parallel when the `parallel` feature is enabled.
+fn interpolate_columns<B: Backend>(
+    columns: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    twiddles: &TwiddleTree<B>,
+) -> ColumnVec<CirclePoly<B>> {
+    #[cfg(not(feature = "parallel"))]
+    let iter = columns.into_iter();
+
+    #[cfg(feature = "parallel")]
+    let iter = columns.into_par_iter();
+
+    iter.map(|eval| eval.interpolate_with_twiddles(twiddles))
+        .collect()
+}
+
+pub fn generate_proof<B: ExtensionBackend<F> + MerkleOps<MerkleHasher>, F: SecureExtensionField>(
+    air: &impl AirProver<B, F>,
+    channel: &mut Channel,
+    interaction_elements: &InteractionElements,
+    twiddles: &TwiddleTree<B>,
+    commitment_scheme: &mut CommitmentSchemeProver<B>,
+) -> Result<StarkProof<F>, ProvingError> {
+    let (lookup_values, samples) = sample_oods_values(
+        air,
+        channel,
+        interaction_elements,
+        twiddles,
+        commitment_scheme,
+    )?;
+
+    // Prove the trace and composition OODS values.
+    let commitment_scheme_proof = commitment_scheme.prove_samples(samples, channel, twiddles);
+
+    Ok(StarkProof {
+        commitments: commitment_scheme.roots(),
+        lookup_values,
+        commitment_scheme_proof,
+    })
+}
+
+/// Commits on the composition polynomial and samples the committed polynomials at a random out of
+/// domain point. Returns the lookup values and the samples, which are yet to be proven.
+#[allow(clippy::type_complexity)]
+fn sample_oods_values<B: ExtensionBackend<F> + MerkleOps<MerkleHasher>, F: SecureExtensionField>(
+    air: &impl AirProver<B, F>,
     channel: &mut Channel,
     interaction_elements: &InteractionElements,
     twiddles: &TwiddleTree<B>,
     commitment_scheme: &mut CommitmentSchemeProver<B>,
-) -> Result<StarkProof, ProvingError> {
+) -> Result<(LookupValues, TreeVec<ColumnVec<Vec<PointSample<F>>>>), ProvingError> {
     let component_traces = air.component_traces(&commitment_scheme.trees);
     let lookup_values = air.lookup_values(&component_traces);
     channel.mix_felts(
@@ -107,7 +159,7 @@ pub fn generate_proof<B: Backend + MerkleOps<MerkleHasher>>(
     );
 
     // Evaluate and commit on composition polynomial.
-    let random_coeff = channel.draw_felt();
+    let random_coeff = channel.draw_extension_felt();
     let span = span!(Level::INFO, "Composition generation").entered();
     let composition_polynomial_poly = air.compute_composition_polynomial(
         random_coeff,
@@ -122,19 +174,21 @@ pub fn generate_proof<B: Backend + MerkleOps<MerkleHasher>>(
     span.exit();
 
     // Draw OODS point.
-    let oods_point = CirclePoint::<SecureField>::get_random_point(channel);
+    let oods_point = F::draw_circle_point(channel);
 
     // Get mask sample points relative to oods point.
     let sample_points = air.mask_points(oods_point);
 
-    // Prove the trace and composition OODS values, and retrieve them.
-    let commitment_scheme_proof = commitment_scheme.prove_values(sample_points, channel, twiddles);
+    // Sample the trace and composition polynomials at the OODS points.
+    let samples = commitment_scheme.sample_values(sample_points, channel);
 
     // Evaluate composition polynomial at OODS point and check that it matches the trace OODS
     // values. This is a sanity check.
-    // TODO(spapini): Save clone.
+    let sampled_values = samples
+        .as_cols_ref()
+        .map_cols(|x| x.iter().map(|o| o.value).collect());
     let (trace_oods_values, composition_oods_value) =
-        sampled_values_to_mask(air, &commitment_scheme_proof.sampled_values).unwrap();
+        sampled_values_to_mask(air, &sampled_values).unwrap();
 
     if composition_oods_value
         != air.eval_composition_polynomial_at_point(
@@ -148,21 +202,61 @@ pub fn generate_proof<B: Backend + MerkleOps<MerkleHasher>>(
         return Err(ProvingError::ConstraintsNotSatisfied);
     }
 
-    Ok(StarkProof {
-        commitments: commitment_scheme.roots(),
-        lookup_values,
-        commitment_scheme_proof,
-    })
+    Ok((lookup_values, samples))
 }
 
-pub fn prove<B: Backend + MerkleOps<MerkleHasher>>(
-    air: &impl AirTraceGenerator<B>,
+pub fn prove<B: ExtensionBackend<F> + MerkleOps<MerkleHasher>, F: SecureExtensionField>(
+    air: &impl AirTraceGenerator<B, F>,
     channel: &mut Channel,
     trace: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
-) -> Result<StarkProof, ProvingError> {
+) -> Result<StarkProof<F>, ProvingError> {
+    prove_with_config(air, channel, trace, PcsConfig::default())
+}
+
+/// Same as [prove], with the commitment scheme parameters given by `config`.
+/// The proof must be verified with [verify_with_config] and the same `config`.
+pub fn prove_with_config<
+    B: ExtensionBackend<F> + MerkleOps<MerkleHasher>,
+    F: SecureExtensionField,
+>(
+    air: &impl AirTraceGenerator<B, F>,
+    channel: &mut Channel,
+    trace: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    config: PcsConfig,
+) -> Result<StarkProof<F>, ProvingError> {
+    prove_with_twiddle_cache(air, channel, trace, config, &TwiddleCache::new())
+}
+
+/// Same as [prove_with_config], taking the twiddles from `twiddle_cache`. Sharing the cache between
+/// proofs avoids recomputing the twiddles of the same domains in each proof.
+pub fn prove_with_twiddle_cache<
+    B: ExtensionBackend<F> + MerkleOps<MerkleHasher>,
+    F: SecureExtensionField,
+>(
+    air: &impl AirTraceGenerator<B, F>,
+    channel: &mut Channel,
+    trace: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    config: PcsConfig,
+    twiddle_cache: &TwiddleCache<B>,
+) -> Result<StarkProof<F>, ProvingError> {
+    Ok(open(air, channel, trace, config, twiddle_cache)?.prove(channel))
+}
+
+/// Runs [prove_with_twiddle_cache] up to the out of domain sampling of the committed polynomials.
+/// The samples are then proven either alone, with [OpenedProof::prove], or together with the
+/// samples of other proofs, with [aggregate].
+pub fn open<B: ExtensionBackend<F> + MerkleOps<MerkleHasher>, F: SecureExtensionField>(
+    air: &impl AirTraceGenerator<B, F>,
+    channel: &mut Channel,
+    trace: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    config: PcsConfig,
+    twiddle_cache: &TwiddleCache<B>,
+) -> Result<OpenedProof<B, F>, ProvingError> {
+    let log_blowup_factor = config.fri_config.log_blowup_factor;
+
     // Check that traces are not too big.
     for (i, trace) in trace.iter().enumerate() {
-        if trace.domain.log_size() + LOG_BLOWUP_FACTOR > MAX_CIRCLE_DOMAIN_LOG_SIZE {
+        if trace.domain.log_size() + log_blowup_factor > MAX_CIRCLE_DOMAIN_LOG_SIZE {
             return Err(ProvingError::MaxTraceDegreeExceeded {
                 trace_index: i,
                 degree: trace.domain.log_size(),
@@ -173,42 +267,89 @@ pub fn prove<B: Backend + MerkleOps<MerkleHasher>>(
     // Check that the composition polynomial is not too big.
     // TODO(AlonH): Get traces log degree bounds from trace writer.
     let composition_polynomial_log_degree_bound = air.composition_log_degree_bound();
-    if composition_polynomial_log_degree_bound + LOG_BLOWUP_FACTOR > MAX_CIRCLE_DOMAIN_LOG_SIZE {
+    if composition_polynomial_log_degree_bound + log_blowup_factor > MAX_CIRCLE_DOMAIN_LOG_SIZE {
         return Err(ProvingError::MaxCompositionDegreeExceeded {
             degree: composition_polynomial_log_degree_bound,
         });
     }
 
     let span = span!(Level::INFO, "Precompute twiddle").entered();
-    let twiddles = B::precompute_twiddles(
-        CanonicCoset::new(composition_polynomial_log_degree_bound + LOG_BLOWUP_FACTOR)
+    let twiddles = twiddle_cache.get(
+        CanonicCoset::new(composition_polynomial_log_degree_bound + log_blowup_factor)
             .circle_domain()
             .half_coset,
     );
     span.exit();
 
     let (mut commitment_scheme, interaction_elements) =
-        evaluate_and_commit_on_trace(air, channel, &twiddles, trace)?;
+        evaluate_and_commit_on_trace(air, channel, &twiddles, trace, config)?;
 
-    generate_proof(
+    let (lookup_values, samples) = sample_oods_values(
         &air.to_air_prover(),
         channel,
         &interaction_elements,
         &twiddles,
         &mut commitment_scheme,
-    )
+    )?;
+
+    Ok(OpenedProof {
+        channel_digest: channel.get_digest(),
+        commitment_scheme,
+        twiddles,
+        lookup_values,
+        samples,
+    })
 }
 
-pub fn verify(
-    proof: StarkProof,
-    air: &(impl Air + AirTraceVerifier),
+pub fn verify<F: SecureExtensionField>(
+    proof: StarkProof<F>,
+    air: &(impl Air<F> + AirTraceVerifier),
     channel: &mut Channel,
 ) -> Result<(), VerificationError> {
+    verify_with_config(proof, air, channel, PcsConfig::default())
+}
+
+/// Same as [verify], for proofs generated by [prove_with_config] with the given `config`.
+pub fn verify_with_config<F: SecureExtensionField>(
+    proof: StarkProof<F>,
+    air: &(impl Air<F> + AirTraceVerifier),
+    channel: &mut Channel,
+    config: PcsConfig,
+) -> Result<(), VerificationError> {
+    let (commitment_scheme, sample_points) = verify_oods_values(
+        &proof.commitments,
+        &proof.lookup_values,
+        &proof.commitment_scheme_proof.sampled_values,
+        air,
+        channel,
+        config,
+    )?;
+    commitment_scheme.verify_values(sample_points, proof.commitment_scheme_proof, channel)
+}
+
+/// Reads the commitments of a proof and checks its out of domain samples against the constraints
+/// of `air`. Returns the commitment scheme and the points the samples were taken at, which are
+/// yet to be verified.
+#[allow(clippy::type_complexity)]
+fn verify_oods_values<F: SecureExtensionField>(
+    commitments: &TreeVec<<ChannelHasher as Hasher>::Hash>,
+    lookup_values: &LookupValues,
+    sampled_values: &TreeVec<ColumnVec<Vec<F>>>,
+    air: &(impl Air<F> + AirTraceVerifier + ?Sized),
+    channel: &mut Channel,
+    config: PcsConfig,
+) -> Result<
+    (
+        CommitmentSchemeVerifier,
+        TreeVec<ColumnVec<Vec<CirclePoint<F>>>>,
+    ),
+    VerificationError,
+> {
     // Read trace commitment.
-    let mut commitment_scheme = CommitmentSchemeVerifier::new();
+    let mut commitment_scheme = CommitmentSchemeVerifier::new(config);
     let column_log_sizes = air.column_log_sizes();
     commitment_scheme.commit(
-        proof.commitments[BASE_TRACE],
+        commitments[BASE_TRACE],
         &column_log_sizes[BASE_TRACE],
         channel,
     );
@@ -216,41 +357,38 @@ pub fn verify(
 
     if air.n_interaction_phases() == 2 {
         commitment_scheme.commit(
-            proof.commitments[INTERACTION_TRACE],
+            commitments[INTERACTION_TRACE],
             &column_log_sizes[INTERACTION_TRACE],
             channel,
         );
     }
 
+    air.verify_lookups(&interaction_elements, lookup_values)?;
     channel.mix_felts(
-        &proof
-            .lookup_values
+        &lookup_values
             .0
             .values()
             .map(|v| SecureField::from(*v))
             .collect_vec(),
     );
-    let random_coeff = channel.draw_felt();
+    let random_coeff = channel.draw_extension_felt();
 
     // Read composition polynomial commitment.
     commitment_scheme.commit(
-        *proof.commitments.last().unwrap(),
-        &[air.composition_log_degree_bound(); SECURE_EXTENSION_DEGREE],
+        *commitments.last().unwrap(),
+        &vec![air.composition_log_degree_bound(); F::EXTENSION_DEGREE],
         channel,
     );
 
     // Draw OODS point.
-    let oods_point = CirclePoint::<SecureField>::get_random_point(channel);
+    let oods_point = F::draw_circle_point(channel);
 
     // Get mask sample points relative to oods point.
     let sample_points = air.mask_points(oods_point);
 
     // TODO(spapini): Save clone.
-    let (trace_oods_values, composition_oods_value) = sampled_values_to_mask(
-        air,
-        &proof.commitment_scheme_proof.sampled_values,
-    )
-    .map_err(|_| {
+    let (trace_oods_values, composition_oods_value) = sampled_values_to_mask(air, sampled_values)
+        .map_err(|_| {
         VerificationError::InvalidStructure("Unexpected sampled_values structure".to_string())
     })?;
 
@@ -260,22 +398,22 @@ pub fn verify(
             &trace_oods_values,
             random_coeff,
             &interaction_elements,
-            &proof.lookup_values,
+            lookup_values,
         )
     {
         return Err(VerificationError::OodsNotMatching);
     }
 
-    commitment_scheme.verify_values(sample_points, proof.commitment_scheme_proof, channel)
+    Ok((commitment_scheme, sample_points))
 }
 
 #[allow(clippy::type_complexity)]
 /// Structures the tree-wise sampled values into component-wise OODS values and a composition
 /// polynomial OODS value.
-fn sampled_values_to_mask(
-    air: &impl Air,
-    sampled_values: &TreeVec<ColumnVec<Vec<SecureField>>>,
-) -> Result<(Vec<TreeVec<Vec<Vec<SecureField>>>>, SecureField), InvalidOodsSampleStructure> {
+fn sampled_values_to_mask<F: SecureExtensionField>(
+    air: &(impl Air<F> + ?Sized),
+    sampled_values: &TreeVec<ColumnVec<Vec<F>>>,
+) -> Result<(Vec<TreeVec<Vec<Vec<F>>>>, F), InvalidOodsSampleStructure> {
     let mut sampled_values = sampled_values.as_ref();
     let composition_values = sampled_values.pop().ok_or(InvalidOodsSampleStructure)?;
 
@@ -293,15 +431,11 @@ fn sampled_values_to_mask(
         })
         .collect_vec();
 
-    let composition_oods_value = SecureField::from_partial_evals(
-        composition_values
-            .iter()
-            .flatten()
-            .cloned()
-            .collect_vec()
-            .try_into()
-            .map_err(|_| InvalidOodsSampleStructure)?,
-    );
+    let composition_values = composition_values.iter().flatten().cloned().collect_vec();
+    if composition_values.len() != F::EXTENSION_DEGREE {
+        return Err(InvalidOodsSampleStructure);
+    }
+    let composition_oods_value = F::from_partial_evals(&composition_values);
 
     Ok((trace_oods_values, composition_oods_value))
 }
@@ -322,6 +456,8 @@ pub enum ProvingError {
         MAX_CIRCLE_DOMAIN_LOG_SIZE - LOG_BLOWUP_FACTOR
     )]
     MaxCompositionDegreeExceeded { degree: u32 },
+    #[error("Expected one trace per AIR, got {n_traces} traces for {n_airs} AIRs.")]
+    TraceCountMismatch { n_airs: usize, n_traces: usize },
     #[error("Constraints not satisfied.")]
     ConstraintsNotSatisfied,
 }
@@ -341,6 +477,8 @@ pub enum VerificationError {
     Fri(#[from] FriVerificationError),
     #[error(transparent)]
     ProofOfWork(#[from] ProofOfWorkVerificationError),
+    #[error(transparent)]
+    Lookup(#[from] LookupError),
 }
 
 #[cfg(test)]
@@ -348,6 +486,7 @@ mod tests {
     use num_traits::Zero;
 
     use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+    use crate::core::air::logup::LookupError;
     use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
     use crate::core::backend::cpu::CpuCircleEvaluation;
     use crate::core::backend::CpuBackend;
@@ -382,6 +521,14 @@ mod tests {
         fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
             InteractionElements::default()
         }
+
+        fn verify_lookups(
+            &self,
+            _interaction_elements: &InteractionElements,
+            _lookup_values: &LookupValues,
+        ) -> Result<(), LookupError> {
+            Ok(())
+        }
     }
 
     impl AirTraceGenerator<CpuBackend> for TestAir<TestComponent> {
@@ -1,8 +1,21 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::zip_eq;
+
+use super::air::Air;
 use super::backend::cpu::CpuCircleEvaluation;
+use super::backend::Backend;
 use super::channel::Blake2sChannel;
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
+use super::poly::circle::CircleEvaluation;
+use super::poly::BitReversedOrder;
+use super::prover::{prove, verify};
+use super::vcs::blake2_merkle::Blake2sMerkleHasher;
+use super::vcs::ops::MerkleOps;
+use super::{ColumnVec, InteractionElements};
 use crate::core::channel::Channel;
+use crate::trace_generation::AirTraceGenerator;
 
 pub fn secure_eval_to_base_eval<EvalOrder>(
     eval: &CpuCircleEvaluation<SecureField, EvalOrder>,
@@ -19,3 +32,22 @@ pub fn test_channel() -> Blake2sChannel {
     let seed = Blake2sHash::from(vec![0; 32]);
     Blake2sChannel::new(seed)
 }
+
+/// Draws an interaction element for each of the distinct `ids`, in their sorted order.
+pub fn draw_interaction_elements(
+    channel: &mut Blake2sChannel,
+    ids: impl IntoIterator<Item = String>,
+) -> InteractionElements {
+    let ids = ids.into_iter().collect::<BTreeSet<_>>();
+    let elements = channel.draw_felts(ids.len());
+    InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
+}
+
+/// Proves `trace` with `air` and verifies the proof, panicking on any error.
+pub fn prove_and_verify<B: Backend + MerkleOps<Blake2sMerkleHasher>>(
+    air: &(impl Air + AirTraceGenerator<B>),
+    trace: ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+) {
+    let proof = prove::<B, _>(air, &mut test_channel(), trace).unwrap();
+    verify(proof, air, &mut test_channel()).unwrap();
+}
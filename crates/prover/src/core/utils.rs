@@ -1,11 +1,11 @@
-use std::iter::Peekable;
+use std::iter::{zip, Peekable};
 use std::ops::Add;
 
-use num_traits::{One, Zero};
+use num_traits::Zero;
 
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
-use super::fields::ExtensionOf;
+use super::fields::{ExtensionOf, Field};
 
 pub trait IteratorMutExt<'a, T: 'a>: Iterator<Item = &'a mut T> {
     fn assign(self, other: impl IntoIterator<Item = T>)
@@ -67,17 +67,39 @@ pub(crate) fn previous_bit_reversed_circle_domain_index(
     i: usize,
     domain_log_size: u32,
     eval_log_size: u32,
+) -> usize {
+    offset_bit_reversed_circle_domain_index(i, domain_log_size, eval_log_size, -1)
+}
+
+/// Returns the index of the element `offset` steps after the `i`-th element in a bit reversed
+/// [super::poly::circle::CircleEvaluation] of log size `eval_log_size`, where a step is the step
+/// of a smaller domain of size `domain_log_size`.
+pub(crate) fn offset_bit_reversed_circle_domain_index(
+    i: usize,
+    domain_log_size: u32,
+    eval_log_size: u32,
+    offset: isize,
 ) -> usize {
     assert!(domain_log_size < eval_log_size);
-    let step_size = 1 << (eval_log_size - domain_log_size - 1) as usize;
-    let mut prev_index = bit_reverse_index(i, eval_log_size);
+    let step_size = offset * (1 << (eval_log_size - domain_log_size - 1)) as isize;
+    let mut offset_index = bit_reverse_index(i, eval_log_size);
     let half_size = 1 << (eval_log_size - 1);
-    if prev_index < half_size {
-        prev_index = (prev_index + half_size - step_size) % half_size;
+    // The second half of the circle domain is the conjugate of the first, traversed backwards.
+    if offset_index < half_size {
+        offset_index = (offset_index as isize + step_size).rem_euclid(half_size as isize) as usize;
     } else {
-        prev_index = ((prev_index + step_size) % half_size) + half_size;
+        offset_index =
+            (offset_index as isize - step_size).rem_euclid(half_size as isize) as usize + half_size;
     }
-    bit_reverse_index(prev_index, eval_log_size)
+    bit_reverse_index(offset_index, eval_log_size)
+}
+
+/// Returns the index of the `i`-th element of a coset of log size `log_size`, in coset order, in a
+/// bit reversed [super::poly::circle::CircleEvaluation] on the circle domain of that coset.
+pub(crate) fn coset_index_to_bit_reversed_circle_domain_index(i: usize, log_size: u32) -> usize {
+    let n = 1 << log_size;
+    let circle_domain_index = if i % 2 == 0 { i / 2 } else { n - 1 - i / 2 };
+    bit_reverse_index(circle_domain_index, log_size)
 }
 
 // TODO(AlonH): Pair both functions below with bit reverse. Consider removing both and calculating
@@ -125,15 +147,56 @@ pub fn bit_reverse<T>(v: &mut [T]) {
 }
 
 pub fn generate_secure_powers(felt: SecureField, n_powers: usize) -> Vec<SecureField> {
+    generate_powers(felt, n_powers)
+}
+
+/// Returns `[1, base, base^2, ..., base^(n_powers - 1)]`.
+pub fn generate_powers<F: Field>(base: F, n_powers: usize) -> Vec<F> {
     (0..n_powers)
-        .scan(SecureField::one(), |acc, _| {
+        .scan(F::one(), |acc, _| {
             let res = *acc;
-            *acc *= felt;
+            *acc *= base;
             Some(res)
         })
         .collect()
 }
 
+/// Returns the sum of the pointwise products of `lhs` and `rhs`.
+pub fn dot_product<F: Field>(lhs: &[F], rhs: &[F]) -> F {
+    assert_eq!(lhs.len(), rhs.len());
+    zip(lhs, rhs).map(|(&a, &b)| a * b).sum()
+}
+
+/// Evaluates the univariate polynomial with the given coefficients, ordered from the lowest
+/// degree, at `point`, using Horner's method.
+pub fn horner_eval<F: Field, E: ExtensionOf<F>>(coeffs: &[F], point: E) -> E {
+    coeffs
+        .iter()
+        .rfold(E::zero(), |acc, &coeff| acc * point + coeff)
+}
+
+/// Returns the inclusive prefix sums of `values`.
+pub fn prefix_sums<F: Field>(values: &[F]) -> Vec<F> {
+    values
+        .iter()
+        .scan(F::zero(), |acc, &value| {
+            *acc += value;
+            Some(*acc)
+        })
+        .collect()
+}
+
+/// Returns the inclusive prefix products of `values`.
+pub fn prefix_products<F: Field>(values: &[F]) -> Vec<F> {
+    values
+        .iter()
+        .scan(F::one(), |acc, &value| {
+            *acc *= value;
+            Some(*acc)
+        })
+        .collect()
+}
+
 /// Securely combines the given values using the given random alpha and z.
 /// Alpha and z should be secure field elements for soundness.
 pub fn shifted_secure_combination<F: ExtensionOf<BaseField>>(
@@ -160,7 +223,11 @@ mod tests {
     use crate::core::fields::FieldExpOps;
     use crate::core::poly::circle::CanonicCoset;
     use crate::core::poly::NaturalOrder;
-    use crate::core::utils::{bit_reverse, previous_bit_reversed_circle_domain_index};
+    use crate::core::utils::{
+        bit_reverse, circle_domain_order_to_coset_order,
+        coset_index_to_bit_reversed_circle_domain_index, offset_bit_reversed_circle_domain_index,
+        previous_bit_reversed_circle_domain_index,
+    };
     use crate::{m31, qm31};
 
     #[test]
@@ -200,6 +267,20 @@ mod tests {
         assert_eq!(powers, vec![]);
     }
 
+    #[test]
+    fn test_coset_index_to_bit_reversed_circle_domain_index() {
+        let log_size = 4;
+        let mut values = (0..1 << log_size).map(|i| m31!(i)).collect_vec();
+        bit_reverse(&mut values);
+        let coset_order =
+            circle_domain_order_to_coset_order(&(0..1 << log_size).map(|i| m31!(i)).collect_vec());
+
+        for (i, value) in coset_order.into_iter().enumerate() {
+            let index = coset_index_to_bit_reversed_circle_domain_index(i, log_size);
+            assert_eq!(values[index], value);
+        }
+    }
+
     #[test]
     fn test_previous_bit_reversed_circle_domain_index() {
         let log_size = 4;
@@ -257,4 +338,19 @@ mod tests {
 
         assert_eq!(neighbor_pairs, expected_neighbor_pairs);
     }
+
+    #[test]
+    fn test_offset_bit_reversed_circle_domain_index() {
+        let domain_log_size = 3;
+        let eval_log_size = 5;
+
+        for index in 0..1 << eval_log_size {
+            let offset_index =
+                offset_bit_reversed_circle_domain_index(index, domain_log_size, eval_log_size, 3);
+            let back = (0..3).fold(offset_index, |i, _| {
+                previous_bit_reversed_circle_domain_index(i, domain_log_size, eval_log_size)
+            });
+            assert_eq!(back, index);
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 use super::hasher::Name;
 
 // Wrapper for the blake3 hash type.
-#[derive(Clone, Copy, PartialEq, Default, Eq)]
+#[derive(Clone, Copy, PartialEq, Default, Eq, Serialize, Deserialize)]
 pub struct Blake3Hash([u8; 32]);
 
 impl From<Blake3Hash> for Vec<u8> {
@@ -1,5 +1,8 @@
 use std::fmt::Debug;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
 use crate::core::backend::{Col, ColumnOps};
 use crate::core::fields::m31::BaseField;
 
@@ -11,7 +14,7 @@ use crate::core::fields::m31::BaseField;
 /// At each layer, the tree may have multiple columns of the same length as the layer.
 /// Each node in that layer contains one value from each column.
 pub trait MerkleHasher: Debug {
-    type Hash: Copy + Clone + Eq + std::fmt::Debug;
+    type Hash: Copy + Clone + Eq + std::fmt::Debug + Serialize + DeserializeOwned;
     /// Hashes a single Merkle node. See [MerkleHasher] for more details.
     fn hash_node(
         children_hashes: Option<(Self::Hash, Self::Hash)>,
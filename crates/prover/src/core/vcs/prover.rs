@@ -1,8 +1,10 @@
 use std::cmp::Reverse;
 use std::collections::BTreeMap;
+use std::convert::Infallible;
 
 use educe::Educe;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
 use super::ops::{MerkleHasher, MerkleOps};
 use super::utils::{next_decommitment_node, option_flatten_peekable};
@@ -78,133 +80,184 @@ impl<B: MerkleOps<H>, H: MerkleHasher> MerkleProver<B, H> {
         queries_per_log_size: BTreeMap<u32, Vec<usize>>,
         columns: Vec<&Col<B, BaseField>>,
     ) -> (ColumnVec<Vec<BaseField>>, MerkleDecommitment<H>) {
-        // Check that queries are sorted and deduped.
-        // TODO(andrew): Consider using a Queries struct to prevent this.
-        for queries in queries_per_log_size.values() {
-            assert!(
-                queries.windows(2).all(|w| w[0] < w[1]),
-                "Queries are not sorted."
-            );
-        }
+        let layers = self.layers.iter().map(ColumnRef).collect_vec();
+        let columns = columns.into_iter().map(ColumnRef).collect_vec();
+        decommit_on_layers(&layers, queries_per_log_size, columns.iter().collect())
+            .unwrap_or_else(|e| match e {})
+    }
 
-        // Prepare output buffers.
-        let mut queried_values_by_layer = vec![];
-        let mut decommitment = MerkleDecommitment::empty();
+    pub fn root(&self) -> H::Hash {
+        self.layers.first().unwrap().at(0)
+    }
+}
 
-        // Sort columns by layer.
-        let mut columns_by_layer = columns
-            .iter()
-            .sorted_by_key(|c| Reverse(c.len()))
-            .peekable();
+/// Read access to the values of a column or a Merkle layer, as needed for decommitment.
+/// Allows decommitting columns that are not held in memory (see
+/// [OutOfCoreTreeProver](crate::core::pcs::OutOfCoreTreeProver)).
+pub trait ReadColumn<T> {
+    /// The error of reading a value.
+    type Error;
+    /// Returns the number of values in the column.
+    fn read_len(&self) -> usize;
+    /// Reads the value at the given index.
+    fn read_at(&self, index: usize) -> Result<T, Self::Error>;
+}
 
-        let mut last_layer_queries = vec![];
-        for layer_log_size in (0..self.layers.len() as u32).rev() {
-            // Prepare write buffer for queried values to the current layer.
-            let mut layer_queried_values = vec![];
+/// A backend [Column], read through [ReadColumn].
+struct ColumnRef<'a, C>(&'a C);
 
-            // Prepare write buffer for queries to the current layer. This will propagate to the
-            // next layer.
-            let mut layer_total_queries = vec![];
+impl<T, C: Column<T>> ReadColumn<T> for ColumnRef<'_, C> {
+    type Error = Infallible;
 
-            // Each layer node is a hash of column values as previous layer hashes.
-            // Prepare the relevant columns and previous layer hashes to read from.
-            let layer_columns = columns_by_layer
-                .peek_take_while(|column| column.len().ilog2() == layer_log_size)
-                .collect_vec();
-            let previous_layer_hashes = self.layers.get(layer_log_size as usize + 1);
-
-            // Queries to this layer come from queried node in the previous layer and queried
-            // columns in this one.
-            let mut prev_layer_queries = last_layer_queries.into_iter().peekable();
-            let mut layer_column_queries =
-                option_flatten_peekable(queries_per_log_size.get(&layer_log_size));
-
-            // Merge previous layer queries and column queries.
-            while let Some(node_index) =
-                next_decommitment_node(&mut prev_layer_queries, &mut layer_column_queries)
-            {
-                if let Some(previous_layer_hashes) = previous_layer_hashes {
-                    // If the left child was not computed, add it to the witness.
-                    if prev_layer_queries.next_if_eq(&(2 * node_index)).is_none() {
-                        decommitment
-                            .hash_witness
-                            .push(previous_layer_hashes.at(2 * node_index));
-                    }
-
-                    // If the right child was not computed, add it to the witness.
-                    if prev_layer_queries
-                        .next_if_eq(&(2 * node_index + 1))
-                        .is_none()
-                    {
-                        decommitment
-                            .hash_witness
-                            .push(previous_layer_hashes.at(2 * node_index + 1));
-                    }
-                }
+    fn read_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn read_at(&self, index: usize) -> Result<T, Infallible> {
+        Ok(self.0.at(index))
+    }
+}
+
+/// Decommits to columns on the given queries, given the layers of the Merkle tree committing to
+/// them. See [MerkleProver::decommit].
+/// Fails if reading from the layers or the columns fails.
+#[allow(clippy::type_complexity)]
+pub(crate) fn decommit_on_layers<H, L, C, E>(
+    layers: &[L],
+    queries_per_log_size: BTreeMap<u32, Vec<usize>>,
+    columns: Vec<&C>,
+) -> Result<(ColumnVec<Vec<BaseField>>, MerkleDecommitment<H>), E>
+where
+    H: MerkleHasher,
+    L: ReadColumn<H::Hash, Error = E>,
+    C: ReadColumn<BaseField, Error = E> + ?Sized,
+{
+    // Check that queries are sorted and deduped.
+    // TODO(andrew): Consider using a Queries struct to prevent this.
+    for queries in queries_per_log_size.values() {
+        assert!(
+            queries.windows(2).all(|w| w[0] < w[1]),
+            "Queries are not sorted."
+        );
+    }
+
+    // Prepare output buffers.
+    let mut queried_values_by_layer = vec![];
+    let mut decommitment = MerkleDecommitment::empty();
 
-                // If the column values were queried, return them.
-                let node_values = layer_columns.iter().map(|c| c.at(node_index));
-                if layer_column_queries.next_if_eq(&node_index).is_some() {
-                    layer_queried_values.push(node_values.collect_vec());
-                } else {
-                    // Otherwise, add them to the witness.
-                    decommitment.column_witness.extend(node_values);
+    // Sort columns by layer.
+    let mut columns_by_layer = columns
+        .iter()
+        .sorted_by_key(|c| Reverse(c.read_len()))
+        .peekable();
+
+    let mut last_layer_queries = vec![];
+    for layer_log_size in (0..layers.len() as u32).rev() {
+        // Prepare write buffer for queried values to the current layer.
+        let mut layer_queried_values = vec![];
+
+        // Prepare write buffer for queries to the current layer. This will propagate to the
+        // next layer.
+        let mut layer_total_queries = vec![];
+
+        // Each layer node is a hash of column values as previous layer hashes.
+        // Prepare the relevant columns and previous layer hashes to read from.
+        let layer_columns = columns_by_layer
+            .peek_take_while(|column| column.read_len().ilog2() == layer_log_size)
+            .collect_vec();
+        let previous_layer_hashes = layers.get(layer_log_size as usize + 1);
+
+        // Queries to this layer come from queried node in the previous layer and queried
+        // columns in this one.
+        let mut prev_layer_queries = last_layer_queries.into_iter().peekable();
+        let mut layer_column_queries =
+            option_flatten_peekable(queries_per_log_size.get(&layer_log_size));
+
+        // Merge previous layer queries and column queries.
+        while let Some(node_index) =
+            next_decommitment_node(&mut prev_layer_queries, &mut layer_column_queries)
+        {
+            if let Some(previous_layer_hashes) = previous_layer_hashes {
+                // If the left child was not computed, add it to the witness.
+                if prev_layer_queries.next_if_eq(&(2 * node_index)).is_none() {
+                    decommitment
+                        .hash_witness
+                        .push(previous_layer_hashes.read_at(2 * node_index)?);
                 }
 
-                layer_total_queries.push(node_index);
+                // If the right child was not computed, add it to the witness.
+                if prev_layer_queries
+                    .next_if_eq(&(2 * node_index + 1))
+                    .is_none()
+                {
+                    decommitment
+                        .hash_witness
+                        .push(previous_layer_hashes.read_at(2 * node_index + 1)?);
+                }
             }
 
-            queried_values_by_layer.push(layer_queried_values);
+            // If the column values were queried, return them.
+            let node_values = layer_columns
+                .iter()
+                .map(|c| c.read_at(node_index))
+                .collect::<Result<Vec<_>, _>>()?;
+            if layer_column_queries.next_if_eq(&node_index).is_some() {
+                layer_queried_values.push(node_values);
+            } else {
+                // Otherwise, add them to the witness.
+                decommitment.column_witness.extend(node_values);
+            }
 
-            // Propagate queries to the next layer.
-            last_layer_queries = layer_total_queries;
+            layer_total_queries.push(node_index);
         }
-        queried_values_by_layer.reverse();
 
-        // Rearrange returned queried values according to input, and not by layer.
-        let queried_values = Self::rearrange_queried_values(queried_values_by_layer, columns);
+        queried_values_by_layer.push(layer_queried_values);
 
-        (queried_values, decommitment)
+        // Propagate queries to the next layer.
+        last_layer_queries = layer_total_queries;
     }
+    queried_values_by_layer.reverse();
 
-    /// Given queried values by layer, rearranges in the order of input columns.
-    fn rearrange_queried_values(
-        queried_values_by_layer: Vec<Vec<Vec<BaseField>>>,
-        columns: Vec<&Col<B, BaseField>>,
-    ) -> Vec<Vec<BaseField>> {
-        // Turn each column queried values into an iterator.
-        let mut queried_values_by_layer = queried_values_by_layer
-            .into_iter()
-            .map(|layer_results| {
-                layer_results
-                    .into_iter()
-                    .map(|x| x.into_iter())
-                    .collect_vec()
-            })
-            .collect_vec();
+    // Rearrange returned queried values according to input, and not by layer.
+    let queried_values = rearrange_queried_values(queried_values_by_layer, columns);
 
-        // For each input column, fetch the queried values from the corresponding layer.
-        let queried_values = columns
-            .iter()
-            .map(|column| {
-                queried_values_by_layer
-                    .get_mut(column.len().ilog2() as usize)
-                    .unwrap()
-                    .iter_mut()
-                    .map(|x| x.next().unwrap())
-                    .collect_vec()
-            })
-            .collect_vec();
-        queried_values
-    }
+    Ok((queried_values, decommitment))
+}
 
-    pub fn root(&self) -> H::Hash {
-        self.layers.first().unwrap().at(0)
-    }
+/// Given queried values by layer, rearranges in the order of input columns.
+fn rearrange_queried_values<C: ReadColumn<BaseField> + ?Sized>(
+    queried_values_by_layer: Vec<Vec<Vec<BaseField>>>,
+    columns: Vec<&C>,
+) -> Vec<Vec<BaseField>> {
+    // Turn each column queried values into an iterator.
+    let mut queried_values_by_layer = queried_values_by_layer
+        .into_iter()
+        .map(|layer_results| {
+            layer_results
+                .into_iter()
+                .map(|x| x.into_iter())
+                .collect_vec()
+        })
+        .collect_vec();
+
+    // For each input column, fetch the queried values from the corresponding layer.
+    let queried_values = columns
+        .iter()
+        .map(|column| {
+            queried_values_by_layer
+                .get_mut(column.read_len().ilog2() as usize)
+                .unwrap()
+                .iter_mut()
+                .map(|x| x.next().unwrap())
+                .collect_vec()
+        })
+        .collect_vec();
+    queried_values
 }
 
-#[derive(Debug, Educe)]
+#[derive(Debug, Educe, Serialize, Deserialize)]
 #[educe(Clone)]
+#[serde(bound = "")]
 pub struct MerkleDecommitment<H: MerkleHasher> {
     /// Hash values that the verifier needs but cannot deduce from previous computations, in the
     /// order they are needed.
@@ -0,0 +1,439 @@
+use std::collections::BTreeSet;
+use std::ops::{Add, Mul, Range, Sub};
+
+use itertools::Itertools;
+use num_traits::{One, Zero};
+
+use crate::components::bitwise::{BitwiseComponent, Xor};
+use crate::components::range_check::RangeCheckComponent;
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{Logup, LogupOps, LookupEntry, Multiplicity};
+use crate::core::air::{Component, ComponentProver, ComponentTrace};
+use crate::core::backend::Column;
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::coset_vanishing;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::FieldExpOps;
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::CanonicCoset;
+use crate::core::prover::BASE_TRACE;
+use crate::core::vcs::blake2s_ref::{IV, SIGMA};
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+
+/// The log of the size of the pieces XOR'ed words are split into, and of their XOR table.
+pub const LOG_XOR_LIMB: u32 = 4;
+/// The log of the size of the pieces of 4 bits split by the rotation by 7, besides single bits,
+/// whose XORs are constrained directly.
+pub const LOG_SMALL_XOR_LIMB: u32 = 3;
+/// The log of the range of the bytes of the message words.
+pub const LOG_RANGE: u32 = 8;
+pub const XOR_ID: &str = "blake2s_xor";
+pub const SMALL_XOR_ID: &str = "blake2s_small_xor";
+pub const RANGE_CHECK_ID: &str = "blake2s_range_check";
+
+const N_OUTPUT_COLUMNS: usize = 16;
+/// The state words each G function mixes, in the order of the G functions in a round.
+const G_STATE_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The operations of a compression on words.
+///
+/// The compression is written once over these operations, and run both to write the trace and to
+/// evaluate the constraints, so that both agree on the layout of the columns.
+pub(crate) trait Blake2sOps {
+    type Word: Copy;
+
+    /// Returns an input word of the compression, whose limbs are committed. Input words are
+    /// range checked by the XORs they go through.
+    fn input(&mut self) -> Self::Word;
+
+    /// Returns a message word, whose limbs and bytes are committed. Message words are only added,
+    /// so their bytes are range checked.
+    fn message(&mut self) -> Self::Word;
+
+    fn constant(&mut self, value: u32) -> Self::Word;
+
+    /// Returns the sum of 2 or 3 words modulo `2^32`, whose limbs are committed.
+    fn add(&mut self, words: &[Self::Word]) -> Self::Word;
+
+    /// Returns `(a ^ b) >>> r`.
+    ///
+    /// The [xor_pieces] of `a`, `b` and `a ^ b` are committed, and looked up in the XOR tables,
+    /// which range checks `a` and `b`. The rotation recomposes the pieces of `a ^ b` at their
+    /// rotated offsets.
+    fn xor_rotr(&mut self, a: Self::Word, b: Self::Word, r: u32) -> Self::Word;
+
+    /// Returns an output word of the compression, whose limbs are committed.
+    fn output(&mut self, word: Self::Word) -> Self::Word;
+}
+
+/// Runs a compression on `ops`, as [compress](crate::core::vcs::blake2s_ref::compress) does on
+/// words, and returns its output.
+///
+/// The input words are read in the order: the 8 chaining words, the 16 message words, the low and
+/// high counters, and the last block and last node flags.
+pub(crate) fn compress<O: Blake2sOps>(ops: &mut O) -> [O::Word; 8] {
+    let h: [_; 8] = std::array::from_fn(|_| ops.input());
+    let m: [_; 16] = std::array::from_fn(|_| ops.message());
+    let counters_and_flags: [_; 4] = std::array::from_fn(|_| ops.input());
+    let iv = IV.map(|word| ops.constant(word));
+
+    let mut v: [_; 16] = std::array::from_fn(|i| if i < 8 { h[i] } else { iv[i - 8] });
+    for (i, word) in counters_and_flags.into_iter().enumerate() {
+        v[12 + i] = ops.xor_rotr(iv[4 + i], word, 0);
+    }
+    for sigma in SIGMA {
+        for (i, state_indices) in G_STATE_INDICES.into_iter().enumerate() {
+            let message = [m[sigma[2 * i] as usize], m[sigma[2 * i + 1] as usize]];
+            g(ops, &mut v, state_indices, message);
+        }
+    }
+
+    let output: [_; 8] = std::array::from_fn(|i| {
+        let word = ops.xor_rotr(h[i], v[i], 0);
+        ops.xor_rotr(word, v[i + 8], 0)
+    });
+    output.map(|word| ops.output(word))
+}
+
+/// The G function, mixing 4 words of the state with 2 message words.
+fn g<O: Blake2sOps>(
+    ops: &mut O,
+    v: &mut [O::Word; 16],
+    [a, b, c, d]: [usize; 4],
+    [m0, m1]: [O::Word; 2],
+) {
+    v[a] = ops.add(&[v[a], v[b], m0]);
+    v[d] = ops.xor_rotr(v[d], v[a], 16);
+    v[c] = ops.add(&[v[c], v[d]]);
+    v[b] = ops.xor_rotr(v[b], v[c], 12);
+    v[a] = ops.add(&[v[a], v[b], m1]);
+    v[d] = ops.xor_rotr(v[d], v[a], 8);
+    v[c] = ops.add(&[v[c], v[d]]);
+    v[b] = ops.xor_rotr(v[b], v[c], 7);
+}
+
+/// Returns the offsets and sizes of the pieces the operands of an XOR rotated by `r` are split
+/// into: pieces of [LOG_XOR_LIMB] bits, split further at the bits rotated to the start of a limb.
+pub(crate) fn xor_pieces(r: u32) -> Vec<(u32, u32)> {
+    (0..=32)
+        .step_by(LOG_XOR_LIMB as usize)
+        .chain([r % 16, r % 16 + 16])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .tuple_windows()
+        .map(|(start, end)| (start, end - start))
+        .collect()
+}
+
+/// Returns the relation of the XOR table of pieces of `n_bits` bits.
+fn xor_relation(n_bits: u32) -> String {
+    match n_bits {
+        LOG_XOR_LIMB => BitwiseComponent::<Xor, LOG_XOR_LIMB>::relation(),
+        LOG_SMALL_XOR_LIMB => BitwiseComponent::<Xor, LOG_SMALL_XOR_LIMB>::relation(),
+        _ => unreachable!("no XOR table of {n_bits} bits"),
+    }
+}
+
+/// A 32-bit word, as its low and high 16-bit limbs.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Word<F> {
+    lo: F,
+    hi: F,
+}
+
+/// Evaluates the constraints of a compression on the values of its columns, given by `mask`.
+struct ConstraintEvaluator<F, M: Fn(usize) -> F> {
+    mask: M,
+    col_index: usize,
+    constraints: Vec<F>,
+    /// The lookups of the columns, only recorded to lay out the component.
+    lookups: Option<Vec<LookupEntry>>,
+}
+
+impl<F, M: Fn(usize) -> F> ConstraintEvaluator<F, M> {
+    fn new(mask: M) -> Self {
+        Self {
+            mask,
+            col_index: 0,
+            constraints: vec![],
+            lookups: None,
+        }
+    }
+
+    fn next(&mut self) -> F {
+        let res = (self.mask)(self.col_index);
+        self.col_index += 1;
+        res
+    }
+
+    fn next_word(&mut self) -> Word<F> {
+        Word {
+            lo: self.next(),
+            hi: self.next(),
+        }
+    }
+
+    fn lookup(&mut self, relation: String, values: Vec<usize>) {
+        if let Some(lookups) = &mut self.lookups {
+            lookups.push(LookupEntry::uses(relation, values, Multiplicity::One));
+        }
+    }
+}
+
+impl<F, M: Fn(usize) -> F> Blake2sOps for ConstraintEvaluator<F, M>
+where
+    F: Copy
+        + From<BaseField>
+        + Add<Output = F>
+        + Sub<Output = F>
+        + Mul<Output = F>
+        + Mul<BaseField, Output = F>,
+{
+    type Word = Word<F>;
+
+    fn input(&mut self) -> Word<F> {
+        self.next_word()
+    }
+
+    fn message(&mut self) -> Word<F> {
+        let word = self.next_word();
+        let first_byte = self.col_index;
+        let bytes: [_; 4] = std::array::from_fn(|_| self.next());
+        for i in 0..4 {
+            self.lookup(
+                RangeCheckComponent::<LOG_RANGE>::relation(),
+                vec![first_byte + i],
+            );
+        }
+        let byte_shift = BaseField::from(1 << 8);
+        self.constraints.extend([
+            word.lo - (bytes[0] + bytes[1] * byte_shift),
+            word.hi - (bytes[2] + bytes[3] * byte_shift),
+        ]);
+        word
+    }
+
+    fn constant(&mut self, value: u32) -> Word<F> {
+        Word {
+            lo: BaseField::from(value & 0xffff).into(),
+            hi: BaseField::from(value >> 16).into(),
+        }
+    }
+
+    fn add(&mut self, words: &[Word<F>]) -> Word<F> {
+        assert!(words.len() <= 3);
+        let sum = self.next_word();
+        // 2^-16, as 2^31 = 1.
+        let shift_inverse = BaseField::from(1 << 15);
+        let carry_lo = (words
+            .iter()
+            .fold(F::from(BaseField::zero()), |acc, word| acc + word.lo)
+            - sum.lo)
+            * shift_inverse;
+        let carry_hi =
+            (words.iter().fold(carry_lo, |acc, word| acc + word.hi) - sum.hi) * shift_inverse;
+        // The carries are at most 2, as at most 3 words are added.
+        for carry in [carry_lo, carry_hi] {
+            self.constraints.push(
+                carry * (carry - BaseField::one().into()) * (carry - BaseField::from(2).into()),
+            );
+        }
+        sum
+    }
+
+    fn xor_rotr(&mut self, a: Word<F>, b: Word<F>, r: u32) -> Word<F> {
+        let zero = F::from(BaseField::zero());
+        let [mut a_limbs, mut b_limbs, mut res_limbs] = [[zero; 2]; 3];
+        for (start, n_bits) in xor_pieces(r) {
+            let first_column = self.col_index;
+            let [a_piece, b_piece, res_piece] = std::array::from_fn(|_| self.next());
+            if n_bits == 1 {
+                let one = F::from(BaseField::one());
+                self.constraints.extend([
+                    a_piece * (a_piece - one),
+                    b_piece * (b_piece - one),
+                    res_piece - (a_piece + b_piece - a_piece * b_piece * BaseField::from(2)),
+                ]);
+            } else {
+                self.lookup(
+                    xor_relation(n_bits),
+                    (first_column..first_column + 3).collect(),
+                );
+            }
+            let shift = BaseField::from(1 << (start % 16));
+            a_limbs[start as usize / 16] = a_limbs[start as usize / 16] + a_piece * shift;
+            b_limbs[start as usize / 16] = b_limbs[start as usize / 16] + b_piece * shift;
+            let rotated_start = (start + 32 - r) % 32;
+            let rotated_shift = BaseField::from(1 << (rotated_start % 16));
+            res_limbs[rotated_start as usize / 16] =
+                res_limbs[rotated_start as usize / 16] + res_piece * rotated_shift;
+        }
+        for (word, limbs) in [(a, a_limbs), (b, b_limbs)] {
+            self.constraints
+                .extend([word.lo - limbs[0], word.hi - limbs[1]]);
+        }
+        Word {
+            lo: res_limbs[0],
+            hi: res_limbs[1],
+        }
+    }
+
+    fn output(&mut self, word: Word<F>) -> Word<F> {
+        let output = self.next_word();
+        self.constraints
+            .extend([output.lo - word.lo, output.hi - word.hi]);
+        output
+    }
+}
+
+/// Component proving Blake2s compressions, one in each row. See the [module](super)
+/// documentation.
+#[derive(Clone, Debug)]
+pub struct Blake2sComponent {
+    pub log_n_rows: u32,
+    n_columns: usize,
+    n_compression_constraints: usize,
+    lookup_entries: Vec<LookupEntry>,
+}
+
+impl Blake2sComponent {
+    pub fn new(log_n_rows: u32) -> Self {
+        // Lays out the columns by evaluating the constraints on dummy values.
+        let mut evaluator = ConstraintEvaluator::new(|_| BaseField::zero());
+        evaluator.lookups = Some(vec![]);
+        compress(&mut evaluator);
+        Self {
+            log_n_rows,
+            n_columns: evaluator.col_index,
+            n_compression_constraints: evaluator.constraints.len(),
+            lookup_entries: evaluator.lookups.unwrap(),
+        }
+    }
+
+    pub fn n_columns(&self) -> usize {
+        self.n_columns
+    }
+
+    /// Returns the columns of the low and high limbs of the output words, which come last.
+    pub fn output_columns(&self) -> Range<usize> {
+        self.n_columns - N_OUTPUT_COLUMNS..self.n_columns
+    }
+
+    pub fn logup(&self) -> Logup {
+        Logup::new("blake2s", self.log_n_rows, self.lookup_entries.clone())
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.logup().interaction_element_ids()
+    }
+}
+
+impl Component for Blake2sComponent {
+    fn n_constraints(&self) -> usize {
+        self.n_compression_constraints + self.logup().n_constraints()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        // The carry constraints have degree 3, the highest of the component. Their quotients by the
+        // vanishing polynomial of the `2^log_n_rows` rows have degree below `2^(log_n_rows + 1)`,
+        // so they still fit the domain of the degree 2 lookup constraints.
+        self.logup().max_constraint_log_degree_bound()
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        2
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        TreeVec::new(vec![
+            vec![self.log_n_rows; self.n_columns],
+            self.logup().interaction_log_degree_bounds(),
+        ])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        TreeVec::new(vec![
+            vec![vec![point]; self.n_columns],
+            self.logup().interaction_mask_points(point),
+        ])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let domain = CanonicCoset::new(self.log_n_rows).coset;
+        let denom_inverse = coset_vanishing(domain, point).inverse();
+        let mut evaluator = ConstraintEvaluator::new(|column| mask[BASE_TRACE][column][0]);
+        compress(&mut evaluator);
+        for constraint in evaluator.constraints {
+            evaluation_accumulator.accumulate(constraint * denom_inverse);
+        }
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+}
+
+impl<B: LogupOps> ComponentProver<B> for Blake2sComponent {
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[BASE_TRACE][0].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let columns = &trace.evals[BASE_TRACE];
+        let domain = CanonicCoset::new(self.log_n_rows).coset;
+
+        let [accum] =
+            evaluation_accumulator.columns([(eval_log_size, self.n_compression_constraints)]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| [(coset_vanishing(domain, point), BaseField::one())],
+            |i, [denom_inverse]| {
+                let mut evaluator = ConstraintEvaluator::new(|column| columns[column].values.at(i));
+                compress(&mut evaluator);
+                evaluator
+                    .constraints
+                    .into_iter()
+                    .map(move |constraint| constraint * denom_inverse)
+            },
+        );
+
+        self.logup().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+
+    fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        self.logup().lookup_values(trace)
+    }
+}
@@ -0,0 +1,257 @@
+//! An example AIR proving Blake2s compressions, one in each row.
+//!
+//! Words are represented by their low and high 16-bit limbs. Additions are constrained through
+//! the carries of their limbs. XORs split their operands into pieces of at most [LOG_XOR_LIMB]
+//! bits, and look up the pieces and their XORs in [BitwiseComponent] tables. Rotations are free,
+//! as the pieces of an XOR are split at the bits its rotation moves, and recomposed at their
+//! rotated offsets. The bytes of the message words are looked up in a [RangeCheckComponent], and
+//! all the other words are range checked by the XORs they go through.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter::zip;
+
+use itertools::zip_eq;
+
+use self::component::{
+    Blake2sComponent, LOG_RANGE, LOG_SMALL_XOR_LIMB, LOG_XOR_LIMB, RANGE_CHECK_ID, SMALL_XOR_ID,
+    XOR_ID,
+};
+use self::trace_gen::{Blake2sInput, Blake2sTraceGenerator};
+use crate::components::bitwise::{BitwiseComponent, BitwiseTraceGenerator, Xor};
+use crate::components::range_check::{RangeCheckComponent, RangeCheckTraceGenerator};
+use crate::core::air::logup::{verify_balanced, Logup, LogupOps, LookupError};
+use crate::core::air::{Air, AirProver, Component, ComponentProver};
+use crate::core::backend::simd::SimdBackend;
+use crate::core::channel::{Blake2sChannel, Channel};
+use crate::core::fields::m31::BaseField;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+pub mod component;
+pub mod trace_gen;
+
+const BLAKE2S_ID: &str = "blake2s";
+
+#[derive(Clone)]
+pub struct Blake2sAir {
+    pub blake2s: Blake2sComponent,
+    pub xor: BitwiseComponent<Xor, LOG_XOR_LIMB>,
+    pub small_xor: BitwiseComponent<Xor, LOG_SMALL_XOR_LIMB>,
+    pub range_check: RangeCheckComponent<LOG_RANGE>,
+}
+
+impl Blake2sAir {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self {
+            blake2s: Blake2sComponent::new(log_n_rows),
+            xor: BitwiseComponent::new(),
+            small_xor: BitwiseComponent::new(),
+            range_check: RangeCheckComponent,
+        }
+    }
+
+    /// Returns the lookups of the components, in their order.
+    fn logups(&self) -> [Logup; 4] {
+        [
+            self.blake2s.logup(),
+            self.xor.logup(),
+            self.small_xor.logup(),
+            self.range_check.logup(),
+        ]
+    }
+}
+
+impl Air for Blake2sAir {
+    fn components(&self) -> Vec<&dyn Component> {
+        vec![&self.blake2s, &self.xor, &self.small_xor, &self.range_check]
+    }
+}
+
+impl AirTraceVerifier for Blake2sAir {
+    fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+        let ids = self
+            .logups()
+            .iter()
+            .flat_map(|logup| logup.interaction_element_ids())
+            .collect::<BTreeSet<_>>();
+        let elements = channel.draw_felts(ids.len());
+        InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
+    }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        verify_balanced(&self.logups(), lookup_values)
+    }
+}
+
+impl<B: LogupOps + 'static> AirTraceGenerator<B> for Blake2sAir {
+    fn interact(
+        &self,
+        trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let mut trace = &trace[..];
+        let mut interaction_trace = vec![];
+        for (component, logup) in zip(self.components(), self.logups()) {
+            let n_columns = component.trace_log_degree_bounds()[BASE_TRACE].len();
+            let (component_trace, rest) = trace.split_at(n_columns);
+            interaction_trace
+                .extend(logup.write_interaction_trace(&component_trace.iter().collect(), elements));
+            trace = rest;
+        }
+        interaction_trace
+    }
+
+    fn to_air_prover(&self) -> impl AirProver<B> {
+        self.clone()
+    }
+
+    fn composition_log_degree_bound(&self) -> u32 {
+        self.components()
+            .iter()
+            .map(|component| component.max_constraint_log_degree_bound())
+            .max()
+            .unwrap()
+    }
+}
+
+impl<B: LogupOps> AirProver<B> for Blake2sAir {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<B>> {
+        vec![&self.blake2s, &self.xor, &self.small_xor, &self.range_check]
+    }
+}
+
+/// Writes the trace of [Blake2sAir] for the compressions of `inputs`, in `2^log_n_rows` rows.
+pub fn write_trace(
+    inputs: &[Blake2sInput],
+    log_n_rows: u32,
+) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+    let mut registry = ComponentGenerationRegistry::default();
+    registry.register(BLAKE2S_ID, Blake2sTraceGenerator::new(log_n_rows));
+    registry.register(
+        XOR_ID,
+        BitwiseTraceGenerator::<SimdBackend, Xor, LOG_XOR_LIMB>::new(),
+    );
+    registry.register(
+        SMALL_XOR_ID,
+        BitwiseTraceGenerator::<SimdBackend, Xor, LOG_SMALL_XOR_LIMB>::new(),
+    );
+    registry.register(
+        RANGE_CHECK_ID,
+        RangeCheckTraceGenerator::<SimdBackend, LOG_RANGE>::new(),
+    );
+    registry
+        .get_generator_mut::<Blake2sTraceGenerator>(BLAKE2S_ID)
+        .add_inputs(&inputs.to_vec());
+
+    let mut trace = Blake2sTraceGenerator::write_trace(BLAKE2S_ID, &mut registry);
+    trace.extend(
+        BitwiseTraceGenerator::<SimdBackend, Xor, LOG_XOR_LIMB>::write_trace(XOR_ID, &mut registry),
+    );
+    trace.extend(
+        BitwiseTraceGenerator::<SimdBackend, Xor, LOG_SMALL_XOR_LIMB>::write_trace(
+            SMALL_XOR_ID,
+            &mut registry,
+        ),
+    );
+    trace.extend(
+        RangeCheckTraceGenerator::<SimdBackend, LOG_RANGE>::write_trace(
+            RANGE_CHECK_ID,
+            &mut registry,
+        ),
+    );
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::trace_gen::Blake2sInput;
+    use super::{write_trace, Blake2sAir};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::Column;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::poly::circle::CircleEvaluation;
+    use crate::core::prover::{prove, verify, ProvingError};
+    use crate::core::test_utils::test_channel;
+    use crate::core::vcs::blake2s_ref;
+
+    const LOG_N_ROWS: u32 = 4;
+
+    fn random_inputs(n_inputs: usize) -> Vec<Blake2sInput> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..n_inputs)
+            .map(|_| Blake2sInput {
+                h: rng.gen(),
+                m: rng.gen(),
+                count_low: rng.gen(),
+                count_high: rng.gen(),
+                last_block: rng.gen(),
+                last_node: rng.gen(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_blake2s_prove() {
+        let air = Blake2sAir::new(LOG_N_ROWS);
+        let trace = write_trace(&random_inputs(1 << LOG_N_ROWS), LOG_N_ROWS);
+
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        verify(proof, &air, &mut test_channel()).unwrap();
+    }
+
+    #[test]
+    fn test_trace_outputs_match_reference() {
+        let mut inputs = random_inputs(10);
+        let air = Blake2sAir::new(LOG_N_ROWS);
+
+        let trace = write_trace(&inputs, LOG_N_ROWS);
+
+        // The remaining rows compress zeroed inputs.
+        inputs.resize(1 << LOG_N_ROWS, Blake2sInput::default());
+        let output_limbs = trace[air.blake2s.output_columns()]
+            .iter()
+            .map(|column| column.values.to_cpu())
+            .collect_vec();
+        for (row, input) in inputs.iter().enumerate() {
+            let output: [u32; 8] = std::array::from_fn(|i| {
+                output_limbs[2 * i][row].0 + (output_limbs[2 * i + 1][row].0 << 16)
+            });
+            let expected = blake2s_ref::compress(
+                input.h,
+                input.m,
+                input.count_low,
+                input.count_high,
+                input.last_block,
+                input.last_node,
+            );
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn test_wrong_output_fails() {
+        let air = Blake2sAir::new(LOG_N_ROWS);
+        let mut trace = write_trace(&random_inputs(1 << LOG_N_ROWS), LOG_N_ROWS);
+        let column = air.blake2s.output_columns().start;
+        let mut values = trace[column].values.to_cpu();
+        values[3] += BaseField::from(1);
+        trace[column] = CircleEvaluation::new(trace[column].domain, values.into_iter().collect());
+
+        let result = prove::<SimdBackend, _>(&air, &mut test_channel(), trace);
+
+        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
+    }
+}
@@ -0,0 +1,244 @@
+use std::simd::u32x16;
+
+use itertools::Itertools;
+
+use super::component::{
+    compress, xor_pieces, Blake2sComponent, Blake2sOps, LOG_RANGE, LOG_SMALL_XOR_LIMB,
+    LOG_XOR_LIMB, RANGE_CHECK_ID, SMALL_XOR_ID, XOR_ID,
+};
+use crate::components::bitwise::{BitwiseTraceGenerator, Xor};
+use crate::components::range_check::RangeCheckTraceGenerator;
+use crate::core::backend::simd::column::BaseFieldVec;
+use crate::core::backend::simd::m31::{PackedBaseField, LOG_N_LANES, N_LANES};
+use crate::core::backend::simd::SimdBackend;
+use crate::core::backend::Column;
+use crate::core::fields::m31::BaseField;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::{ColumnVec, InteractionElements};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+/// The inputs of a compression, as in [compress](crate::core::vcs::blake2s_ref::compress).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Blake2sInput {
+    pub h: [u32; 8],
+    pub m: [u32; 16],
+    pub count_low: u32,
+    pub count_high: u32,
+    pub last_block: u32,
+    pub last_node: u32,
+}
+
+impl Blake2sInput {
+    /// Returns the input words, in the order the compression reads them.
+    fn words(&self) -> impl Iterator<Item = u32> + '_ {
+        self.h.iter().chain(&self.m).copied().chain([
+            self.count_low,
+            self.count_high,
+            self.last_block,
+            self.last_node,
+        ])
+    }
+}
+
+/// The pieces and bytes looked up by the compressions, added to the tables once the trace is
+/// written.
+#[derive(Default)]
+struct TableInputs {
+    xor: Vec<[BaseField; 2]>,
+    small_xor: Vec<[BaseField; 2]>,
+    bytes: Vec<BaseField>,
+}
+
+/// Writes the columns of [N_LANES] compressions at a time, one vector of each column.
+struct TraceWriter<'a> {
+    /// The remaining input words of the compressions.
+    inputs: std::vec::IntoIter<u32x16>,
+    trace: &'a mut [BaseFieldVec],
+    vec_index: usize,
+    col_index: usize,
+    table_inputs: &'a mut TableInputs,
+}
+
+impl<'a> TraceWriter<'a> {
+    fn write(&mut self, values: u32x16) {
+        // Safe, as the values written are limbs of at most 16 bits.
+        self.trace[self.col_index].data[self.vec_index] =
+            unsafe { PackedBaseField::from_simd_unchecked(values) };
+        self.col_index += 1;
+    }
+
+    fn write_word(&mut self, word: u32x16) {
+        self.write(word & u32x16::splat(0xffff));
+        self.write(word >> u32x16::splat(16));
+    }
+}
+
+/// Returns the lanes of the values, as elements of the base field.
+fn lanes(values: u32x16) -> [BaseField; N_LANES] {
+    values.to_array().map(BaseField::from)
+}
+
+impl<'a> Blake2sOps for TraceWriter<'a> {
+    type Word = u32x16;
+
+    fn input(&mut self) -> u32x16 {
+        let word = self.inputs.next().unwrap();
+        self.write_word(word);
+        word
+    }
+
+    fn message(&mut self) -> u32x16 {
+        let word = self.input();
+        for i in 0..4 {
+            let byte = (word >> u32x16::splat(8 * i)) & u32x16::splat(0xff);
+            self.write(byte);
+            self.table_inputs.bytes.extend(lanes(byte));
+        }
+        word
+    }
+
+    fn constant(&mut self, value: u32) -> u32x16 {
+        u32x16::splat(value)
+    }
+
+    fn add(&mut self, words: &[u32x16]) -> u32x16 {
+        let sum = words.iter().fold(u32x16::splat(0), |acc, &word| acc + word);
+        self.write_word(sum);
+        sum
+    }
+
+    fn xor_rotr(&mut self, a: u32x16, b: u32x16, r: u32) -> u32x16 {
+        for (start, n_bits) in xor_pieces(r) {
+            let piece =
+                |word: u32x16| (word >> u32x16::splat(start)) & u32x16::splat((1 << n_bits) - 1);
+            let [a_piece, b_piece] = [piece(a), piece(b)];
+            self.write(a_piece);
+            self.write(b_piece);
+            self.write(a_piece ^ b_piece);
+            let pairs = zip_lanes(a_piece, b_piece);
+            match n_bits {
+                LOG_XOR_LIMB => self.table_inputs.xor.extend(pairs),
+                LOG_SMALL_XOR_LIMB => self.table_inputs.small_xor.extend(pairs),
+                _ => {}
+            }
+        }
+        let res = a ^ b;
+        if r == 0 {
+            res
+        } else {
+            (res >> u32x16::splat(r)) | (res << u32x16::splat(32 - r))
+        }
+    }
+
+    fn output(&mut self, word: u32x16) -> u32x16 {
+        self.write_word(word);
+        word
+    }
+}
+
+fn zip_lanes(a: u32x16, b: u32x16) -> impl Iterator<Item = [BaseField; 2]> {
+    std::iter::zip(lanes(a), lanes(b)).map(|(a, b)| [a, b])
+}
+
+/// Writes the trace of a [Blake2sComponent] on SIMD, and adds its lookups to the XOR tables
+/// registered as [XOR_ID] and [SMALL_XOR_ID] and to the range check registered as
+/// [RANGE_CHECK_ID].
+pub struct Blake2sTraceGenerator {
+    log_n_rows: u32,
+    inputs: Vec<Blake2sInput>,
+}
+
+impl Blake2sTraceGenerator {
+    pub fn new(log_n_rows: u32) -> Self {
+        assert!(log_n_rows >= LOG_N_LANES);
+        Self {
+            log_n_rows,
+            inputs: vec![],
+        }
+    }
+}
+
+impl ComponentGen for Blake2sTraceGenerator {}
+
+impl ComponentTraceGenerator<SimdBackend> for Blake2sTraceGenerator {
+    type Component = Blake2sComponent;
+    type Inputs = Vec<Blake2sInput>;
+
+    /// Adds compressions to prove. The rows left once all the compressions are added are filled
+    /// with compressions of zeroed inputs.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        self.inputs.extend(inputs);
+        assert!(
+            self.inputs.len() <= 1 << self.log_n_rows,
+            "more than 2^{} compressions",
+            self.log_n_rows
+        );
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let component = generator.component();
+        let mut inputs = generator.inputs.clone();
+        inputs.resize(1 << component.log_n_rows, Blake2sInput::default());
+
+        let mut trace = (0..component.n_columns())
+            .map(|_| BaseFieldVec::zeros(1 << component.log_n_rows))
+            .collect_vec();
+        let mut table_inputs = TableInputs::default();
+        for (vec_index, chunk) in inputs.chunks(N_LANES).enumerate() {
+            let words = chunk
+                .iter()
+                .map(|input| input.words().collect_vec())
+                .collect_vec();
+            let inputs = (0..words[0].len())
+                .map(|i| u32x16::from_array(std::array::from_fn(|lane| words[lane][i])))
+                .collect_vec();
+            let mut writer = TraceWriter {
+                inputs: inputs.into_iter(),
+                trace: &mut trace,
+                vec_index,
+                col_index: 0,
+                table_inputs: &mut table_inputs,
+            };
+            compress(&mut writer);
+            assert_eq!(writer.col_index, component.n_columns());
+        }
+
+        registry
+            .get_generator_mut::<BitwiseTraceGenerator<SimdBackend, Xor, LOG_XOR_LIMB>>(XOR_ID)
+            .add_inputs(&table_inputs.xor);
+        registry
+            .get_generator_mut::<BitwiseTraceGenerator<SimdBackend, Xor, LOG_SMALL_XOR_LIMB>>(
+                SMALL_XOR_ID,
+            )
+            .add_inputs(&table_inputs.small_xor);
+        registry
+            .get_generator_mut::<RangeCheckTraceGenerator<SimdBackend, LOG_RANGE>>(RANGE_CHECK_ID)
+            .add_inputs(&table_inputs.bytes);
+
+        let domain = CanonicCoset::new(component.log_n_rows).circle_domain();
+        trace
+            .into_iter()
+            .map(|column| CircleEvaluation::new(domain, column))
+            .collect()
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        Blake2sComponent::new(self.log_n_rows)
+    }
+}
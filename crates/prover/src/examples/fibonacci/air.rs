@@ -1,13 +1,15 @@
 use itertools::{zip_eq, Itertools};
 
 use super::component::{FibonacciComponent, FibonacciInput, FibonacciTraceGenerator};
+use crate::core::air::logup::LookupError;
 use crate::core::air::{Air, AirProver, Component, ComponentProver};
 use crate::core::backend::CpuBackend;
 use crate::core::channel::Blake2sChannel;
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::SecureExtensionField;
 use crate::core::poly::circle::CircleEvaluation;
 use crate::core::poly::BitReversedOrder;
-use crate::core::{ColumnVec, InteractionElements};
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
 use crate::trace_generation::registry::ComponentGenerationRegistry;
 use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
 
@@ -29,6 +31,14 @@ impl AirTraceVerifier for FibonacciAirGenerator {
     fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
         InteractionElements::default()
     }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        Ok(())
+    }
 }
 
 impl AirTraceGenerator<CpuBackend> for FibonacciAirGenerator {
@@ -58,9 +68,9 @@ impl AirTraceGenerator<CpuBackend> for FibonacciAirGenerator {
             .registry
             .get_generator::<FibonacciTraceGenerator>("fibonacci");
         assert!(component_generator.inputs_set(), "Fibonacci input not set.");
-        component_generator
-            .component()
-            .max_constraint_log_degree_bound()
+        <FibonacciComponent as Component>::max_constraint_log_degree_bound(
+            &component_generator.component(),
+        )
     }
 }
 
@@ -75,8 +85,8 @@ impl FibonacciAir {
     }
 }
 
-impl Air for FibonacciAir {
-    fn components(&self) -> Vec<&dyn Component> {
+impl<F: SecureExtensionField> Air<F> for FibonacciAir {
+    fn components(&self) -> Vec<&dyn Component<F>> {
         vec![&self.component]
     }
 }
@@ -85,9 +95,17 @@ impl AirTraceVerifier for FibonacciAir {
     fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
         InteractionElements::default()
     }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        Ok(())
+    }
 }
 
-impl AirTraceGenerator<CpuBackend> for FibonacciAir {
+impl<F: SecureExtensionField> AirTraceGenerator<CpuBackend, F> for FibonacciAir {
     fn interact(
         &self,
         _trace: &ColumnVec<CircleEvaluation<CpuBackend, BaseField, BitReversedOrder>>,
@@ -96,17 +114,17 @@ impl AirTraceGenerator<CpuBackend> for FibonacciAir {
         vec![]
     }
 
-    fn to_air_prover(&self) -> impl AirProver<CpuBackend> {
+    fn to_air_prover(&self) -> impl AirProver<CpuBackend, F> {
         self.clone()
     }
 
     fn composition_log_degree_bound(&self) -> u32 {
-        self.component.max_constraint_log_degree_bound()
+        <FibonacciComponent as Component>::max_constraint_log_degree_bound(&self.component)
     }
 }
 
-impl AirProver<CpuBackend> for FibonacciAir {
-    fn prover_components(&self) -> Vec<&dyn ComponentProver<CpuBackend>> {
+impl<F: SecureExtensionField> AirProver<CpuBackend, F> for FibonacciAir {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<CpuBackend, F>> {
         vec![&self.component]
     }
 }
@@ -139,6 +157,14 @@ impl AirTraceVerifier for MultiFibonacciAir {
     fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
         InteractionElements::default()
     }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        Ok(())
+    }
 }
 
 impl AirTraceGenerator<CpuBackend> for MultiFibonacciAir {
@@ -157,7 +183,7 @@ impl AirTraceGenerator<CpuBackend> for MultiFibonacciAir {
     fn composition_log_degree_bound(&self) -> u32 {
         self.components
             .iter()
-            .map(|component| component.max_constraint_log_degree_bound())
+            .map(<FibonacciComponent as Component>::max_constraint_log_degree_bound)
             .max()
             .unwrap()
     }
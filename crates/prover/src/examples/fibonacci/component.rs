@@ -9,8 +9,7 @@ use crate::core::backend::CpuBackend;
 use crate::core::circle::{CirclePoint, Coset};
 use crate::core::constraints::{coset_vanishing, pair_vanishing};
 use crate::core::fields::m31::BaseField;
-use crate::core::fields::qm31::SecureField;
-use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::fields::{ExtensionOf, FieldExpOps, SecureExtensionField};
 use crate::core::pcs::TreeVec;
 use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
 use crate::core::poly::BitReversedOrder;
@@ -75,7 +74,7 @@ impl FibonacciComponent {
     }
 }
 
-impl Component for FibonacciComponent {
+impl<F: SecureExtensionField> Component<F> for FibonacciComponent {
     fn n_constraints(&self) -> usize {
         2
     }
@@ -93,10 +92,7 @@ impl Component for FibonacciComponent {
         TreeVec::new(vec![vec![self.log_size]])
     }
 
-    fn mask_points(
-        &self,
-        point: CirclePoint<SecureField>,
-    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+    fn mask_points(&self, point: CirclePoint<F>) -> TreeVec<ColumnVec<Vec<CirclePoint<F>>>> {
         TreeVec::new(vec![shifted_mask_points(
             &vec![vec![0, 1, 2]],
             &[CanonicCoset::new(self.log_size)],
@@ -106,9 +102,9 @@ impl Component for FibonacciComponent {
 
     fn evaluate_constraint_quotients_at_point(
         &self,
-        point: CirclePoint<SecureField>,
-        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
-        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        point: CirclePoint<F>,
+        mask: &TreeVec<ColumnVec<Vec<F>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator<F>,
         _interaction_elements: &InteractionElements,
         _lookup_values: &LookupValues,
     ) {
@@ -192,11 +188,11 @@ impl ComponentTraceGenerator<CpuBackend> for FibonacciTraceGenerator {
     }
 }
 
-impl ComponentProver<CpuBackend> for FibonacciComponent {
+impl<F: SecureExtensionField> ComponentProver<CpuBackend, F> for FibonacciComponent {
     fn evaluate_constraint_quotients_on_domain(
         &self,
         trace: &ComponentTrace<'_, CpuBackend>,
-        evaluation_accumulator: &mut DomainEvaluationAccumulator<CpuBackend>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<CpuBackend, F>,
         _interaction_elements: &InteractionElements,
         _lookup_values: &LookupValues,
     ) {
@@ -220,10 +216,10 @@ impl ComponentProver<CpuBackend> for FibonacciComponent {
             let mul = trace_domain.step_size().div(point_coset.step_size);
             for (i, point) in point_coset.iter().enumerate() {
                 let mask = [eval[i], eval[i as isize + mul], eval[i as isize + 2 * mul]];
-                let mut res = self.boundary_constraint_eval_quotient_by_mask(point, &[mask[0]])
-                    * accum.random_coeff_powers[0];
-                res += self.step_constraint_eval_quotient_by_mask(point, &mask)
-                    * accum.random_coeff_powers[1];
+                let mut res = accum.random_coeff_powers[0]
+                    * self.boundary_constraint_eval_quotient_by_mask(point, &[mask[0]]);
+                res += accum.random_coeff_powers[1]
+                    * self.step_constraint_eval_quotient_by_mask(point, &mask);
                 accum.accumulate(bit_reverse_index(i + off, constraint_log_degree_bound), res);
             }
         }
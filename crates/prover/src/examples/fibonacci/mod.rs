@@ -122,14 +122,19 @@ mod tests {
     use super::{Fibonacci, MultiFibonacci};
     use crate::core::air::accumulation::PointEvaluationAccumulator;
     use crate::core::air::{AirExt, AirProverExt, Component, ComponentTrace};
+    use crate::core::backend::CpuBackend;
     use crate::core::channel::{Blake2sChannel, Channel};
     use crate::core::circle::CirclePoint;
     use crate::core::fields::m31::BaseField;
+    use crate::core::fields::om31::OM31;
     use crate::core::fields::qm31::SecureField;
     use crate::core::fields::IntoSlice;
-    use crate::core::pcs::TreeVec;
+    use crate::core::pcs::{PcsConfig, TreeVec};
     use crate::core::poly::circle::CanonicCoset;
-    use crate::core::prover::{prove, VerificationError, BASE_TRACE};
+    use crate::core::poly::twiddles::TwiddleCache;
+    use crate::core::prover::{
+        prove, prove_with_twiddle_cache, verify, VerificationError, BASE_TRACE,
+    };
     use crate::core::queries::Queries;
     use crate::core::utils::bit_reverse;
     use crate::core::vcs::blake2_hash::Blake2sHasher;
@@ -241,6 +246,58 @@ mod tests {
         fib.verify(proof).unwrap();
     }
 
+    #[test]
+    fn test_fib_prove_over_om31() {
+        const FIB_LOG_SIZE: u32 = 5;
+        const CLAIM: BaseField = m31!(443693538);
+        let fib = Fibonacci::new(FIB_LOG_SIZE, CLAIM);
+        let channel = || Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[CLAIM])));
+
+        let proof =
+            prove::<CpuBackend, OM31>(&fib.air, &mut channel(), vec![fib.get_trace()]).unwrap();
+
+        verify(proof, &fib.air, &mut channel()).unwrap();
+    }
+
+    #[test]
+    fn test_fib_prove_over_om31_with_wrong_claim_fails() {
+        const FIB_LOG_SIZE: u32 = 5;
+        const CLAIM: BaseField = m31!(443693538);
+        let fib = Fibonacci::new(FIB_LOG_SIZE, CLAIM);
+        let channel = || Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[CLAIM])));
+        let proof =
+            prove::<CpuBackend, OM31>(&fib.air, &mut channel(), vec![fib.get_trace()]).unwrap();
+        let wrong_fib = Fibonacci::new(FIB_LOG_SIZE, CLAIM + BaseField::one());
+
+        let error = verify(proof, &wrong_fib.air, &mut channel()).unwrap_err();
+
+        assert_matches!(error, VerificationError::OodsNotMatching);
+    }
+
+    #[test]
+    fn test_fib_prove_with_twiddle_cache() {
+        const FIB_LOG_SIZE: u32 = 5;
+        const CLAIM: BaseField = m31!(443693538);
+        let fib = Fibonacci::new(FIB_LOG_SIZE, CLAIM);
+        let twiddle_cache = TwiddleCache::new();
+
+        for _ in 0..2 {
+            let channel =
+                &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[CLAIM])));
+            let proof = prove_with_twiddle_cache(
+                &fib.air,
+                channel,
+                vec![fib.get_trace()],
+                PcsConfig::default(),
+                &twiddle_cache,
+            )
+            .unwrap();
+            fib.verify(proof).unwrap();
+        }
+
+        assert_eq!(twiddle_cache.len(), 1);
+    }
+
     #[test]
     fn test_fib_prove_2() {
         const FIB_LOG_SIZE: u32 = 5;
@@ -0,0 +1,503 @@
+//! An example AIR checking a chain of FRI line folds (see [fold_line]).
+//!
+//! A [FriFoldComponent] checks that `f_1, ..., f_n` are the successive folds of a line evaluation
+//! `f_0` on a domain `D_0`, where `f_j` is on `D_j = π^j(D_0)` and
+//!   `f_{j+1}(π(x)) = (f_j(x) + f_j(-x)) + alpha_j * (f_j(x) - f_j(-x)) / x`.
+//! The trace has a row for each point of `D_0`, and its `k`-th row holds `f_0` at the `k`-th point
+//! of `D_0` and, for each fold `j`, the `k`-th point `x_j` of `D_j` and `f_{j+1}(π(x_j))`, where
+//! the points of `D_j` are taken cyclically. Secure field values are split into
+//! [SECURE_EXTENSION_DEGREE] base field columns.
+//!
+//! The constraints of the `j`-th fold are:
+//! * `x_0` is the x-coordinate of the trace point shifted by a constant, and `x_j = 2 * x_{j-1}^2 -
+//!   1`.
+//! * `x_j * (f_{j+1}(π(x_j)) - f_j(x_j) - f_j(-x_j)) = alpha_j * (f_j(x_j) - f_j(-x_j))`, where
+//!   `f_j(-x_j)` is read `|D_j| / 2` rows after `f_j(x_j)`, in the same column.
+//!
+//! Hence all the values of `f_1, ..., f_n` are determined by those of `f_0`, which are given in
+//! the trace. This is not a verifier of FRI proofs: it checks whole evaluations rather than queried
+//! values, and neither binds `f_0` to a commitment nor derives the `alpha_j` from a channel.
+
+use itertools::{zip_eq, Itertools};
+use num_traits::One;
+
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::LookupError;
+use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
+use crate::core::backend::CpuBackend;
+use crate::core::channel::Blake2sChannel;
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::coset_vanishing;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::fields::{ExtensionOf, Field, FieldExpOps};
+use crate::core::fri::fold_line;
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::line::{LineDomain, LineEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::utils::{bit_reverse_index, offset_bit_reversed_circle_domain_index};
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier};
+
+/// The columns of `f_0`.
+const F_0: usize = 0;
+
+/// The smallest trace for which the constraints are well defined.
+pub const MIN_LOG_N_ROWS: u32 = 3;
+
+/// Checks the folds of a line evaluation on `domain` with the folding coefficients `alphas`.
+#[derive(Clone)]
+pub struct FriFoldComponent {
+    pub domain: LineDomain,
+    pub alphas: Vec<SecureField>,
+}
+
+impl FriFoldComponent {
+    pub fn new(domain: LineDomain, alphas: Vec<SecureField>) -> Self {
+        assert!(domain.log_size() >= MIN_LOG_N_ROWS);
+        assert!(!alphas.is_empty() && alphas.len() <= domain.log_size() as usize);
+        // Makes the `k`-th point of the domain a shift of the `k`-th point of the trace domain.
+        assert_eq!(
+            domain.coset().step_size,
+            CanonicCoset::new(domain.log_size()).step_size(),
+            "domain is not a coset of the trace domain's subgroup"
+        );
+        Self { domain, alphas }
+    }
+
+    pub fn log_n_rows(&self) -> u32 {
+        self.domain.log_size()
+    }
+
+    pub fn n_columns(&self) -> usize {
+        x_column(self.alphas.len())
+    }
+
+    /// Returns the shift from the trace domain to the domain of `f_0`.
+    fn domain_shift(&self) -> CirclePoint<BaseField> {
+        (self.domain.coset().initial_index - CanonicCoset::new(self.log_n_rows()).initial_index())
+            .to_point()
+    }
+
+    /// Returns the offsets of the mask items of each column: the inputs of the `j`-th fold are
+    /// also read `|D_j| / 2` rows ahead.
+    fn mask_offsets(&self) -> ColumnVec<Vec<usize>> {
+        let n_folds = self.alphas.len();
+        let input_offsets = |fold: usize| vec![0, 1 << (self.log_n_rows() as usize - 1 - fold)];
+        let mut offsets = vec![input_offsets(0); SECURE_EXTENSION_DEGREE];
+        for fold in 0..n_folds {
+            offsets.push(vec![0]);
+            let folded_offsets = if fold + 1 < n_folds {
+                input_offsets(fold + 1)
+            } else {
+                vec![0]
+            };
+            offsets.extend(vec![folded_offsets; SECURE_EXTENSION_DEGREE]);
+        }
+        offsets
+    }
+
+    /// Returns the denominator of the constraints at a point: the vanishing polynomial of the
+    /// trace domain.
+    fn constraint_denom<F: ExtensionOf<BaseField>>(&self, point: CirclePoint<F>) -> F {
+        coset_vanishing(CanonicCoset::new(self.log_n_rows()).coset, point)
+    }
+
+    /// Evaluates the constraint quotients at a point, given the inverse of their denominator and
+    /// the values of the mask items of each column at that point.
+    fn constraint_quotients(
+        &self,
+        point: CirclePoint<SecureField>,
+        denom_inverse: SecureField,
+        mask: &[Vec<SecureField>],
+    ) -> Vec<SecureField> {
+        let secure_value = |column: usize, mask_item: usize| {
+            SecureField::from_partial_evals(std::array::from_fn(|i| mask[column + i][mask_item]))
+        };
+
+        let mut quotients = Vec::with_capacity(self.n_constraints());
+        let mut expected_x = (point + self.domain_shift().into_ef()).x;
+        for (fold, alpha) in self.alphas.iter().enumerate() {
+            let x = mask[x_column(fold)][0];
+            let f_x = secure_value(input_column(fold), 0);
+            let f_neg_x = secure_value(input_column(fold), 1);
+            let folded = secure_value(x_column(fold) + 1, 0);
+            quotients.push((x - expected_x) * denom_inverse);
+            quotients
+                .push((x * (folded - (f_x + f_neg_x)) - *alpha * (f_x - f_neg_x)) * denom_inverse);
+            expected_x = x.square().double() - SecureField::one();
+        }
+        quotients
+    }
+}
+
+/// Returns the column of `x_j` of the `fold`-th fold, followed by the columns of its folded values.
+fn x_column(fold: usize) -> usize {
+    SECURE_EXTENSION_DEGREE + fold * (SECURE_EXTENSION_DEGREE + 1)
+}
+
+/// Returns the columns of the values folded by the `fold`-th fold.
+fn input_column(fold: usize) -> usize {
+    match fold {
+        0 => F_0,
+        _ => x_column(fold - 1) + 1,
+    }
+}
+
+impl Component for FriFoldComponent {
+    fn n_constraints(&self) -> usize {
+        2 * self.alphas.len()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        // The constraints are of degree 2.
+        self.log_n_rows() + 1
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        1
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        TreeVec::new(vec![vec![self.log_n_rows(); self.n_columns()]])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        let step_size = CanonicCoset::new(self.log_n_rows()).step_size();
+        TreeVec::new(vec![self
+            .mask_offsets()
+            .into_iter()
+            .map(|offsets| {
+                offsets
+                    .into_iter()
+                    .map(|offset| point + (step_size * offset).to_point().into_ef())
+                    .collect()
+            })
+            .collect()])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) {
+        let denom_inverse = self.constraint_denom(point).inverse();
+        for quotient in self.constraint_quotients(point, denom_inverse, &mask[BASE_TRACE]) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+    }
+}
+
+impl ComponentProver<CpuBackend> for FriFoldComponent {
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, CpuBackend>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<CpuBackend>,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let trace_evals = &trace.evals[BASE_TRACE];
+        let eval_domain = trace_evals[0].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+
+        let mask_offsets = self.mask_offsets();
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, self.n_constraints())]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| [(self.constraint_denom(point), BaseField::one())],
+            |i, [denom_inverse]| {
+                let point = eval_domain.at(bit_reverse_index(i, eval_log_size));
+                let mask = zip_eq(trace_evals, &mask_offsets)
+                    .map(|(eval, offsets)| {
+                        offsets
+                            .iter()
+                            .map(|&offset| {
+                                let index = offset_bit_reversed_circle_domain_index(
+                                    i,
+                                    self.log_n_rows(),
+                                    eval_log_size,
+                                    offset as isize,
+                                );
+                                eval.values[index].into()
+                            })
+                            .collect()
+                    })
+                    .collect_vec();
+                self.constraint_quotients(point.into_ef(), denom_inverse.into(), &mask)
+            },
+        );
+    }
+
+    fn lookup_values(&self, _trace: &ComponentTrace<'_, CpuBackend>) -> LookupValues {
+        LookupValues::default()
+    }
+}
+
+/// Checks several chains of folds, e.g. the line folds of several FRI proofs.
+#[derive(Clone)]
+pub struct FriFoldAir {
+    pub components: Vec<FriFoldComponent>,
+}
+
+impl Air for FriFoldAir {
+    fn components(&self) -> Vec<&dyn Component> {
+        self.components
+            .iter()
+            .map(|component| component as &dyn Component)
+            .collect()
+    }
+}
+
+impl AirTraceVerifier for FriFoldAir {
+    fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
+        InteractionElements::default()
+    }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        Ok(())
+    }
+}
+
+impl AirTraceGenerator<CpuBackend> for FriFoldAir {
+    fn interact(
+        &self,
+        _trace: &ColumnVec<CircleEvaluation<CpuBackend, BaseField, BitReversedOrder>>,
+        _elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<CpuBackend, BaseField, BitReversedOrder>> {
+        vec![]
+    }
+
+    fn to_air_prover(&self) -> impl AirProver<CpuBackend> {
+        self.clone()
+    }
+
+    fn composition_log_degree_bound(&self) -> u32 {
+        self.components
+            .iter()
+            .map(|component| component.max_constraint_log_degree_bound())
+            .max()
+            .unwrap()
+    }
+}
+
+impl AirProver<CpuBackend> for FriFoldAir {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<CpuBackend>> {
+        self.components
+            .iter()
+            .map(|component| component as &dyn ComponentProver<CpuBackend>)
+            .collect()
+    }
+}
+
+/// Generates the trace of a [FriFoldComponent] checking the folds of `eval` with `alphas`. `eval`
+/// is in bit reversed order, as in FRI.
+pub fn gen_trace(
+    eval: &LineEvaluation<CpuBackend>,
+    alphas: &[SecureField],
+) -> ColumnVec<CircleEvaluation<CpuBackend, BaseField, BitReversedOrder>> {
+    let trace_domain = CanonicCoset::new(eval.domain().log_size());
+    gen_trace_columns(eval, alphas)
+        .into_iter()
+        .map(|column| CircleEvaluation::new_canonical_ordered(trace_domain, column))
+        .collect()
+}
+
+/// Returns the columns of the trace generated by [gen_trace], in coset order.
+fn gen_trace_columns(
+    eval: &LineEvaluation<CpuBackend>,
+    alphas: &[SecureField],
+) -> Vec<Vec<BaseField>> {
+    let n_rows = eval.len();
+    let mut columns = coset_ordered_columns(eval, n_rows);
+    let mut layer = eval.clone();
+    for alpha in alphas {
+        let domain = layer.domain();
+        columns.push((0..n_rows).map(|k| domain.at(k % domain.size())).collect());
+        layer = fold_line(&layer, *alpha);
+        columns.extend(coset_ordered_columns(&layer, n_rows));
+    }
+    columns
+}
+
+/// Returns the base field columns of a bit reversed line evaluation in coset order, repeated
+/// cyclically to `n_rows` rows.
+fn coset_ordered_columns(eval: &LineEvaluation<CpuBackend>, n_rows: usize) -> Vec<Vec<BaseField>> {
+    let log_size = eval.domain().log_size();
+    let mut columns = (0..SECURE_EXTENSION_DEGREE)
+        .map(|_| Vec::with_capacity(n_rows))
+        .collect_vec();
+    for k in 0..n_rows {
+        let value = eval.values.at(bit_reverse_index(k % eval.len(), log_size));
+        for (column, value) in columns.iter_mut().zip_eq(value.to_m31_array()) {
+            column.push(value);
+        }
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use num_traits::One;
+
+    use super::{
+        gen_trace, gen_trace_columns, input_column, x_column, FriFoldAir, FriFoldComponent,
+        MIN_LOG_N_ROWS,
+    };
+    use crate::core::backend::cpu::CpuCircleEvaluation;
+    use crate::core::backend::{ColumnOps, CpuBackend};
+    use crate::core::channel::Channel;
+    use crate::core::circle::Coset;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::FieldExpOps;
+    use crate::core::fri::fold_line;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::poly::line::{LineDomain, LineEvaluation, LinePoly};
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::prover::{prove, verify, ProvingError};
+    use crate::core::test_utils::test_channel;
+    use crate::core::ColumnVec;
+
+    const LOG_DEGREE: u32 = 6;
+
+    /// Returns the evaluation of a polynomial of degree `2^LOG_DEGREE` on a line domain with
+    /// blowup factor 2, in bit reversed order.
+    fn low_degree_line_evaluation() -> LineEvaluation<CpuBackend> {
+        let mut channel = test_channel();
+        let poly = LinePoly::new(channel.draw_felts(1 << LOG_DEGREE));
+        let domain = LineDomain::new(Coset::half_odds(LOG_DEGREE + 1));
+        let mut values = domain
+            .iter()
+            .map(|x| poly.eval_at_point(x.into()))
+            .collect();
+        CpuBackend::bit_reverse_column(&mut values);
+        LineEvaluation::new(domain, values.into_iter().collect())
+    }
+
+    /// Returns an AIR checking the folds of `eval` until the folded evaluation has
+    /// `2^MIN_LOG_N_ROWS` values.
+    fn fri_fold_air(eval: &LineEvaluation<CpuBackend>) -> FriFoldAir {
+        let n_folds = eval.domain().log_size() - MIN_LOG_N_ROWS;
+        let alphas = test_channel().draw_felts(n_folds as usize);
+        FriFoldAir {
+            components: vec![FriFoldComponent::new(eval.domain(), alphas)],
+        }
+    }
+
+    fn to_trace(
+        columns: Vec<Vec<BaseField>>,
+        log_size: u32,
+    ) -> ColumnVec<CpuCircleEvaluation<BaseField, BitReversedOrder>> {
+        columns
+            .into_iter()
+            .map(|column| {
+                CircleEvaluation::new_canonical_ordered(CanonicCoset::new(log_size), column)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fri_fold_prove_and_verify() {
+        let eval = low_degree_line_evaluation();
+        let air = fri_fold_air(&eval);
+        let alphas = &air.components[0].alphas;
+        let trace = gen_trace(&eval, alphas);
+        // The polynomial is of low degree, so its folds are too.
+        let last_layer = alphas
+            .iter()
+            .fold(eval, |eval, alpha| fold_line(&eval, *alpha));
+        let last_layer_coeffs = last_layer.interpolate().into_ordered_coefficients();
+        assert!(last_layer_coeffs[1 << (MIN_LOG_N_ROWS - 1)..]
+            .iter()
+            .all(|c| *c == SecureField::default()));
+
+        let proof = prove::<CpuBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        verify(proof, &air, &mut test_channel()).unwrap();
+    }
+
+    #[test]
+    fn test_fri_fold_with_wrong_alpha_fails() {
+        let eval = low_degree_line_evaluation();
+        let mut air = fri_fold_air(&eval);
+        let trace = gen_trace(&eval, &air.components[0].alphas);
+        air.components[0].alphas[1] += SecureField::one();
+
+        let error = prove::<CpuBackend, _>(&air, &mut test_channel(), trace).unwrap_err();
+
+        assert_matches!(error, ProvingError::ConstraintsNotSatisfied);
+    }
+
+    #[test]
+    fn test_fri_fold_with_wrong_x_fails() {
+        let eval = low_degree_line_evaluation();
+        let log_size = eval.domain().log_size();
+        let air = fri_fold_air(&eval);
+        let alphas = &air.components[0].alphas;
+        let mut columns = gen_trace_columns(&eval, alphas);
+        // Changes `x` of the last fold in the first row, and its folded value accordingly.
+        let fold = alphas.len() - 1;
+        let read = |columns: &[Vec<BaseField>], column: usize, row: usize| {
+            SecureField::from_m31_array(std::array::from_fn(|i| columns[column + i][row]))
+        };
+        let f_x = read(&columns, input_column(fold), 0);
+        let f_neg_x = read(
+            &columns,
+            input_column(fold),
+            1 << (log_size as usize - 1 - fold),
+        );
+        let x = columns[x_column(fold)][0] + BaseField::one();
+        let folded = (f_x + f_neg_x) + alphas[fold] * (f_x - f_neg_x) * x.inverse();
+        columns[x_column(fold)][0] = x;
+        for (i, value) in folded.to_m31_array().into_iter().enumerate() {
+            columns[x_column(fold) + 1 + i][0] = value;
+        }
+
+        let error = prove::<CpuBackend, _>(&air, &mut test_channel(), to_trace(columns, log_size))
+            .unwrap_err();
+
+        assert_matches!(error, ProvingError::ConstraintsNotSatisfied);
+    }
+
+    #[test]
+    fn test_fri_fold_with_unchained_folds_fails() {
+        let eval = low_degree_line_evaluation();
+        let log_size = eval.domain().log_size();
+        let air = fri_fold_air(&eval);
+        let mut columns = gen_trace_columns(&eval, &air.components[0].alphas);
+        // Replaces the folds after the first by the (consistent) folds of another evaluation.
+        let other_columns = gen_trace_columns(
+            &LineEvaluation::new(
+                eval.domain(),
+                (0..eval.len())
+                    .map(|i| SecureField::from(BaseField::from(i)))
+                    .collect(),
+            ),
+            &air.components[0].alphas,
+        );
+        let second_fold_input = input_column(1);
+        columns.truncate(second_fold_input);
+        columns.extend_from_slice(&other_columns[second_fold_input..]);
+
+        let error = prove::<CpuBackend, _>(&air, &mut test_channel(), to_trace(columns, log_size))
+            .unwrap_err();
+
+        assert_matches!(error, ProvingError::ConstraintsNotSatisfied);
+    }
+}
@@ -0,0 +1,396 @@
+use std::iter::zip;
+
+use num_traits::{One, Zero};
+
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{Logup, LogupOps, LookupEntry, Multiplicity};
+use crate::core::air::{Component, ComponentProver, ComponentTrace};
+use crate::core::backend::simd::SimdBackend;
+use crate::core::backend::Column;
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::coset_vanishing;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::examples::poseidon::{PoseidonTraceGenerator, POSEIDON2_RELATION};
+use crate::hash_functions::poseidon2::{compress, permute, DIGEST_SIZE, WIDTH};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+pub type Digest = [BaseField; DIGEST_SIZE];
+
+/// The relation chaining the rows of a path, as tuples of the index of the path, the level of a
+/// node, `2^level`, the bits of the leaf index below the level, and the node.
+pub const MERKLE_PATH_RELATION: &str = "merkle_path";
+pub const POSEIDON_ID: &str = "merkle_path_poseidon";
+/// The maximal depth of a path, so that the leaf indices are field elements.
+pub const MAX_DEPTH: usize = 30;
+
+const PATH: usize = 0;
+const LEVEL: usize = 1;
+const LEVEL_POW: usize = 2;
+const INDEX_PREFIX: usize = 3;
+const NODE: usize = 4;
+const SIBLING: usize = NODE + DIGEST_SIZE;
+/// Whether the node is the right child of its parent.
+const DIRECTION: usize = SIBLING + DIGEST_SIZE;
+const LEFT: usize = DIRECTION + 1;
+const RIGHT: usize = LEFT + DIGEST_SIZE;
+/// The output of the permutation of the children, starting with their parent.
+const OUTPUT: usize = RIGHT + DIGEST_SIZE;
+const NEXT_LEVEL: usize = OUTPUT + WIDTH;
+const NEXT_LEVEL_POW: usize = NEXT_LEVEL + 1;
+const NEXT_INDEX_PREFIX: usize = NEXT_LEVEL_POW + 1;
+/// Whether the row is a step of a path, rather than padding.
+const ENABLED: usize = NEXT_INDEX_PREFIX + 1;
+const N_COLUMNS: usize = ENABLED + 1;
+const N_ROW_CONSTRAINTS: usize = 2 + 2 * DIGEST_SIZE + 3;
+
+/// The authentication path of a leaf of a Merkle tree whose nodes are the Poseidon2
+/// [compress]ions of their children, like a
+/// [MerkleDecommitment](crate::core::vcs::prover::MerkleDecommitment) of a single leaf.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerklePath {
+    pub leaf: Digest,
+    pub index: u32,
+    /// The siblings of the nodes from the leaf up to the root, excluded.
+    pub siblings: Vec<Digest>,
+}
+
+impl MerklePath {
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Returns whether the node at `level` is the right child of its parent.
+    fn is_right(&self, level: usize) -> bool {
+        (self.index >> level) & 1 == 1
+    }
+
+    pub fn root(&self) -> Digest {
+        self.siblings
+            .iter()
+            .enumerate()
+            .fold(self.leaf, |node, (level, &sibling)| {
+                if self.is_right(level) {
+                    compress(sibling, node)
+                } else {
+                    compress(node, sibling)
+                }
+            })
+    }
+}
+
+/// Component proving the steps of Merkle paths, one in each row.
+///
+/// Each step uses its node from [MERKLE_PATH_RELATION] and yields its parent at the next level,
+/// so a path is proven by yielding its leaf at level 0 and using its root at its depth. The parent
+/// is the start of the output of the permutation of the children, used from
+/// [POSEIDON2_RELATION].
+#[derive(Clone, Debug)]
+pub struct MerklePathComponent {
+    pub log_n_rows: u32,
+}
+
+impl MerklePathComponent {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self { log_n_rows }
+    }
+
+    pub fn n_columns(&self) -> usize {
+        N_COLUMNS
+    }
+
+    pub fn logup(&self) -> Logup {
+        let digest = |start: usize| start..start + DIGEST_SIZE;
+        Logup::new(
+            "merkle_path",
+            self.log_n_rows,
+            vec![
+                LookupEntry::uses(
+                    MERKLE_PATH_RELATION,
+                    [PATH, LEVEL, LEVEL_POW, INDEX_PREFIX]
+                        .into_iter()
+                        .chain(digest(NODE))
+                        .collect(),
+                    Multiplicity::Column(ENABLED),
+                ),
+                LookupEntry::yields(
+                    MERKLE_PATH_RELATION,
+                    [PATH, NEXT_LEVEL, NEXT_LEVEL_POW, NEXT_INDEX_PREFIX]
+                        .into_iter()
+                        .chain(digest(OUTPUT))
+                        .collect(),
+                    Multiplicity::Column(ENABLED),
+                ),
+                LookupEntry::uses(
+                    POSEIDON2_RELATION,
+                    (LEFT..OUTPUT + WIDTH).collect(),
+                    Multiplicity::Column(ENABLED),
+                ),
+            ],
+        )
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.logup().interaction_element_ids()
+    }
+
+    /// Returns the denominator of the row constraints at a point: the vanishing polynomial of the
+    /// trace domain.
+    fn row_constraint_denom<F: ExtensionOf<BaseField>>(&self, point: CirclePoint<F>) -> F {
+        coset_vanishing(CanonicCoset::new(self.log_n_rows).coset, point)
+    }
+
+    /// Evaluates the quotients of the constraints of a row, given the inverse of their denominator
+    /// and the values of the columns of the row.
+    fn row_constraint_quotients<F: ExtensionOf<BaseField>>(
+        &self,
+        denom_inverse: F,
+        value: impl Fn(usize) -> F,
+    ) -> Vec<F> {
+        let one = F::one();
+        let direction = value(DIRECTION);
+        let enabled = value(ENABLED);
+
+        let mut constraints = vec![direction * (direction - one), enabled * (enabled - one)];
+        for i in 0..DIGEST_SIZE {
+            let [node, sibling, left, right] =
+                [NODE, SIBLING, LEFT, RIGHT].map(|column| value(column + i));
+            constraints.push(left - node - direction * (sibling - node));
+            constraints.push(right - node - sibling + left);
+        }
+        constraints.extend([
+            value(NEXT_LEVEL) - value(LEVEL) - one,
+            value(NEXT_LEVEL_POW) - value(LEVEL_POW) * BaseField::from(2),
+            value(NEXT_INDEX_PREFIX) - value(INDEX_PREFIX) - direction * value(LEVEL_POW),
+        ]);
+        constraints
+            .into_iter()
+            .map(|constraint| constraint * denom_inverse)
+            .collect()
+    }
+}
+
+impl Component for MerklePathComponent {
+    fn n_constraints(&self) -> usize {
+        N_ROW_CONSTRAINTS + self.logup().n_constraints()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        // The step constraints are at most quadratic, selecting the children by the direction bit,
+        // and so are the lookup constraints.
+        self.logup().max_constraint_log_degree_bound()
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        2
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        TreeVec::new(vec![
+            vec![self.log_n_rows; N_COLUMNS],
+            self.logup().interaction_log_degree_bounds(),
+        ])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        TreeVec::new(vec![
+            vec![vec![point]; N_COLUMNS],
+            self.logup().interaction_mask_points(point),
+        ])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let denom_inverse = self.row_constraint_denom(point).inverse();
+        let value = |column: usize| mask[BASE_TRACE][column][0];
+        for quotient in self.row_constraint_quotients(denom_inverse, value) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+}
+
+impl<B: LogupOps> ComponentProver<B> for MerklePathComponent {
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[BASE_TRACE][PATH].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let columns = &trace.evals[BASE_TRACE];
+
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, N_ROW_CONSTRAINTS)]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| [(self.row_constraint_denom(point), BaseField::one())],
+            |i, [denom_inverse]| {
+                self.row_constraint_quotients(denom_inverse, |column| columns[column].values.at(i))
+            },
+        );
+
+        self.logup().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+
+    fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        self.logup().lookup_values(trace)
+    }
+}
+
+/// Writes the trace of a [MerklePathComponent] on SIMD, and adds the permutations of its steps to
+/// the [PoseidonTraceGenerator] registered as [POSEIDON_ID].
+pub struct MerklePathTraceGenerator {
+    log_n_rows: u32,
+    paths: Vec<MerklePath>,
+}
+
+impl MerklePathTraceGenerator {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self {
+            log_n_rows,
+            paths: vec![],
+        }
+    }
+
+    fn n_rows(&self) -> usize {
+        self.paths.iter().map(MerklePath::depth).sum()
+    }
+}
+
+impl ComponentGen for MerklePathTraceGenerator {}
+
+impl ComponentTraceGenerator<SimdBackend> for MerklePathTraceGenerator {
+    type Component = MerklePathComponent;
+    type Inputs = Vec<MerklePath>;
+
+    /// Adds paths to prove. They are indexed in the order they are added, from zero.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        for path in inputs {
+            assert!(path.depth() <= MAX_DEPTH, "path of depth {}", path.depth());
+        }
+        self.paths.extend(inputs.iter().cloned());
+        assert!(
+            self.n_rows() <= 1 << self.log_n_rows,
+            "more than 2^{} steps",
+            self.log_n_rows
+        );
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let log_n_rows = generator.log_n_rows;
+        let mut trace = vec![vec![BaseField::zero(); 1 << log_n_rows]; N_COLUMNS];
+        let mut permutation_inputs = vec![];
+
+        let steps = generator
+            .paths
+            .iter()
+            .enumerate()
+            .flat_map(|(path_index, path)| {
+                (0..path.depth()).map(move |level| (path_index, path, level))
+            });
+        let mut row = 0;
+        let mut node = Digest::default();
+        for (path_index, path, level) in steps {
+            if level == 0 {
+                node = path.leaf;
+            }
+            let sibling = path.siblings[level];
+            let (left, right) = if path.is_right(level) {
+                (sibling, node)
+            } else {
+                (node, sibling)
+            };
+            let mut output = [BaseField::zero(); WIDTH];
+            output[..DIGEST_SIZE].copy_from_slice(&left);
+            output[DIGEST_SIZE..].copy_from_slice(&right);
+            permutation_inputs.push(output);
+            permute(&mut output);
+
+            let level_pow = 1 << level;
+            let index_prefix = path.index & (level_pow - 1);
+            let mut values = vec![
+                BaseField::from(path_index),
+                BaseField::from(level),
+                BaseField::from(level_pow),
+                BaseField::from(index_prefix),
+            ];
+            values.extend(node);
+            values.extend(sibling);
+            values.push(BaseField::from(path.is_right(level) as u32));
+            values.extend(left);
+            values.extend(right);
+            values.extend(output);
+            values.extend([
+                BaseField::from(level + 1),
+                BaseField::from(2 * level_pow),
+                BaseField::from(path.index & (2 * level_pow - 1)),
+                BaseField::one(),
+            ]);
+            for (column, value) in zip(&mut trace, values) {
+                column[row] = value;
+            }
+            node = output[..DIGEST_SIZE].try_into().unwrap();
+            row += 1;
+        }
+        // The remaining rows satisfy the constraints with zeroes, except for the next level.
+        trace[NEXT_LEVEL][row..].fill(BaseField::one());
+
+        registry
+            .get_generator_mut::<PoseidonTraceGenerator>(POSEIDON_ID)
+            .add_inputs(&permutation_inputs);
+
+        let domain = CanonicCoset::new(log_n_rows).circle_domain();
+        trace
+            .into_iter()
+            .map(|column| CircleEvaluation::new(domain, column.into_iter().collect()))
+            .collect()
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        MerklePathComponent::new(self.log_n_rows)
+    }
+}
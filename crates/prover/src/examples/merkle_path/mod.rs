@@ -0,0 +1,391 @@
+//! An example AIR proving Merkle paths of Poseidon2 digests from public leaves to public roots.
+//!
+//! The steps of the paths are proven by a [MerklePathComponent], whose compressions are proven by
+//! a [PoseidonComponent] through the
+//! [POSEIDON2_RELATION](crate::examples::poseidon::POSEIDON2_RELATION).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::{zip_eq, Itertools};
+
+use self::component::{
+    Digest, MerklePath, MerklePathComponent, MerklePathTraceGenerator, MAX_DEPTH,
+    MERKLE_PATH_RELATION, POSEIDON_ID,
+};
+use crate::core::air::logup::{
+    public_sum, verify_balanced_with_public, LookupDirection, LookupError,
+};
+use crate::core::air::{Air, AirProver, Component, ComponentProver};
+use crate::core::backend::simd::m31::LOG_N_LANES;
+use crate::core::backend::simd::SimdBackend;
+use crate::core::channel::{Blake2sChannel, Channel};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::examples::poseidon::{
+    PoseidonComponent, PoseidonTraceGenerator, N_LOG_INSTANCES_PER_ROW,
+};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+pub mod component;
+
+const MERKLE_PATH_ID: &str = "merkle_path";
+
+/// The public part of a [MerklePath]: the leaf, its index and the root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerklePathClaim {
+    pub leaf: Digest,
+    pub index: u32,
+    pub depth: u32,
+    pub root: Digest,
+}
+
+impl From<&MerklePath> for MerklePathClaim {
+    fn from(path: &MerklePath) -> Self {
+        Self {
+            leaf: path.leaf,
+            index: path.index,
+            depth: path.depth() as u32,
+            root: path.root(),
+        }
+    }
+}
+
+impl MerklePathClaim {
+    /// Checks that the path has at most [MAX_DEPTH] levels, and that its index is in range. Longer
+    /// paths would wrap the level sizes `2^level` around `P`.
+    fn validate(&self) -> Result<(), LookupError> {
+        if self.depth as usize > MAX_DEPTH {
+            return Err(LookupError::InvalidClaim(format!(
+                "path of depth {} is deeper than {MAX_DEPTH}",
+                self.depth
+            )));
+        }
+        if self.index >= 1 << self.depth {
+            return Err(LookupError::InvalidClaim(format!(
+                "index {} is out of a tree of depth {}",
+                self.index, self.depth
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the tuples of [MERKLE_PATH_RELATION] starting and ending the path of index
+    /// `path_index`. The claim must be valid.
+    fn endpoints(&self, path_index: usize) -> [Vec<BaseField>; 2] {
+        let tuple = |level: u32, index_prefix: u32, node: Digest| {
+            [
+                path_index,
+                level as usize,
+                1 << level,
+                index_prefix as usize,
+            ]
+            .map(BaseField::from)
+            .into_iter()
+            .chain(node)
+            .collect()
+        };
+        [
+            tuple(0, 0, self.leaf),
+            tuple(self.depth, self.index, self.root),
+        ]
+    }
+}
+
+/// An AIR proving the paths of its claims, with `2^log_n_rows` steps.
+#[derive(Clone)]
+pub struct MerklePathAir {
+    pub merkle_path: MerklePathComponent,
+    pub poseidon: PoseidonComponent,
+    pub claims: Vec<MerklePathClaim>,
+}
+
+impl MerklePathAir {
+    pub fn new(log_n_rows: u32, claims: Vec<MerklePathClaim>) -> Self {
+        Self {
+            merkle_path: MerklePathComponent::new(log_n_rows),
+            poseidon: PoseidonComponent {
+                log_n_rows: poseidon_log_n_rows(log_n_rows),
+            },
+            claims,
+        }
+    }
+}
+
+/// Returns the number of rows of the [PoseidonComponent] permuting the children of each step.
+fn poseidon_log_n_rows(log_n_rows: u32) -> u32 {
+    assert!(log_n_rows >= LOG_N_LANES + N_LOG_INSTANCES_PER_ROW as u32);
+    log_n_rows - N_LOG_INSTANCES_PER_ROW as u32
+}
+
+impl Air for MerklePathAir {
+    fn components(&self) -> Vec<&dyn Component> {
+        vec![&self.merkle_path, &self.poseidon]
+    }
+}
+
+impl AirTraceVerifier for MerklePathAir {
+    fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+        // The claims are bound before the elements are drawn.
+        let claims = self
+            .claims
+            .iter()
+            .flat_map(|claim| {
+                claim
+                    .leaf
+                    .into_iter()
+                    .chain([claim.index, claim.depth].map(BaseField::from))
+                    .chain(claim.root)
+            })
+            .map(SecureField::from)
+            .collect_vec();
+        channel.mix_felts(&claims);
+
+        let ids = self
+            .merkle_path
+            .interaction_element_ids()
+            .into_iter()
+            .chain(self.poseidon.interaction_element_ids())
+            .collect::<BTreeSet<_>>();
+        let elements = channel.draw_felts(ids.len());
+        InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
+    }
+
+    fn verify_lookups(
+        &self,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        for claim in &self.claims {
+            claim.validate()?;
+        }
+        // Each path starts by yielding its leaf, and ends by using its root.
+        let (starts, ends): (Vec<_>, Vec<_>) = self
+            .claims
+            .iter()
+            .enumerate()
+            .map(|(path_index, claim)| {
+                let [start, end] = claim.endpoints(path_index);
+                (start, end)
+            })
+            .unzip();
+        let public_sum = public_sum(
+            MERKLE_PATH_RELATION,
+            LookupDirection::Yield,
+            starts,
+            interaction_elements,
+        ) + public_sum(
+            MERKLE_PATH_RELATION,
+            LookupDirection::Use,
+            ends,
+            interaction_elements,
+        );
+        verify_balanced_with_public(
+            &[self.merkle_path.logup(), self.poseidon.logup()],
+            public_sum,
+            lookup_values,
+        )
+    }
+}
+
+impl AirTraceGenerator<SimdBackend> for MerklePathAir {
+    fn interact(
+        &self,
+        trace: &ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let n_merkle_path_columns = self.merkle_path.trace_log_degree_bounds()[BASE_TRACE].len();
+        let (merkle_path_trace, poseidon_trace) = trace.split_at(n_merkle_path_columns);
+        let mut interaction_trace = self
+            .merkle_path
+            .logup()
+            .write_interaction_trace(&merkle_path_trace.iter().collect(), elements);
+        interaction_trace.extend(
+            self.poseidon
+                .logup()
+                .write_interaction_trace(&poseidon_trace.iter().collect(), elements),
+        );
+        interaction_trace
+    }
+
+    fn to_air_prover(&self) -> impl AirProver<SimdBackend> {
+        self.clone()
+    }
+
+    fn composition_log_degree_bound(&self) -> u32 {
+        self.components()
+            .iter()
+            .map(|component| component.max_constraint_log_degree_bound())
+            .max()
+            .unwrap()
+    }
+}
+
+impl AirProver<SimdBackend> for MerklePathAir {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<SimdBackend>> {
+        vec![&self.merkle_path, &self.poseidon]
+    }
+}
+
+/// Writes the trace of a [MerklePathAir] of `2^log_n_rows` steps, proving `paths`.
+pub fn write_trace(
+    log_n_rows: u32,
+    paths: &[MerklePath],
+) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+    let mut registry = ComponentGenerationRegistry::default();
+    registry.register(MERKLE_PATH_ID, MerklePathTraceGenerator::new(log_n_rows));
+    registry.register(
+        POSEIDON_ID,
+        PoseidonTraceGenerator::new(poseidon_log_n_rows(log_n_rows)),
+    );
+    registry
+        .get_generator_mut::<MerklePathTraceGenerator>(MERKLE_PATH_ID)
+        .add_inputs(&paths.to_vec());
+
+    let mut trace = MerklePathTraceGenerator::write_trace(MERKLE_PATH_ID, &mut registry);
+    trace.extend(PoseidonTraceGenerator::write_trace(
+        POSEIDON_ID,
+        &mut registry,
+    ));
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::component::{Digest, MerklePath};
+    use super::{write_trace, MerklePathAir};
+    use crate::core::air::logup::LookupError;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::prover::{prove, verify, VerificationError};
+    use crate::core::test_utils::test_channel;
+    use crate::hash_functions::poseidon2::compress;
+
+    const LOG_N_ROWS: u32 = 7;
+    const TREE_DEPTH: usize = 10;
+
+    /// Returns the layers of a random tree of depth [TREE_DEPTH], from the leaves to the root.
+    fn random_tree() -> Vec<Vec<Digest>> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let leaves = (0..1 << TREE_DEPTH)
+            .map(|_| std::array::from_fn(|_| BaseField::from(rng.gen::<u32>() >> 1)))
+            .collect_vec();
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .iter()
+                .tuples()
+                .map(|(&left, &right)| compress(left, right))
+                .collect();
+            layers.push(layer);
+        }
+        layers
+    }
+
+    fn path(layers: &[Vec<Digest>], index: u32) -> MerklePath {
+        MerklePath {
+            leaf: layers[0][index as usize],
+            index,
+            siblings: layers[..TREE_DEPTH]
+                .iter()
+                .enumerate()
+                .map(|(level, layer)| layer[(index as usize >> level) ^ 1])
+                .collect(),
+        }
+    }
+
+    fn test_paths() -> Vec<MerklePath> {
+        let layers = random_tree();
+        [0, 1, 341, 682, 1023]
+            .map(|index| path(&layers, index))
+            .to_vec()
+    }
+
+    #[test]
+    fn test_path_root_matches_tree() {
+        let layers = random_tree();
+        let root = layers[TREE_DEPTH][0];
+
+        for path in test_paths() {
+            assert_eq!(path.root(), root);
+        }
+    }
+
+    #[test]
+    fn test_merkle_path_prove() {
+        let paths = test_paths();
+        let air = MerklePathAir::new(LOG_N_ROWS, paths.iter().map_into().collect());
+        let trace = write_trace(LOG_N_ROWS, &paths);
+
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        verify(proof, &air, &mut test_channel()).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_claim_fails_verification() {
+        let paths = test_paths();
+        let claims = paths.iter().map_into().collect_vec();
+        let air = MerklePathAir::new(LOG_N_ROWS, claims.clone());
+        let trace = write_trace(LOG_N_ROWS, &paths);
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+        let mut wrong_claims = claims;
+        wrong_claims[2].index ^= 1;
+        let wrong_air = MerklePathAir::new(LOG_N_ROWS, wrong_claims);
+
+        let result = verify(proof, &wrong_air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    fn test_wrong_sibling_fails_verification() {
+        let mut paths = test_paths();
+        let claims = paths.iter().map_into().collect_vec();
+        paths[1].siblings[4][0] += BaseField::from(1);
+        let air = MerklePathAir::new(LOG_N_ROWS, claims);
+        let trace = write_trace(LOG_N_ROWS, &paths);
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        let result = verify(proof, &air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    fn test_out_of_range_claim_fails_verification() {
+        let paths = test_paths();
+        let claims = paths.iter().map_into().collect_vec();
+        let air = MerklePathAir::new(LOG_N_ROWS, claims.clone());
+        let trace = write_trace(LOG_N_ROWS, &paths);
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+        // The index differs from the proven one by 2^31, which is 1 in the field.
+        let mut wrong_claims = claims;
+        wrong_claims[0].depth = 32;
+        wrong_claims[0].index += 1 << 31;
+        let wrong_air = MerklePathAir::new(LOG_N_ROWS, wrong_claims);
+
+        let result = verify(proof, &wrong_air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::InvalidClaim(_)))
+        ));
+    }
+}
@@ -1,3 +1,8 @@
+pub mod blake2s;
 pub mod fibonacci;
+pub mod fri_fold;
+pub mod merkle_path;
 pub mod poseidon;
+pub mod register_vm;
+pub mod sorted_column;
 pub mod wide_fibonacci;
@@ -1,20 +1,31 @@
 //! AIR for Poseidon2 hash function from <https://eprint.iacr.org/2023/323.pdf>.
-
+//!
+//! Each row holds `N_INSTANCES_PER_ROW` instances of the permutation of
+//! [hash_functions::poseidon2](crate::hash_functions::poseidon2). Each instance yields its input
+//! followed by its output to [POSEIDON2_RELATION], with the multiplicity of its last column, so
+//! other components can request invocations of the permutation by using these tuples. The
+//! invocations of the statement of a [PoseidonAir] are used by the verifier itself.
+
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::{Add, AddAssign, Mul, Sub};
 
-use itertools::Itertools;
-use num_traits::Zero;
+use itertools::{zip_eq, Itertools};
+use num_traits::{One, Zero};
 use tracing::{span, Level};
 
 use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{
+    public_sum, verify_balanced_with_public, Logup, LookupDirection, LookupEntry, LookupError,
+    Multiplicity,
+};
 use crate::core::air::mask::fixed_mask_points;
 use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
 use crate::core::backend::simd::column::BaseFieldVec;
-use crate::core::backend::simd::m31::{PackedBaseField, LOG_N_LANES};
+use crate::core::backend::simd::m31::{PackedBaseField, LOG_N_LANES, N_LANES};
 use crate::core::backend::simd::qm31::PackedSecureField;
 use crate::core::backend::simd::SimdBackend;
 use crate::core::backend::{Col, Column, ColumnOps};
-use crate::core::channel::Blake2sChannel;
+use crate::core::channel::{Blake2sChannel, Channel};
 use crate::core::circle::CirclePoint;
 use crate::core::constraints::coset_vanishing;
 use crate::core::fields::m31::BaseField;
@@ -23,42 +34,98 @@ use crate::core::fields::{FieldExpOps, FieldOps};
 use crate::core::pcs::TreeVec;
 use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps};
 use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
 use crate::core::{ColumnVec, InteractionElements, LookupValues};
-use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
-
-const N_LOG_INSTANCES_PER_ROW: usize = 3;
+use crate::hash_functions::poseidon2::{
+    apply_external_round_matrix, apply_internal_round_matrix, permute, pow5, round_constants,
+    N_FULL_ROUNDS, N_HALF_FULL_ROUNDS, N_PARTIAL_ROUNDS, WIDTH,
+};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{
+    AirTraceGenerator, AirTraceVerifier, ComponentGen, ComponentTraceGenerator,
+};
+
+/// The relation of the invocations of the permutation, as tuples of their input followed by their
+/// output.
+pub const POSEIDON2_RELATION: &str = "poseidon2";
+pub const N_LOG_INSTANCES_PER_ROW: usize = 3;
 const N_INSTANCES_PER_ROW: usize = 1 << N_LOG_INSTANCES_PER_ROW;
-const N_STATE: usize = 16;
-const N_PARTIAL_ROUNDS: usize = 14;
-const N_HALF_FULL_ROUNDS: usize = 4;
-const FULL_ROUNDS: usize = 2 * N_HALF_FULL_ROUNDS;
-const N_COLUMNS_PER_REP: usize = N_STATE * (1 + FULL_ROUNDS) + N_PARTIAL_ROUNDS;
+/// The columns of an instance: its input, the states after the S-boxes of the full rounds, the
+/// first elements after the S-boxes of the partial rounds, its output and its multiplicity.
+const N_COLUMNS_PER_REP: usize = WIDTH * (2 + N_FULL_ROUNDS) + N_PARTIAL_ROUNDS + 1;
+const OUTPUT_OFFSET: usize = N_COLUMNS_PER_REP - WIDTH - 1;
+const MULTIPLICITY_OFFSET: usize = N_COLUMNS_PER_REP - 1;
+/// All the columns but the input and the multiplicity are constrained.
+const N_CONSTRAINTS_PER_REP: usize = N_COLUMNS_PER_REP - WIDTH - 1;
 const N_COLUMNS: usize = N_INSTANCES_PER_ROW * N_COLUMNS_PER_REP;
 const LOG_EXPAND: u32 = 2;
-// TODO(spapini): Pick better constants.
-const EXTERNAL_ROUND_CONSTS: [[BaseField; N_STATE]; 2 * N_HALF_FULL_ROUNDS] =
-    [[BaseField::from_u32_unchecked(1234); N_STATE]; 2 * N_HALF_FULL_ROUNDS];
-const INTERNAL_ROUND_CONSTS: [BaseField; N_PARTIAL_ROUNDS] =
-    [BaseField::from_u32_unchecked(1234); N_PARTIAL_ROUNDS];
+
+/// An invocation of the permutation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Poseidon2Invocation {
+    pub input: [BaseField; WIDTH],
+    pub output: [BaseField; WIDTH],
+}
+
+impl Poseidon2Invocation {
+    pub fn new(input: [BaseField; WIDTH]) -> Self {
+        let mut output = input;
+        permute(&mut output);
+        Self { input, output }
+    }
+
+    /// Returns the tuple of the invocation in [POSEIDON2_RELATION].
+    pub fn tuple(&self) -> Vec<BaseField> {
+        self.input.iter().chain(&self.output).copied().collect()
+    }
+}
 
 #[derive(Clone)]
 pub struct PoseidonComponent {
-    pub log_n_instances: u32,
+    pub log_n_rows: u32,
 }
 
 impl PoseidonComponent {
     pub fn log_column_size(&self) -> u32 {
-        self.log_n_instances
+        self.log_n_rows
     }
 
     pub fn n_columns(&self) -> usize {
         N_COLUMNS
     }
+
+    /// Returns the lookup yielding the invocations of the instances to [POSEIDON2_RELATION].
+    pub fn logup(&self) -> Logup {
+        let entries = (0..N_INSTANCES_PER_ROW)
+            .map(|rep| {
+                let offset = rep * N_COLUMNS_PER_REP;
+                let values = (offset..offset + WIDTH)
+                    .chain(offset + OUTPUT_OFFSET..offset + OUTPUT_OFFSET + WIDTH)
+                    .collect();
+                LookupEntry::yields(
+                    POSEIDON2_RELATION,
+                    values,
+                    Multiplicity::Column(offset + MULTIPLICITY_OFFSET),
+                )
+            })
+            .collect();
+        Logup::new("poseidon2", self.log_n_rows, entries)
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        self.logup().interaction_element_ids()
+    }
+
+    fn n_permutation_constraints(&self) -> usize {
+        N_CONSTRAINTS_PER_REP * N_INSTANCES_PER_ROW
+    }
 }
 
+/// An AIR proving the invocations of its statement.
 #[derive(Clone)]
 pub struct PoseidonAir {
     pub component: PoseidonComponent,
+    pub invocations: Vec<Poseidon2Invocation>,
 }
 
 impl Air for PoseidonAir {
@@ -68,18 +135,53 @@ impl Air for PoseidonAir {
 }
 
 impl AirTraceVerifier for PoseidonAir {
-    fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
-        InteractionElements::default()
+    fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+        // The statement is bound before the elements are drawn.
+        let statement = self
+            .invocations
+            .iter()
+            .flat_map(|invocation| invocation.tuple())
+            .collect_vec();
+        channel.mix_felts(
+            &statement
+                .chunks(4)
+                .map(|chunk| SecureField::from_m31_array(chunk.try_into().unwrap()))
+                .collect_vec(),
+        );
+
+        let ids = self
+            .component
+            .interaction_element_ids()
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let elements = channel.draw_felts(ids.len());
+        InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
+    }
+
+    fn verify_lookups(
+        &self,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        let public_sum = public_sum(
+            POSEIDON2_RELATION,
+            LookupDirection::Use,
+            self.invocations.iter().map(|invocation| invocation.tuple()),
+            interaction_elements,
+        );
+        verify_balanced_with_public(&[self.component.logup()], public_sum, lookup_values)
     }
 }
 
 impl AirTraceGenerator<SimdBackend> for PoseidonAir {
     fn interact(
         &self,
-        _trace: &ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
-        _elements: &InteractionElements,
+        trace: &ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
     ) -> Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
-        vec![]
+        self.component
+            .logup()
+            .write_interaction_trace(&trace.iter().collect(), elements)
     }
 
     fn to_air_prover(&self) -> impl AirProver<SimdBackend> {
@@ -93,7 +195,7 @@ impl AirTraceGenerator<SimdBackend> for PoseidonAir {
 
 impl Component for PoseidonComponent {
     fn n_constraints(&self) -> usize {
-        (N_COLUMNS_PER_REP - N_STATE) * N_INSTANCES_PER_ROW
+        self.n_permutation_constraints() + self.logup().n_constraints()
     }
 
     fn max_constraint_log_degree_bound(&self) -> u32 {
@@ -101,21 +203,24 @@ impl Component for PoseidonComponent {
     }
 
     fn n_interaction_phases(&self) -> u32 {
-        1
+        2
     }
 
     fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
-        TreeVec::new(vec![vec![self.log_column_size(); N_COLUMNS]])
+        TreeVec::new(vec![
+            vec![self.log_column_size(); N_COLUMNS],
+            self.logup().interaction_log_degree_bounds(),
+        ])
     }
 
     fn mask_points(
         &self,
         point: CirclePoint<SecureField>,
     ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
-        TreeVec::new(vec![fixed_mask_points(
-            &vec![vec![0_usize]; N_COLUMNS],
-            point,
-        )])
+        TreeVec::new(vec![
+            fixed_mask_points(&vec![vec![0_usize]; N_COLUMNS], point),
+            self.logup().interaction_mask_points(point),
+        ])
     }
 
     fn evaluate_constraint_quotients_at_point(
@@ -123,14 +228,14 @@ impl Component for PoseidonComponent {
         point: CirclePoint<SecureField>,
         mask: &TreeVec<Vec<Vec<SecureField>>>,
         evaluation_accumulator: &mut PointEvaluationAccumulator,
-        _interaction_elements: &InteractionElements,
-        _lookup_values: &LookupValues,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
     ) {
         let constraint_zero_domain = CanonicCoset::new(self.log_column_size()).coset;
         let denom = coset_vanishing(constraint_zero_domain, point);
         let denom_inverse = denom.inverse();
         let mut eval = PoseidonEvalAtPoint {
-            mask: &mask[0],
+            mask: &mask[BASE_TRACE],
             evaluation_accumulator,
             col_index: 0,
             denom_inverse,
@@ -139,72 +244,17 @@ impl Component for PoseidonComponent {
             eval.eval();
         }
         assert_eq!(eval.col_index, N_COLUMNS);
-    }
-}
-
-#[inline(always)]
-/// Applies the M4 MDS matrix described in <https://eprint.iacr.org/2023/323.pdf> 5.1.
-fn apply_m4<F>(x: [F; 4]) -> [F; 4]
-where
-    F: Copy + AddAssign<F> + Add<F, Output = F> + Sub<F, Output = F> + Mul<BaseField, Output = F>,
-{
-    let t0 = x[0] + x[1];
-    let t02 = t0 + t0;
-    let t1 = x[2] + x[3];
-    let t12 = t1 + t1;
-    let t2 = x[1] + x[1] + t1;
-    let t3 = x[3] + x[3] + t0;
-    let t4 = t12 + t12 + t3;
-    let t5 = t02 + t02 + t2;
-    let t6 = t3 + t5;
-    let t7 = t2 + t4;
-    [t6, t5, t7, t4]
-}
 
-/// Applies the external round matrix.
-/// See <https://eprint.iacr.org/2023/323.pdf> 5.1 and Appendix B.
-fn apply_external_round_matrix<F>(state: &mut [F; 16])
-where
-    F: Copy + AddAssign<F> + Add<F, Output = F> + Sub<F, Output = F> + Mul<BaseField, Output = F>,
-{
-    // Applies circ(2M4, M4, M4, M4).
-    for i in 0..4 {
-        [
-            state[4 * i],
-            state[4 * i + 1],
-            state[4 * i + 2],
-            state[4 * i + 3],
-        ] = apply_m4([
-            state[4 * i],
-            state[4 * i + 1],
-            state[4 * i + 2],
-            state[4 * i + 3],
-        ]);
-    }
-    for j in 0..4 {
-        let s = state[j] + state[j + 4] + state[j + 8] + state[j + 12];
-        for i in 0..4 {
-            state[4 * i + j] += s;
-        }
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
     }
 }
 
-// Applies the internal round matrix.
-//   mu_i = 2^{i+1} + 1.
-// See <https://eprint.iacr.org/2023/323.pdf> 5.2.
-fn apply_internal_round_matrix<F>(state: &mut [F; 16])
-where
-    F: Copy + AddAssign<F> + Add<F, Output = F> + Sub<F, Output = F> + Mul<BaseField, Output = F>,
-{
-    // TODO(spapini): Check that these coefficients are good according to section  5.3 of Poseidon2
-    // paper.
-    let sum = state[1..].iter().fold(state[0], |acc, s| acc + *s);
-    state.iter_mut().enumerate().for_each(|(i, s)| {
-        // TODO(spapini): Change to rotations.
-        *s = *s * BaseField::from_u32_unchecked(1 << (i + 1)) + sum;
-    });
-}
-
 struct PoseidonEvalAtPoint<'a> {
     mask: &'a ColumnVec<Vec<SecureField>>,
     evaluation_accumulator: &'a mut PointEvaluationAccumulator,
@@ -225,12 +275,6 @@ impl<'a> PoseidonEval for PoseidonEvalAtPoint<'a> {
     }
 }
 
-fn pow5<F: FieldExpOps>(x: F) -> F {
-    let x2 = x * x;
-    let x4 = x2 * x2;
-    x4 * x
-}
-
 trait PoseidonEval {
     type F: FieldExpOps
         + Copy
@@ -243,46 +287,45 @@ trait PoseidonEval {
     fn next_mask(&mut self) -> Self::F;
     fn add_constraint(&mut self, constraint: Self::F);
 
+    /// Constrains the next column to `value`, and returns it.
+    fn commit(&mut self, value: Self::F) -> Self::F {
+        let m = self.next_mask();
+        self.add_constraint(value - m);
+        m
+    }
+
+    fn full_round(&mut self, state: &mut [Self::F; WIDTH], round: usize) {
+        let constants = &round_constants().external[round];
+        for (s, &c) in state.iter_mut().zip(constants) {
+            *s += c;
+            *s = self.commit(pow5(*s));
+        }
+        apply_external_round_matrix(state);
+    }
+
     fn eval(&mut self) {
-        let mut state: [_; N_STATE] = std::array::from_fn(|_| self.next_mask());
+        let mut state: [_; WIDTH] = std::array::from_fn(|_| self.next_mask());
+        apply_external_round_matrix(&mut state);
 
         // 4 full rounds.
-        (0..N_HALF_FULL_ROUNDS).for_each(|round| {
-            (0..N_STATE).for_each(|i| {
-                state[i] += EXTERNAL_ROUND_CONSTS[round][i];
-            });
-            apply_external_round_matrix(&mut state);
-            state = std::array::from_fn(|i| pow5(state[i]));
-            state.iter_mut().for_each(|s| {
-                let m = self.next_mask();
-                self.add_constraint(*s - m);
-                *s = m;
-            });
-        });
+        (0..N_HALF_FULL_ROUNDS).for_each(|round| self.full_round(&mut state, round));
 
         // Partial rounds.
-        (0..N_PARTIAL_ROUNDS).for_each(|round| {
-            state[0] += INTERNAL_ROUND_CONSTS[round];
+        for &c in &round_constants().internal {
+            state[0] += c;
+            state[0] = self.commit(pow5(state[0]));
             apply_internal_round_matrix(&mut state);
-            state[0] = pow5(state[0]);
-            let m = self.next_mask();
-            self.add_constraint(state[0] - m);
-            state[0] = m;
-        });
+        }
 
         // 4 full rounds.
-        (0..N_HALF_FULL_ROUNDS).for_each(|round| {
-            (0..N_STATE).for_each(|i| {
-                state[i] += EXTERNAL_ROUND_CONSTS[round + N_HALF_FULL_ROUNDS][i];
-            });
-            apply_external_round_matrix(&mut state);
-            state = std::array::from_fn(|i| pow5(state[i]));
-            state.iter_mut().for_each(|s| {
-                let m = self.next_mask();
-                self.add_constraint(*s - m);
-                *s = m;
-            });
+        (N_HALF_FULL_ROUNDS..N_FULL_ROUNDS).for_each(|round| self.full_round(&mut state, round));
+
+        // Output.
+        state.into_iter().for_each(|s| {
+            self.commit(s);
         });
+        // Multiplicity.
+        self.next_mask();
     }
 }
 
@@ -292,102 +335,155 @@ impl AirProver<SimdBackend> for PoseidonAir {
     }
 }
 
-pub fn gen_trace(
-    log_size: u32,
-) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
-    assert!(log_size >= LOG_N_LANES);
-    let mut trace = (0..N_COLUMNS)
-        .map(|_| Col::<SimdBackend, BaseField>::zeros(1 << log_size))
-        .collect_vec();
-    for vec_index in 0..(1 << (log_size - LOG_N_LANES)) {
-        // Initial state.
-        let mut col_index = 0;
-        for rep_i in 0..N_INSTANCES_PER_ROW {
-            let mut state: [_; N_STATE] = std::array::from_fn(|state_i| {
-                PackedBaseField::from_array(std::array::from_fn(|i| {
-                    BaseField::from_u32_unchecked((vec_index * 16 + i + state_i + rep_i) as u32)
-                }))
-            });
-            state.iter().copied().for_each(|s| {
-                trace[col_index].data[vec_index] = s;
-                col_index += 1;
-            });
-
-            // 4 full rounds.
-            (0..N_HALF_FULL_ROUNDS).for_each(|round| {
-                (0..N_STATE).for_each(|i| {
-                    state[i] += PackedBaseField::broadcast(EXTERNAL_ROUND_CONSTS[round][i]);
-                });
-                apply_external_round_matrix(&mut state);
-                state = std::array::from_fn(|i| pow5(state[i]));
-                state.iter().copied().for_each(|s| {
-                    trace[col_index].data[vec_index] = s;
-                    col_index += 1;
-                });
-            });
-
-            // Partial rounds.
-            (0..N_PARTIAL_ROUNDS).for_each(|round| {
-                state[0] += PackedBaseField::broadcast(INTERNAL_ROUND_CONSTS[round]);
-                apply_internal_round_matrix(&mut state);
-                state[0] = pow5(state[0]);
-                trace[col_index].data[vec_index] = state[0];
-                col_index += 1;
-            });
-
-            // 4 full rounds.
-            (0..N_HALF_FULL_ROUNDS).for_each(|round| {
-                (0..N_STATE).for_each(|i| {
-                    state[i] += PackedBaseField::broadcast(
-                        EXTERNAL_ROUND_CONSTS[round + N_HALF_FULL_ROUNDS][i],
-                    );
-                });
-                apply_external_round_matrix(&mut state);
-                state = std::array::from_fn(|i| pow5(state[i]));
-                state.iter().copied().for_each(|s| {
-                    trace[col_index].data[vec_index] = s;
-                    col_index += 1;
-                });
-            });
+/// Writes the columns of [N_LANES] instances at a time, one vector of each column.
+struct PoseidonTraceWriter<'a> {
+    trace: &'a mut [Col<SimdBackend, BaseField>],
+    vec_index: usize,
+    col_index: usize,
+    /// The values of the columns of the instance which are not committed, i.e. its input and
+    /// multiplicity.
+    free_values: std::vec::IntoIter<PackedBaseField>,
+}
+
+impl<'a> PoseidonTraceWriter<'a> {
+    fn write(&mut self, value: PackedBaseField) -> PackedBaseField {
+        self.trace[self.col_index].data[self.vec_index] = value;
+        self.col_index += 1;
+        value
+    }
+}
+
+impl<'a> PoseidonEval for PoseidonTraceWriter<'a> {
+    type F = PackedBaseField;
+
+    fn next_mask(&mut self) -> Self::F {
+        let value = self.free_values.next().unwrap();
+        self.write(value)
+    }
+
+    fn add_constraint(&mut self, _constraint: Self::F) {
+        unreachable!("the committed values are written, not constrained");
+    }
+
+    fn commit(&mut self, value: Self::F) -> Self::F {
+        self.write(value)
+    }
+}
+
+/// Writes the trace of a [PoseidonComponent] on SIMD, proving the invocations of the inputs added
+/// by other components.
+pub struct PoseidonTraceGenerator {
+    log_n_rows: u32,
+    inputs: Vec<[BaseField; WIDTH]>,
+}
+
+impl PoseidonTraceGenerator {
+    pub fn new(log_n_rows: u32) -> Self {
+        assert!(log_n_rows >= LOG_N_LANES);
+        Self {
+            log_n_rows,
+            inputs: vec![],
         }
     }
-    let domain = CanonicCoset::new(log_size).circle_domain();
-    trace
-        .into_iter()
-        .map(|eval| CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(domain, eval))
-        .collect_vec()
 }
 
-impl ComponentTraceGenerator<SimdBackend> for PoseidonComponent {
-    type Component = Self;
-    type Inputs = ();
+impl ComponentGen for PoseidonTraceGenerator {}
 
-    fn add_inputs(&mut self, _inputs: &Self::Inputs) {
-        todo!()
+impl ComponentTraceGenerator<SimdBackend> for PoseidonTraceGenerator {
+    type Component = PoseidonComponent;
+    type Inputs = Vec<[BaseField; WIDTH]>;
+
+    /// Adds inputs to permute, each yielding its invocation once. The remaining instances permute
+    /// zeroes, with a multiplicity of zero.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        self.inputs.extend(inputs);
+        assert!(
+            self.inputs.len() <= N_INSTANCES_PER_ROW << self.log_n_rows,
+            "more than 2^{} invocations",
+            self.log_n_rows as usize + N_LOG_INSTANCES_PER_ROW
+        );
     }
 
     fn write_trace(
-        _component_id: &str,
-        _registry: &mut crate::trace_generation::registry::ComponentGenerationRegistry,
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
     ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
-        todo!()
+        let generator = registry.get_generator::<Self>(component_id);
+        let log_size = generator.log_n_rows;
+        let n_inputs = generator.inputs.len();
+        let mut inputs = generator.inputs.clone();
+        inputs.resize(N_INSTANCES_PER_ROW << log_size, [BaseField::zero(); WIDTH]);
+
+        let mut trace = (0..N_COLUMNS)
+            .map(|_| Col::<SimdBackend, BaseField>::zeros(1 << log_size))
+            .collect_vec();
+        for vec_index in 0..(1 << (log_size - LOG_N_LANES)) {
+            let mut writer = PoseidonTraceWriter {
+                trace: &mut trace,
+                vec_index,
+                col_index: 0,
+                free_values: vec![].into_iter(),
+            };
+            for rep_i in 0..N_INSTANCES_PER_ROW {
+                // The instances of each repetition are consecutive invocations.
+                let start = (rep_i << log_size) + vec_index * N_LANES;
+                let instances = &inputs[start..start + N_LANES];
+                let input = (0..WIDTH).map(|state_i| {
+                    PackedBaseField::from_array(std::array::from_fn(|i| instances[i][state_i]))
+                });
+                let multiplicity = PackedBaseField::from_array(std::array::from_fn(|i| {
+                    if start + i < n_inputs {
+                        BaseField::one()
+                    } else {
+                        BaseField::zero()
+                    }
+                }));
+                writer.free_values = input.chain([multiplicity]).collect_vec().into_iter();
+                writer.eval();
+            }
+            assert_eq!(writer.col_index, N_COLUMNS);
+        }
+        let domain = CanonicCoset::new(log_size).circle_domain();
+        trace
+            .into_iter()
+            .map(|eval| CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(domain, eval))
+            .collect_vec()
     }
 
     fn write_interaction_trace(
         &self,
-        _trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
-        _elements: &InteractionElements,
+        trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
     ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
-        vec![]
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
     }
 
     fn component(&self) -> Self::Component {
-        todo!()
+        PoseidonComponent {
+            log_n_rows: self.log_n_rows,
+        }
     }
 }
 
+/// Writes the trace of a [PoseidonComponent] of `2^log_n_rows` rows, proving the invocations of
+/// `inputs`.
+pub fn gen_trace(
+    log_n_rows: u32,
+    inputs: &[[BaseField; WIDTH]],
+) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+    const POSEIDON_ID: &str = "poseidon";
+    let mut registry = ComponentGenerationRegistry::default();
+    registry.register(POSEIDON_ID, PoseidonTraceGenerator::new(log_n_rows));
+    registry
+        .get_generator_mut::<PoseidonTraceGenerator>(POSEIDON_ID)
+        .add_inputs(&inputs.to_vec());
+    PoseidonTraceGenerator::write_trace(POSEIDON_ID, &mut registry)
+}
+
 struct PoseidonEvalAtDomain<'a> {
-    trace_eval: &'a TreeVec<Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>>,
+    trace_eval: &'a [CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>],
     vec_row: usize,
     random_coeff_powers: &'a [SecureField],
     row_res: PackedSecureField,
@@ -399,7 +495,8 @@ impl<'a> PoseidonEval for PoseidonEvalAtDomain<'a> {
 
     fn next_mask(&mut self) -> Self::F {
         let res = unsafe {
-            *self.trace_eval[0]
+            *self
+                .trace_eval
                 .get_unchecked(self.col_index)
                 .data
                 .get_unchecked(self.vec_row)
@@ -420,10 +517,10 @@ impl ComponentProver<SimdBackend> for PoseidonComponent {
         &self,
         trace: &ComponentTrace<'_, SimdBackend>,
         evaluation_accumulator: &mut DomainEvaluationAccumulator<SimdBackend>,
-        _interaction_elements: &InteractionElements,
-        _lookup_values: &LookupValues,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
     ) {
-        assert_eq!(trace.polys[0].len(), self.n_columns());
+        assert_eq!(trace.polys[BASE_TRACE].len(), self.n_columns());
         let eval_domain = CanonicCoset::new(self.log_column_size() + LOG_EXPAND).circle_domain();
 
         // Create a new evaluation.
@@ -433,10 +530,10 @@ impl ComponentProver<SimdBackend> for PoseidonComponent {
                 .circle_domain()
                 .half_coset,
         );
-        let trace_eval = trace
-            .polys
-            .as_cols_ref()
-            .map_cols(|col| col.evaluate_with_twiddles(eval_domain, &twiddles));
+        let trace_eval = trace.polys[BASE_TRACE]
+            .iter()
+            .map(|poly| poly.evaluate_with_twiddles(eval_domain, &twiddles))
+            .collect_vec();
         span.exit();
 
         // Denoms.
@@ -449,10 +546,10 @@ impl ComponentProver<SimdBackend> for PoseidonComponent {
         <SimdBackend as FieldOps<BaseField>>::batch_inverse(&denoms, &mut denom_inverses);
         span.exit();
 
-        let _span = span!(Level::INFO, "Constraint pointwise eval").entered();
+        let span = span!(Level::INFO, "Constraint pointwise eval").entered();
 
         let constraint_log_degree_bound = self.max_constraint_log_degree_bound();
-        let n_constraints = self.n_constraints();
+        let n_constraints = self.n_permutation_constraints();
         let [accum] =
             evaluation_accumulator.columns([(constraint_log_degree_bound, n_constraints)]);
         let mut pows = accum.random_coeff_powers.clone();
@@ -480,10 +577,19 @@ impl ComponentProver<SimdBackend> for PoseidonComponent {
             }
             assert_eq!(evaluator.constraint_index, n_constraints);
         }
+        span.exit();
+
+        let _span = span!(Level::INFO, "Lookup constraints eval").entered();
+        self.logup().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
     }
 
-    fn lookup_values(&self, _trace: &ComponentTrace<'_, SimdBackend>) -> LookupValues {
-        LookupValues::default()
+    fn lookup_values(&self, trace: &ComponentTrace<'_, SimdBackend>) -> LookupValues {
+        self.logup().lookup_values(trace)
     }
 }
 
@@ -492,57 +598,61 @@ mod tests {
     use std::env;
 
     use itertools::Itertools;
-    use num_traits::One;
     use tracing::{span, Level};
 
-    use super::N_LOG_INSTANCES_PER_ROW;
+    use super::{
+        gen_trace, Poseidon2Invocation, PoseidonAir, PoseidonComponent, N_COLUMNS_PER_REP,
+        N_LOG_INSTANCES_PER_ROW, OUTPUT_OFFSET,
+    };
+    use crate::core::air::logup::LookupError;
     use crate::core::backend::simd::SimdBackend;
-    use crate::core::channel::{Blake2sChannel, Channel};
+    use crate::core::backend::Column;
     use crate::core::fields::m31::BaseField;
-    use crate::core::fields::IntoSlice;
-    use crate::core::prover::{prove, verify};
-    use crate::core::vcs::blake2_hash::Blake2sHasher;
-    use crate::core::vcs::hasher::Hasher;
-    use crate::examples::poseidon::{
-        apply_internal_round_matrix, apply_m4, gen_trace, PoseidonAir, PoseidonComponent,
-    };
-    use crate::math::matrix::{RowMajorMatrix, SquareMatrix};
+    use crate::core::poly::circle::CircleEvaluation;
+    use crate::core::prover::{prove, verify, ProvingError, VerificationError};
+    use crate::core::test_utils::test_channel;
+    use crate::hash_functions::poseidon2::WIDTH;
 
-    #[test]
-    fn test_apply_m4() {
-        let m4 = RowMajorMatrix::<BaseField, 4>::new(
-            [5, 7, 1, 3, 4, 6, 1, 1, 1, 3, 5, 7, 1, 1, 4, 6]
-                .map(BaseField::from_u32_unchecked)
-                .into_iter()
-                .collect_vec(),
-        );
-        let state = (0..4)
-            .map(BaseField::from_u32_unchecked)
-            .collect_vec()
-            .try_into()
-            .unwrap();
+    const LOG_N_ROWS: u32 = 5;
 
-        assert_eq!(apply_m4(state), m4.mul(state));
+    fn test_inputs(n_inputs: usize) -> Vec<[BaseField; WIDTH]> {
+        (0..n_inputs)
+            .map(|i| std::array::from_fn(|j| BaseField::from(i * WIDTH + j)))
+            .collect()
     }
 
-    #[test]
-    fn test_apply_internal() {
-        let mut state: [BaseField; 16] = (0..16)
-            .map(|i| BaseField::from_u32_unchecked(i * 3 + 187))
-            .collect_vec()
-            .try_into()
-            .unwrap();
-        let mut internal_matrix = [[BaseField::one(); 16]; 16];
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..16 {
-            internal_matrix[i][i] += BaseField::from_u32_unchecked(1 << (i + 1));
+    fn test_air(inputs: &[[BaseField; WIDTH]]) -> PoseidonAir {
+        PoseidonAir {
+            component: PoseidonComponent {
+                log_n_rows: LOG_N_ROWS,
+            },
+            invocations: inputs
+                .iter()
+                .map(|&input| Poseidon2Invocation::new(input))
+                .collect(),
         }
-        let matrix = RowMajorMatrix::<BaseField, 16>::new(internal_matrix.flatten().to_vec());
-
-        let expected_state = matrix.mul(state);
-        apply_internal_round_matrix(&mut state);
+    }
 
-        assert_eq!(state, expected_state);
+    #[test]
+    fn test_trace_outputs_match_native_permutation() {
+        let inputs = test_inputs(100);
+        let air = test_air(&inputs);
+
+        let trace = gen_trace(LOG_N_ROWS, &inputs);
+
+        // Invocation `k` is in row `k mod 2^LOG_N_ROWS` of repetition `k / 2^LOG_N_ROWS`.
+        let columns = trace
+            .iter()
+            .map(|column| column.values.to_cpu())
+            .collect_vec();
+        for (k, invocation) in air.invocations.iter().enumerate() {
+            let (rep, row) = (k >> LOG_N_ROWS, k % (1 << LOG_N_ROWS));
+            let offset = rep * N_COLUMNS_PER_REP;
+            let input: [_; WIDTH] = std::array::from_fn(|i| columns[offset + i][row]);
+            let output: [_; WIDTH] =
+                std::array::from_fn(|i| columns[offset + OUTPUT_OFFSET + i][row]);
+            assert_eq!(Poseidon2Invocation { input, output }, *invocation);
+        }
     }
 
     #[test_log::test]
@@ -554,22 +664,67 @@ mod tests {
 
         // Get from environment variable:
         let log_n_instances = env::var("LOG_N_INSTANCES")
-            .unwrap_or_else(|_| "10".to_string())
+            .unwrap_or_else(|_| "8".to_string())
             .parse::<u32>()
             .unwrap();
         let log_n_rows = log_n_instances - N_LOG_INSTANCES_PER_ROW as u32;
-        let component = PoseidonComponent {
-            log_n_instances: log_n_rows,
+        let inputs = test_inputs(1 << log_n_instances);
+        let air = PoseidonAir {
+            component: PoseidonComponent { log_n_rows },
+            invocations: inputs
+                .iter()
+                .map(|&input| Poseidon2Invocation::new(input))
+                .collect(),
         };
         let span = span!(Level::INFO, "Trace generation").entered();
-        let trace = gen_trace(component.log_column_size());
+        let trace = gen_trace(log_n_rows, &inputs);
         span.exit();
 
-        let channel = &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
-        let air = PoseidonAir { component };
-        let proof = prove::<SimdBackend>(&air, channel, trace).unwrap();
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        verify(proof, &air, &mut test_channel()).unwrap();
+    }
+
+    #[test]
+    fn test_partial_statement_prove() {
+        let inputs = test_inputs(37);
+        let air = test_air(&inputs);
+        let trace = gen_trace(LOG_N_ROWS, &inputs);
+
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        verify(proof, &air, &mut test_channel()).unwrap();
+    }
 
-        let channel = &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
-        verify(proof, &air, channel).unwrap();
+    #[test]
+    fn test_wrong_statement_fails_verification() {
+        let inputs = test_inputs(37);
+        let air = test_air(&inputs);
+        let trace = gen_trace(LOG_N_ROWS, &inputs);
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+        let mut wrong_air = air.clone();
+        wrong_air.invocations[3].output[0] += BaseField::from(1);
+
+        let result = verify(proof, &wrong_air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    fn test_wrong_output_fails() {
+        let inputs = test_inputs(37);
+        let air = test_air(&inputs);
+        let mut trace = gen_trace(LOG_N_ROWS, &inputs);
+        let column = OUTPUT_OFFSET;
+        let mut values = trace[column].values.to_cpu();
+        values[5] += BaseField::from(1);
+        trace[column] = CircleEvaluation::new(trace[column].domain, values.into_iter().collect());
+
+        let result = prove::<SimdBackend, _>(&air, &mut test_channel(), trace);
+
+        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
     }
 }
@@ -0,0 +1,150 @@
+use num_traits::One;
+
+use super::program::Program;
+use super::{to_evaluations, VmComponent, INSTRUCTION_RELATION, PROGRAM_RELATION};
+use crate::core::air::logup::{Logup, LookupEntry, Multiplicity};
+use crate::core::backend::simd::SimdBackend;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::{ColumnVec, InteractionElements};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+const PC: usize = 0;
+const IMM: usize = 5;
+/// The number of executions of the instruction.
+const MULTIPLICITY: usize = 6;
+/// Whether the row holds an instruction, rather than padding.
+const ENABLED: usize = 7;
+const N_COLUMNS: usize = 8;
+
+/// Component holding the instructions of the program, one in each row.
+///
+/// Each instruction is used from [PROGRAM_RELATION], where the verifier yields the program, and
+/// yielded to [INSTRUCTION_RELATION] for each of its executions.
+#[derive(Clone, Debug)]
+pub struct DecodeComponent {
+    pub log_n_rows: u32,
+}
+
+impl DecodeComponent {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self { log_n_rows }
+    }
+}
+
+impl VmComponent for DecodeComponent {
+    fn log_n_rows(&self) -> u32 {
+        self.log_n_rows
+    }
+
+    fn n_columns(&self) -> usize {
+        N_COLUMNS
+    }
+
+    fn n_row_constraints(&self) -> usize {
+        2
+    }
+
+    fn logup(&self) -> Logup {
+        let instruction = (PC..=IMM).collect::<Vec<_>>();
+        Logup::new(
+            "vm_decode",
+            self.log_n_rows,
+            vec![
+                LookupEntry::uses(
+                    PROGRAM_RELATION,
+                    instruction.clone(),
+                    Multiplicity::Column(ENABLED),
+                ),
+                LookupEntry::yields(
+                    INSTRUCTION_RELATION,
+                    instruction,
+                    Multiplicity::Column(MULTIPLICITY),
+                ),
+            ],
+        )
+    }
+
+    fn row_constraints(&self, value: &dyn Fn(usize) -> SecureField) -> Vec<SecureField> {
+        let enabled = value(ENABLED);
+        // Padding rows yield nothing.
+        vec![
+            enabled * (enabled - SecureField::one()),
+            value(MULTIPLICITY) * (enabled - SecureField::one()),
+        ]
+    }
+}
+
+/// Writes the trace of a [DecodeComponent] on SIMD, counting the executions of each instruction.
+pub struct DecodeTraceGenerator {
+    log_n_rows: u32,
+    program: Program,
+    multiplicities: Vec<u32>,
+}
+
+impl DecodeTraceGenerator {
+    pub fn new(log_n_rows: u32, program: Program) -> Self {
+        assert!(program.instructions.len() <= 1 << log_n_rows);
+        Self {
+            log_n_rows,
+            multiplicities: vec![0; program.instructions.len()],
+            program,
+        }
+    }
+}
+
+impl ComponentGen for DecodeTraceGenerator {}
+
+impl ComponentTraceGenerator<SimdBackend> for DecodeTraceGenerator {
+    type Component = DecodeComponent;
+    /// The pcs of executed instructions.
+    type Inputs = Vec<usize>;
+
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        for &pc in inputs {
+            self.multiplicities[pc] += 1;
+        }
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let n_rows = 1 << generator.log_n_rows;
+        let mut trace = vec![vec![BaseField::from(0); n_rows]; N_COLUMNS];
+        for (pc, (instruction, &multiplicity)) in generator
+            .program
+            .instructions
+            .iter()
+            .zip(&generator.multiplicities)
+            .enumerate()
+        {
+            let values = instruction
+                .tuple(pc)
+                .into_iter()
+                .chain([multiplicity.into(), BaseField::one()]);
+            for (column, value) in trace.iter_mut().zip(values) {
+                column[pc] = value;
+            }
+        }
+        to_evaluations(generator.log_n_rows, trace)
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        DecodeComponent::new(self.log_n_rows)
+    }
+}
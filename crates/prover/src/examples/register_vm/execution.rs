@@ -0,0 +1,246 @@
+use num_traits::{One, Zero};
+
+use super::decode::DecodeTraceGenerator;
+use super::memory::MemoryTraceGenerator;
+use super::program::{Opcode, Step};
+use super::{
+    to_evaluations, VmComponent, DECODE_ID, INSTRUCTION_RELATION, MEMORY_ID, READ_RELATION,
+    STATE_RELATION, WRITE_RELATION,
+};
+use crate::core::air::logup::{Logup, LookupEntry, Multiplicity};
+use crate::core::backend::simd::SimdBackend;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::FieldExpOps;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::{ColumnVec, InteractionElements};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+const CLK: usize = 0;
+const NEXT_CLK: usize = 1;
+const PC: usize = 2;
+const NEXT_PC: usize = 3;
+// The instruction, in the order of its tuple.
+const OPCODE: usize = 4;
+const DST: usize = 5;
+const SRC0: usize = 6;
+const SRC1: usize = 7;
+const IMM: usize = 8;
+// The flags of the opcode.
+const IS_ADD: usize = 9;
+const IS_MUL: usize = 10;
+const IS_JNZ: usize = 11;
+const IS_ASSERT: usize = 12;
+/// Whether the row is a step, rather than padding.
+const ENABLED: usize = 13;
+/// Whether the step writes its result.
+const WRITES: usize = 14;
+/// The values of the registers `src0` and `src1`.
+const A: usize = 15;
+const B: usize = 16;
+const PROD: usize = 17;
+pub const RES: usize = 18;
+/// The inverse of `a`, or zero.
+const INV: usize = 19;
+/// Whether `a` is not zero.
+const NONZERO: usize = 20;
+/// Whether the step jumps.
+const TAKEN: usize = 21;
+const N_COLUMNS: usize = 22;
+
+/// Component executing the steps, one in each row.
+///
+/// Each step uses its state from [STATE_RELATION] and its instruction from
+/// [INSTRUCTION_RELATION], reads `src0` and `src1` from [READ_RELATION], yields its write to
+/// [WRITE_RELATION] if the instruction is arithmetic, and yields the next state.
+#[derive(Clone, Debug)]
+pub struct ExecutionComponent {
+    pub log_n_rows: u32,
+}
+
+impl ExecutionComponent {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self { log_n_rows }
+    }
+}
+
+impl VmComponent for ExecutionComponent {
+    fn log_n_rows(&self) -> u32 {
+        self.log_n_rows
+    }
+
+    fn n_columns(&self) -> usize {
+        N_COLUMNS
+    }
+
+    fn n_row_constraints(&self) -> usize {
+        17
+    }
+
+    fn logup(&self) -> Logup {
+        let step = Multiplicity::Column(ENABLED);
+        Logup::new(
+            "vm_execution",
+            self.log_n_rows,
+            vec![
+                LookupEntry::uses(
+                    INSTRUCTION_RELATION,
+                    vec![PC, OPCODE, DST, SRC0, SRC1, IMM],
+                    step,
+                ),
+                LookupEntry::uses(STATE_RELATION, vec![CLK, PC], step),
+                LookupEntry::yields(STATE_RELATION, vec![NEXT_CLK, NEXT_PC], step),
+                LookupEntry::uses(READ_RELATION, vec![CLK, SRC0, A], step),
+                LookupEntry::uses(READ_RELATION, vec![CLK, SRC1, B], step),
+                LookupEntry::yields(
+                    WRITE_RELATION,
+                    vec![CLK, DST, RES],
+                    Multiplicity::Column(WRITES),
+                ),
+            ],
+        )
+    }
+
+    fn row_constraints(&self, value: &dyn Fn(usize) -> SecureField) -> Vec<SecureField> {
+        let one = SecureField::one();
+        let flags = [IS_ADD, IS_MUL, IS_JNZ, IS_ASSERT].map(value);
+        let [is_add, is_mul, is_jnz, is_assert] = flags;
+        let [enabled, a, b, prod, res, nonzero, taken, pc, imm] =
+            [ENABLED, A, B, PROD, RES, NONZERO, TAKEN, PC, IMM].map(value);
+
+        let mut constraints = flags.map(|flag| flag * (flag - one)).to_vec();
+        constraints.extend([
+            enabled * (enabled - one),
+            enabled - flags.into_iter().sum::<SecureField>(),
+            value(OPCODE)
+                - is_mul * BaseField::from(Opcode::Mul as u32)
+                - is_jnz * BaseField::from(Opcode::Jnz as u32)
+                - is_assert * BaseField::from(Opcode::Assert as u32),
+            value(WRITES) - is_add - is_mul,
+            value(NEXT_CLK) - value(CLK) - one,
+            // Arithmetic.
+            prod - a * b,
+            is_add * (res - a - b),
+            is_mul * (res - prod),
+            // `nonzero` is 1 if `a` is not zero, in which case `inv` is its inverse, and 0
+            // otherwise.
+            nonzero - a * value(INV),
+            a * (nonzero - one),
+            // Jumps.
+            taken - is_jnz * nonzero,
+            value(NEXT_PC) - pc - one - taken * (imm - pc - one),
+            // Assertions.
+            is_assert * (a - imm),
+        ]);
+        constraints
+    }
+}
+
+/// Writes the trace of an [ExecutionComponent] on SIMD, and adds the decoded instructions and the
+/// register accesses of the steps to the [DecodeTraceGenerator] and the [MemoryTraceGenerator].
+pub struct ExecutionTraceGenerator {
+    log_n_rows: u32,
+    steps: Vec<Step>,
+}
+
+impl ExecutionTraceGenerator {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self {
+            log_n_rows,
+            steps: vec![],
+        }
+    }
+}
+
+impl ComponentGen for ExecutionTraceGenerator {}
+
+impl ComponentTraceGenerator<SimdBackend> for ExecutionTraceGenerator {
+    type Component = ExecutionComponent;
+    /// Consecutive steps, from the first one.
+    type Inputs = Vec<Step>;
+
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        self.steps.extend(inputs);
+        assert!(
+            self.steps.len() <= 1 << self.log_n_rows,
+            "more than 2^{} steps",
+            self.log_n_rows
+        );
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let log_n_rows = generator.log_n_rows;
+        let steps = generator.steps.clone();
+        let mut trace = vec![vec![BaseField::zero(); 1 << log_n_rows]; N_COLUMNS];
+        for (clk, step) in steps.iter().enumerate() {
+            let instruction = step.instruction;
+            let (a, b) = (
+                step.registers[instruction.src0],
+                step.registers[instruction.src1],
+            );
+            let mut flags = [BaseField::zero(); 4];
+            flags[instruction.opcode as usize] = BaseField::one();
+            let nonzero = !a.is_zero();
+            let write = step.write();
+            let values = [
+                clk.into(),
+                (clk + 1).into(),
+                step.pc.into(),
+                step.next_pc().into(),
+            ]
+            .into_iter()
+            .chain(instruction.tuple(step.pc).into_iter().skip(1))
+            .chain(flags)
+            .chain([
+                BaseField::one(),
+                (write.is_some() as u32).into(),
+                a,
+                b,
+                a * b,
+                write.map_or(BaseField::zero(), |(_, res)| res),
+                if nonzero {
+                    a.inverse()
+                } else {
+                    BaseField::zero()
+                },
+                (nonzero as u32).into(),
+                ((instruction.opcode == Opcode::Jnz && nonzero) as u32).into(),
+            ]);
+            for (column, value) in trace.iter_mut().zip(values) {
+                column[clk] = value;
+            }
+        }
+        // The remaining rows satisfy the constraints with zeroes, except for the next state.
+        for column in [NEXT_CLK, NEXT_PC] {
+            trace[column][steps.len()..].fill(BaseField::one());
+        }
+
+        registry
+            .get_generator_mut::<DecodeTraceGenerator>(DECODE_ID)
+            .add_inputs(&steps.iter().map(|step| step.pc).collect());
+        registry
+            .get_generator_mut::<MemoryTraceGenerator>(MEMORY_ID)
+            .add_inputs(&steps);
+        to_evaluations(log_n_rows, trace)
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        ExecutionComponent::new(self.log_n_rows)
+    }
+}
@@ -0,0 +1,179 @@
+use num_traits::One;
+
+use super::program::{Step, N_REGISTERS};
+use super::{to_evaluations, VmComponent, READ_RELATION, REGISTER_RELATION, WRITE_RELATION};
+use crate::core::air::logup::{Logup, LookupEntry, Multiplicity};
+use crate::core::backend::simd::SimdBackend;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::{ColumnVec, InteractionElements};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+const CLK: usize = 0;
+const NEXT_CLK: usize = 1;
+const REGISTER: usize = 2;
+const VALUE: usize = 3;
+const NEXT_VALUE: usize = 4;
+const N_READS: usize = 5;
+const WRITTEN: usize = 6;
+/// Whether the row holds a register, rather than padding.
+const ENABLED: usize = 7;
+const N_COLUMNS: usize = 8;
+
+/// Component holding the value of each register before each step, one in each row.
+///
+/// Each row uses the value from [REGISTER_RELATION] and yields it for the next step, after the
+/// write of the step if any is used from [WRITE_RELATION]. The value is yielded to
+/// [READ_RELATION] for each read of the step.
+#[derive(Clone, Debug)]
+pub struct MemoryComponent {
+    pub log_n_rows: u32,
+}
+
+impl MemoryComponent {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self { log_n_rows }
+    }
+}
+
+impl VmComponent for MemoryComponent {
+    fn log_n_rows(&self) -> u32 {
+        self.log_n_rows
+    }
+
+    fn n_columns(&self) -> usize {
+        N_COLUMNS
+    }
+
+    fn n_row_constraints(&self) -> usize {
+        6
+    }
+
+    fn logup(&self) -> Logup {
+        Logup::new(
+            "vm_memory",
+            self.log_n_rows,
+            vec![
+                LookupEntry::uses(
+                    REGISTER_RELATION,
+                    vec![CLK, REGISTER, VALUE],
+                    Multiplicity::Column(ENABLED),
+                ),
+                LookupEntry::yields(
+                    REGISTER_RELATION,
+                    vec![NEXT_CLK, REGISTER, NEXT_VALUE],
+                    Multiplicity::Column(ENABLED),
+                ),
+                LookupEntry::yields(
+                    READ_RELATION,
+                    vec![CLK, REGISTER, VALUE],
+                    Multiplicity::Column(N_READS),
+                ),
+                LookupEntry::uses(
+                    WRITE_RELATION,
+                    vec![CLK, REGISTER, NEXT_VALUE],
+                    Multiplicity::Column(WRITTEN),
+                ),
+            ],
+        )
+    }
+
+    fn row_constraints(&self, value: &dyn Fn(usize) -> SecureField) -> Vec<SecureField> {
+        let one = SecureField::one();
+        let [written, enabled] = [WRITTEN, ENABLED].map(value);
+        vec![
+            written * (written - one),
+            enabled * (enabled - one),
+            value(NEXT_CLK) - value(CLK) - one,
+            // A register is unchanged unless written.
+            (written - one) * (value(NEXT_VALUE) - value(VALUE)),
+            // Padding rows are neither read nor written.
+            value(N_READS) * (enabled - one),
+            written * (enabled - one),
+        ]
+    }
+}
+
+/// Writes the trace of a [MemoryComponent] on SIMD, from the register accesses of the steps.
+pub struct MemoryTraceGenerator {
+    log_n_rows: u32,
+    steps: Vec<Step>,
+}
+
+impl MemoryTraceGenerator {
+    pub fn new(log_n_rows: u32) -> Self {
+        Self {
+            log_n_rows,
+            steps: vec![],
+        }
+    }
+}
+
+impl ComponentGen for MemoryTraceGenerator {}
+
+impl ComponentTraceGenerator<SimdBackend> for MemoryTraceGenerator {
+    type Component = MemoryComponent;
+    /// Consecutive steps, from the first one.
+    type Inputs = Vec<Step>;
+
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        self.steps.extend(inputs);
+        assert!(self.steps.len() * N_REGISTERS <= 1 << self.log_n_rows);
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let n_rows = 1 << generator.log_n_rows;
+        let mut trace = vec![vec![BaseField::from(0); n_rows]; N_COLUMNS];
+        for (clk, step) in generator.steps.iter().enumerate() {
+            let write = step.write();
+            for (register, &value) in step.registers.iter().enumerate() {
+                let n_reads = [step.instruction.src0, step.instruction.src1]
+                    .iter()
+                    .filter(|&&src| src == register)
+                    .count();
+                let (written, next_value) = match write {
+                    Some((dst, next_value)) if dst == register => (true, next_value),
+                    _ => (false, value),
+                };
+                let values = [
+                    clk.into(),
+                    (clk + 1).into(),
+                    register.into(),
+                    value,
+                    next_value,
+                    n_reads.into(),
+                    (written as u32).into(),
+                    BaseField::one(),
+                ];
+                let row = clk * N_REGISTERS + register;
+                for (column, value) in trace.iter_mut().zip(values) {
+                    column[row] = value;
+                }
+            }
+        }
+        // The remaining rows satisfy the constraints with zeroes, except for the next clock.
+        trace[NEXT_CLK][generator.steps.len() * N_REGISTERS..].fill(BaseField::one());
+        to_evaluations(generator.log_n_rows, trace)
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        self.component()
+            .logup()
+            .write_interaction_trace(trace, elements)
+    }
+
+    fn component(&self) -> Self::Component {
+        MemoryComponent::new(self.log_n_rows)
+    }
+}
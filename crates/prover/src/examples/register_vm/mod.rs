@@ -0,0 +1,499 @@
+//! An example AIR proving the execution of a [Program] of a minimal register machine.
+//!
+//! The AIR has three components, connected by lookups:
+//! * The [DecodeComponent] holds the program, and yields its instructions to the execution.
+//! * The [ExecutionComponent] has a row per step, using its instruction and its state `(clk, pc)`
+//!   and yielding the next state. It reads two registers, and writes one for arithmetic.
+//! * The [MemoryComponent] has a row per register per step, chaining the values of the registers
+//!   from a step to the next, yielding their reads and using their writes.
+//!
+//! The verifier yields the program, the initial state and the inputs, and uses the final state and
+//! the outputs.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::{zip_eq, Itertools};
+use num_traits::One;
+
+use self::decode::{DecodeComponent, DecodeTraceGenerator};
+use self::execution::{ExecutionComponent, ExecutionTraceGenerator};
+use self::memory::{MemoryComponent, MemoryTraceGenerator};
+use self::program::{load_inputs, Execution, Program, Registers, N_REGISTERS};
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{
+    public_sum, verify_balanced_with_public, Logup, LogupOps, LookupDirection, LookupError,
+};
+use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
+use crate::core::backend::simd::m31::LOG_N_LANES;
+use crate::core::backend::simd::SimdBackend;
+use crate::core::backend::Column;
+use crate::core::channel::{Blake2sChannel, Channel};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::coset_vanishing;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::{ExtensionOf, FieldExpOps};
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+pub mod decode;
+pub mod execution;
+pub mod memory;
+pub mod program;
+
+/// The instructions of the program, as tuples `(pc, opcode, dst, src0, src1, imm)`.
+pub const PROGRAM_RELATION: &str = "vm_program";
+/// The executed instructions, as the tuples of [PROGRAM_RELATION].
+pub const INSTRUCTION_RELATION: &str = "vm_instruction";
+/// The states of the execution, as tuples `(clk, pc)`.
+pub const STATE_RELATION: &str = "vm_state";
+/// The values of the registers before each step, as tuples `(clk, register, value)`.
+pub const REGISTER_RELATION: &str = "vm_register";
+/// The reads of the steps, as the tuples of [REGISTER_RELATION].
+pub const READ_RELATION: &str = "vm_read";
+/// The writes of the steps, as the tuples of [REGISTER_RELATION] with the written value.
+pub const WRITE_RELATION: &str = "vm_write";
+
+const DECODE_ID: &str = "vm_decode";
+const EXECUTION_ID: &str = "vm_execution";
+const MEMORY_ID: &str = "vm_memory";
+const LOG_N_REGISTERS: u32 = N_REGISTERS.ilog2();
+
+/// A component of the VM, whose constraints relate the columns of each row, besides its lookups.
+trait VmComponent {
+    fn log_n_rows(&self) -> u32;
+
+    fn n_columns(&self) -> usize;
+
+    fn n_row_constraints(&self) -> usize;
+
+    fn logup(&self) -> Logup;
+
+    /// Evaluates the constraints of a row from the values of its columns.
+    fn row_constraints(&self, value: &dyn Fn(usize) -> SecureField) -> Vec<SecureField>;
+
+    /// Returns the denominator of the row constraints at a point: the vanishing polynomial of the
+    /// trace domain.
+    fn row_constraint_denom<F: ExtensionOf<BaseField>>(&self, point: CirclePoint<F>) -> F {
+        coset_vanishing(CanonicCoset::new(self.log_n_rows()).coset, point)
+    }
+
+    /// Evaluates the quotients of the row constraints, given the inverse of their denominator.
+    fn row_constraint_quotients(
+        &self,
+        denom_inverse: SecureField,
+        value: &dyn Fn(usize) -> SecureField,
+    ) -> Vec<SecureField> {
+        self.row_constraints(value)
+            .into_iter()
+            .map(|constraint| constraint * denom_inverse)
+            .collect()
+    }
+}
+
+/// Implements [Component] and [ComponentProver] for [VmComponent]s, from their row constraints and
+/// their lookups.
+macro_rules! impl_component {
+    ($($component:ty),*) => {$(
+        impl Component for $component {
+            fn n_constraints(&self) -> usize {
+                self.n_row_constraints() + self.logup().n_constraints()
+            }
+
+            fn max_constraint_log_degree_bound(&self) -> u32 {
+                // The row constraints of the VM components multiply at most two trace values, as in
+                // `prod - a * b`, so they are quadratic like the lookup constraints.
+                self.logup().max_constraint_log_degree_bound()
+            }
+
+            fn n_interaction_phases(&self) -> u32 {
+                2
+            }
+
+            fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+                TreeVec::new(vec![
+                    vec![self.log_n_rows(); self.n_columns()],
+                    self.logup().interaction_log_degree_bounds(),
+                ])
+            }
+
+            fn mask_points(
+                &self,
+                point: CirclePoint<SecureField>,
+            ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+                TreeVec::new(vec![
+                    vec![vec![point]; self.n_columns()],
+                    self.logup().interaction_mask_points(point),
+                ])
+            }
+
+            fn evaluate_constraint_quotients_at_point(
+                &self,
+                point: CirclePoint<SecureField>,
+                mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+                evaluation_accumulator: &mut PointEvaluationAccumulator,
+                interaction_elements: &InteractionElements,
+                lookup_values: &LookupValues,
+            ) {
+                let denom_inverse = self.row_constraint_denom(point).inverse();
+                let value = |column: usize| mask[BASE_TRACE][column][0];
+                for quotient in self.row_constraint_quotients(denom_inverse, &value) {
+                    evaluation_accumulator.accumulate(quotient);
+                }
+                self.logup().evaluate_constraint_quotients_at_point(
+                    point,
+                    mask,
+                    evaluation_accumulator,
+                    interaction_elements,
+                    lookup_values,
+                );
+            }
+        }
+
+        impl<B: LogupOps> ComponentProver<B> for $component {
+            fn evaluate_constraint_quotients_on_domain(
+                &self,
+                trace: &ComponentTrace<'_, B>,
+                evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+                interaction_elements: &InteractionElements,
+                lookup_values: &LookupValues,
+            ) {
+                let eval_log_size = self.max_constraint_log_degree_bound();
+                let eval_domain = trace.evals[BASE_TRACE][0].domain;
+                assert_eq!(eval_domain.log_size(), eval_log_size);
+                let columns = &trace.evals[BASE_TRACE];
+
+                let [accum] =
+                    evaluation_accumulator.columns([(eval_log_size, self.n_row_constraints())]);
+                accum.accumulate_row_quotients(
+                    eval_domain,
+                    |point| [(self.row_constraint_denom(point), BaseField::one())],
+                    |i, [denom_inverse]| {
+                        let value = |column: usize| columns[column].values.at(i).into();
+                        self.row_constraint_quotients(denom_inverse.into(), &value)
+                    },
+                );
+
+                self.logup().evaluate_constraint_quotients_on_domain(
+                    trace,
+                    evaluation_accumulator,
+                    interaction_elements,
+                    lookup_values,
+                );
+            }
+
+            fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+                self.logup().lookup_values(trace)
+            }
+        }
+    )*};
+}
+
+impl_component!(DecodeComponent, ExecutionComponent, MemoryComponent);
+
+/// Returns the evaluations of columns of `2^log_n_rows` values, in bit-reversed order.
+fn to_evaluations(
+    log_n_rows: u32,
+    columns: Vec<Vec<BaseField>>,
+) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+    let domain = CanonicCoset::new(log_n_rows).circle_domain();
+    columns
+        .into_iter()
+        .map(|column| {
+            assert_eq!(column.len(), 1 << log_n_rows);
+            CircleEvaluation::new(domain, column.into_iter().collect())
+        })
+        .collect()
+}
+
+/// The statement of an execution: the program, the registers before and after it, and its
+/// number of steps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VmClaim {
+    pub program: Program,
+    pub inputs: Registers,
+    pub n_steps: usize,
+    pub outputs: Registers,
+}
+
+impl VmClaim {
+    pub fn new(program: &Program, inputs: &[BaseField], execution: &Execution) -> Self {
+        Self {
+            program: program.clone(),
+            inputs: load_inputs(inputs),
+            n_steps: execution.steps.len(),
+            outputs: execution.outputs,
+        }
+    }
+
+    fn program_tuples(&self) -> impl Iterator<Item = Vec<BaseField>> + '_ {
+        self.program
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(pc, instruction)| instruction.tuple(pc))
+    }
+
+    /// Returns the tuples of [REGISTER_RELATION] of the registers before the step `clk`.
+    fn register_tuples(clk: usize, registers: Registers) -> Vec<Vec<BaseField>> {
+        registers
+            .into_iter()
+            .enumerate()
+            .map(|(register, value)| vec![clk.into(), register.into(), value])
+            .collect()
+    }
+}
+
+/// An AIR proving the execution of its claim, in at most `2^log_n_steps` steps.
+#[derive(Clone)]
+pub struct VmAir {
+    pub decode: DecodeComponent,
+    pub execution: ExecutionComponent,
+    pub memory: MemoryComponent,
+    pub claim: VmClaim,
+}
+
+impl VmAir {
+    pub fn new(log_n_steps: u32, claim: VmClaim) -> Self {
+        assert!(log_n_steps >= LOG_N_LANES);
+        Self {
+            decode: DecodeComponent::new(decode_log_n_rows(&claim.program)),
+            execution: ExecutionComponent::new(log_n_steps),
+            memory: MemoryComponent::new(log_n_steps + LOG_N_REGISTERS),
+            claim,
+        }
+    }
+}
+
+/// Returns the number of rows of the [DecodeComponent] of a program.
+fn decode_log_n_rows(program: &Program) -> u32 {
+    program
+        .instructions
+        .len()
+        .next_power_of_two()
+        .ilog2()
+        .max(LOG_N_LANES)
+}
+
+impl Air for VmAir {
+    fn components(&self) -> Vec<&dyn Component> {
+        vec![&self.decode, &self.execution, &self.memory]
+    }
+}
+
+impl AirTraceVerifier for VmAir {
+    fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+        // The claim is bound before the elements are drawn.
+        let claim = self
+            .claim
+            .program_tuples()
+            .flatten()
+            .chain(self.claim.inputs)
+            .chain([BaseField::from(self.claim.n_steps)])
+            .chain(self.claim.outputs)
+            .map(SecureField::from)
+            .collect_vec();
+        channel.mix_felts(&claim);
+
+        let ids = self
+            .decode
+            .logup()
+            .interaction_element_ids()
+            .into_iter()
+            .chain(self.execution.logup().interaction_element_ids())
+            .chain(self.memory.logup().interaction_element_ids())
+            .collect::<BTreeSet<_>>();
+        let elements = channel.draw_felts(ids.len());
+        InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
+    }
+
+    fn verify_lookups(
+        &self,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        let claim = &self.claim;
+        let sum = |relation, direction, tuples: Vec<Vec<BaseField>>| {
+            public_sum(relation, direction, tuples, interaction_elements)
+        };
+        let final_state = vec![
+            claim.n_steps.into(),
+            claim.program.instructions.len().into(),
+        ];
+        let public_sum = sum(
+            PROGRAM_RELATION,
+            LookupDirection::Yield,
+            claim.program_tuples().collect(),
+        ) + sum(
+            STATE_RELATION,
+            LookupDirection::Yield,
+            vec![vec![0.into(), 0.into()]],
+        ) + sum(STATE_RELATION, LookupDirection::Use, vec![final_state])
+            + sum(
+                REGISTER_RELATION,
+                LookupDirection::Yield,
+                VmClaim::register_tuples(0, claim.inputs),
+            )
+            + sum(
+                REGISTER_RELATION,
+                LookupDirection::Use,
+                VmClaim::register_tuples(claim.n_steps, claim.outputs),
+            );
+        verify_balanced_with_public(
+            &[
+                self.decode.logup(),
+                self.execution.logup(),
+                self.memory.logup(),
+            ],
+            public_sum,
+            lookup_values,
+        )
+    }
+}
+
+impl AirTraceGenerator<SimdBackend> for VmAir {
+    fn interact(
+        &self,
+        trace: &ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+        let (decode_trace, trace) = trace.split_at(self.decode.n_columns());
+        let (execution_trace, memory_trace) = trace.split_at(self.execution.n_columns());
+        [
+            (self.decode.logup(), decode_trace),
+            (self.execution.logup(), execution_trace),
+            (self.memory.logup(), memory_trace),
+        ]
+        .into_iter()
+        .flat_map(|(logup, trace)| logup.write_interaction_trace(&trace.iter().collect(), elements))
+        .collect()
+    }
+
+    fn to_air_prover(&self) -> impl AirProver<SimdBackend> {
+        self.clone()
+    }
+
+    fn composition_log_degree_bound(&self) -> u32 {
+        self.components()
+            .iter()
+            .map(|component| component.max_constraint_log_degree_bound())
+            .max()
+            .unwrap()
+    }
+}
+
+impl AirProver<SimdBackend> for VmAir {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<SimdBackend>> {
+        vec![&self.decode, &self.execution, &self.memory]
+    }
+}
+
+/// Writes the trace of a [VmAir] of at most `2^log_n_steps` steps, proving the execution of
+/// `program`.
+pub fn write_trace(
+    log_n_steps: u32,
+    program: &Program,
+    execution: &Execution,
+) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+    let mut registry = ComponentGenerationRegistry::default();
+    registry.register(
+        DECODE_ID,
+        DecodeTraceGenerator::new(decode_log_n_rows(program), program.clone()),
+    );
+    registry.register(EXECUTION_ID, ExecutionTraceGenerator::new(log_n_steps));
+    registry.register(
+        MEMORY_ID,
+        MemoryTraceGenerator::new(log_n_steps + LOG_N_REGISTERS),
+    );
+    registry
+        .get_generator_mut::<ExecutionTraceGenerator>(EXECUTION_ID)
+        .add_inputs(&execution.steps);
+
+    // The execution adds the instructions it decodes and the registers it accesses.
+    let execution_trace = ExecutionTraceGenerator::write_trace(EXECUTION_ID, &mut registry);
+    let mut trace = DecodeTraceGenerator::write_trace(DECODE_ID, &mut registry);
+    trace.extend(execution_trace);
+    trace.extend(MemoryTraceGenerator::write_trace(MEMORY_ID, &mut registry));
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execution::RES;
+    use super::program::{Execution, Program};
+    use super::{write_trace, VmAir, VmClaim, VmComponent};
+    use crate::core::air::logup::LookupError;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::Column;
+    use crate::core::fields::m31::{BaseField, P};
+    use crate::core::poly::circle::CircleEvaluation;
+    use crate::core::prover::{prove, verify, ProvingError, VerificationError};
+    use crate::core::test_utils::test_channel;
+
+    const LOG_N_STEPS: u32 = 5;
+    /// Computes the factorial of `r0` in `r1`, and checks that of 10.
+    const FACTORIAL: &str = "
+        mul r1 r1 r0 ; r1 *= r0
+        add r0 r0 r2 ; r0 -= 1
+        jnz r0 0
+        assert r1 3628800
+    ";
+
+    fn factorial_execution() -> (Program, Execution, VmClaim) {
+        let program: Program = FACTORIAL.parse().unwrap();
+        let inputs = [10, 1, P - 1].map(BaseField::from);
+        let execution = program.execute(&inputs, 1 << LOG_N_STEPS).unwrap();
+        let claim = VmClaim::new(&program, &inputs, &execution);
+        (program, execution, claim)
+    }
+
+    #[test]
+    fn test_loop_program_prove() {
+        let (program, execution, claim) = factorial_execution();
+        assert_eq!(claim.n_steps, 31);
+        let air = VmAir::new(LOG_N_STEPS, claim);
+        let trace = write_trace(LOG_N_STEPS, &program, &execution);
+
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        verify(proof, &air, &mut test_channel()).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_output_fails_verification() {
+        let (program, execution, claim) = factorial_execution();
+        let air = VmAir::new(LOG_N_STEPS, claim.clone());
+        let trace = write_trace(LOG_N_STEPS, &program, &execution);
+        let proof = prove::<SimdBackend, _>(&air, &mut test_channel(), trace).unwrap();
+        let mut wrong_claim = claim;
+        wrong_claim.outputs[1] += BaseField::from(1);
+        let wrong_air = VmAir::new(LOG_N_STEPS, wrong_claim);
+
+        let result = verify(proof, &wrong_air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+
+    #[test]
+    fn test_wrong_result_fails() {
+        let (program, execution, claim) = factorial_execution();
+        let air = VmAir::new(LOG_N_STEPS, claim);
+        let mut trace = write_trace(LOG_N_STEPS, &program, &execution);
+        let column = air.decode.n_columns() + RES;
+        let mut values = trace[column].values.to_cpu();
+        values[0] += BaseField::from(1);
+        trace[column] = CircleEvaluation::new(trace[column].domain, values.into_iter().collect());
+
+        let result = prove::<SimdBackend, _>(&air, &mut test_channel(), trace);
+
+        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
+    }
+}
@@ -0,0 +1,334 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::core::fields::m31::{BaseField, P};
+
+pub const N_REGISTERS: usize = 4;
+
+pub type Registers = [BaseField; N_REGISTERS];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    /// `add dst src0 src1` sets `dst` to `src0 + src1`.
+    Add = 0,
+    /// `mul dst src0 src1` sets `dst` to `src0 * src1`.
+    Mul = 1,
+    /// `jnz src0 target` jumps to `target` if `src0` is not zero.
+    Jnz = 2,
+    /// `assert src0 value` halts with an error unless `src0` equals `value`.
+    Assert = 3,
+}
+
+/// An instruction, whose unused operands are zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub dst: usize,
+    pub src0: usize,
+    pub src1: usize,
+    pub imm: BaseField,
+}
+
+impl Instruction {
+    /// Returns the instruction at `pc` as a tuple of the decoding relations.
+    pub fn tuple(&self, pc: usize) -> Vec<BaseField> {
+        [pc, self.opcode as usize, self.dst, self.src0, self.src1]
+            .map(BaseField::from)
+            .into_iter()
+            .chain([self.imm])
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("Line {line}: unknown opcode `{opcode}`.")]
+    UnknownOpcode { line: usize, opcode: String },
+    #[error("Line {line}: expected {expected} operands, found {found}.")]
+    WrongOperandCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Line {line}: invalid register `{operand}`.")]
+    InvalidRegister { line: usize, operand: String },
+    #[error("Line {line}: invalid immediate `{operand}`.")]
+    InvalidImmediate { line: usize, operand: String },
+    #[error("Line {line}: jump target {target} is past the end of the program.")]
+    InvalidJumpTarget { line: usize, target: usize },
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ExecutionError {
+    #[error("Assertion failed at pc {pc}: {value} is not {expected}.")]
+    AssertionFailed {
+        pc: usize,
+        value: BaseField,
+        expected: BaseField,
+    },
+    #[error("The program did not halt within {0} steps.")]
+    StepLimitExceeded(usize),
+}
+
+/// A program of the VM, which halts when its pc reaches its end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+}
+
+/// Parses a program from its assembly, with an instruction per line, e.g. `add r0 r1 r2`.
+/// Comments start with `;`, and blank lines are skipped.
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let lines = source
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.split(';').next().unwrap().trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect::<Vec<_>>();
+        let instructions = lines
+            .iter()
+            .map(|&(line, text)| parse_instruction(line, text))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (&(line, _), instruction) in lines.iter().zip(&instructions) {
+            let target = instruction.imm.0 as usize;
+            if instruction.opcode == Opcode::Jnz && target > instructions.len() {
+                return Err(ParseError::InvalidJumpTarget { line, target });
+            }
+        }
+        Ok(Self { instructions })
+    }
+}
+
+fn parse_instruction(line: usize, text: &str) -> Result<Instruction, ParseError> {
+    let mut tokens = text.split_whitespace();
+    let opcode = tokens.next().unwrap();
+    let operands = tokens.collect::<Vec<_>>();
+    let (opcode, n_operands) = match opcode {
+        "add" => (Opcode::Add, 3),
+        "mul" => (Opcode::Mul, 3),
+        "jnz" => (Opcode::Jnz, 2),
+        "assert" => (Opcode::Assert, 2),
+        _ => {
+            return Err(ParseError::UnknownOpcode {
+                line,
+                opcode: opcode.to_string(),
+            })
+        }
+    };
+    if operands.len() != n_operands {
+        return Err(ParseError::WrongOperandCount {
+            line,
+            expected: n_operands,
+            found: operands.len(),
+        });
+    }
+
+    let register = |operand: &str| {
+        operand
+            .strip_prefix('r')
+            .and_then(|index| index.parse::<usize>().ok())
+            .filter(|&index| index < N_REGISTERS)
+            .ok_or_else(|| ParseError::InvalidRegister {
+                line,
+                operand: operand.to_string(),
+            })
+    };
+    let immediate = |operand: &str| {
+        operand
+            .parse::<i64>()
+            .map(|value| BaseField::from(value.rem_euclid(P as i64) as u32))
+            .map_err(|_| ParseError::InvalidImmediate {
+                line,
+                operand: operand.to_string(),
+            })
+    };
+    let mut instruction = Instruction {
+        opcode,
+        dst: 0,
+        src0: 0,
+        src1: 0,
+        imm: BaseField::from(0),
+    };
+    match opcode {
+        Opcode::Add | Opcode::Mul => {
+            instruction.dst = register(operands[0])?;
+            instruction.src0 = register(operands[1])?;
+            instruction.src1 = register(operands[2])?;
+        }
+        Opcode::Jnz | Opcode::Assert => {
+            instruction.src0 = register(operands[0])?;
+            instruction.imm = immediate(operands[1])?;
+        }
+    }
+    Ok(instruction)
+}
+
+/// A step of an execution: the state before executing the instruction at `pc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub pc: usize,
+    pub registers: Registers,
+    pub instruction: Instruction,
+}
+
+impl Step {
+    /// Returns the register written by the step, and its new value.
+    pub fn write(&self) -> Option<(usize, BaseField)> {
+        let Instruction {
+            opcode,
+            dst,
+            src0,
+            src1,
+            ..
+        } = self.instruction;
+        let (a, b) = (self.registers[src0], self.registers[src1]);
+        match opcode {
+            Opcode::Add => Some((dst, a + b)),
+            Opcode::Mul => Some((dst, a * b)),
+            Opcode::Jnz | Opcode::Assert => None,
+        }
+    }
+
+    pub fn next_pc(&self) -> usize {
+        let Instruction {
+            opcode, src0, imm, ..
+        } = self.instruction;
+        if opcode == Opcode::Jnz && self.registers[src0].0 != 0 {
+            imm.0 as usize
+        } else {
+            self.pc + 1
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Execution {
+    pub steps: Vec<Step>,
+    pub outputs: Registers,
+}
+
+/// Returns the registers holding `inputs`, followed by zeroes.
+pub fn load_inputs(inputs: &[BaseField]) -> Registers {
+    assert!(inputs.len() <= N_REGISTERS, "{} inputs", inputs.len());
+    let mut registers = [BaseField::from(0); N_REGISTERS];
+    registers[..inputs.len()].copy_from_slice(inputs);
+    registers
+}
+
+impl Program {
+    /// Runs the program from the registers holding `inputs`, for at most `max_steps` steps.
+    pub fn execute(
+        &self,
+        inputs: &[BaseField],
+        max_steps: usize,
+    ) -> Result<Execution, ExecutionError> {
+        let mut registers = load_inputs(inputs);
+        let mut pc = 0;
+        let mut steps = vec![];
+        while pc < self.instructions.len() {
+            if steps.len() == max_steps {
+                return Err(ExecutionError::StepLimitExceeded(max_steps));
+            }
+            let step = Step {
+                pc,
+                registers,
+                instruction: self.instructions[pc],
+            };
+            let Instruction {
+                opcode, src0, imm, ..
+            } = step.instruction;
+            if opcode == Opcode::Assert && registers[src0] != imm {
+                return Err(ExecutionError::AssertionFailed {
+                    pc,
+                    value: registers[src0],
+                    expected: imm,
+                });
+            }
+            if let Some((dst, value)) = step.write() {
+                registers[dst] = value;
+            }
+            pc = step.next_pc();
+            steps.push(step);
+        }
+        Ok(Execution {
+            steps,
+            outputs: registers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecutionError, Opcode, ParseError, Program};
+    use crate::core::fields::m31::{BaseField, P};
+
+    #[test]
+    fn test_parse_program() {
+        let program: Program = "mul r1 r1 r0 ; acc *= n\n\n  jnz r0 0\nassert r1 -1"
+            .parse()
+            .unwrap();
+
+        let opcodes = program
+            .instructions
+            .iter()
+            .map(|i| i.opcode)
+            .collect::<Vec<_>>();
+        assert_eq!(opcodes, [Opcode::Mul, Opcode::Jnz, Opcode::Assert]);
+        assert_eq!(program.instructions[0].dst, 1);
+        assert_eq!(program.instructions[2].imm, BaseField::from(P - 1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |source: &str| source.parse::<Program>().unwrap_err();
+
+        assert!(matches!(
+            error("add r0 r1 r2\nsub r0 r1 r2"),
+            ParseError::UnknownOpcode { line: 2, .. }
+        ));
+        assert!(matches!(
+            error("add r0 r1"),
+            ParseError::WrongOperandCount { line: 1, .. }
+        ));
+        assert!(matches!(
+            error("add r0 r1 r4"),
+            ParseError::InvalidRegister { line: 1, .. }
+        ));
+        assert!(matches!(
+            error("jnz r0 2"),
+            ParseError::InvalidJumpTarget { line: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_execute_factorial() {
+        let program: Program = "mul r1 r1 r0\nadd r0 r0 r2\njnz r0 0".parse().unwrap();
+        let inputs = [5, 1, P - 1].map(BaseField::from);
+
+        let execution = program.execute(&inputs, 100).unwrap();
+
+        assert_eq!(execution.steps.len(), 15);
+        assert_eq!(execution.outputs[1], BaseField::from(120));
+        assert_eq!(
+            program.execute(&inputs, 14),
+            Err(ExecutionError::StepLimitExceeded(14))
+        );
+    }
+
+    #[test]
+    fn test_failed_assertion() {
+        let program: Program = "add r0 r0 r0\nassert r0 5".parse().unwrap();
+
+        let result = program.execute(&[BaseField::from(2)], 100);
+
+        assert!(matches!(
+            result,
+            Err(ExecutionError::AssertionFailed { pc: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,336 @@
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+
+use crate::components::range_check::{RangeCheckComponent, RangeCheckTraceGenerator};
+use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::{Logup, LogupOps, LookupEntry, Multiplicity};
+use crate::core::air::permutation::{Permutation, PermutationOps};
+use crate::core::air::{Component, ComponentProver, ComponentTrace};
+use crate::core::backend::{Backend, Column};
+use crate::core::circle::CirclePoint;
+use crate::core::constraints::{coset_vanishing, point_excluder, point_vanishing_fraction};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::fields::ExtensionOf;
+use crate::core::pcs::TreeVec;
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::{BASE_TRACE, INTERACTION_TRACE};
+use crate::core::utils::previous_bit_reversed_circle_domain_index;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{ComponentGen, ComponentTraceGenerator};
+
+/// The log of the bound on the values, and on the steps between consecutive sorted values.
+pub const LOG_RANGE: u32 = 8;
+pub const RANGE_CHECK_ID: &str = "sorted_column_range_check";
+
+const VALUES: usize = 0;
+const SORTED: usize = 1;
+const STEP: usize = 2;
+const N_TRACE_COLUMNS: usize = 3;
+const N_STEP_CONSTRAINTS: usize = 2;
+
+/// Component proving that a column is a sorted copy of another.
+///
+/// The trace holds the values, the sorted values, and the steps between consecutive sorted values
+/// (the first step being the first sorted value). A [Permutation] asserts that the sorted values
+/// are a permutation of the values, and the steps are looked up in a range check of
+/// [LOG_RANGE] bits. As the steps add up to less than `2^(log_size + LOG_RANGE)`, this proves that
+/// the sorted values are non-decreasing integers, for `log_size + LOG_RANGE < 31`.
+#[derive(Clone, Debug)]
+pub struct SortedColumnComponent {
+    pub log_size: u32,
+}
+
+impl SortedColumnComponent {
+    pub fn new(log_size: u32) -> Self {
+        assert!(log_size + LOG_RANGE < 31, "the steps may wrap around P");
+        Self { log_size }
+    }
+
+    pub fn permutation(&self) -> Permutation {
+        Permutation::new(
+            "sorted_column_permutation",
+            self.log_size,
+            vec![VALUES],
+            vec![SORTED],
+        )
+    }
+
+    pub fn logup(&self) -> Logup {
+        Logup::new(
+            "sorted_column",
+            self.log_size,
+            vec![LookupEntry::uses(
+                RangeCheckComponent::<LOG_RANGE>::relation(),
+                vec![STEP],
+                Multiplicity::One,
+            )],
+        )
+    }
+
+    pub fn interaction_element_ids(&self) -> Vec<String> {
+        let mut ids = self.permutation().interaction_element_ids();
+        ids.extend(self.logup().interaction_element_ids());
+        ids
+    }
+
+    /// Returns the denominators of the step constraints at a point, as fractions `(numerator,
+    /// denominator)`.
+    fn step_constraint_denoms<F: ExtensionOf<BaseField>>(
+        &self,
+        point: CirclePoint<F>,
+    ) -> [(F, F); N_STEP_CONSTRAINTS] {
+        let domain = CanonicCoset::new(self.log_size).coset;
+        let first_point = domain.at(0);
+        [
+            point_vanishing_fraction(first_point, point),
+            (
+                coset_vanishing(domain, point),
+                point_excluder(first_point, point),
+            ),
+        ]
+    }
+
+    /// Evaluates the quotients of the step constraints, given the inverses of their denominators.
+    fn step_constraint_quotients<F: ExtensionOf<BaseField>>(
+        &self,
+        [first_inverse, step_inverse]: [F; N_STEP_CONSTRAINTS],
+        sorted: F,
+        prev_sorted: F,
+        step: F,
+    ) -> [F; N_STEP_CONSTRAINTS] {
+        [
+            (step - sorted) * first_inverse,
+            (step - sorted + prev_sorted) * step_inverse,
+        ]
+    }
+}
+
+/// Returns the mask read by the [Logup] of the component, whose interaction columns follow those
+/// of the [Permutation].
+fn logup_mask(mask: &TreeVec<ColumnVec<Vec<SecureField>>>) -> TreeVec<ColumnVec<Vec<SecureField>>> {
+    TreeVec::new(vec![
+        mask[BASE_TRACE].clone(),
+        mask[INTERACTION_TRACE][SECURE_EXTENSION_DEGREE..].to_vec(),
+    ])
+}
+
+/// Returns the trace read by the [Logup] of the component, see [logup_mask].
+fn logup_trace<'a, B: Backend>(trace: &ComponentTrace<'a, B>) -> ComponentTrace<'a, B> {
+    ComponentTrace::new(
+        TreeVec::new(vec![
+            trace.polys[BASE_TRACE].clone(),
+            trace.polys[INTERACTION_TRACE][SECURE_EXTENSION_DEGREE..].to_vec(),
+        ]),
+        TreeVec::new(vec![
+            trace.evals[BASE_TRACE].clone(),
+            trace.evals[INTERACTION_TRACE][SECURE_EXTENSION_DEGREE..].to_vec(),
+        ]),
+    )
+}
+
+impl Component for SortedColumnComponent {
+    fn n_constraints(&self) -> usize {
+        N_STEP_CONSTRAINTS + self.permutation().n_constraints() + self.logup().n_constraints()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    fn n_interaction_phases(&self) -> u32 {
+        2
+    }
+
+    fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+        let mut interaction_bounds = self.permutation().interaction_log_degree_bounds();
+        interaction_bounds.extend(self.logup().interaction_log_degree_bounds());
+        TreeVec::new(vec![
+            vec![self.log_size; N_TRACE_COLUMNS],
+            interaction_bounds,
+        ])
+    }
+
+    fn mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+        let prev_point = point - CanonicCoset::new(self.log_size).step().into_ef();
+        let mut interaction_points = self.permutation().interaction_mask_points(point);
+        interaction_points.extend(self.logup().interaction_mask_points(point));
+        TreeVec::new(vec![
+            vec![vec![point], vec![point, prev_point], vec![point]],
+            interaction_points,
+        ])
+    }
+
+    fn evaluate_constraint_quotients_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+        evaluation_accumulator: &mut PointEvaluationAccumulator,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let [sorted, prev_sorted] = mask[BASE_TRACE][SORTED][..] else {
+            unreachable!()
+        };
+        let denom_inverses = self
+            .step_constraint_denoms(point)
+            .map(|(numerator, denominator)| denominator / numerator);
+        let step = mask[BASE_TRACE][STEP][0];
+        for quotient in self.step_constraint_quotients(denom_inverses, sorted, prev_sorted, step) {
+            evaluation_accumulator.accumulate(quotient);
+        }
+        self.permutation().evaluate_constraint_quotients_at_point(
+            point,
+            mask,
+            evaluation_accumulator,
+            interaction_elements,
+        );
+        self.logup().evaluate_constraint_quotients_at_point(
+            point,
+            &logup_mask(mask),
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+}
+
+impl<B: PermutationOps + LogupOps> ComponentProver<B> for SortedColumnComponent {
+    fn evaluate_constraint_quotients_on_domain(
+        &self,
+        trace: &ComponentTrace<'_, B>,
+        evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) {
+        let eval_log_size = self.max_constraint_log_degree_bound();
+        let eval_domain = trace.evals[BASE_TRACE][SORTED].domain;
+        assert_eq!(eval_domain.log_size(), eval_log_size);
+        let [sorted, step] = [SORTED, STEP].map(|column| &trace.evals[BASE_TRACE][column].values);
+
+        let [accum] = evaluation_accumulator.columns([(eval_log_size, N_STEP_CONSTRAINTS)]);
+        accum.accumulate_row_quotients(
+            eval_domain,
+            |point| self.step_constraint_denoms(point),
+            |i, denom_inverses| {
+                let prev_i =
+                    previous_bit_reversed_circle_domain_index(i, self.log_size, eval_log_size);
+                self.step_constraint_quotients(
+                    denom_inverses,
+                    sorted.at(i),
+                    sorted.at(prev_i),
+                    step.at(i),
+                )
+            },
+        );
+
+        self.permutation().evaluate_constraint_quotients_on_domain(
+            trace,
+            evaluation_accumulator,
+            interaction_elements,
+        );
+        self.logup().evaluate_constraint_quotients_on_domain(
+            &logup_trace(trace),
+            evaluation_accumulator,
+            interaction_elements,
+            lookup_values,
+        );
+    }
+
+    fn lookup_values(&self, trace: &ComponentTrace<'_, B>) -> LookupValues {
+        self.logup().lookup_values(&logup_trace(trace))
+    }
+}
+
+/// Sorts the values added with [add_inputs](ComponentTraceGenerator::add_inputs), and adds the
+/// steps between them to the range check registered as [RANGE_CHECK_ID].
+pub struct SortedColumnTraceGenerator<B: Backend> {
+    values: Vec<BaseField>,
+    _backend: PhantomData<B>,
+}
+
+impl<B: Backend> SortedColumnTraceGenerator<B> {
+    pub fn new() -> Self {
+        Self {
+            values: vec![],
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Default for SortedColumnTraceGenerator<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend + 'static> ComponentGen for SortedColumnTraceGenerator<B> {}
+
+impl<B: PermutationOps + 'static> ComponentTraceGenerator<B> for SortedColumnTraceGenerator<B> {
+    type Component = SortedColumnComponent;
+    type Inputs = Vec<BaseField>;
+
+    /// Adds values to sort. Their total number must be a power of two, and they must be smaller
+    /// than `2^LOG_RANGE`.
+    fn add_inputs(&mut self, inputs: &Self::Inputs) {
+        self.values.extend(inputs);
+    }
+
+    fn write_trace(
+        component_id: &str,
+        registry: &mut ComponentGenerationRegistry,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let generator = registry.get_generator::<Self>(component_id);
+        let component = generator.component();
+        let values = generator.values.clone();
+        let sorted = values
+            .iter()
+            .copied()
+            .sorted_by_key(|value| value.0)
+            .collect_vec();
+        let steps = sorted
+            .iter()
+            .scan(BaseField::from(0), |prev, &value| {
+                let step = value - *prev;
+                *prev = value;
+                Some(step)
+            })
+            .collect_vec();
+        registry
+            .get_generator_mut::<RangeCheckTraceGenerator<B, LOG_RANGE>>(RANGE_CHECK_ID)
+            .add_inputs(&steps);
+
+        let domain = CanonicCoset::new(component.log_size);
+        [values, sorted, steps]
+            .into_iter()
+            .map(|column| {
+                CircleEvaluation::new_canonical_ordered(domain, column.into_iter().collect())
+            })
+            .collect()
+    }
+
+    fn write_interaction_trace(
+        &self,
+        trace: &ColumnVec<&CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let component = self.component();
+        let mut interaction_trace = component
+            .permutation()
+            .write_interaction_trace(trace, elements);
+        interaction_trace.extend(component.logup().write_interaction_trace(trace, elements));
+        interaction_trace
+    }
+
+    fn component(&self) -> Self::Component {
+        assert!(self.values.len().is_power_of_two());
+        SortedColumnComponent::new(self.values.len().ilog2())
+    }
+}
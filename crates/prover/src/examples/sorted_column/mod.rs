@@ -0,0 +1,264 @@
+//! An example AIR proving that a column is a sorted copy of another, with the [Permutation]
+//! argument and a range check of the steps between the sorted values.
+//!
+//! [Permutation]: crate::core::air::permutation::Permutation
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::{zip_eq, Itertools};
+
+use self::component::{
+    SortedColumnComponent, SortedColumnTraceGenerator, LOG_RANGE, RANGE_CHECK_ID,
+};
+use crate::components::range_check::{RangeCheckComponent, RangeCheckTraceGenerator};
+use crate::core::air::logup::{verify_balanced, LogupOps, LookupError};
+use crate::core::air::permutation::PermutationOps;
+use crate::core::air::{Air, AirProver, Component, ComponentProver};
+use crate::core::channel::{Blake2sChannel, Channel};
+use crate::core::fields::m31::BaseField;
+use crate::core::poly::circle::CircleEvaluation;
+use crate::core::poly::BitReversedOrder;
+use crate::core::prover::BASE_TRACE;
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
+use crate::trace_generation::registry::ComponentGenerationRegistry;
+use crate::trace_generation::{AirTraceGenerator, AirTraceVerifier, ComponentTraceGenerator};
+
+pub mod component;
+
+const SORTED_COLUMN_ID: &str = "sorted_column";
+
+#[derive(Clone)]
+pub struct SortedColumnAir {
+    pub sorted_column: SortedColumnComponent,
+    pub range_check: RangeCheckComponent<LOG_RANGE>,
+}
+
+impl SortedColumnAir {
+    pub fn new(log_size: u32) -> Self {
+        Self {
+            sorted_column: SortedColumnComponent::new(log_size),
+            range_check: RangeCheckComponent,
+        }
+    }
+}
+
+impl Air for SortedColumnAir {
+    fn components(&self) -> Vec<&dyn Component> {
+        vec![&self.sorted_column, &self.range_check]
+    }
+}
+
+impl AirTraceVerifier for SortedColumnAir {
+    fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements {
+        let ids = self
+            .sorted_column
+            .interaction_element_ids()
+            .into_iter()
+            .chain(self.range_check.interaction_element_ids())
+            .collect::<BTreeSet<_>>();
+        let elements = channel.draw_felts(ids.len());
+        InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
+    }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        verify_balanced(
+            &[self.sorted_column.logup(), self.range_check.logup()],
+            lookup_values,
+        )
+    }
+}
+
+impl<B: PermutationOps + LogupOps + 'static> AirTraceGenerator<B> for SortedColumnAir {
+    fn interact(
+        &self,
+        trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        let n_sorted_column_columns =
+            self.sorted_column.trace_log_degree_bounds()[BASE_TRACE].len();
+        let (sorted_column_trace, range_check_trace) = trace.split_at(n_sorted_column_columns);
+        let sorted_column_trace = sorted_column_trace.iter().collect_vec();
+        let mut interaction_trace = self
+            .sorted_column
+            .permutation()
+            .write_interaction_trace(&sorted_column_trace, elements);
+        interaction_trace.extend(
+            self.sorted_column
+                .logup()
+                .write_interaction_trace(&sorted_column_trace, elements),
+        );
+        interaction_trace.extend(
+            self.range_check
+                .logup()
+                .write_interaction_trace(&range_check_trace.iter().collect(), elements),
+        );
+        interaction_trace
+    }
+
+    fn to_air_prover(&self) -> impl AirProver<B> {
+        self.clone()
+    }
+
+    fn composition_log_degree_bound(&self) -> u32 {
+        self.components()
+            .iter()
+            .map(|component| component.max_constraint_log_degree_bound())
+            .max()
+            .unwrap()
+    }
+}
+
+impl<B: PermutationOps + LogupOps> AirProver<B> for SortedColumnAir {
+    fn prover_components(&self) -> Vec<&dyn ComponentProver<B>> {
+        vec![&self.sorted_column, &self.range_check]
+    }
+}
+
+/// Writes the trace of [SortedColumnAir] for `values`, whose number must be a power of two.
+pub fn write_trace<B: PermutationOps + 'static>(
+    values: &[BaseField],
+) -> ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+    let mut registry = ComponentGenerationRegistry::default();
+    registry.register(SORTED_COLUMN_ID, SortedColumnTraceGenerator::<B>::new());
+    registry.register(
+        RANGE_CHECK_ID,
+        RangeCheckTraceGenerator::<B, LOG_RANGE>::new(),
+    );
+    registry
+        .get_generator_mut::<SortedColumnTraceGenerator<B>>(SORTED_COLUMN_ID)
+        .add_inputs(&values.to_vec());
+
+    let mut trace = SortedColumnTraceGenerator::write_trace(SORTED_COLUMN_ID, &mut registry);
+    trace.extend(RangeCheckTraceGenerator::<B, LOG_RANGE>::write_trace(
+        RANGE_CHECK_ID,
+        &mut registry,
+    ));
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::component::{LOG_RANGE, RANGE_CHECK_ID};
+    use super::{write_trace, SortedColumnAir};
+    use crate::components::range_check::RangeCheckTraceGenerator;
+    use crate::core::air::logup::{LogupOps, LookupError};
+    use crate::core::air::permutation::PermutationOps;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Column, CpuBackend};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::prover::{prove, verify, ProvingError, VerificationError};
+    use crate::core::test_utils::{prove_and_verify, test_channel};
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::ops::MerkleOps;
+    use crate::trace_generation::registry::ComponentGenerationRegistry;
+    use crate::trace_generation::ComponentTraceGenerator;
+
+    const LOG_SIZE: u32 = 6;
+
+    fn random_values() -> Vec<BaseField> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..1 << LOG_SIZE)
+            .map(|_| BaseField::from(rng.gen_range(0..1 << LOG_RANGE)))
+            .collect()
+    }
+
+    fn test_prove_and_verify<
+        B: PermutationOps + LogupOps + MerkleOps<Blake2sMerkleHasher> + 'static,
+    >() {
+        let air = SortedColumnAir::new(LOG_SIZE);
+        let trace = write_trace::<B>(&random_values());
+
+        prove_and_verify(&air, trace);
+    }
+
+    #[test]
+    fn test_sorted_column_prove_cpu() {
+        test_prove_and_verify::<CpuBackend>();
+    }
+
+    #[test]
+    fn test_sorted_column_prove_simd() {
+        test_prove_and_verify::<SimdBackend>();
+    }
+
+    #[test]
+    fn test_sorted_copy_of_other_values_fails() {
+        let air = SortedColumnAir::new(LOG_SIZE);
+        let mut trace = write_trace::<CpuBackend>(&random_values());
+        // Changes a value without changing the sorted values.
+        let mut values = trace[0].values.to_cpu();
+        values[3] += BaseField::from(1);
+        trace[0] = CircleEvaluation::new(trace[0].domain, values);
+
+        let result = prove::<CpuBackend, _>(&air, &mut test_channel(), trace);
+
+        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
+    }
+
+    #[test]
+    fn test_unsorted_permutation_fails() {
+        let air = SortedColumnAir::new(LOG_SIZE);
+        let values = random_values();
+        // A permutation of the values, sorted except for two swapped values.
+        let mut unsorted = values
+            .iter()
+            .copied()
+            .sorted_by_key(|value| value.0)
+            .collect_vec();
+        let i = (0..unsorted.len() - 1)
+            .find(|&i| unsorted[i] != unsorted[i + 1])
+            .unwrap();
+        unsorted.swap(i, i + 1);
+        let steps = unsorted
+            .iter()
+            .scan(BaseField::from(0), |prev, &value| {
+                let step = value - *prev;
+                *prev = value;
+                Some(step)
+            })
+            .collect_vec();
+        // The negative step can't be looked up, so the range check only yields the other steps.
+        let mut registry = ComponentGenerationRegistry::default();
+        registry.register(
+            RANGE_CHECK_ID,
+            RangeCheckTraceGenerator::<CpuBackend, LOG_RANGE>::new(),
+        );
+        registry
+            .get_generator_mut::<RangeCheckTraceGenerator<CpuBackend, LOG_RANGE>>(RANGE_CHECK_ID)
+            .add_inputs(
+                &steps
+                    .iter()
+                    .copied()
+                    .filter(|step| step.0 < 1 << LOG_RANGE)
+                    .collect(),
+            );
+        let domain = CanonicCoset::new(LOG_SIZE);
+        let mut trace = [values, unsorted, steps]
+            .into_iter()
+            .map(|column| CircleEvaluation::new_canonical_ordered(domain, column))
+            .collect_vec();
+        trace.extend(
+            RangeCheckTraceGenerator::<CpuBackend, LOG_RANGE>::write_trace(
+                RANGE_CHECK_ID,
+                &mut registry,
+            ),
+        );
+        let proof = prove::<CpuBackend, _>(&air, &mut test_channel(), trace).unwrap();
+
+        let result = verify(proof, &air, &mut test_channel());
+
+        assert!(matches!(
+            result,
+            Err(VerificationError::Lookup(LookupError::NotBalanced(_)))
+        ));
+    }
+}
@@ -9,6 +9,7 @@ use super::component::{
 };
 use super::trace_gen::write_trace_row;
 use crate::core::air::accumulation::{ColumnAccumulator, DomainEvaluationAccumulator};
+use crate::core::air::logup::LookupError;
 use crate::core::air::{AirProver, Component, ComponentProver, ComponentTrace};
 use crate::core::backend::CpuBackend;
 use crate::core::channel::{Blake2sChannel, Channel};
@@ -36,6 +37,15 @@ impl AirTraceVerifier for WideFibAir {
         let elements = channel.draw_felts(ids.len());
         InteractionElements::new(BTreeMap::from_iter(zip_eq(ids, elements)))
     }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        // The lookup values are the boundary values of the trace, which the constraints bind.
+        Ok(())
+    }
 }
 
 impl AirTraceGenerator<CpuBackend> for WideFibAir {
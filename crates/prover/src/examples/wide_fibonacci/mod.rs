@@ -202,7 +202,7 @@ mod tests {
         );
 
         let res = acc.finalize();
-        let poly = res.0[0].clone();
+        let poly = res[0].clone();
         for coeff in poly.coeffs[(1 << wide_fib.max_constraint_log_degree_bound()) - 1..].iter() {
             assert_eq!(*coeff, BaseField::zero());
         }
@@ -235,7 +235,7 @@ mod tests {
         let air = WideFibAir { component };
         let prover_channel =
             &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
-        let proof = prove::<CpuBackend>(&air, prover_channel, trace).unwrap();
+        let proof = prove::<CpuBackend, _>(&air, prover_channel, trace).unwrap();
 
         let verifier_channel =
             &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
@@ -4,6 +4,7 @@ use tracing::{span, Level};
 
 use super::component::LOG_N_COLUMNS;
 use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+use crate::core::air::logup::LookupError;
 use crate::core::air::mask::fixed_mask_points;
 use crate::core::air::{Air, AirProver, Component, ComponentProver, ComponentTrace};
 use crate::core::backend::simd::column::BaseFieldVec;
@@ -65,6 +66,14 @@ impl AirTraceVerifier for SimdWideFibAir {
     fn interaction_elements(&self, _channel: &mut Blake2sChannel) -> InteractionElements {
         InteractionElements::default()
     }
+
+    fn verify_lookups(
+        &self,
+        _interaction_elements: &InteractionElements,
+        _lookup_values: &LookupValues,
+    ) -> Result<(), LookupError> {
+        Ok(())
+    }
 }
 
 impl AirTraceGenerator<SimdBackend> for SimdWideFibAir {
@@ -286,7 +295,7 @@ mod tests {
         span.exit();
         let channel = &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
         let air = SimdWideFibAir { component };
-        let proof = prove::<SimdBackend>(&air, channel, trace).unwrap();
+        let proof = prove::<SimdBackend, _>(&air, channel, trace).unwrap();
 
         let channel = &mut Blake2sChannel::new(Blake2sHasher::hash(BaseField::into_slice(&[])));
         verify(proof, &air, channel).unwrap();
@@ -0,0 +1,91 @@
+//! The Grain LFSR generating the round constants of Poseidon and Poseidon2, as in the reference
+//! scripts of <https://eprint.iacr.org/2019/458.pdf> (Appendix F).
+
+use std::collections::VecDeque;
+
+use crate::core::fields::m31::{BaseField, P};
+
+const N_STATE_BITS: usize = 80;
+const N_DISCARDED_BITS: usize = 160;
+/// The bit size of the field.
+const N_FIELD_BITS: usize = 31;
+
+/// The Grain LFSR of a permutation over [BaseField], with the power S-box.
+pub struct GrainLfsr {
+    state: VecDeque<bool>,
+}
+
+impl GrainLfsr {
+    /// Initializes the LFSR with the parameters of a permutation of `width` elements, with
+    /// `n_full_rounds` full rounds and `n_partial_rounds` partial rounds.
+    pub fn new(width: usize, n_full_rounds: usize, n_partial_rounds: usize) -> Self {
+        // A prime field, and the power S-box.
+        let (field, sbox) = (1, 0);
+        let mut state = VecDeque::with_capacity(N_STATE_BITS);
+        for (value, n_bits) in [
+            (field, 2),
+            (sbox, 4),
+            (N_FIELD_BITS, 12),
+            (width, 12),
+            (n_full_rounds, 10),
+            (n_partial_rounds, 10),
+        ] {
+            state.extend((0..n_bits).rev().map(|i| (value >> i) & 1 == 1));
+        }
+        state.resize(N_STATE_BITS, true);
+
+        let mut lfsr = Self { state };
+        for _ in 0..N_DISCARDED_BITS {
+            lfsr.next_lfsr_bit();
+        }
+        lfsr
+    }
+
+    fn next_lfsr_bit(&mut self) -> bool {
+        let bit = [62, 51, 38, 23, 13, 0]
+            .into_iter()
+            .fold(false, |acc, i| acc ^ self.state[i]);
+        self.state.pop_front();
+        self.state.push_back(bit);
+        bit
+    }
+
+    /// Returns the next bit of the self-shrinking generator: of each pair of bits of the LFSR, the
+    /// second is output if the first is set, and both are discarded otherwise.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_lfsr_bit();
+            let bit = self.next_lfsr_bit();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Returns the next field element, sampled from big-endian bits by rejection.
+    pub fn next_base_field(&mut self) -> BaseField {
+        loop {
+            let value = (0..N_FIELD_BITS).fold(0, |acc, _| (acc << 1) | self.next_bit() as u32);
+            if value < P {
+                return BaseField::from(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GrainLfsr;
+
+    #[test]
+    fn test_grain_is_deterministic_in_the_parameters() {
+        let sample = |width, n_full_rounds, n_partial_rounds| {
+            let mut lfsr = GrainLfsr::new(width, n_full_rounds, n_partial_rounds);
+            (0..8).map(|_| lfsr.next_base_field()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(16, 8, 14), sample(16, 8, 14));
+        assert_ne!(sample(16, 8, 14), sample(16, 8, 13));
+        assert_ne!(sample(16, 8, 14), sample(24, 8, 14));
+    }
+}
@@ -0,0 +1,163 @@
+//! Checks of the linear layers of the permutations.
+
+use num_traits::{One, Zero};
+
+use crate::core::fields::m31::{BaseField, P};
+use crate::core::fields::FieldExpOps;
+
+/// Polynomials over [BaseField], by coefficients from the lowest degree.
+type Poly = Vec<BaseField>;
+
+type Matrix = Vec<Vec<BaseField>>;
+
+fn trim(mut a: Poly) -> Poly {
+    while a.last().is_some_and(|c| c.is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+/// Returns `a mod b`.
+fn poly_rem(a: Poly, b: &Poly) -> Poly {
+    let mut a = trim(a);
+    let b_lead_inverse = b.last().unwrap().inverse();
+    while a.len() >= b.len() {
+        let factor = *a.last().unwrap() * b_lead_inverse;
+        let shift = a.len() - b.len();
+        for (i, &c) in b.iter().enumerate() {
+            a[shift + i] -= factor * c;
+        }
+        a = trim(a);
+    }
+    a
+}
+
+fn poly_mul_mod(a: &Poly, b: &Poly, modulus: &Poly) -> Poly {
+    let mut res = vec![BaseField::zero(); (a.len() + b.len()).saturating_sub(1)];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    poly_rem(res, modulus)
+}
+
+fn poly_pow_mod(a: &Poly, mut exp: u32, modulus: &Poly) -> Poly {
+    let mut res = vec![BaseField::one()];
+    let mut base = a.clone();
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = poly_mul_mod(&res, &base, modulus);
+        }
+        base = poly_mul_mod(&base, &base, modulus);
+        exp >>= 1;
+    }
+    res
+}
+
+fn poly_gcd(a: Poly, b: Poly) -> Poly {
+    let (mut a, mut b) = (trim(a), trim(b));
+    while !b.is_empty() {
+        let r = poly_rem(a, &b);
+        (a, b) = (b, r);
+    }
+    a
+}
+
+/// Returns the degrees of the maximal proper subfields of the extension of degree `n`.
+fn maximal_subfield_degrees(n: usize) -> impl Iterator<Item = usize> {
+    (2..=n)
+        .filter(move |&q| n % q == 0 && (2..q).all(|d| q % d != 0))
+        .map(move |q| n / q)
+}
+
+/// Returns `x^(p^i) mod f` for `i` in `0..=deg(f) / 2` if `f` is irreducible, by Ben-Or's test:
+/// `f` of degree `n` is irreducible iff `gcd(x^(p^i) - x, f) = 1` for all `i <= n / 2`.
+fn frobenius_powers_if_irreducible(f: &Poly) -> Option<Vec<Poly>> {
+    let x = vec![BaseField::zero(), BaseField::one()];
+    let mut powers = vec![x];
+    for i in 0..(f.len() - 1) / 2 {
+        let power = poly_pow_mod(&powers[i], P, f);
+        let mut diff = power.clone();
+        diff.resize(diff.len().max(2), BaseField::zero());
+        diff[1] -= BaseField::one();
+        if poly_gcd(f.clone(), diff).len() != 1 {
+            return None;
+        }
+        powers.push(power);
+    }
+    Some(powers)
+}
+
+fn matrix_mul(a: &Matrix, b: &Matrix) -> Matrix {
+    let n = a.len();
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| (0..n).map(|k| a[i][k] * b[k][j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the characteristic polynomial of `a`, by the Faddeev–LeVerrier algorithm.
+fn characteristic_polynomial(a: &Matrix) -> Poly {
+    let n = a.len();
+    let mut coefficients = vec![BaseField::zero(); n + 1];
+    coefficients[n] = BaseField::one();
+    // The product of `a` and the matrix `M_k` of the algorithm.
+    let mut product = vec![vec![BaseField::zero(); n]; n];
+    for k in 1..=n {
+        let mut m = product;
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] += coefficients[n - k + 1];
+        }
+        product = matrix_mul(a, &m);
+        let trace: BaseField = (0..n).map(|i| product[i][i]).sum();
+        coefficients[n - k] = -trace * BaseField::from(k).inverse();
+    }
+    coefficients
+}
+
+/// Returns the matrix of a linear map of `n` elements.
+#[cfg(test)]
+pub fn matrix_of(n: usize, map: impl Fn(&mut [BaseField])) -> Matrix {
+    let mut matrix = vec![vec![BaseField::zero(); n]; n];
+    for j in 0..n {
+        let mut state = vec![BaseField::zero(); n];
+        state[j] = BaseField::one();
+        map(&mut state);
+        (0..n).for_each(|i| matrix[i][j] = state[i]);
+    }
+    matrix
+}
+
+#[cfg(test)]
+pub fn is_invertible(matrix: &Matrix) -> bool {
+    !characteristic_polynomial(matrix)[0].is_zero()
+}
+
+/// Returns whether the characteristic polynomials of `M, M^2, ..., M^(2n)` are irreducible, for
+/// a matrix `M` of size `n`. Their minimal polynomials are then irreducible of maximal degree,
+/// which is the sufficient condition of <https://eprint.iacr.org/2019/458.pdf> against infinitely
+/// long invariant subspace trails.
+pub fn has_irreducible_powers(matrix: &Matrix) -> bool {
+    let n = matrix.len();
+    let f = characteristic_polynomial(matrix);
+    let Some(frobenius_powers) = frobenius_powers_if_irreducible(&f) else {
+        return false;
+    };
+    // The eigenvalues of `M^k` are the `k`-th powers of the conjugate roots `x^(p^i)` of `f`, so
+    // its characteristic polynomial is irreducible iff `x^k` is in no proper subfield, i.e. iff
+    // `x^(k p^d) != x^k mod f` for the degrees `d` of the maximal proper subfields.
+    maximal_subfield_degrees(n).all(|d| {
+        let x = &frobenius_powers[0];
+        let (mut power, mut conjugate_power) = (x.clone(), frobenius_powers[d].clone());
+        (1..=2 * n).all(|_| {
+            let in_subfield = trim(power.clone()) == trim(conjugate_power.clone());
+            power = poly_mul_mod(&power, x, &f);
+            conjugate_power = poly_mul_mod(&conjugate_power, &frobenius_powers[d], &f);
+            !in_subfield
+        })
+    })
+}
@@ -1 +1,4 @@
+pub mod grain;
+mod linear_layer;
 pub mod poseidon;
+pub mod poseidon2;
@@ -1,7 +1,11 @@
 use core::fmt;
+use std::sync::OnceLock;
 
+use itertools::Itertools;
 use num_traits::{One, Zero};
 
+use super::grain::GrainLfsr;
+use super::linear_layer::has_irreducible_powers;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::FieldExpOps;
 use crate::core::vcs::hasher::{self, Hasher, Name};
@@ -9,7 +13,12 @@ use crate::math::matrix::{RowMajorMatrix, SquareMatrix};
 
 const POSEIDON_WIDTH: usize = 24; // in BaseField elements.
 const POSEIDON_CAPACITY: usize = 8; // in BaseField elements.
+const POSEIDON_RATE: usize = POSEIDON_WIDTH - POSEIDON_CAPACITY;
 const POSEIDON_POWER: usize = 5;
+// The round numbers for 128 bits of security with the x^5 S-box over a 31-bit field, including
+// the security margin of <https://eprint.iacr.org/2019/458.pdf> 5.4.
+const POSEIDON_N_HALF_FULL_ROUNDS: usize = 4;
+const POSEIDON_N_PARTIAL_ROUNDS: usize = 22;
 
 /// Parameters for the Poseidon hash function.
 /// For more info, see <https://eprint.iacr.org/2019/458.pdf>
@@ -19,28 +28,70 @@ pub struct PoseidonParams {
     pub n_half_full_rounds: usize,
     pub n_partial_rounds: usize,
     pub mds: RowMajorMatrix<BaseField, POSEIDON_WIDTH>,
-    // TODO(ShaharS): check if more constants are needed.
-    pub constants: [BaseField; POSEIDON_WIDTH],
+    /// The constants added to the state at the start of each round.
+    pub round_constants: Vec<[BaseField; POSEIDON_WIDTH]>,
+}
+
+impl PoseidonParams {
+    pub fn n_rounds(&self) -> usize {
+        2 * self.n_half_full_rounds + self.n_partial_rounds
+    }
 }
 
-// TODO(ShaharS) Replace with real poseidon parameters.
 impl Default for PoseidonParams {
+    /// Samples the parameters from the [GrainLfsr] as in the reference implementation: the
+    /// constants of all the rounds, and then the `x_i` and `y_j` of the Cauchy matrix
+    /// `1 / (x_i + y_j)`, resampled until they are all distinct and no sum is zero so that the
+    /// matrix is MDS, and until the matrix has no infinitely long invariant subspace trails.
+    ///
+    /// The round constants are those of the reference, but the matrix deviates from it: the
+    /// reference keeps the first candidate that passes its search for invariant subspace trails
+    /// (its algorithms 1 to 3), which is not implemented here. Instead, candidates are resampled
+    /// until `has_irreducible_powers` holds, a sufficient condition against infinitely long
+    /// trails. This keeps the 31st candidate of the stream.
     fn default() -> Self {
+        let n_full_rounds = 2 * POSEIDON_N_HALF_FULL_ROUNDS;
+        let mut lfsr = GrainLfsr::new(POSEIDON_WIDTH, n_full_rounds, POSEIDON_N_PARTIAL_ROUNDS);
+        let round_constants = (0..n_full_rounds + POSEIDON_N_PARTIAL_ROUNDS)
+            .map(|_| std::array::from_fn(|_| lfsr.next_base_field()))
+            .collect();
+
+        let mds = loop {
+            let xs = (0..POSEIDON_WIDTH)
+                .map(|_| lfsr.next_base_field())
+                .collect_vec();
+            let ys = (0..POSEIDON_WIDTH)
+                .map(|_| lfsr.next_base_field())
+                .collect_vec();
+            let sums = xs.iter().cartesian_product(&ys).map(|(&x, &y)| x + y);
+            if !xs.iter().chain(&ys).map(|v| v.0).all_unique()
+                || sums.clone().any(|sum| sum.is_zero())
+            {
+                continue;
+            }
+            let matrix = sums.map(|sum| sum.inverse()).collect_vec();
+            if has_irreducible_powers(&matrix.chunks(POSEIDON_WIDTH).map(<[_]>::to_vec).collect()) {
+                break RowMajorMatrix::new(matrix);
+            }
+        };
+
         Self {
-            rate: POSEIDON_WIDTH - POSEIDON_CAPACITY,
+            rate: POSEIDON_RATE,
             capacity: POSEIDON_CAPACITY,
-            n_half_full_rounds: 1,
-            n_partial_rounds: 1,
-            mds: RowMajorMatrix::<BaseField, POSEIDON_WIDTH>::new(
-                (0..POSEIDON_WIDTH * POSEIDON_WIDTH)
-                    .map(|x| BaseField::from_u32_unchecked(x as u32))
-                    .collect::<Vec<_>>(),
-            ),
-            constants: [BaseField::one(); POSEIDON_WIDTH],
+            n_half_full_rounds: POSEIDON_N_HALF_FULL_ROUNDS,
+            n_partial_rounds: POSEIDON_N_PARTIAL_ROUNDS,
+            mds,
+            round_constants,
         }
     }
 }
 
+/// Returns the default parameters, sampled once.
+fn default_params() -> &'static PoseidonParams {
+    static PARAMS: OnceLock<PoseidonParams> = OnceLock::new();
+    PARAMS.get_or_init(PoseidonParams::default)
+}
+
 #[derive(Clone, Copy, PartialEq, Debug, Default, Eq)]
 pub struct PoseidonHash([BaseField; POSEIDON_CAPACITY]);
 
@@ -112,82 +163,109 @@ impl From<PoseidonHash> for [BaseField; POSEIDON_CAPACITY] {
 
 impl hasher::Hash<BaseField> for PoseidonHash {}
 
-#[derive(Default)]
+/// The Poseidon sponge, absorbing `POSEIDON_RATE` elements per permutation into the start of the
+/// state. The input is padded with a one followed by zeros.
+#[derive(Clone)]
 pub struct PoseidonHasher {
-    params: PoseidonParams,
+    params: &'static PoseidonParams,
     state: [BaseField; POSEIDON_WIDTH],
+    /// The number of elements absorbed into the rate since the last permutation.
+    n_absorbed: usize,
+    squeezing: bool,
 }
 
 impl PoseidonHasher {
+    /// Returns a sponge whose capacity is initialized with `initial_hash`, e.g. for domain
+    /// separation.
     pub fn from_hash(initial_hash: PoseidonHash) -> Self {
-        // TODO(ShaharS): change to another default state.
+        let mut state = [BaseField::zero(); POSEIDON_WIDTH];
+        state[POSEIDON_RATE..].copy_from_slice(&initial_hash.0);
         Self {
-            params: PoseidonParams::default(),
-            state: initial_hash.into(),
+            params: default_params(),
+            state,
+            n_absorbed: 0,
+            squeezing: false,
         }
     }
 
-    // Setter for the prefix of the state.
-    fn set_state_prefix(&mut self, prefix: &[BaseField]) {
-        self.state[..prefix.len()].copy_from_slice(prefix);
+    fn add_round_constants(&mut self, round: usize) {
+        for (val, constant) in self
+            .state
+            .iter_mut()
+            .zip(self.params.round_constants[round])
+        {
+            *val += constant;
+        }
     }
 
-    fn add_param_constants(&mut self) {
-        self.set_state_prefix(
-            self.state
-                .into_iter()
-                .zip(self.params.constants.iter())
-                .map(|(val, constant)| val + *constant)
-                .collect::<Vec<_>>()
-                .as_slice(),
-        );
+    fn apply_mds(&mut self) {
+        self.state = self.params.mds.mul(self.state);
     }
 
-    fn hades_partial_round(&mut self) {
-        self.add_param_constants();
-        self.set_state_prefix(&[self.state.as_ref()[0].pow(POSEIDON_POWER as u128)]);
-        self.set_state_prefix(
-            self.params
-                .mds
-                .mul(self.state.as_ref().try_into().unwrap())
-                .as_slice(),
-        );
+    fn hades_partial_round(&mut self, round: usize) {
+        self.add_round_constants(round);
+        self.state[0] = self.state[0].pow(POSEIDON_POWER as u128);
+        self.apply_mds();
     }
 
-    fn hades_full_round(&mut self) {
-        self.add_param_constants();
-        self.set_state_prefix(
-            self.state
-                .as_ref()
-                .iter()
-                .map(|x| x.pow(POSEIDON_POWER as u128))
-                .collect::<Vec<_>>()
-                .as_slice(),
-        );
-        self.set_state_prefix(
-            self.params
-                .mds
-                .mul(self.state.as_ref().try_into().unwrap())
-                .as_slice(),
-        );
+    fn hades_full_round(&mut self, round: usize) {
+        self.add_round_constants(round);
+        for val in &mut self.state {
+            *val = val.pow(POSEIDON_POWER as u128);
+        }
+        self.apply_mds();
     }
 
     pub fn hades_permutation(&mut self) {
-        for _ in 0..self.params.n_half_full_rounds {
-            self.hades_full_round();
+        let n_half_full_rounds = self.params.n_half_full_rounds;
+        let n_partial_rounds = self.params.n_partial_rounds;
+        for round in 0..n_half_full_rounds {
+            self.hades_full_round(round);
+        }
+        for round in n_half_full_rounds..n_half_full_rounds + n_partial_rounds {
+            self.hades_partial_round(round);
         }
-        for _ in 0..self.params.n_partial_rounds {
-            self.hades_partial_round();
+        for round in n_half_full_rounds + n_partial_rounds..self.params.n_rounds() {
+            self.hades_full_round(round);
         }
-        for _ in 0..self.params.n_half_full_rounds {
-            self.hades_full_round();
+    }
+
+    pub fn absorb(&mut self, data: &[BaseField]) {
+        assert!(!self.squeezing, "absorbing after squeezing");
+        for &x in data {
+            if self.n_absorbed == POSEIDON_RATE {
+                self.hades_permutation();
+                self.n_absorbed = 0;
+            }
+            self.state[self.n_absorbed] += x;
+            self.n_absorbed += 1;
         }
     }
+
+    /// Returns the next `POSEIDON_RATE` output elements. The first call pads the input.
+    pub fn squeeze(&mut self) -> [BaseField; POSEIDON_RATE] {
+        if !self.squeezing {
+            if self.n_absorbed == POSEIDON_RATE {
+                self.hades_permutation();
+                self.n_absorbed = 0;
+            }
+            self.state[self.n_absorbed] += BaseField::one();
+            self.squeezing = true;
+        }
+        self.hades_permutation();
+        self.state[..POSEIDON_RATE].try_into().unwrap()
+    }
+}
+
+impl Default for PoseidonHasher {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Hasher for PoseidonHasher {
     type Hash = PoseidonHash;
-    const BLOCK_SIZE: usize = POSEIDON_WIDTH;
+    const BLOCK_SIZE: usize = POSEIDON_RATE;
     const OUTPUT_SIZE: usize = POSEIDON_CAPACITY;
     type NativeType = BaseField;
 
@@ -196,49 +274,65 @@ impl Hasher for PoseidonHasher {
     }
 
     fn reset(&mut self) {
-        self.state = PoseidonHash::default().into();
+        *self = Self::new();
     }
 
-    fn update(&mut self, _data: &[BaseField]) {
-        unimplemented!("update for PoseidonHasher")
+    fn update(&mut self, data: &[BaseField]) {
+        self.absorb(data);
     }
 
     fn finalize(mut self) -> PoseidonHash {
-        self.hades_permutation();
-        self.state.into()
+        self.finalize_reset()
     }
 
     fn finalize_reset(&mut self) -> PoseidonHash {
-        self.hades_permutation();
-        let res = self.state.into();
+        let res = PoseidonHash(self.squeeze()[..POSEIDON_CAPACITY].try_into().unwrap());
         self.reset();
         res
     }
-
-    fn concat_and_hash(_v1: &PoseidonHash, _v2: &PoseidonHash) -> PoseidonHash {
-        unimplemented!("concat_and_hash for PoseidonHasher")
-    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{PoseidonHasher, POSEIDON_CAPACITY};
+    use itertools::Itertools;
+
+    use super::{default_params, PoseidonHasher, POSEIDON_CAPACITY, POSEIDON_WIDTH};
     use crate::core::fields::m31::BaseField;
     use crate::core::vcs::hasher::Hasher;
+    use crate::hash_functions::linear_layer::has_irreducible_powers;
     use crate::hash_functions::poseidon::PoseidonHash;
     use crate::m31;
+    use crate::math::matrix::SquareMatrix;
 
+    // Regression values of this implementation, whose parameters are checked in
+    // `params_match_generator_script`.
     const ZERO_HASH_RESULT: [BaseField; POSEIDON_CAPACITY] = [
-        m31!(1783652178),
-        m31!(1273199544),
-        m31!(762746910),
-        m31!(252294276),
-        m31!(1889325289),
-        m31!(1378872655),
-        m31!(868420021),
-        m31!(357967387),
+        m31!(835677296),
+        m31!(829699570),
+        m31!(777125930),
+        m31!(422836305),
+        m31!(1350817953),
+        m31!(1199648662),
+        m31!(1096596035),
+        m31!(1876389794),
     ];
 
+    // The hash of `0..40`, absorbed in three permutations.
+    const RANGE_HASH_RESULT: [BaseField; POSEIDON_CAPACITY] = [
+        m31!(1570228819),
+        m31!(1548094960),
+        m31!(1387263901),
+        m31!(354179613),
+        m31!(859240593),
+        m31!(831723010),
+        m31!(1960771124),
+        m31!(864641871),
+    ];
+
+    fn range(n: u32) -> Vec<BaseField> {
+        (0..n).map(|x| m31!(x)).collect()
+    }
+
     #[test]
     fn hash_debug_test() {
         let values = (0..POSEIDON_CAPACITY as u32)
@@ -262,31 +356,138 @@ mod tests {
     }
 
     #[test]
-    fn hasher_set_state_test() {
-        let values = (0..POSEIDON_CAPACITY as u32)
-            .map(|x| m31!(x))
-            .collect::<Vec<BaseField>>();
-        let mut hasher = PoseidonHasher::from_hash(PoseidonHash::from(values));
+    fn hasher_regression_test() {
+        let mut hasher = PoseidonHasher::new();
 
-        hasher.set_state_prefix(&[m31!(100)]);
-        let poseidon_hash: PoseidonHash = hasher.state.into();
+        let res = hasher.finalize_reset();
 
-        for (i, x) in poseidon_hash.into_iter().enumerate() {
-            if i == 0 {
-                assert_eq!(x, m31!(100));
-            } else {
-                assert_eq!(x, m31!(i as u32));
-            }
-        }
+        assert_eq!(res, PoseidonHash(ZERO_HASH_RESULT));
+        assert_eq!(hasher.state, PoseidonHasher::new().state);
     }
 
     #[test]
-    fn hasher_regression_test() {
+    fn hasher_test_vector() {
+        assert_eq!(
+            PoseidonHasher::hash(&range(40)),
+            PoseidonHash(RANGE_HASH_RESULT)
+        );
+    }
+
+    #[test]
+    fn mds_is_secure() {
+        let mds = &default_params().mds;
+        let matrix = (0..POSEIDON_WIDTH)
+            .map(|i| (0..POSEIDON_WIDTH).map(|j| mds.get_at(i, j)).collect_vec())
+            .collect_vec();
+
+        assert!(has_irreducible_powers(&matrix));
+    }
+
+    // The expected values are printed by `scripts/poseidon/poseidon_params.py`, which samples the
+    // matrix candidates as `generate_parameters_grain.sage` does, and checks them with its own
+    // characteristic polynomials and irreducibility test.
+    #[test]
+    fn params_match_generator_script() {
+        let params = default_params();
+
+        assert_eq!(
+            params.round_constants[0][..4],
+            [
+                m31!(535476833),
+                m31!(1394754644),
+                m31!(1808710114),
+                m31!(1343879604)
+            ]
+        );
+        assert_eq!(
+            params.round_constants[params.n_rounds() - 1][POSEIDON_WIDTH - 1],
+            m31!(1273641865)
+        );
+        assert_eq!(
+            (0..4).map(|j| params.mds.get_at(0, j)).collect_vec(),
+            [
+                m31!(1011628012),
+                m31!(1661586501),
+                m31!(810951713),
+                m31!(1565614701)
+            ]
+        );
+        assert_eq!(
+            params.mds.get_at(POSEIDON_WIDTH - 1, POSEIDON_WIDTH - 1),
+            m31!(1779123207)
+        );
+    }
+
+    #[test]
+    fn round_constants_are_distinct() {
+        let params = default_params();
+        let constants = params.round_constants.iter().flatten().map(|c| c.0);
+
+        assert_eq!(params.round_constants.len(), params.n_rounds());
+        assert!(constants.clone().all_unique());
+    }
+
+    #[test]
+    fn hasher_update_is_incremental() {
+        let input = range(40);
         let mut hasher = PoseidonHasher::new();
 
-        let res = hasher.finalize_reset();
+        hasher.update(&input[..7]);
+        hasher.update(&input[7..16]);
+        hasher.update(&input[16..]);
 
-        assert_eq!(res, PoseidonHash(ZERO_HASH_RESULT));
-        assert_eq!(hasher.state, PoseidonHasher::new().state);
+        assert_eq!(hasher.finalize(), PoseidonHasher::hash(&input));
+    }
+
+    #[test]
+    fn hasher_padding_is_injective() {
+        let input = range(16);
+        let mut extended = input.clone();
+        extended.push(m31!(1));
+
+        assert_ne!(
+            PoseidonHasher::hash(&input),
+            PoseidonHasher::hash(&extended)
+        );
+        assert_ne!(PoseidonHasher::hash(&[]), PoseidonHasher::hash(&[m31!(0)]));
+        assert_ne!(
+            PoseidonHasher::hash(&input[..15]),
+            PoseidonHasher::hash(&input)
+        );
+    }
+
+    #[test]
+    fn concat_and_hash_test() {
+        let [a, b] =
+            [0, 8].map(|offset| PoseidonHash::from(range(offset + 8)[offset as usize..].to_vec()));
+
+        let res = PoseidonHasher::concat_and_hash(&a, &b);
+
+        assert_eq!(res, PoseidonHasher::hash(&range(16)));
+    }
+
+    #[test]
+    fn from_hash_separates_domains() {
+        let input = range(5);
+        let mut hasher = PoseidonHasher::from_hash(PoseidonHash::from(range(8)));
+
+        hasher.update(&input);
+
+        assert_ne!(hasher.finalize(), PoseidonHasher::hash(&input));
+    }
+
+    #[test]
+    fn squeeze_continues_output() {
+        let mut hasher = PoseidonHasher::new();
+        hasher.absorb(&range(3));
+
+        let first = hasher.squeeze();
+        let second = hasher.squeeze();
+
+        assert_eq!(
+            first[..POSEIDON_CAPACITY],
+            PoseidonHasher::hash(&range(3)).0
+        );
+        assert_ne!(first, second);
     }
 }
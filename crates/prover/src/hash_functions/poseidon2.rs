@@ -0,0 +1,358 @@
+//! The Poseidon2 permutation over [BaseField] from <https://eprint.iacr.org/2023/323.pdf>, and
+//! the sponge and compression function built on it.
+//!
+//! The parameters are this crate's own instance, a deliberate deviation from the Plonky3 M31
+//! instance: Plonky3 derives its M31 round constants from a seeded RNG rather than from the Grain
+//! LFSR, and neither those constants nor their test vectors are reproduced here. Instead, the
+//! round constants are sampled with the Grain LFSR of the Poseidon reference scripts for
+//! `p = 2^31 - 1`, `t = 16`, `R_F = 8` and `R_P = 14`, and the internal diagonal is chosen in
+//! `INTERNAL_DIAGONAL_SHIFTS`. Hashes are therefore not interoperable with Plonky3.
+//! `scripts/poseidon/poseidon2_vectors.py` recomputes the constants and the permutation.
+
+use std::ops::{Add, AddAssign, Mul, Sub};
+use std::sync::OnceLock;
+
+use num_traits::{One, Zero};
+
+use super::grain::GrainLfsr;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::FieldExpOps;
+
+pub const WIDTH: usize = 16;
+/// The number of elements absorbed and squeezed by each permutation of the sponge.
+pub const RATE: usize = 8;
+pub const CAPACITY: usize = WIDTH - RATE;
+/// The number of elements of a digest.
+pub const DIGEST_SIZE: usize = 8;
+pub const N_HALF_FULL_ROUNDS: usize = 4;
+pub const N_FULL_ROUNDS: usize = 2 * N_HALF_FULL_ROUNDS;
+pub const N_PARTIAL_ROUNDS: usize = 14;
+
+/// The round constants of the permutation.
+pub struct RoundConstants {
+    /// The constants of the full rounds, in order.
+    pub external: [[BaseField; WIDTH]; N_FULL_ROUNDS],
+    /// The constants of the partial rounds, added to the first element of the state.
+    pub internal: [BaseField; N_PARTIAL_ROUNDS],
+}
+
+/// Returns the round constants, sampled from the [GrainLfsr] as in the reference implementation
+/// of Poseidon: a row of [WIDTH] elements for each round, of which the partial rounds only use the
+/// first.
+pub fn round_constants() -> &'static RoundConstants {
+    static ROUND_CONSTANTS: OnceLock<RoundConstants> = OnceLock::new();
+    ROUND_CONSTANTS.get_or_init(|| {
+        let mut lfsr = GrainLfsr::new(WIDTH, N_FULL_ROUNDS, N_PARTIAL_ROUNDS);
+        let mut next_row =
+            || -> [BaseField; WIDTH] { std::array::from_fn(|_| lfsr.next_base_field()) };
+        let first_half: [_; N_HALF_FULL_ROUNDS] = std::array::from_fn(|_| next_row());
+        let internal = std::array::from_fn(|_| next_row()[0]);
+        let second_half: [_; N_HALF_FULL_ROUNDS] = std::array::from_fn(|_| next_row());
+        let mut external = [[BaseField::zero(); WIDTH]; N_FULL_ROUNDS];
+        external[..N_HALF_FULL_ROUNDS].copy_from_slice(&first_half);
+        external[N_HALF_FULL_ROUNDS..].copy_from_slice(&second_half);
+        RoundConstants { external, internal }
+    })
+}
+
+#[inline(always)]
+/// Applies the M4 MDS matrix described in <https://eprint.iacr.org/2023/323.pdf> 5.1.
+pub fn apply_m4<F>(x: [F; 4]) -> [F; 4]
+where
+    F: Copy + AddAssign<F> + Add<F, Output = F> + Sub<F, Output = F> + Mul<BaseField, Output = F>,
+{
+    let t0 = x[0] + x[1];
+    let t02 = t0 + t0;
+    let t1 = x[2] + x[3];
+    let t12 = t1 + t1;
+    let t2 = x[1] + x[1] + t1;
+    let t3 = x[3] + x[3] + t0;
+    let t4 = t12 + t12 + t3;
+    let t5 = t02 + t02 + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    [t6, t5, t7, t4]
+}
+
+/// Applies the external round matrix.
+/// See <https://eprint.iacr.org/2023/323.pdf> 5.1 and Appendix B.
+pub fn apply_external_round_matrix<F>(state: &mut [F; WIDTH])
+where
+    F: Copy + AddAssign<F> + Add<F, Output = F> + Sub<F, Output = F> + Mul<BaseField, Output = F>,
+{
+    // Applies circ(2M4, M4, M4, M4).
+    for i in 0..4 {
+        [
+            state[4 * i],
+            state[4 * i + 1],
+            state[4 * i + 2],
+            state[4 * i + 3],
+        ] = apply_m4([
+            state[4 * i],
+            state[4 * i + 1],
+            state[4 * i + 2],
+            state[4 * i + 3],
+        ]);
+    }
+    for j in 0..4 {
+        let s = state[j] + state[j + 4] + state[j + 8] + state[j + 12];
+        for i in 0..4 {
+            state[4 * i + j] += s;
+        }
+    }
+}
+
+/// The logarithms of the diagonal entries of `M_I - 1` after the first, which is -2.
+const INTERNAL_DIAGONAL_SHIFTS: [u32; WIDTH - 1] =
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 12, 13, 14, 15, 16];
+
+// Applies the internal round matrix.
+//   mu_0 = -1, mu_i = 2^{INTERNAL_DIAGONAL_SHIFTS[i - 1]} + 1.
+// See <https://eprint.iacr.org/2023/323.pdf> 5.2. These coefficients satisfy the condition of 5.3
+// against infinitely long invariant subspace trails, see `test_internal_matrix_is_secure`.
+pub fn apply_internal_round_matrix<F>(state: &mut [F; WIDTH])
+where
+    F: Copy + AddAssign<F> + Add<F, Output = F> + Sub<F, Output = F> + Mul<BaseField, Output = F>,
+{
+    let sum = state[1..].iter().fold(state[0], |acc, s| acc + *s);
+    state[0] = sum - state[0] - state[0];
+    for (s, shift) in state[1..].iter_mut().zip(INTERNAL_DIAGONAL_SHIFTS) {
+        // TODO(spapini): Change to rotations.
+        *s = *s * BaseField::from_u32_unchecked(1 << shift) + sum;
+    }
+}
+
+pub fn pow5<F: FieldExpOps>(x: F) -> F {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    x4 * x
+}
+
+/// Applies the Poseidon2 permutation to `state`.
+pub fn permute(state: &mut [BaseField; WIDTH]) {
+    let constants = round_constants();
+    apply_external_round_matrix(state);
+
+    let full_round = |state: &mut [BaseField; WIDTH], round: usize| {
+        for (s, c) in state.iter_mut().zip(constants.external[round]) {
+            *s = pow5(*s + c);
+        }
+        apply_external_round_matrix(state);
+    };
+    (0..N_HALF_FULL_ROUNDS).for_each(|round| full_round(state, round));
+    for c in constants.internal {
+        state[0] = pow5(state[0] + c);
+        apply_internal_round_matrix(state);
+    }
+    (N_HALF_FULL_ROUNDS..N_FULL_ROUNDS).for_each(|round| full_round(state, round));
+}
+
+/// A sponge over the Poseidon2 permutation, absorbing [RATE] elements per permutation.
+#[derive(Clone, Debug)]
+pub struct Poseidon2Sponge {
+    state: [BaseField; WIDTH],
+    /// The number of elements absorbed into the rate since the last permutation.
+    n_absorbed: usize,
+}
+
+impl Poseidon2Sponge {
+    /// Returns a sponge for inputs of `len` elements, which are bound to the capacity.
+    pub fn new(len: usize) -> Self {
+        let mut state = [BaseField::zero(); WIDTH];
+        state[RATE] = BaseField::from(len);
+        Self {
+            state,
+            n_absorbed: 0,
+        }
+    }
+
+    pub fn absorb(&mut self, input: &[BaseField]) {
+        for &x in input {
+            if self.n_absorbed == RATE {
+                permute(&mut self.state);
+                self.n_absorbed = 0;
+            }
+            self.state[self.n_absorbed] += x;
+            self.n_absorbed += 1;
+        }
+    }
+
+    /// Pads the absorbed input and returns [RATE] output elements. Further calls squeeze more
+    /// elements.
+    pub fn squeeze(&mut self) -> [BaseField; RATE] {
+        if self.n_absorbed < RATE {
+            // Pads with a one followed by zeros.
+            self.state[self.n_absorbed] += BaseField::one();
+            self.n_absorbed = RATE;
+        }
+        permute(&mut self.state);
+        self.state[..RATE].try_into().unwrap()
+    }
+}
+
+/// Hashes `input` with a [Poseidon2Sponge].
+pub fn hash(input: &[BaseField]) -> [BaseField; DIGEST_SIZE] {
+    let mut sponge = Poseidon2Sponge::new(input.len());
+    sponge.absorb(input);
+    sponge.squeeze()
+}
+
+/// Compresses two digests into one, with the permutation truncated to its first [DIGEST_SIZE]
+/// elements, e.g. for the nodes of a Merkle tree.
+pub fn compress(
+    left: [BaseField; DIGEST_SIZE],
+    right: [BaseField; DIGEST_SIZE],
+) -> [BaseField; DIGEST_SIZE] {
+    let mut state = [BaseField::zero(); WIDTH];
+    state[..DIGEST_SIZE].copy_from_slice(&left);
+    state[DIGEST_SIZE..].copy_from_slice(&right);
+    permute(&mut state);
+    state[..DIGEST_SIZE].try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use num_traits::{One, Zero};
+
+    use super::{
+        apply_external_round_matrix, apply_internal_round_matrix, apply_m4, compress, hash,
+        permute, round_constants, Poseidon2Sponge, INTERNAL_DIAGONAL_SHIFTS, N_FULL_ROUNDS, WIDTH,
+    };
+    use crate::core::fields::m31::BaseField;
+    use crate::hash_functions::linear_layer::{has_irreducible_powers, is_invertible, matrix_of};
+    use crate::math::matrix::{RowMajorMatrix, SquareMatrix};
+
+    fn internal_matrix() -> Vec<Vec<BaseField>> {
+        matrix_of(WIDTH, |state| {
+            apply_internal_round_matrix(state.try_into().unwrap())
+        })
+    }
+
+    fn test_state() -> [BaseField; WIDTH] {
+        std::array::from_fn(|i| BaseField::from(i * 3 + 187))
+    }
+
+    #[test]
+    fn test_apply_m4() {
+        let m4 = RowMajorMatrix::<BaseField, 4>::new(
+            [5, 7, 1, 3, 4, 6, 1, 1, 1, 3, 5, 7, 1, 1, 4, 6]
+                .map(BaseField::from_u32_unchecked)
+                .into_iter()
+                .collect_vec(),
+        );
+        let state = (0..4)
+            .map(BaseField::from_u32_unchecked)
+            .collect_vec()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(apply_m4(state), m4.mul(state));
+    }
+
+    #[test]
+    fn test_apply_internal() {
+        let mut state = test_state();
+        let mut internal_matrix = [[BaseField::one(); 16]; 16];
+        internal_matrix[0][0] -= BaseField::from(2);
+        for (i, shift) in INTERNAL_DIAGONAL_SHIFTS.into_iter().enumerate() {
+            internal_matrix[i + 1][i + 1] += BaseField::from_u32_unchecked(1 << shift);
+        }
+        let matrix = RowMajorMatrix::<BaseField, 16>::new(internal_matrix.flatten().to_vec());
+
+        let expected_state = matrix.mul(state);
+        apply_internal_round_matrix(&mut state);
+
+        assert_eq!(state, expected_state);
+    }
+
+    #[test]
+    fn test_internal_matrix_is_secure() {
+        assert!(has_irreducible_powers(&internal_matrix()));
+    }
+
+    #[test]
+    fn test_external_matrix_is_invertible() {
+        let matrix = matrix_of(WIDTH, |state| {
+            apply_external_round_matrix(state.try_into().unwrap())
+        });
+
+        assert!(is_invertible(&matrix));
+    }
+
+    // The expected values are printed by `scripts/poseidon/poseidon2_vectors.py`.
+    #[test]
+    fn test_round_constants_known_answer() {
+        let constants = round_constants();
+
+        assert_eq!(
+            constants.external[0][..4],
+            [1988864850, 1893772157, 1025928330, 1839472709].map(BaseField::from_u32_unchecked)
+        );
+        assert_eq!(
+            constants.internal[0],
+            BaseField::from_u32_unchecked(2139014335)
+        );
+        assert_eq!(
+            constants.external[N_FULL_ROUNDS - 1][WIDTH - 1],
+            BaseField::from_u32_unchecked(1057147875)
+        );
+    }
+
+    #[test]
+    fn test_permute_known_answer() {
+        let mut state = test_state();
+
+        permute(&mut state);
+
+        let expected = [
+            800400833, 374897078, 1406290171, 716421845, 853098980, 1411801954, 976698911,
+            1857532499, 470490870, 531063909, 713276393, 1657642571, 71152569, 244708684,
+            581790567, 820744834,
+        ];
+        assert_eq!(state, expected.map(BaseField::from_u32_unchecked));
+    }
+
+    #[test]
+    fn test_round_constants_are_distinct() {
+        let constants = round_constants();
+        let all = constants
+            .external
+            .flatten()
+            .iter()
+            .chain(&constants.internal)
+            .map(|c| c.0)
+            .collect_vec();
+
+        assert_eq!(all.iter().unique().count(), all.len());
+    }
+
+    #[test]
+    fn test_sponge_absorbs_incrementally() {
+        let input = (0..21).map(BaseField::from).collect_vec();
+        let mut sponge = Poseidon2Sponge::new(input.len());
+
+        sponge.absorb(&input[..5]);
+        sponge.absorb(&input[5..]);
+
+        assert_eq!(sponge.squeeze(), hash(&input));
+    }
+
+    #[test]
+    fn test_hash_binds_padding_and_length() {
+        let input = (1..9).map(BaseField::from).collect_vec();
+        let mut padded = input.clone();
+        padded.push(BaseField::one());
+
+        assert_ne!(hash(&input), hash(&padded));
+        assert_ne!(hash(&input[..7]), hash(&input));
+        assert_ne!(hash(&[]), hash(&[BaseField::zero()]));
+    }
+
+    #[test]
+    fn test_compress_is_ordered() {
+        let [a, b] = [0, 8].map(|offset| std::array::from_fn(|i| BaseField::from(offset + i)));
+
+        assert_ne!(compress(a, b), compress(b, a));
+    }
+}
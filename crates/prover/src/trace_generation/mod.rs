@@ -3,13 +3,16 @@ pub mod registry;
 use downcast_rs::{impl_downcast, Downcast};
 use registry::ComponentGenerationRegistry;
 
-use crate::core::air::{AirProver, Component};
+use crate::core::air::logup::LookupError;
+use crate::core::air::{Air, AirProver, Component};
 use crate::core::backend::Backend;
 use crate::core::channel::Blake2sChannel;
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::SecureExtensionField;
 use crate::core::poly::circle::CircleEvaluation;
 use crate::core::poly::BitReversedOrder;
-use crate::core::{ColumnVec, InteractionElements};
+use crate::core::{ColumnVec, InteractionElements, LookupValues};
 
 pub trait ComponentGen: Downcast {}
 impl_downcast!(ComponentGen);
@@ -46,9 +49,20 @@ pub trait ComponentTraceGenerator<B: Backend> {
 
 pub trait AirTraceVerifier {
     fn interaction_elements(&self, channel: &mut Blake2sChannel) -> InteractionElements;
+
+    /// Checks the lookup values of a proof, e.g. that the claimed sums of the
+    /// [Logup](crate::core::air::logup::Logup)s of the components are balanced, together with the
+    /// tuples of the public statement.
+    fn verify_lookups(
+        &self,
+        interaction_elements: &InteractionElements,
+        lookup_values: &LookupValues,
+    ) -> Result<(), LookupError>;
 }
 
-pub trait AirTraceGenerator<B: Backend>: AirTraceVerifier {
+pub trait AirTraceGenerator<B: Backend, F: SecureExtensionField = SecureField>:
+    AirTraceVerifier
+{
     fn composition_log_degree_bound(&self) -> u32;
 
     // TODO(AlonH): Remove default implementation once all the components are implemented.
@@ -62,5 +76,40 @@ pub trait AirTraceGenerator<B: Backend>: AirTraceVerifier {
         elements: &InteractionElements,
     ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>>;
 
-    fn to_air_prover(&self) -> impl AirProver<B>;
+    fn to_air_prover(&self) -> impl AirProver<B, F>;
+}
+
+/// Object-safe view of an [AirTraceGenerator], implemented for every AIR.
+/// Allows proving several unrelated AIRs together with
+/// [prove_batch](crate::core::prover::prove_batch).
+pub trait BatchAirTraceGenerator<B: Backend>: AirTraceVerifier {
+    /// See [AirTraceGenerator::interact].
+    fn batch_interact(
+        &self,
+        trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>>;
+
+    /// See [AirTraceGenerator::to_air_prover].
+    fn batch_air_prover(&self) -> Box<dyn AirProver<B> + '_>;
 }
+
+impl<B: Backend + 'static, A: AirTraceGenerator<B>> BatchAirTraceGenerator<B> for A {
+    fn batch_interact(
+        &self,
+        trace: &ColumnVec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+        elements: &InteractionElements,
+    ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+        self.interact(trace, elements)
+    }
+
+    fn batch_air_prover(&self) -> Box<dyn AirProver<B> + '_> {
+        Box::new(self.to_air_prover())
+    }
+}
+
+/// Verifier side counterpart of [BatchAirTraceGenerator], used by
+/// [verify_batch](crate::core::prover::verify_batch).
+pub trait BatchAirTraceVerifier: Air + AirTraceVerifier {}
+
+impl<A: Air + AirTraceVerifier> BatchAirTraceVerifier for A {}
@@ -0,0 +1,116 @@
+//! Checks that an out-of-core commitment scheme holds only a small part of the committed data in
+//! memory.
+//! Lives in its own test binary, as it replaces the global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use stwo_prover::core::backend::CpuBackend;
+use stwo_prover::core::channel::{Blake2sChannel, Channel};
+use stwo_prover::core::circle::SECURE_FIELD_CIRCLE_GEN;
+use stwo_prover::core::fields::m31::BaseField;
+use stwo_prover::core::fields::qm31::SecureField;
+use stwo_prover::core::pcs::{CommitmentSchemeProver, PcsConfig, TreeVec};
+use stwo_prover::core::poly::circle::{CanonicCoset, CirclePoly, PolyOps};
+use stwo_prover::core::vcs::blake2_hash::Blake2sHash;
+
+/// An allocator keeping track of the peak heap usage.
+struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl PeakAllocator {
+    /// Resets the peak to the current usage, and returns it.
+    fn reset_peak(&self) -> usize {
+        let current = self.current.load(Ordering::SeqCst);
+        self.peak.store(current, Ordering::SeqCst);
+        current
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            self.peak.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+#[test]
+fn test_out_of_core_commitment_memory_budget() {
+    const LOG_SIZE: u32 = 16;
+    const N_COLUMNS: usize = 32;
+    let config = PcsConfig::default();
+    let log_blowup_factor = config.fri_config.log_blowup_factor;
+    let evaluation_size = (1 << (LOG_SIZE + log_blowup_factor)) * std::mem::size_of::<BaseField>();
+    let committed_size = N_COLUMNS * evaluation_size
+        + (2 << (LOG_SIZE + log_blowup_factor)) * std::mem::size_of::<Blake2sHash>();
+    // Enough for evaluating a single column at a time.
+    let memory_budget = 4 * evaluation_size;
+    // Enough for the FRI layers, of at most twice the values of the largest quotient and their
+    // Merkle nodes, whose size does not depend on the number of columns.
+    let proving_memory_budget = 2
+        * (1 << (LOG_SIZE + log_blowup_factor))
+        * (std::mem::size_of::<SecureField>() + 2 * std::mem::size_of::<Blake2sHash>());
+    let twiddles = CpuBackend::precompute_twiddles(
+        CanonicCoset::new(LOG_SIZE + log_blowup_factor)
+            .circle_domain()
+            .half_coset,
+    );
+    let channel = &mut Blake2sChannel::new(Blake2sHash::default());
+    let polynomials = (0..N_COLUMNS).map(|i| {
+        CirclePoly::<CpuBackend>::new(
+            (0..1 << LOG_SIZE)
+                .map(|j| BaseField::from(i * j + 1))
+                .collect(),
+        )
+    });
+    let mut commitment_scheme = CommitmentSchemeProver::new_out_of_core(config);
+
+    let initial_usage = ALLOCATOR.reset_peak();
+    commitment_scheme
+        .commit(polynomials, channel, &twiddles, &std::env::temp_dir())
+        .unwrap();
+    let commit_peak = ALLOCATOR.peak.load(Ordering::SeqCst) - initial_usage;
+    ALLOCATOR.reset_peak();
+    let (values, _) = commitment_scheme.trees[0]
+        .decommit([(LOG_SIZE + log_blowup_factor, vec![3, 1000, 70000])].into())
+        .unwrap();
+    let decommit_peak = ALLOCATOR.peak.load(Ordering::SeqCst) - initial_usage;
+    ALLOCATOR.reset_peak();
+    let sampled_points = TreeVec::new(vec![vec![vec![SECURE_FIELD_CIRCLE_GEN]; N_COLUMNS]]);
+    commitment_scheme
+        .prove_values(sampled_points, channel, &twiddles)
+        .unwrap();
+    let proving_peak = ALLOCATOR.peak.load(Ordering::SeqCst) - initial_usage;
+
+    assert_eq!(values.len(), N_COLUMNS);
+    assert!(committed_size > 8 * memory_budget);
+    assert!(
+        commit_peak < memory_budget,
+        "commitment used {commit_peak} bytes, more than {memory_budget}"
+    );
+    assert!(
+        decommit_peak < memory_budget,
+        "decommitment used {decommit_peak} bytes, more than {memory_budget}"
+    );
+    assert!(
+        proving_peak < proving_memory_budget,
+        "proving used {proving_peak} bytes, more than {proving_memory_budget}"
+    );
+}
@@ -0,0 +1,60 @@
+"""The Grain LFSR of the Poseidon reference parameter generation script,
+`generate_parameters_grain.sage` (https://eprint.iacr.org/2019/458.pdf, Appendix F), ported to
+Python for a prime field of modulus `2^31 - 1` and the power S-box.
+
+This is independent of the Rust implementation in `crates/prover/src/hash_functions/grain.rs`, and
+is used to compute the expected values of its tests.
+"""
+
+P = 2**31 - 1
+N_FIELD_BITS = 31
+
+
+class Grain:
+    def __init__(self, width, n_full_rounds, n_partial_rounds):
+        # A prime field (1), the power S-box (0), then the sizes of the permutation.
+        bits = []
+        for value, n_bits in [
+            (1, 2),
+            (0, 4),
+            (N_FIELD_BITS, 12),
+            (width, 12),
+            (n_full_rounds, 10),
+            (n_partial_rounds, 10),
+        ]:
+            bits += [int(b) for b in bin(value)[2:].zfill(n_bits)]
+        bits += [1] * 30
+        assert len(bits) == 80
+        self.state = bits
+        for _ in range(160):
+            self._step()
+
+    def _step(self):
+        s = self.state
+        new = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0]
+        s.pop(0)
+        s.append(new)
+        return new
+
+    def bit(self):
+        """Returns the next output bit, after the self-shrinking of the reference."""
+        new = self._step()
+        while new == 0:
+            self._step()
+            new = self._step()
+        return self._step()
+
+    def bits(self, n):
+        return int("".join(str(self.bit()) for _ in range(n)), 2)
+
+    def field_element(self):
+        """Returns the next field element, resampling the values not below `P`."""
+        value = self.bits(N_FIELD_BITS)
+        while value >= P:
+            value = self.bits(N_FIELD_BITS)
+        return value
+
+
+def round_constants(grain, width, n_rounds):
+    """Returns a row of `width` constants for each of the `n_rounds` rounds."""
+    return [[grain.field_element() for _ in range(width)] for _ in range(n_rounds)]
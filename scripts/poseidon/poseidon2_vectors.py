@@ -0,0 +1,66 @@
+"""Prints the expected values of the known-answer tests of
+`crates/prover/src/hash_functions/poseidon2.rs`.
+
+The round constants are sampled with the Grain LFSR of the Poseidon reference for `t = 16`,
+`R_F = 8` and `R_P = 14`: a row of 16 constants per round, of which the partial rounds use the
+first. The permutation follows https://eprint.iacr.org/2023/323.pdf, with the external matrix
+built from the M4 matrix of section 5.1 and the internal matrix `1 + diag(-2, 2^0, ..., 2^16)`.
+
+Usage: `python3 scripts/poseidon/poseidon2_vectors.py`.
+"""
+
+from grain import P, Grain, round_constants
+
+WIDTH = 16
+N_HALF_FULL_ROUNDS = 4
+N_PARTIAL_ROUNDS = 14
+INTERNAL_DIAGONAL_SHIFTS = [0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 12, 13, 14, 15, 16]
+
+
+def mat_vec(matrix, state):
+    return [sum(a * b for a, b in zip(row, state)) % P for row in matrix]
+
+
+def external_matrix():
+    m4 = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]]
+    return [
+        [(2 if i // 4 == j // 4 else 1) * m4[i % 4][j % 4] for j in range(WIDTH)]
+        for i in range(WIDTH)
+    ]
+
+
+def internal_matrix():
+    diagonal = [P - 2] + [1 << shift for shift in INTERNAL_DIAGONAL_SHIFTS]
+    return [
+        [(1 + (diagonal[i] if i == j else 0)) % P for j in range(WIDTH)] for i in range(WIDTH)
+    ]
+
+
+def permute(state, rows):
+    external, internal = external_matrix(), internal_matrix()
+    full_rows = rows[:N_HALF_FULL_ROUNDS] + rows[N_HALF_FULL_ROUNDS + N_PARTIAL_ROUNDS :]
+    partial_rows = rows[N_HALF_FULL_ROUNDS : N_HALF_FULL_ROUNDS + N_PARTIAL_ROUNDS]
+    partial_constants = [row[0] for row in partial_rows]
+
+    def full_round(state, constants):
+        return mat_vec(external, [pow(s + c, 5, P) for s, c in zip(state, constants)])
+
+    state = mat_vec(external, state)
+    for constants in full_rows[:N_HALF_FULL_ROUNDS]:
+        state = full_round(state, constants)
+    for constant in partial_constants:
+        state[0] = pow(state[0] + constant, 5, P)
+        state = mat_vec(internal, state)
+    for constants in full_rows[N_HALF_FULL_ROUNDS:]:
+        state = full_round(state, constants)
+    return state
+
+
+if __name__ == "__main__":
+    n_full_rounds = 2 * N_HALF_FULL_ROUNDS
+    grain = Grain(WIDTH, n_full_rounds, N_PARTIAL_ROUNDS)
+    rows = round_constants(grain, WIDTH, n_full_rounds + N_PARTIAL_ROUNDS)
+    print("external[0][:4]:", rows[0][:4])
+    print("internal[0]:", rows[N_HALF_FULL_ROUNDS][0])
+    print("external[7][15]:", rows[-1][WIDTH - 1])
+    print("permute([3 * i + 187]):", permute([3 * i + 187 for i in range(WIDTH)], rows))
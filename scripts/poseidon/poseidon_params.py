@@ -0,0 +1,158 @@
+"""Prints the expected values of `params_match_generator_script` in
+`crates/prover/src/hash_functions/poseidon.rs`.
+
+The round constants and the Cauchy matrix candidates are sampled with the Grain LFSR of the
+Poseidon reference for `t = 24`, `R_F = 8` and `R_P = 22`, as in `generate_parameters_grain.sage`.
+Where the reference runs its invariant subspace trail search (its algorithms 1 to 3) on each
+candidate, this keeps the first candidate `M` such that the characteristic polynomials of
+`M, M^2, ..., M^(2t)` are irreducible, a sufficient condition against infinitely long invariant
+subspace trails. The characteristic polynomials are computed by Hessenberg reduction, and
+checked with Rabin's irreducibility test, independently of the Rust implementation.
+
+Usage: `python3 scripts/poseidon/poseidon_params.py`. It takes a few minutes.
+"""
+
+from grain import N_FIELD_BITS, P, Grain, round_constants
+
+WIDTH = 24
+N_FULL_ROUNDS = 8
+N_PARTIAL_ROUNDS = 22
+
+
+def matmul(a, b):
+    n = len(a)
+    bt = list(zip(*b))
+    return [[sum(x * y for x, y in zip(r, c)) % P for c in bt] for r in a]
+
+
+def charpoly(a):
+    # Hessenberg reduction, then the recurrence for the characteristic polynomial.
+    n = len(a)
+    h = [row[:] for row in a]
+    for m in range(1, n - 1):
+        piv = next((i for i in range(m, n) if h[i][m - 1]), None)
+        if piv is None:
+            continue
+        if piv != m:
+            h[piv], h[m] = h[m], h[piv]
+            for r in h:
+                r[piv], r[m] = r[m], r[piv]
+        inv = pow(h[m][m - 1], P - 2, P)
+        for i in range(m + 1, n):
+            f = h[i][m - 1] * inv % P
+            if f:
+                h[i] = [(x - f * y) % P for x, y in zip(h[i], h[m])]
+                for r in h:
+                    r[m] = (r[m] + f * r[i]) % P
+    # p_k(x) coefficients, lowest degree first.
+    polys = [[1]]
+    for k in range(1, n + 1):
+        pk = [0] + polys[k - 1]
+        pk = [(c - h[k - 1][k - 1] * (polys[k - 1][i] if i < len(polys[k - 1]) else 0)) % P for i, c in enumerate(pk)]
+        prod = 1
+        for i in range(1, k):
+            prod = prod * h[k - i][k - i - 1] % P
+            c = h[k - i - 1][k - 1] * prod % P
+            for j, v in enumerate(polys[k - i - 1]):
+                pk[j] = (pk[j] - c * v) % P
+        polys.append(pk)
+    return polys[n]
+
+
+def trim(a):
+    while a and a[-1] == 0:
+        a.pop()
+    return a
+
+
+def pmod(a, f):
+    a = trim(a[:])
+    inv = pow(f[-1], P - 2, P)
+    while len(a) >= len(f):
+        c = a[-1] * inv % P
+        s = len(a) - len(f)
+        for i, v in enumerate(f):
+            a[s + i] = (a[s + i] - c * v) % P
+        a = trim(a)
+    return a
+
+
+def pmulmod(a, b, f):
+    r = [0] * (len(a) + len(b))
+    for i, x in enumerate(a):
+        if x:
+            for j, y in enumerate(b):
+                r[i + j] = (r[i + j] + x * y) % P
+    return pmod(r, f)
+
+
+def ppowmod(a, e, f):
+    r, b = [1], pmod(a, f)
+    while e:
+        if e & 1:
+            r = pmulmod(r, b, f)
+        b = pmulmod(b, b, f)
+        e >>= 1
+    return r
+
+
+def pgcd(a, b):
+    a, b = trim(a[:]), trim(b[:])
+    while b:
+        a, b = b, pmod(a, b)
+    return a
+
+
+def frob(f, k):
+    x = [0, 1]
+    for _ in range(k):
+        x = ppowmod(x, P, f)
+    return x
+
+
+def irreducible(f):
+    # Rabin: x^(p^n) = x mod f, and gcd(x^(p^(n/q)) - x, f) = 1 for the primes q | n.
+    n = len(f) - 1
+    sub = lambda a: trim([(c - (1 if i == 1 else 0)) % P for i, c in enumerate(a + [0] * max(0, 2 - len(a)))])
+    if sub(frob(f, n)):
+        return False
+    for q in [q for q in range(2, n + 1) if n % q == 0 and all(q % d for d in range(2, q))]:
+        if len(pgcd(f, sub(frob(f, n // q)))) != 1:
+            return False
+    return True
+
+
+def accepted(m):
+    t = len(m)
+    power = m
+    for _ in range(2 * t):
+        if not irreducible(charpoly(power)):
+            return False
+        power = matmul(power, m)
+    return True
+
+
+def candidate_matrices(grain):
+    """Yields the Cauchy matrices `1 / (x_i + y_j)` of the reference, numbered from 1."""
+    index = 0
+    while True:
+        # As in the reference, the values are reduced rather than resampled.
+        values = [grain.bits(N_FIELD_BITS) % P for _ in range(2 * WIDTH)]
+        index += 1
+        xs, ys = values[:WIDTH], values[WIDTH:]
+        if len(set(values)) != 2 * WIDTH or any((x + y) % P == 0 for x in xs for y in ys):
+            continue
+        yield index, [[pow(x + y, P - 2, P) for y in ys] for x in xs]
+
+
+if __name__ == "__main__":
+    grain = Grain(WIDTH, N_FULL_ROUNDS, N_PARTIAL_ROUNDS)
+    rows = round_constants(grain, WIDTH, N_FULL_ROUNDS + N_PARTIAL_ROUNDS)
+    print("round_constants[0][:4]:", rows[0][:4])
+    print("round_constants[29][23]:", rows[-1][WIDTH - 1])
+    for index, matrix in candidate_matrices(grain):
+        if accepted(matrix):
+            print("candidate", index)
+            print("mds[0][:4]:", matrix[0][:4])
+            print("mds[23][23]:", matrix[WIDTH - 1][WIDTH - 1])
+            break